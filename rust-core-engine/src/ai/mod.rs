@@ -624,6 +624,8 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1700000000000,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         }
     }
 
@@ -14,7 +14,7 @@ use crate::auth::{AuthService, SecurityService, SessionRepository, UserRepositor
 use crate::config::{ApiConfig, BinanceConfig};
 use crate::market_data::MarketDataProcessor;
 use crate::monitoring::MonitoringService;
-use crate::paper_trading::PaperTradingEngine;
+use crate::paper_trading::{PaperPortfolioRegistry, PaperTradingEngine};
 use crate::real_trading::RealTradingEngine;
 use crate::storage::Storage;
 use crate::trading::TradingEngine;
@@ -31,6 +31,7 @@ pub struct ApiServer {
     market_data: MarketDataProcessor,
     trading_engine: TradingEngine,
     paper_trading_engine: Arc<PaperTradingEngine>,
+    paper_portfolios: PaperPortfolioRegistry,
     real_trading_engine: Option<Arc<RealTradingEngine>>,
     monitoring: Arc<RwLock<MonitoringService>>,
     ws_broadcaster: broadcast::Sender<String>,
@@ -118,6 +119,7 @@ impl ApiServer {
             cache_ttl_seconds: 300,
         };
         let ai_service = AIService::new(ai_config);
+        let paper_portfolios = PaperPortfolioRegistry::new(paper_trading_engine.clone());
 
         Ok(Self {
             config,
@@ -125,6 +127,7 @@ impl ApiServer {
             market_data,
             trading_engine,
             paper_trading_engine,
+            paper_portfolios,
             real_trading_engine,
             monitoring: Arc::new(RwLock::new(MonitoringService::new())),
             ws_broadcaster,
@@ -135,6 +138,11 @@ impl ApiServer {
         })
     }
 
+    /// Named paper portfolios served next to the default paper trading engine
+    pub fn paper_portfolios(&self) -> &PaperPortfolioRegistry {
+        &self.paper_portfolios
+    }
+
     pub async fn start(&self) -> Result<()> {
         info!(
             "Starting API server on {}:{}",
@@ -179,8 +187,11 @@ impl ApiServer {
         let ai_routes = self.clone().ai_routes();
 
         // Paper trading routes
-        let paper_trading =
-            paper_trading::PaperTradingApi::new(self.paper_trading_engine.clone()).routes();
+        let paper_trading = paper_trading::PaperTradingApi::with_portfolios(
+            self.paper_trading_engine.clone(),
+            self.paper_portfolios.clone(),
+        )
+        .routes();
 
         // Real trading routes - the API handles the None case internally
         let real_trading =
@@ -945,6 +956,8 @@ impl From<crate::ai::AIAnalysisRequest> for crate::strategies::StrategyInput {
             current_price: request.current_price,
             volume_24h: request.volume_24h,
             timestamp: request.timestamp,
+            indicators: std::collections::HashMap::new(),
+            related_data: std::collections::HashMap::new(),
         }
     }
 }
@@ -957,6 +970,8 @@ impl From<crate::ai::StrategyRecommendationRequest> for crate::strategies::Strat
             current_price: request.current_price,
             volume_24h: 0.0, // Not available in strategy recommendation request
             timestamp: request.timestamp,
+            indicators: std::collections::HashMap::new(),
+            related_data: std::collections::HashMap::new(),
         }
     }
 }
@@ -969,6 +984,8 @@ impl From<crate::ai::MarketConditionRequest> for crate::strategies::StrategyInpu
             current_price: request.current_price,
             volume_24h: request.volume_24h,
             timestamp: request.timestamp,
+            indicators: std::collections::HashMap::new(),
+            related_data: std::collections::HashMap::new(),
         }
     }
 }
@@ -1,14 +1,20 @@
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use warp::filters::BoxedFilter;
 use warp::{Filter, Rejection, Reply};
 // Removed unused import
 use warp::http::StatusCode;
 
-use crate::paper_trading::{PaperTradingEngine, PaperTradingSettings};
+use crate::paper_trading::monte_carlo::MonteCarloConfig;
+use crate::paper_trading::registry::DEFAULT_PORTFOLIO_NAME;
+use crate::paper_trading::{
+    PairTradeParams, PaperPortfolioRegistry, PaperTradingEngine, PaperTradingSettings,
+};
 
 /// API handlers for paper trading functionality
 pub struct PaperTradingApi {
     engine: Arc<PaperTradingEngine>,
+    portfolios: PaperPortfolioRegistry,
 }
 
 /// Request to update paper trading settings
@@ -22,6 +28,44 @@ pub struct UpdateSettingsRequest {
 pub struct CloseTradeRequest {
     pub trade_id: Option<String>,
     pub reason: Option<String>,
+    /// Fraction of the position to close, (0, 1]; closes all of it when omitted
+    #[serde(default)]
+    pub fraction: Option<f64>,
+}
+
+/// Request to add to an open trade at the current price
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScaleInRequest {
+    pub quantity: f64,
+}
+
+/// One take profit level: close `fraction` of the position at `price`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TakeProfitLevelRequest {
+    pub price: f64,
+    pub fraction: f64,
+}
+
+/// Request to set multi-level take profits on an open trade
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SetTakeProfitLevelsRequest {
+    pub levels: Vec<TakeProfitLevelRequest>,
+}
+
+/// Request to create a named paper portfolio
+#[derive(Debug, Deserialize)]
+pub struct CreatePortfolioRequest {
+    pub name: String,
+    /// Settings of the new portfolio; the default portfolio's settings when omitted
+    #[serde(default)]
+    pub settings: Option<PaperTradingSettings>,
+    /// Start the portfolio's engine right away
+    #[serde(default = "default_start_portfolio")]
+    pub start: bool,
+}
+
+fn default_start_portfolio() -> bool {
+    true
 }
 
 /// Request to create a manual order
@@ -32,7 +76,7 @@ pub struct CreateOrderRequest {
     pub symbol: String,
     /// Order side: "buy" or "sell" (maps to Long/Short)
     pub side: String,
-    /// Order type: "market", "limit", "stop-limit"
+    /// Order type: "market", "limit", "stop-limit", "bracket" or "oco"
     pub order_type: String,
     /// Quantity to trade
     pub quantity: f64,
@@ -46,6 +90,18 @@ pub struct CreateOrderRequest {
     pub stop_loss_pct: Option<f64>,
     /// Take profit percentage (optional)
     pub take_profit_pct: Option<f64>,
+    /// Time in force for limit orders: "GTC" (default), "IOC", "FOK" or "GTX"
+    #[serde(default)]
+    pub time_in_force: Option<crate::binance::types::TimeInForce>,
+    /// Limit price of an OCO order's stop leg (defaults to stop_price)
+    #[serde(default)]
+    pub stop_limit_price: Option<f64>,
+    /// Absolute stop loss price (required for bracket orders)
+    #[serde(default)]
+    pub stop_loss_price: Option<f64>,
+    /// Absolute take profit price (required for bracket orders)
+    #[serde(default)]
+    pub take_profit_price: Option<f64>,
 }
 
 /// Response for create order
@@ -331,6 +387,46 @@ pub struct SignalsHistoryQuery {
     pub limit: Option<i64>,
 }
 
+/// Request to replay historical candles through the strategy engine
+/// @spec:FR-STRATEGIES-008 - Strategy Backtesting
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BacktestRequest {
+    /// Symbol to backtest (e.g., "BTCUSDT")
+    pub symbol: String,
+}
+
+/// Request to optimize one strategy's parameters with walk-forward validation
+/// @spec:FR-STRATEGIES-007 - Strategy Optimizer
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WalkForwardRequest {
+    /// Symbol whose history is used (e.g., "BTCUSDT")
+    pub symbol: String,
+    /// Strategy to optimize (e.g., "RSI Strategy")
+    pub strategy: String,
+}
+
+/// Request to replace the strategies that run in the signal loop
+/// @spec:FR-STRATEGIES-005 - Strategy Parameters
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateEnabledStrategiesRequest {
+    /// Full strategy names (e.g., "RSI Strategy"); must not be empty
+    pub enabled_strategies: Vec<String>,
+}
+
+/// Request naming a single strategy to enable or disable
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StrategyToggleRequest {
+    /// Full strategy name (e.g., "MACD Strategy")
+    pub name: String,
+}
+
+/// A loaded strategy and whether it runs in the signal loop
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StrategyStatus {
+    pub name: String,
+    pub enabled: bool,
+}
+
 /// Indicator settings for API (matches Rust struct)
 /// @spec:FR-SETTINGS-001 - Unified indicator settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -431,10 +527,22 @@ impl<T> ApiResponse<T> {
 
 impl PaperTradingApi {
     pub fn new(engine: Arc<PaperTradingEngine>) -> Self {
-        Self { engine }
+        let portfolios = PaperPortfolioRegistry::new(engine.clone());
+        Self { engine, portfolios }
+    }
+
+    /// API for `engine` that also serves the named portfolios of `portfolios`
+    pub fn with_portfolios(
+        engine: Arc<PaperTradingEngine>,
+        portfolios: PaperPortfolioRegistry,
+    ) -> Self {
+        Self { engine, portfolios }
     }
 
     /// Create paper trading API routes
+    ///
+    /// The default portfolio is served under `/paper-trading/...` and named portfolios
+    /// under `/paper-trading/{portfolio}/...`.
     pub fn routes(self) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
         let api = Arc::new(self);
 
@@ -443,326 +551,552 @@ impl PaperTradingApi {
             .allow_headers(vec!["content-type", "authorization"])
             .allow_methods(vec!["GET", "POST", "PUT", "DELETE", "OPTIONS"]);
 
-        let base_path = warp::path("paper-trading");
-
-        // GET /api/paper-trading/status
-        let status_route = base_path
-            .and(warp::path("status"))
+        // GET /api/paper-trading/portfolios
+        let list_portfolios_route = warp::path("paper-trading")
+            .and(warp::path("portfolios"))
             .and(warp::path::end())
             .and(warp::get())
             .and(with_api(api.clone()))
-            .and_then(get_status);
+            .and_then(list_portfolios);
 
-        // GET /api/paper-trading/portfolio
-        let portfolio_route = base_path
-            .and(warp::path("portfolio"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_portfolio);
-
-        // GET /api/paper-trading/trades/open
-        let open_trades_route = base_path
-            .and(warp::path("trades"))
-            .and(warp::path("open"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_open_trades);
-
-        // GET /api/paper-trading/trades/closed
-        let closed_trades_route = base_path
-            .and(warp::path("trades"))
-            .and(warp::path("closed"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_closed_trades);
-
-        // POST /api/paper-trading/trades/{trade_id}/close
-        let close_trade_route = base_path
-            .and(warp::path("trades"))
-            .and(warp::path::param::<String>())
-            .and(warp::path("close"))
+        // POST /api/paper-trading/portfolios
+        let create_portfolio_route = warp::path("paper-trading")
+            .and(warp::path("portfolios"))
             .and(warp::path::end())
             .and(warp::post())
             .and(warp::body::json())
             .and(with_api(api.clone()))
-            .and_then(close_trade);
-
-        // PUT /api/paper-trading/settings
-        let update_settings_route = base_path
-            .and(warp::path("settings"))
-            .and(warp::path::end())
-            .and(warp::put())
-            .and(warp::body::json())
-            .and(with_api(api.clone()))
-            .and_then(update_settings);
-
-        // GET /api/paper-trading/strategy-settings
-        let get_strategy_settings_route = base_path
-            .and(warp::path("strategy-settings"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_strategy_settings);
-
-        // PUT /api/paper-trading/strategy-settings
-        let update_strategy_settings_route = base_path
-            .and(warp::path("strategy-settings"))
-            .and(warp::path::end())
-            .and(warp::put())
-            .and(warp::body::json())
-            .and(with_api(api.clone()))
-            .and_then(update_strategy_settings);
-
-        // GET /api/paper-trading/basic-settings
-        let get_basic_settings_route = base_path
-            .and(warp::path("basic-settings"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_basic_settings);
-
-        // PUT /api/paper-trading/basic-settings
-        let update_basic_settings_route = base_path
-            .and(warp::path("basic-settings"))
-            .and(warp::path::end())
-            .and(warp::put())
-            .and(warp::body::json())
-            .and(with_api(api.clone()))
-            .and_then(update_basic_settings);
-
-        // GET /api/paper-trading/execution-settings
-        let get_execution_settings_route = base_path
-            .and(warp::path("execution-settings"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_execution_settings);
-
-        // PUT /api/paper-trading/execution-settings
-        let update_execution_settings_route = base_path
-            .and(warp::path("execution-settings"))
-            .and(warp::path::end())
-            .and(warp::put())
-            .and(warp::body::json())
-            .and(with_api(api.clone()))
-            .and_then(update_execution_settings);
-
-        // GET /api/paper-trading/ai-settings
-        let get_ai_settings_route = base_path
-            .and(warp::path("ai-settings"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_ai_settings);
+            .and_then(create_portfolio);
 
-        // PUT /api/paper-trading/ai-settings
-        let update_ai_settings_route = base_path
-            .and(warp::path("ai-settings"))
-            .and(warp::path::end())
-            .and(warp::put())
-            .and(warp::body::json())
-            .and(with_api(api.clone()))
-            .and_then(update_ai_settings);
-
-        // GET /api/paper-trading/notification-settings
-        let get_notification_settings_route = base_path
-            .and(warp::path("notification-settings"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_notification_settings);
-
-        // PUT /api/paper-trading/notification-settings
-        let update_notification_settings_route = base_path
-            .and(warp::path("notification-settings"))
-            .and(warp::path::end())
-            .and(warp::put())
-            .and(warp::body::json())
-            .and(with_api(api.clone()))
-            .and_then(update_notification_settings);
-
-        // GET /api/paper-trading/symbols
-        let get_symbols_route = base_path
-            .and(warp::path("symbols"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_symbol_settings);
-
-        // PUT /api/paper-trading/symbols
-        let update_symbols_route = base_path
-            .and(warp::path("symbols"))
-            .and(warp::path::end())
-            .and(warp::put())
-            .and(warp::body::json())
-            .and(with_api(api.clone()))
-            .and_then(update_symbol_settings);
-
-        // POST /api/paper-trading/reset
-        let reset_route = base_path
-            .and(warp::path("reset"))
-            .and(warp::path::end())
-            .and(warp::post())
-            .and(with_api(api.clone()))
-            .and_then(reset_portfolio);
-
-        // POST /api/paper-trading/start
-        let start_route = base_path
-            .and(warp::path("start"))
-            .and(warp::path::end())
-            .and(warp::post())
-            .and(with_api(api.clone()))
-            .and_then(start_engine);
-
-        // POST /api/paper-trading/stop
-        let stop_route = base_path
-            .and(warp::path("stop"))
-            .and(warp::path::end())
-            .and(warp::post())
-            .and(with_api(api.clone()))
-            .and_then(stop_engine);
-
-        // POST /api/paper-trading/orders
-        // @spec:FR-PAPER-003 - Manual Order Placement
-        let create_order_route = base_path
-            .and(warp::path("orders"))
-            .and(warp::path::end())
-            .and(warp::post())
-            .and(warp::body::json())
-            .and(with_api(api.clone()))
-            .and_then(create_manual_order);
-
-        // GET /api/paper-trading/pending-orders
-        // @spec:FR-PAPER-003 - Stop-Limit Pending Orders List
-        let get_pending_orders_route = base_path
-            .and(warp::path("pending-orders"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_pending_orders);
-
-        // DELETE /api/paper-trading/pending-orders/{order_id}
-        // @spec:FR-PAPER-003 - Cancel Pending Order
-        let cancel_pending_order_route = base_path
-            .and(warp::path("pending-orders"))
+        // DELETE /api/paper-trading/portfolios/{name}
+        let delete_portfolio_route = warp::path("paper-trading")
+            .and(warp::path("portfolios"))
             .and(warp::path::param::<String>())
             .and(warp::path::end())
             .and(warp::delete())
             .and(with_api(api.clone()))
-            .and_then(cancel_pending_order);
-
-        // POST /api/paper-trading/trigger-analysis
-        let trigger_analysis_route = base_path
-            .and(warp::path("trigger-analysis"))
-            .and(warp::path::end())
-            .and(warp::post())
-            .and(with_api(api.clone()))
-            .and_then(trigger_manual_analysis);
-
-        // PUT /api/paper-trading/signal-interval
-        let update_signal_interval_route = base_path
-            .and(warp::path("signal-interval"))
-            .and(warp::path::end())
-            .and(warp::put())
-            .and(warp::body::json())
-            .and(with_api(api.clone()))
-            .and_then(update_signal_refresh_interval);
-
-        // GET /api/paper-trading/indicator-settings
-        // @spec:FR-SETTINGS-001 - Unified indicator settings API
-        let get_indicator_settings_route = base_path
-            .and(warp::path("indicator-settings"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_indicator_settings);
-
-        // PUT /api/paper-trading/indicator-settings
-        // @spec:FR-SETTINGS-002 - Update indicator and signal generation settings
-        let update_indicator_settings_route = base_path
-            .and(warp::path("indicator-settings"))
-            .and(warp::path::end())
-            .and(warp::put())
-            .and(warp::body::json())
-            .and(with_api(api.clone()))
-            .and_then(update_indicator_settings);
-
-        // GET /api/paper-trading/trade-analyses
-        // @spec:FR-ASYNC-011 - GPT-4 Individual Trade Analysis
-        let get_trade_analyses_route = base_path
-            .and(warp::path("trade-analyses"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(warp::query::<TradeAnalysesQuery>())
-            .and(with_api(api.clone()))
-            .and_then(get_trade_analyses);
-
-        // GET /api/paper-trading/trade-analyses/{trade_id}
-        let get_trade_analysis_by_id_route = base_path
-            .and(warp::path("trade-analyses"))
-            .and(warp::path::param::<String>())
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_trade_analysis_by_id);
-
-        // GET /api/paper-trading/config-suggestions
-        // @spec:FR-ASYNC-009 - GPT-4 Config Improvement Suggestions
-        let get_config_suggestions_route = base_path
-            .and(warp::path("config-suggestions"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(warp::query::<ConfigSuggestionsQuery>())
-            .and(with_api(api.clone()))
-            .and_then(get_config_suggestions);
-
-        // GET /api/paper-trading/config-suggestions/latest
-        let get_latest_config_suggestion_route = base_path
-            .and(warp::path("config-suggestions"))
-            .and(warp::path("latest"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_latest_config_suggestion);
+            .and_then(delete_portfolio);
 
-        // GET /api/paper-trading/atr-diagnostics
-        // @spec:FR-RISK-010 - ATR diagnostics for monitoring
-        let get_atr_diagnostics_route = base_path
-            .and(warp::path("atr-diagnostics"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_atr_diagnostics);
-
-        // GET /api/paper-trading/signals-history
-        // @spec:FR-AI-012 - Signal Outcome Tracking
-        // Returns AI signals with their outcomes (win/loss/pending)
-        let get_signals_history_route = base_path
-            .and(warp::path("signals-history"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(warp::query::<SignalsHistoryQuery>())
-            .and(with_api(api.clone()))
-            .and_then(get_signals_history);
+        let default_portfolio_routes = portfolio_routes(
+            warp::path("paper-trading").boxed(),
+            with_api(api.clone()).boxed(),
+        );
+        let named_portfolio_routes = portfolio_routes(
+            warp::path("paper-trading")
+                .and(existing_portfolio(api.portfolios.clone()))
+                .boxed(),
+            with_named_portfolio(api.portfolios.clone()).boxed(),
+        );
 
-        // GET /api/paper-trading/latest-signals
-        // @spec:FR-AI-013 - Cached Signal Display
-        // Returns the most recent signal for each symbol (for quick page load)
-        let get_latest_signals_route = base_path
-            .and(warp::path("latest-signals"))
-            .and(warp::path::end())
-            .and(warp::get())
-            .and(with_api(api.clone()))
-            .and_then(get_latest_signals);
+        list_portfolios_route
+            .or(create_portfolio_route)
+            .or(delete_portfolio_route)
+            .or(default_portfolio_routes)
+            .or(named_portfolio_routes)
+            .with(cors)
+    }
+}
 
-        status_route
+/// Routes of one portfolio under `base_path`, whose engine is extracted by `api`
+fn portfolio_routes(
+    base_path: BoxedFilter<()>,
+    api: BoxedFilter<(Arc<PaperTradingApi>,)>,
+) -> impl Filter<Extract = impl Reply, Error = Rejection> + Clone {
+    // GET /api/paper-trading/status
+    let status_route = base_path
+        .clone()
+        .and(warp::path("status"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_status);
+
+    // GET /api/paper-trading/portfolio
+    let portfolio_route = base_path
+        .clone()
+        .and(warp::path("portfolio"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_portfolio);
+
+    // GET /api/paper-trading/trades/open
+    let open_trades_route = base_path
+        .clone()
+        .and(warp::path("trades"))
+        .and(warp::path("open"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_open_trades);
+
+    // GET /api/paper-trading/trades/closed
+    let closed_trades_route = base_path
+        .clone()
+        .and(warp::path("trades"))
+        .and(warp::path("closed"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_closed_trades);
+
+    // POST /api/paper-trading/trades/{trade_id}/close
+    let close_trade_route = base_path
+        .clone()
+        .and(warp::path("trades"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("close"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(close_trade);
+
+    // POST /api/paper-trading/trades/{trade_id}/scale-in
+    let scale_in_trade_route = base_path
+        .clone()
+        .and(warp::path("trades"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("scale-in"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(scale_in_trade);
+
+    // PUT /api/paper-trading/trades/{trade_id}/take-profit-levels
+    let take_profit_levels_route = base_path
+        .clone()
+        .and(warp::path("trades"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("take-profit-levels"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(set_take_profit_levels);
+
+    // PUT /api/paper-trading/settings
+    let update_settings_route = base_path
+        .clone()
+        .and(warp::path("settings"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(update_settings);
+
+    // GET /api/paper-trading/strategy-settings
+    let get_strategy_settings_route = base_path
+        .clone()
+        .and(warp::path("strategy-settings"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_strategy_settings);
+
+    // PUT /api/paper-trading/strategy-settings
+    let update_strategy_settings_route = base_path
+        .clone()
+        .and(warp::path("strategy-settings"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(update_strategy_settings);
+
+    // GET /api/paper-trading/basic-settings
+    let get_basic_settings_route = base_path
+        .clone()
+        .and(warp::path("basic-settings"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_basic_settings);
+
+    // PUT /api/paper-trading/basic-settings
+    let update_basic_settings_route = base_path
+        .clone()
+        .and(warp::path("basic-settings"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(update_basic_settings);
+
+    // GET /api/paper-trading/execution-settings
+    let get_execution_settings_route = base_path
+        .clone()
+        .and(warp::path("execution-settings"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_execution_settings);
+
+    // PUT /api/paper-trading/execution-settings
+    let update_execution_settings_route = base_path
+        .clone()
+        .and(warp::path("execution-settings"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(update_execution_settings);
+
+    // GET /api/paper-trading/ai-settings
+    let get_ai_settings_route = base_path
+        .clone()
+        .and(warp::path("ai-settings"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_ai_settings);
+
+    // PUT /api/paper-trading/ai-settings
+    let update_ai_settings_route = base_path
+        .clone()
+        .and(warp::path("ai-settings"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(update_ai_settings);
+
+    // GET /api/paper-trading/notification-settings
+    let get_notification_settings_route = base_path
+        .clone()
+        .and(warp::path("notification-settings"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_notification_settings);
+
+    // PUT /api/paper-trading/notification-settings
+    let update_notification_settings_route = base_path
+        .clone()
+        .and(warp::path("notification-settings"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(update_notification_settings);
+
+    // GET /api/paper-trading/symbols
+    let get_symbols_route = base_path
+        .clone()
+        .and(warp::path("symbols"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_symbol_settings);
+
+    // PUT /api/paper-trading/symbols
+    let update_symbols_route = base_path
+        .clone()
+        .and(warp::path("symbols"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(update_symbol_settings);
+
+    // POST /api/paper-trading/reset
+    let reset_route = base_path
+        .clone()
+        .and(warp::path("reset"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(api.clone())
+        .and_then(reset_portfolio);
+
+    // POST /api/paper-trading/start
+    let start_route = base_path
+        .clone()
+        .and(warp::path("start"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(api.clone())
+        .and_then(start_engine);
+
+    // POST /api/paper-trading/stop
+    let stop_route = base_path
+        .clone()
+        .and(warp::path("stop"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(api.clone())
+        .and_then(stop_engine);
+
+    // POST /api/paper-trading/orders
+    // @spec:FR-PAPER-003 - Manual Order Placement
+    let create_order_route = base_path
+        .clone()
+        .and(warp::path("orders"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(create_manual_order);
+
+    // GET /api/paper-trading/pending-orders
+    // @spec:FR-PAPER-003 - Stop-Limit Pending Orders List
+    let get_pending_orders_route = base_path
+        .clone()
+        .and(warp::path("pending-orders"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_pending_orders);
+
+    // DELETE /api/paper-trading/pending-orders/{order_id}
+    // @spec:FR-PAPER-003 - Cancel Pending Order
+    let cancel_pending_order_route = base_path
+        .clone()
+        .and(warp::path("pending-orders"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::delete())
+        .and(api.clone())
+        .and_then(cancel_pending_order);
+
+    // POST /api/paper-trading/trigger-analysis
+    let trigger_analysis_route = base_path
+        .clone()
+        .and(warp::path("trigger-analysis"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(api.clone())
+        .and_then(trigger_manual_analysis);
+
+    // PUT /api/paper-trading/signal-interval
+    let update_signal_interval_route = base_path
+        .clone()
+        .and(warp::path("signal-interval"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(update_signal_refresh_interval);
+
+    // GET /api/paper-trading/indicator-settings
+    // @spec:FR-SETTINGS-001 - Unified indicator settings API
+    let get_indicator_settings_route = base_path
+        .clone()
+        .and(warp::path("indicator-settings"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_indicator_settings);
+
+    // PUT /api/paper-trading/indicator-settings
+    // @spec:FR-SETTINGS-002 - Update indicator and signal generation settings
+    let update_indicator_settings_route = base_path
+        .clone()
+        .and(warp::path("indicator-settings"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(update_indicator_settings);
+
+    // GET /api/paper-trading/trade-analyses
+    // @spec:FR-ASYNC-011 - GPT-4 Individual Trade Analysis
+    let get_trade_analyses_route = base_path
+        .clone()
+        .and(warp::path("trade-analyses"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<TradeAnalysesQuery>())
+        .and(api.clone())
+        .and_then(get_trade_analyses);
+
+    // GET /api/paper-trading/trade-analyses/{trade_id}
+    let get_trade_analysis_by_id_route = base_path
+        .clone()
+        .and(warp::path("trade-analyses"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_trade_analysis_by_id);
+
+    // GET /api/paper-trading/config-suggestions
+    // @spec:FR-ASYNC-009 - GPT-4 Config Improvement Suggestions
+    let get_config_suggestions_route = base_path
+        .clone()
+        .and(warp::path("config-suggestions"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<ConfigSuggestionsQuery>())
+        .and(api.clone())
+        .and_then(get_config_suggestions);
+
+    // GET /api/paper-trading/config-suggestions/latest
+    let get_latest_config_suggestion_route = base_path
+        .clone()
+        .and(warp::path("config-suggestions"))
+        .and(warp::path("latest"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_latest_config_suggestion);
+
+    // GET /api/paper-trading/atr-diagnostics
+    // @spec:FR-RISK-010 - ATR diagnostics for monitoring
+    let get_atr_diagnostics_route = base_path
+        .clone()
+        .and(warp::path("atr-diagnostics"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_atr_diagnostics);
+
+    // GET /api/paper-trading/signals-history
+    // @spec:FR-AI-012 - Signal Outcome Tracking
+    // Returns AI signals with their outcomes (win/loss/pending)
+    let get_signals_history_route = base_path
+        .clone()
+        .and(warp::path("signals-history"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<SignalsHistoryQuery>())
+        .and(api.clone())
+        .and_then(get_signals_history);
+
+    // GET /api/paper-trading/latest-signals
+    // @spec:FR-AI-013 - Cached Signal Display
+    // Returns the most recent signal for each symbol (for quick page load)
+    let get_latest_signals_route = base_path
+        .clone()
+        .and(warp::path("latest-signals"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_latest_signals);
+
+    // POST /api/paper-trading/backtest
+    // @spec:FR-STRATEGIES-008 - Candle replay backtest with current settings
+    let backtest_route = base_path
+        .clone()
+        .and(warp::path("backtest"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(run_backtest);
+
+    // POST /api/paper-trading/walk-forward
+    // @spec:FR-STRATEGIES-007 - Walk-forward / out-of-sample parameter optimization
+    let walk_forward_route = base_path
+        .clone()
+        .and(warp::path("walk-forward"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(run_walk_forward);
+
+    // POST /api/paper-trading/monte-carlo
+    // @spec:FR-PORTFOLIO-003 - Monte Carlo drawdown / equity confidence intervals
+    let monte_carlo_route = base_path
+        .clone()
+        .and(warp::path("monte-carlo"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(run_monte_carlo);
+
+    // GET /api/paper-trading/strategies
+    // @spec:FR-STRATEGIES-005 - Runtime strategy enable/disable
+    let get_strategies_route = base_path
+        .clone()
+        .and(warp::path("strategies"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_strategies);
+
+    // PUT /api/paper-trading/strategies
+    let update_enabled_strategies_route = base_path
+        .clone()
+        .and(warp::path("strategies"))
+        .and(warp::path::end())
+        .and(warp::put())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(update_enabled_strategies);
+
+    // POST /api/paper-trading/strategies/enable
+    let enable_strategy_route = base_path
+        .clone()
+        .and(warp::path("strategies"))
+        .and(warp::path("enable"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(enable_strategy);
+
+    // POST /api/paper-trading/strategies/disable
+    let disable_strategy_route = base_path
+        .clone()
+        .and(warp::path("strategies"))
+        .and(warp::path("disable"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(disable_strategy);
+
+    // GET /api/paper-trading/strategies/performance
+    let strategy_performance_route = base_path
+        .clone()
+        .and(warp::path("strategies"))
+        .and(warp::path("performance"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_strategy_performance);
+
+    // GET /api/paper-trading/pair-trades
+    // @spec:FR-STRATEGIES-005 - Pairs spread trading
+    let get_pair_trades_route = base_path
+        .clone()
+        .and(warp::path("pair-trades"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(api.clone())
+        .and_then(get_pair_trades);
+
+    // POST /api/paper-trading/pair-trades
+    let open_pair_trade_route = base_path
+        .clone()
+        .and(warp::path("pair-trades"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(api.clone())
+        .and_then(open_pair_trade);
+
+    // POST /api/paper-trading/pair-trades/{pair_id}/close
+    let close_pair_trade_route = base_path
+        .clone()
+        .and(warp::path("pair-trades"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("close"))
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(api.clone())
+        .and_then(close_pair_trade);
+
+    status_route
             .or(portfolio_route)
             .or(open_trades_route)
             .or(closed_trades_route)
             .or(close_trade_route)
+            .or(scale_in_trade_route)
+            .or(take_profit_levels_route)
             .or(update_settings_route)
             .or(get_strategy_settings_route)
             .or(update_strategy_settings_route)
@@ -799,14 +1133,29 @@ impl PaperTradingApi {
             .or(get_signals_history_route)
             // @spec:FR-AI-013 - Cached Signal Display
             .or(get_latest_signals_route)
-            .with(cors)
-    }
+            // @spec:FR-STRATEGIES-008 - Strategy Backtesting
+            .or(backtest_route)
+            // @spec:FR-STRATEGIES-007 - Strategy Optimizer
+            .or(walk_forward_route)
+            // @spec:FR-PORTFOLIO-003 - Monte Carlo robustness analysis
+            .or(monte_carlo_route)
+            // @spec:FR-STRATEGIES-005 - Runtime strategy enable/disable
+            .or(get_strategies_route)
+            .or(update_enabled_strategies_route)
+            .or(enable_strategy_route)
+            .or(disable_strategy_route)
+            .or(strategy_performance_route)
+            // @spec:FR-STRATEGIES-005 - Pairs spread trading
+            .or(get_pair_trades_route)
+            .or(open_pair_trade_route)
+            .or(close_pair_trade_route)
 }
 
 impl Clone for PaperTradingApi {
     fn clone(&self) -> Self {
         Self {
             engine: Arc::clone(&self.engine),
+            portfolios: self.portfolios.clone(),
         }
     }
 }
@@ -818,8 +1167,130 @@ fn with_api(
     warp::any().map(move || Arc::clone(&api))
 }
 
+/// Portfolio segment of a `/paper-trading/{portfolio}/...` request path
+fn portfolio_name_from_path(path: &str) -> Option<&str> {
+    let mut segments = path.split('/');
+    segments.find(|segment| *segment == "paper-trading")?;
+    segments.next().filter(|segment| !segment.is_empty())
+}
+
+// Match the segment of an existing named portfolio, rejecting unknown names with 404
+// before any method filter can turn the rejection into a 405
+fn existing_portfolio(
+    portfolios: PaperPortfolioRegistry,
+) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::path::param::<String>()
+        .and_then(move |name: String| {
+            let portfolios = portfolios.clone();
+            async move {
+                match portfolios.get(&name).await {
+                    Some(_) => Ok(()),
+                    None => Err(warp::reject::not_found()),
+                }
+            }
+        })
+        .untuple_one()
+}
+
+// Inject the API of the named portfolio in the request path, or reject with 404
+fn with_named_portfolio(
+    portfolios: PaperPortfolioRegistry,
+) -> impl Filter<Extract = (Arc<PaperTradingApi>,), Error = Rejection> + Clone {
+    warp::path::full().and_then(move |path: warp::path::FullPath| {
+        let portfolios = portfolios.clone();
+        async move {
+            let name =
+                portfolio_name_from_path(path.as_str()).ok_or_else(warp::reject::not_found)?;
+            let engine = portfolios
+                .get(name)
+                .await
+                .ok_or_else(warp::reject::not_found)?;
+            Ok::<_, Rejection>(Arc::new(PaperTradingApi { engine, portfolios }))
+        }
+    })
+}
+
 // API Handler Functions
 
+/// List the default and named portfolios with their status
+async fn list_portfolios(api: Arc<PaperTradingApi>) -> Result<impl Reply, Rejection> {
+    let mut names = vec![DEFAULT_PORTFOLIO_NAME.to_string()];
+    names.extend(api.portfolios.names().await);
+
+    let mut portfolios = Vec::with_capacity(names.len());
+    for name in names {
+        if let Some(engine) = api.portfolios.get(&name).await {
+            portfolios.push(serde_json::json!({
+                "name": name,
+                "is_running": engine.is_running().await,
+                "portfolio": engine.get_portfolio_status().await,
+            }));
+        }
+    }
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&ApiResponse::success(portfolios)),
+        StatusCode::OK,
+    ))
+}
+
+/// Create a named portfolio and, unless `start` is false, start it
+async fn create_portfolio(
+    request: CreatePortfolioRequest,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    let engine = match api.portfolios.create(&request.name, request.settings).await {
+        Ok(engine) => engine,
+        Err(e) => {
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+                StatusCode::BAD_REQUEST,
+            ))
+        },
+    };
+
+    if request.start {
+        if let Err(e) = engine.start().await {
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&ApiResponse::<()>::error(format!(
+                    "Portfolio {} created but failed to start: {}",
+                    request.name, e
+                ))),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ));
+        }
+    }
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&ApiResponse::success(serde_json::json!({
+            "name": request.name,
+            "is_running": engine.is_running().await,
+            "message": "Portfolio created",
+        }))),
+        StatusCode::CREATED,
+    ))
+}
+
+/// Stop and remove a named portfolio
+async fn delete_portfolio(
+    name: String,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    match api.portfolios.remove(&name).await {
+        Ok(()) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::success(serde_json::json!({
+                "name": name,
+                "message": "Portfolio removed",
+            }))),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+            StatusCode::NOT_FOUND,
+        )),
+    }
+}
+
 /// Get paper trading engine status
 async fn get_status(api: Arc<PaperTradingApi>) -> Result<impl Reply, Rejection> {
     let portfolio_status = api.engine.get_portfolio_status().await;
@@ -867,14 +1338,36 @@ async fn get_closed_trades(api: Arc<PaperTradingApi>) -> Result<impl Reply, Reje
     ))
 }
 
-/// Close a specific trade
+/// Close a specific trade, or part of it when `fraction` is below 1
 async fn close_trade(
     trade_id: String,
-    _request: CloseTradeRequest,
+    request: CloseTradeRequest,
     api: Arc<PaperTradingApi>,
 ) -> Result<impl Reply, Rejection> {
     use crate::paper_trading::CloseReason;
 
+    if let Some(fraction) = request.fraction.filter(|f| *f < 1.0) {
+        return match api
+            .engine
+            .partial_close_trade(&trade_id, fraction, CloseReason::Manual)
+            .await
+        {
+            Ok(realized_pnl) => Ok(warp::reply::with_status(
+                warp::reply::json(&ApiResponse::success(serde_json::json!({
+                    "trade_id": trade_id,
+                    "fraction": fraction,
+                    "realized_pnl": realized_pnl,
+                    "message": "Trade partially closed",
+                }))),
+                StatusCode::OK,
+            )),
+            Err(e) => Ok(warp::reply::with_status(
+                warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+                StatusCode::BAD_REQUEST,
+            )),
+        };
+    }
+
     match api.engine.close_trade(&trade_id, CloseReason::Manual).await {
         Ok(_) => {
             let response = serde_json::json!({
@@ -894,6 +1387,55 @@ async fn close_trade(
     }
 }
 
+/// Add to an open trade at the current price
+async fn scale_in_trade(
+    trade_id: String,
+    request: ScaleInRequest,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    match api.engine.scale_in_trade(&trade_id, request.quantity).await {
+        Ok(()) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::success(serde_json::json!({
+                "trade_id": trade_id,
+                "quantity": request.quantity,
+                "message": "Position scaled in",
+            }))),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+            StatusCode::BAD_REQUEST,
+        )),
+    }
+}
+
+/// Set multi-level take profits on an open trade
+async fn set_take_profit_levels(
+    trade_id: String,
+    request: SetTakeProfitLevelsRequest,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    let levels: Vec<(f64, f64)> = request
+        .levels
+        .iter()
+        .map(|level| (level.price, level.fraction))
+        .collect();
+    match api.engine.set_take_profit_levels(&trade_id, &levels).await {
+        Ok(()) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::success(serde_json::json!({
+                "trade_id": trade_id,
+                "levels": levels.len(),
+                "message": "Take profit levels set",
+            }))),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+            StatusCode::BAD_REQUEST,
+        )),
+    }
+}
+
 /// Update paper trading settings
 async fn update_settings(
     request: UpdateSettingsRequest,
@@ -998,37 +1540,49 @@ async fn create_manual_order(
 
     // Validate order type
     let order_type = request.order_type.to_lowercase();
-    if !["market", "limit", "stop-limit"].contains(&order_type.as_str()) {
+    if !["market", "limit", "stop-limit", "bracket", "oco"].contains(&order_type.as_str()) {
         return Ok(warp::reply::with_status(
             warp::reply::json(&ApiResponse::<()>::error(
-                "Invalid order type. Must be 'market', 'limit', or 'stop-limit'".to_string(),
+                "Invalid order type. Must be 'market', 'limit', 'stop-limit', 'bracket' or 'oco'"
+                    .to_string(),
             )),
             StatusCode::BAD_REQUEST,
         ));
     }
 
-    // For limit and stop-limit orders, price is required
-    if (order_type == "limit" || order_type == "stop-limit") && request.price.is_none() {
+    // For limit, stop-limit and OCO orders, price is required
+    if ["limit", "stop-limit", "oco"].contains(&order_type.as_str()) && request.price.is_none() {
         return Ok(warp::reply::with_status(
             warp::reply::json(&ApiResponse::<()>::error(
-                "Price is required for limit and stop-limit orders".to_string(),
+                "Price is required for limit, stop-limit and oco orders".to_string(),
             )),
             StatusCode::BAD_REQUEST,
         ));
     }
 
-    // For stop-limit orders, stop_price is also required
-    if order_type == "stop-limit" && request.stop_price.is_none() {
+    // For stop-limit and OCO orders, stop_price is also required
+    if (order_type == "stop-limit" || order_type == "oco") && request.stop_price.is_none() {
         return Ok(warp::reply::with_status(
             warp::reply::json(&ApiResponse::<()>::error(
-                "Stop price is required for stop-limit orders".to_string(),
+                "Stop price is required for stop-limit and oco orders".to_string(),
+            )),
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    // Bracket orders need both exit prices
+    if order_type == "bracket"
+        && (request.stop_loss_price.is_none() || request.take_profit_price.is_none())
+    {
+        return Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::<()>::error(
+                "Stop loss and take profit prices are required for bracket orders".to_string(),
             )),
             StatusCode::BAD_REQUEST,
         ));
     }
 
     // Execute the manual order
-    let is_stop_limit = order_type == "stop-limit";
     match api
         .engine
         .execute_manual_order(crate::paper_trading::ManualOrderParams {
@@ -1041,14 +1595,20 @@ async fn create_manual_order(
             leverage: request.leverage,
             stop_loss_pct: request.stop_loss_pct,
             take_profit_pct: request.take_profit_pct,
+            time_in_force: request.time_in_force,
+            stop_limit_price: request.stop_limit_price,
+            stop_loss_price: request.stop_loss_price,
+            take_profit_price: request.take_profit_price,
         })
         .await
     {
         Ok(result) => {
-            // For stop-limit orders, trade_id is the pending order ID and execution_price is None
+            // For stop-limit and resting limit orders, trade_id is the pending order ID
+            // and execution_price is None
+            let is_pending = result.success && result.execution_price.is_none();
             let status = if !result.success {
                 "failed".to_string()
-            } else if is_stop_limit && result.execution_price.is_none() {
+            } else if is_pending {
                 "pending".to_string()
             } else {
                 "filled".to_string()
@@ -1059,11 +1619,22 @@ async fn create_manual_order(
                     .error_message
                     .clone()
                     .unwrap_or("Unknown error".to_string())
-            } else if is_stop_limit && result.execution_price.is_none() {
+            } else if is_pending && order_type == "stop-limit" {
                 format!(
                     "Stop-limit order created. Will trigger when price reaches {}",
                     request.stop_price.unwrap_or(0.0)
                 )
+            } else if is_pending && order_type == "oco" {
+                format!(
+                    "OCO order created. Limit at {} and stop at {}; one fill cancels the other",
+                    request.price.unwrap_or(0.0),
+                    request.stop_price.unwrap_or(0.0)
+                )
+            } else if is_pending {
+                format!(
+                    "Limit order resting at {}. Will fill when price crosses it",
+                    request.price.unwrap_or(0.0)
+                )
             } else {
                 "Order executed successfully".to_string()
             };
@@ -1096,7 +1667,7 @@ async fn create_manual_order(
     }
 }
 
-/// @spec:FR-PAPER-003 - Get all pending stop-limit orders
+/// @spec:FR-PAPER-003 - Get all pending stop-limit, limit and OCO orders
 async fn get_pending_orders(api: Arc<PaperTradingApi>) -> Result<impl Reply, Rejection> {
     let orders = api.engine.get_pending_orders().await;
 
@@ -1106,7 +1677,7 @@ async fn get_pending_orders(api: Arc<PaperTradingApi>) -> Result<impl Reply, Rej
     ))
 }
 
-/// @spec:FR-PAPER-003 - Cancel a pending stop-limit order
+/// @spec:FR-PAPER-003 - Cancel a pending order (an OCO order list id cancels every leg)
 async fn cancel_pending_order(
     order_id: String,
     api: Arc<PaperTradingApi>,
@@ -1201,14 +1772,8 @@ async fn get_strategy_settings(api: Arc<PaperTradingApi>) -> Result<impl Reply,
         },
         engine: EngineSettings {
             min_confidence_threshold: engine_settings.strategy.min_ai_confidence, // 🎯 ACTUAL THRESHOLD
-            signal_combination_mode: "WeightedAverage".to_string(),
-            enabled_strategies: vec![
-                "RSI Strategy".to_string(),
-                "MACD Strategy".to_string(),
-                "Volume Strategy".to_string(),
-                "Bollinger Bands Strategy".to_string(),
-                "Stochastic Strategy".to_string(),
-            ],
+            signal_combination_mode: format!("{:?}", engine_settings.strategy.combination_method),
+            enabled_strategies: api.engine.get_enabled_strategies().await,
             market_condition: "Trending".to_string(),
             risk_level: "Moderate".to_string(),
             data_resolution: engine_settings.strategy.backtesting.data_resolution.clone(), // Current timeframe
@@ -1247,8 +1812,24 @@ async fn update_strategy_settings(
         risk_settings.take_profit_percent
     );
 
+    let combination_method = match serde_json::from_value(serde_json::Value::String(
+        request.settings.engine.signal_combination_mode.clone(),
+    )) {
+        Ok(method) => method,
+        Err(_) => {
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&ApiResponse::<()>::error(format!(
+                    "Unknown signal_combination_mode: {}",
+                    request.settings.engine.signal_combination_mode
+                ))),
+                StatusCode::BAD_REQUEST,
+            ));
+        },
+    };
+
     // Update all settings in current_settings
     current_settings.strategy.min_ai_confidence = confidence_threshold;
+    current_settings.strategy.combination_method = combination_method;
     current_settings.strategy.backtesting.data_resolution = data_resolution.clone();
     current_settings.strategy.market_preset = request.settings.market_preset.clone();
 
@@ -1263,6 +1844,18 @@ async fn update_strategy_settings(
     current_settings.risk.max_consecutive_losses = risk_settings.max_consecutive_losses;
     current_settings.risk.correlation_limit = risk_settings.correlation_limit; // KEY FIX!
 
+    // An empty list leaves the running strategy set unchanged
+    let enabled_strategies = request.settings.engine.enabled_strategies.clone();
+    if !enabled_strategies.is_empty() {
+        if let Err(e) = api.engine.set_enabled_strategies(enabled_strategies).await {
+            log::warn!("❌ Rejected enabled strategies: {e}");
+            return Ok(warp::reply::with_status(
+                warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+    }
+
     // Save all settings to database and memory using the engine's update_settings method
     match api.engine.update_settings(current_settings).await {
         Ok(_) => {
@@ -1282,6 +1875,8 @@ async fn update_strategy_settings(
                     "market_condition": request.settings.engine.market_condition,
                     "risk_level": request.settings.engine.risk_level,
                     "market_preset": request.settings.market_preset,
+                    "enabled_strategies": api.engine.get_enabled_strategies().await,
+                    "combination_method": combination_method,
                 },
             });
 
@@ -2495,11 +3090,232 @@ async fn get_atr_diagnostics(api: Arc<PaperTradingApi>) -> Result<impl Reply, Re
     });
 
     Ok(warp::reply::with_status(
-        warp::reply::json(&ApiResponse::success(response)),
+        warp::reply::json(&ApiResponse::success(response)),
+        StatusCode::OK,
+    ))
+}
+
+/// Run a candle-replay backtest for a symbol with the current settings
+/// @spec:FR-STRATEGIES-008 - Strategy Backtesting
+async fn run_backtest(
+    request: BacktestRequest,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    let symbol = request.symbol.to_uppercase();
+    log::info!("🔁 Running backtest for {}", symbol);
+
+    match api.engine.run_backtest(&symbol).await {
+        Ok(report) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::success(report)),
+            StatusCode::OK,
+        )),
+        Err(e) => {
+            log::error!("Backtest failed for {}: {}", symbol, e);
+            Ok(warp::reply::with_status(
+                warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+                StatusCode::BAD_REQUEST,
+            ))
+        },
+    }
+}
+
+/// Run walk-forward optimization for one strategy with the current settings
+/// @spec:FR-STRATEGIES-007 - Strategy Optimizer
+async fn run_walk_forward(
+    request: WalkForwardRequest,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    let symbol = request.symbol.to_uppercase();
+    log::info!(
+        "🔁 Running walk-forward optimization of {} on {}",
+        request.strategy,
+        symbol
+    );
+
+    match api
+        .engine
+        .run_walk_forward(&symbol, &request.strategy)
+        .await
+    {
+        Ok(report) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::success(report)),
+            StatusCode::OK,
+        )),
+        Err(e) => {
+            log::error!("Walk-forward optimization failed for {}: {}", symbol, e);
+            Ok(warp::reply::with_status(
+                warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+                StatusCode::BAD_REQUEST,
+            ))
+        },
+    }
+}
+
+/// Run Monte Carlo analysis over the closed paper trades
+/// @spec:FR-PORTFOLIO-003 - P&L Calculation
+async fn run_monte_carlo(
+    config: MonteCarloConfig,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    match api.engine.run_monte_carlo(&config).await {
+        Ok(report) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::success(report)),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+            StatusCode::BAD_REQUEST,
+        )),
+    }
+}
+
+/// List the loaded strategies and whether each runs in the signal loop
+/// @spec:FR-STRATEGIES-005 - Strategy Parameters
+async fn get_strategies(api: Arc<PaperTradingApi>) -> Result<impl Reply, Rejection> {
+    let strategies: Vec<StrategyStatus> = api
+        .engine
+        .get_strategy_statuses()
+        .await
+        .into_iter()
+        .map(|(name, enabled)| StrategyStatus { name, enabled })
+        .collect();
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&ApiResponse::success(strategies)),
+        StatusCode::OK,
+    ))
+}
+
+/// Replace the strategies that run in the signal loop
+async fn update_enabled_strategies(
+    request: UpdateEnabledStrategiesRequest,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    if request.enabled_strategies.is_empty() {
+        return Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::<()>::error(
+                "At least one strategy must be enabled".to_string(),
+            )),
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    log::info!(
+        "Setting enabled strategies: {:?}",
+        request.enabled_strategies
+    );
+    strategy_toggle_reply(
+        api.engine
+            .set_enabled_strategies(request.enabled_strategies)
+            .await,
+        &api,
+    )
+    .await
+}
+
+/// Start running one strategy in the signal loop
+async fn enable_strategy(
+    request: StrategyToggleRequest,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    log::info!("Enabling strategy: {}", request.name);
+    strategy_toggle_reply(api.engine.enable_strategy(&request.name).await, &api).await
+}
+
+/// Stop running one strategy in the signal loop
+async fn disable_strategy(
+    request: StrategyToggleRequest,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    log::info!("Disabling strategy: {}", request.name);
+    strategy_toggle_reply(api.engine.disable_strategy(&request.name).await, &api).await
+}
+
+/// Get the combination mode with per-strategy hit rates and learned weights
+async fn get_strategy_performance(api: Arc<PaperTradingApi>) -> Result<impl Reply, Rejection> {
+    let performance = api.engine.get_strategy_performance().await;
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&ApiResponse::success(performance)),
+        StatusCode::OK,
+    ))
+}
+
+/// Reply with the resulting enabled strategies, or the rejection reason
+async fn strategy_toggle_reply(
+    result: anyhow::Result<()>,
+    api: &PaperTradingApi,
+) -> Result<warp::reply::WithStatus<warp::reply::Json>, Rejection> {
+    match result {
+        Ok(()) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::success(serde_json::json!({
+                "enabled_strategies": api.engine.get_enabled_strategies().await,
+            }))),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+            StatusCode::BAD_REQUEST,
+        )),
+    }
+}
+
+/// Get pair trades with combined PnL
+async fn get_pair_trades(api: Arc<PaperTradingApi>) -> Result<impl Reply, Rejection> {
+    let pairs = api.engine.get_pair_trades().await;
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&ApiResponse::success(pairs)),
         StatusCode::OK,
     ))
 }
 
+/// Open both legs of a pair trade
+async fn open_pair_trade(
+    request: PairTradeParams,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    log::info!(
+        "Opening pair trade: {:?} {} vs {}",
+        request.direction,
+        request.symbol,
+        request.pair_symbol
+    );
+    match api.engine.open_pair_trade(request).await {
+        Ok(summary) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::success(summary)),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+            StatusCode::BAD_REQUEST,
+        )),
+    }
+}
+
+/// Close both legs of a pair trade
+async fn close_pair_trade(
+    pair_id: String,
+    api: Arc<PaperTradingApi>,
+) -> Result<impl Reply, Rejection> {
+    use crate::paper_trading::CloseReason;
+
+    match api
+        .engine
+        .close_pair_trade(&pair_id, CloseReason::Manual)
+        .await
+    {
+        Ok(summary) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::success(summary)),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(warp::reply::with_status(
+            warp::reply::json(&ApiResponse::<()>::error(e.to_string())),
+            StatusCode::BAD_REQUEST,
+        )),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2659,6 +3475,7 @@ mod tests {
         let request = CloseTradeRequest {
             trade_id: Some("trade_789".to_string()),
             reason: Some("Stop loss hit".to_string()),
+            fraction: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -3434,6 +4251,7 @@ mod tests {
         let request_body = CloseTradeRequest {
             trade_id: Some("test_trade_123".to_string()),
             reason: Some("Test close".to_string()),
+            fraction: None,
         };
 
         let response = request()
@@ -3457,6 +4275,7 @@ mod tests {
         let request_body = CloseTradeRequest {
             trade_id: Some("test_trade_456".to_string()),
             reason: Some("Manual stop loss".to_string()),
+            fraction: None,
         };
 
         let response = request()
@@ -3479,6 +4298,7 @@ mod tests {
         let request_body = CloseTradeRequest {
             trade_id: Some("test_trade_789".to_string()),
             reason: None,
+            fraction: None,
         };
 
         let response = request()
@@ -3575,7 +4395,10 @@ mod tests {
                 engine: EngineSettings {
                     min_confidence_threshold: 0.7,
                     signal_combination_mode: "WeightedAverage".to_string(),
-                    enabled_strategies: vec!["RSI".to_string(), "MACD".to_string()],
+                    enabled_strategies: vec![
+                        "RSI Strategy".to_string(),
+                        "MACD Strategy".to_string(),
+                    ],
                     market_condition: "Trending".to_string(),
                     risk_level: "Moderate".to_string(),
                     data_resolution: "15m".to_string(),
@@ -3962,6 +4785,7 @@ mod tests {
         let request_body = CloseTradeRequest {
             trade_id: Some("".to_string()),
             reason: None,
+            fraction: None,
         };
 
         let response = request()
@@ -4096,6 +4920,7 @@ mod tests {
         let original = CloseTradeRequest {
             trade_id: Some("trade_123".to_string()),
             reason: Some("Stop loss triggered".to_string()),
+            fraction: None,
         };
 
         let json = serde_json::to_string(&original).unwrap();
@@ -4642,6 +5467,189 @@ mod tests {
         assert!(resp.status().is_success() || resp.status().is_client_error());
     }
 
+    #[tokio::test]
+    async fn test_create_oco_order_and_cancel_list() {
+        let api = create_test_api_no_db().await;
+        let routes = api.clone().routes();
+
+        let body = serde_json::json!({
+            "symbol": "ETHUSDT",
+            "side": "buy",
+            "order_type": "oco",
+            "quantity": 0.01,
+            "price": 2900.0,
+            "stop_price": 3100.0,
+            "stop_limit_price": 3110.0
+        });
+        let resp = request()
+            .method("POST")
+            .path("/paper-trading/orders")
+            .json(&body)
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(body["data"]["status"], "pending");
+        let order_list_id = body["data"]["trade_id"].as_str().unwrap().to_string();
+        assert!(order_list_id.starts_with("oco-"));
+
+        let resp = request()
+            .method("GET")
+            .path("/paper-trading/pending-orders")
+            .reply(&routes)
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(body["data"].as_array().unwrap().len(), 2);
+
+        let resp = request()
+            .method("DELETE")
+            .path(&format!("/paper-trading/pending-orders/{}", order_list_id))
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(api.engine.get_pending_order_count(None).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_bracket_order_requires_exit_prices() {
+        let api = create_test_api_no_db().await;
+        let routes = api.routes();
+
+        let body = serde_json::json!({
+            "symbol": "BTCUSDT",
+            "side": "buy",
+            "order_type": "bracket",
+            "quantity": 0.01,
+            "price": 50000.0,
+            "stop_loss_price": 49000.0
+        });
+        let resp = request()
+            .method("POST")
+            .path("/paper-trading/orders")
+            .json(&body)
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body = String::from_utf8_lossy(resp.body());
+        assert!(body.contains("bracket"));
+    }
+
+    #[tokio::test]
+    async fn test_partial_close_scale_in_and_take_profit_levels_routes() {
+        let api = create_test_api_no_db().await;
+        let routes = api.routes();
+
+        let cases = [
+            (
+                "POST",
+                "/paper-trading/trades/missing/close",
+                serde_json::json!({ "fraction": 0.5 }),
+                "Trade not found",
+            ),
+            (
+                "POST",
+                "/paper-trading/trades/missing/close",
+                serde_json::json!({ "fraction": 0.0 }),
+                "fraction",
+            ),
+            (
+                "POST",
+                "/paper-trading/trades/missing/scale-in",
+                serde_json::json!({ "quantity": 0.1 }),
+                "Trade not found",
+            ),
+            (
+                "PUT",
+                "/paper-trading/trades/missing/take-profit-levels",
+                serde_json::json!({ "levels": [{ "price": 52000.0, "fraction": 0.5 }] }),
+                "Open trade not found",
+            ),
+        ];
+        for (method, path, body, expected) in cases {
+            let resp = request()
+                .method(method)
+                .path(path)
+                .json(&body)
+                .reply(&routes)
+                .await;
+            assert_eq!(
+                resp.status(),
+                StatusCode::BAD_REQUEST,
+                "{} {}",
+                method,
+                path
+            );
+            let body = String::from_utf8_lossy(resp.body());
+            assert!(body.contains(expected), "{}: {}", path, body);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_named_portfolio_routes() {
+        let api = create_test_api_no_db().await;
+        let routes = api.routes();
+
+        let resp = request()
+            .method("POST")
+            .path("/paper-trading/portfolios")
+            .json(&serde_json::json!({ "name": "aggressive", "start": false }))
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+
+        let resp = request()
+            .method("POST")
+            .path("/paper-trading/portfolios")
+            .json(&serde_json::json!({ "name": "trades", "start": false }))
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let resp = request()
+            .method("GET")
+            .path("/paper-trading/portfolios")
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        let names: Vec<&str> = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["default", "aggressive"]);
+
+        let resp = request()
+            .method("GET")
+            .path("/paper-trading/aggressive/status")
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+        assert_eq!(body["data"]["is_running"], false);
+
+        let resp = request()
+            .method("GET")
+            .path("/paper-trading/missing/status")
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        let resp = request()
+            .method("DELETE")
+            .path("/paper-trading/portfolios/aggressive")
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let resp = request()
+            .method("GET")
+            .path("/paper-trading/aggressive/status")
+            .reply(&routes)
+            .await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
     #[tokio::test]
     async fn test_get_pending_orders_handler() {
         let api = create_test_api_no_db().await;
@@ -5362,6 +6370,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let json = serde_json::to_string(&order).unwrap();
@@ -6017,6 +7029,10 @@ mod tests {
             leverage: Some(20),
             stop_loss_pct: Some(5.0),
             take_profit_pct: Some(10.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         assert_eq!(request.symbol, "BTCUSDT");
@@ -6030,6 +7046,7 @@ mod tests {
         let request = CloseTradeRequest {
             trade_id: Some("minimal".to_string()),
             reason: None,
+            fraction: None,
         };
 
         assert_eq!(request.trade_id, Some("minimal".to_string()));
@@ -6112,6 +7129,7 @@ mod tests {
         let req_body = CloseTradeRequest {
             trade_id: Some("test_123".to_string()),
             reason: Some("Manual close".to_string()),
+            fraction: None,
         };
 
         let resp = request()
@@ -6137,6 +7155,7 @@ mod tests {
         let req_body = CloseTradeRequest {
             trade_id: Some("test_456".to_string()),
             reason: None,
+            fraction: None,
         };
 
         let resp = request()
@@ -6221,6 +7240,10 @@ mod tests {
             leverage: Some(1),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let resp = request()
@@ -6248,6 +7271,10 @@ mod tests {
             leverage: Some(2),
             stop_loss_pct: Some(5.0),
             take_profit_pct: Some(10.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let resp = request()
@@ -6275,6 +7302,10 @@ mod tests {
             leverage: Some(3),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let resp = request()
@@ -6302,6 +7333,10 @@ mod tests {
             leverage: Some(1),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let resp = request()
@@ -6334,6 +7369,10 @@ mod tests {
             leverage: Some(1),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let resp = request()
@@ -7356,6 +8395,10 @@ mod tests {
             leverage: Some(1),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let debug_str = format!("{:?}", request);
@@ -7374,6 +8417,7 @@ mod tests {
             let req_body = CloseTradeRequest {
                 trade_id: Some(trade_id.to_string()),
                 reason: None,
+                fraction: None,
             };
 
             let resp = request()
@@ -7416,6 +8460,7 @@ mod tests {
         let close_req = CloseTradeRequest {
             trade_id: Some("test".to_string()),
             reason: None,
+            fraction: None,
         };
         serde_json::to_string(&close_req).unwrap();
 
@@ -7429,6 +8474,10 @@ mod tests {
             leverage: Some(1),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
         serde_json::to_string(&order_req).unwrap();
 
@@ -8693,6 +9742,10 @@ mod tests {
             leverage: Some(5),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(4.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -9304,7 +10357,7 @@ mod tests {
                 "engine": {
                     "min_confidence_threshold": 0.85,
                     "signal_combination_mode": "Consensus",
-                    "enabled_strategies": ["RSI", "MACD", "Bollinger"],
+                    "enabled_strategies": ["RSI Strategy", "MACD Strategy", "Bollinger Bands Strategy"],
                     "market_condition": "Ranging",
                     "risk_level": "Conservative",
                     "data_resolution": "1h"
@@ -9351,7 +10404,7 @@ mod tests {
                 },
                 "engine": {
                     "min_confidence_threshold": 0.6,
-                    "signal_combination_mode": "Any",
+                    "signal_combination_mode": "MaxConfidence",
                     "enabled_strategies": [],
                     "market_condition": "Volatile",
                     "risk_level": "Aggressive",
@@ -10078,6 +11131,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(4.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let json = serde_json::to_string(&request);
@@ -10092,6 +11149,7 @@ mod tests {
         let request = CloseTradeRequest {
             trade_id: Some("test_123".to_string()),
             reason: Some("Manual close".to_string()),
+            fraction: None,
         };
 
         let json = serde_json::to_string(&request);
@@ -10326,6 +11384,10 @@ mod tests {
             leverage: Some(5),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         assert_eq!(request.order_type, "LIMIT");
@@ -10338,6 +11400,7 @@ mod tests {
         let request = CloseTradeRequest {
             trade_id: Some("abc123".to_string()),
             reason: None,
+            fraction: None,
         };
 
         assert_eq!(request.trade_id, Some("abc123".to_string()));
@@ -10356,6 +11419,10 @@ mod tests {
             leverage: None,
             stop_loss_pct: Some(3.0),
             take_profit_pct: Some(6.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         assert_eq!(request.order_type, "MARKET");
@@ -10412,6 +11479,7 @@ mod tests {
         let request = CloseTradeRequest {
             trade_id: Some("trade_xyz".to_string()),
             reason: Some("Stop loss triggered".to_string()),
+            fraction: None,
         };
 
         assert_eq!(request.trade_id, Some("trade_xyz".to_string()));
@@ -10431,6 +11499,10 @@ mod tests {
             leverage: Some(2),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         assert_eq!(request.order_type, "STOP_MARKET");
@@ -10504,6 +11576,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -11329,6 +12405,10 @@ mod tests {
             leverage: Some(3),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         assert_eq!(request.order_type, "limit");
@@ -11348,6 +12428,10 @@ mod tests {
             leverage: Some(5),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         assert_eq!(request.order_type, "stop-limit");
@@ -11360,6 +12444,7 @@ mod tests {
         let request = CloseTradeRequest {
             trade_id: Some("trade-long-id-12345".to_string()),
             reason: Some("Manual close due to market uncertainty and external factors".to_string()),
+            fraction: None,
         };
 
         let json = serde_json::to_string(&request).unwrap();
@@ -11753,6 +12838,7 @@ mod tests {
         let close_req = CloseTradeRequest {
             trade_id: Some("test-trade-123".to_string()),
             reason: Some("Manual close for testing".to_string()),
+            fraction: None,
         };
 
         let filter = warp::path("paper-trading")
@@ -11916,6 +13002,10 @@ mod tests {
             leverage: Some(1),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(4.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let filter = warp::path("paper-trading")
@@ -12461,6 +13551,7 @@ mod tests {
         let close_req = CloseTradeRequest {
             trade_id: None,
             reason: Some("test close".to_string()),
+            fraction: None,
         };
 
         // trade_id not found → returns error, but exercises the handler
@@ -12814,7 +13905,7 @@ mod tests {
                 engine: EngineSettings {
                     min_confidence_threshold: 0.7,
                     signal_combination_mode: "WeightedAverage".to_string(),
-                    enabled_strategies: vec!["RSI".to_string()],
+                    enabled_strategies: vec!["RSI Strategy".to_string()],
                     market_condition: "Trending".to_string(),
                     risk_level: "Moderate".to_string(),
                     data_resolution: "15m".to_string(),
@@ -12858,7 +13949,7 @@ mod tests {
         // First create a trade via limit order (no network needed), then close it
         let engine = create_test_engine().await;
 
-        // Create a limit order which will execute immediately at the given price
+        // Create a limit order; without a market price it rests instead of filling
         let order_params = crate::paper_trading::ManualOrderParams {
             symbol: "BTCUSDT".to_string(),
             side: "buy".to_string(),
@@ -12869,13 +13960,17 @@ mod tests {
             leverage: Some(1),
             stop_loss_pct: Some(5.0),
             take_profit_pct: Some(10.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(order_params).await;
 
-        // If trade was created successfully, try to close it
+        // If a trade was filled, try to close it
         if let Ok(exec_result) = result {
-            if exec_result.success {
+            if exec_result.success && exec_result.execution_price.is_some() {
                 if let Some(trade_id) = exec_result.trade_id {
                     let api = PaperTradingApi::new(engine.clone());
                     let filter = api.routes();
@@ -12883,6 +13978,7 @@ mod tests {
                     let close_req = CloseTradeRequest {
                         trade_id: None,
                         reason: Some("test".to_string()),
+                        fraction: None,
                     };
 
                     let response = request()
@@ -12907,6 +14003,7 @@ mod tests {
         let close_req2 = CloseTradeRequest {
             trade_id: None,
             reason: None,
+            fraction: None,
         };
         let response2 = request()
             .method("POST")
@@ -13123,6 +14220,10 @@ mod tests {
             leverage: Some(1),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let create_response = request()
@@ -13486,6 +14587,10 @@ mod tests {
             leverage: Some(1),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let response = request()
@@ -13689,6 +14794,10 @@ mod tests {
             leverage: Some(1),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let response = request()
@@ -13723,4 +14832,194 @@ mod tests {
                 || response.status() == StatusCode::BAD_REQUEST
         );
     }
+
+    #[tokio::test]
+    async fn test_strategy_enable_disable_routes() {
+        let api = create_test_api_no_db().await;
+        let filter = api.clone().routes();
+
+        let response = request()
+            .method("GET")
+            .path("/paper-trading/strategies")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        let strategies = body["data"].as_array().unwrap();
+        assert_eq!(strategies.len(), 7);
+        let enabled_count = strategies.iter().filter(|s| s["enabled"] == true).count();
+        assert_eq!(enabled_count, 5);
+
+        let response = request()
+            .method("POST")
+            .path("/paper-trading/strategies/disable")
+            .json(&serde_json::json!({"name": "MACD Strategy"}))
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let enabled = api.engine.get_enabled_strategies().await;
+        assert_eq!(enabled.len(), 4);
+        assert!(!enabled.contains(&"MACD Strategy".to_string()));
+
+        let response = request()
+            .method("POST")
+            .path("/paper-trading/strategies/enable")
+            .json(&serde_json::json!({"name": "MACD Strategy"}))
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(api.engine.get_enabled_strategies().await.len(), 5);
+
+        let response = request()
+            .method("PUT")
+            .path("/paper-trading/strategies")
+            .json(&serde_json::json!({"enabled_strategies": ["RSI Strategy"]}))
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            api.engine.get_enabled_strategies().await,
+            vec!["RSI Strategy".to_string()]
+        );
+
+        // Unknown names and an empty set are rejected without changing anything
+        for (method, path, body) in [
+            (
+                "POST",
+                "/paper-trading/strategies/enable",
+                serde_json::json!({"name": "Unknown Strategy"}),
+            ),
+            (
+                "PUT",
+                "/paper-trading/strategies",
+                serde_json::json!({"enabled_strategies": []}),
+            ),
+            (
+                "POST",
+                "/paper-trading/strategies/disable",
+                serde_json::json!({"name": "RSI Strategy"}),
+            ),
+        ] {
+            let response = request()
+                .method(method)
+                .path(path)
+                .json(&body)
+                .reply(&filter)
+                .await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+        assert_eq!(
+            api.engine.get_enabled_strategies().await,
+            vec!["RSI Strategy".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strategy_combination_mode_routes() {
+        let api = create_test_api_no_db().await;
+        let filter = api.clone().routes();
+
+        let response = request()
+            .method("GET")
+            .path("/paper-trading/strategy-settings")
+            .reply(&filter)
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        let mut settings = body["data"].clone();
+
+        settings["engine"]["signal_combination_mode"] = serde_json::json!("NotAMode");
+        let response = request()
+            .method("PUT")
+            .path("/paper-trading/strategy-settings")
+            .json(&serde_json::json!({ "settings": settings }))
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        settings["engine"]["signal_combination_mode"] = serde_json::json!("Unanimous");
+        let response = request()
+            .method("PUT")
+            .path("/paper-trading/strategy-settings")
+            .json(&serde_json::json!({ "settings": settings }))
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = request()
+            .method("GET")
+            .path("/paper-trading/strategies/performance")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["data"]["combination_mode"], "Unanimous");
+        assert_eq!(body["data"]["performance"], serde_json::json!([]));
+        assert_eq!(body["data"]["learned_weights"]["RSI Strategy"], 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_pair_trade_routes() {
+        let api = create_test_api_no_db().await;
+        let filter = api.clone().routes();
+
+        let response = request()
+            .method("GET")
+            .path("/paper-trading/pair-trades")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["data"], serde_json::json!([]));
+
+        // No market prices have been received, so neither leg can be priced
+        let response = request()
+            .method("POST")
+            .path("/paper-trading/pair-trades")
+            .json(&serde_json::json!({
+                "symbol": "ETHUSDT",
+                "pair_symbol": "BTCUSDT",
+                "direction": "long",
+                "quantity": 1.0,
+                "hedge_ratio": 0.5,
+                "leverage": null,
+                "reasoning": null,
+                "confidence": null
+            }))
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert!(api.engine.get_open_trades().await.is_empty());
+
+        let response = request()
+            .method("POST")
+            .path("/paper-trading/pair-trades/missing/close")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_strategy_settings_reflect_enabled_strategies() {
+        let api = create_test_api_no_db().await;
+        let filter = api.clone().routes();
+        api.engine
+            .set_enabled_strategies(vec![
+                "RSI Strategy".to_string(),
+                "MACD Strategy".to_string(),
+            ])
+            .await
+            .unwrap();
+
+        let response = request()
+            .method("GET")
+            .path("/paper-trading/strategy-settings")
+            .reply(&filter)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(
+            body["data"]["engine"]["enabled_strategies"],
+            serde_json::json!(["RSI Strategy", "MACD Strategy"])
+        );
+    }
 }
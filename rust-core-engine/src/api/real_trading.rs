@@ -871,7 +871,7 @@ async fn get_closed_trades(api: Arc<RealTradingApi>) -> Result<impl Reply, Rejec
     }
 
     // Sort by time descending (most recent first)
-    all_trades.sort_by(|a, b| b.time.cmp(&a.time));
+    all_trades.sort_by_key(|t| std::cmp::Reverse(t.time));
 
     // Get leverage from current positions or config
     let position_leverage: std::collections::HashMap<String, u32> = engine
@@ -246,11 +246,31 @@ impl BinanceClient {
         symbol: &str,
         interval: &str,
         limit: Option<u16>,
+    ) -> Result<Vec<Kline>> {
+        self.get_klines_range(symbol, interval, None, None, limit)
+            .await
+    }
+
+    /// Klines opened within `[start_time, end_time]` (ms), oldest first. Binance returns
+    /// at most `limit` (max 1000) per call, so callers page by advancing `start_time`.
+    pub async fn get_klines_range(
+        &self,
+        symbol: &str,
+        interval: &str,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+        limit: Option<u16>,
     ) -> Result<Vec<Kline>> {
         let mut params = HashMap::new();
         params.insert("symbol".to_string(), symbol.to_uppercase());
         params.insert("interval".to_string(), interval.to_string());
 
+        if let Some(start_time) = start_time {
+            params.insert("startTime".to_string(), start_time.to_string());
+        }
+        if let Some(end_time) = end_time {
+            params.insert("endTime".to_string(), end_time.to_string());
+        }
         if let Some(limit) = limit {
             params.insert("limit".to_string(), limit.to_string());
         }
@@ -3020,6 +3040,23 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_get_klines_range_parses_klines() {
+        let port = find_free_port_binance().await;
+        let body = r#"[[1700000000000,"100.0","101.0","99.0","100.5","10.0",1700000059999,"1005.0",5,"4.0","402.0","0"]]"#;
+        spawn_binance_mock_server(port, body, 1).await;
+        let config = create_mock_config(format!("http://127.0.0.1:{}", port));
+        let client = BinanceClient::new(config).expect("Failed to create client");
+
+        let klines = client
+            .get_klines_range("BTCUSDT", "1m", Some(1_700_000_000_000), None, Some(1000))
+            .await
+            .unwrap();
+        assert_eq!(klines.len(), 1);
+        assert_eq!(klines[0].open_time, 1_700_000_000_000);
+        assert_eq!(klines[0].close, "100.5");
+    }
+
     /// Test get_futures_klines with invalid (non-array) element format (line 326)
     #[tokio::test]
     async fn test_cov43_get_futures_klines_invalid_format() {
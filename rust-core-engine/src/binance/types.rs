@@ -523,15 +523,18 @@ impl std::fmt::Display for SpotOrderType {
 }
 
 /// Time in force options
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TimeInForce {
     /// Good Till Cancelled
+    #[default]
     Gtc,
     /// Immediate or Cancel
     Ioc,
     /// Fill or Kill
     Fok,
+    /// Good Till Crossing (post-only, futures)
+    Gtx,
 }
 
 impl std::fmt::Display for TimeInForce {
@@ -540,6 +543,7 @@ impl std::fmt::Display for TimeInForce {
             TimeInForce::Gtc => write!(f, "GTC"),
             TimeInForce::Ioc => write!(f, "IOC"),
             TimeInForce::Fok => write!(f, "FOK"),
+            TimeInForce::Gtx => write!(f, "GTX"),
         }
     }
 }
@@ -44,6 +44,51 @@ struct Opt {
 
     #[structopt(short = "v", long = "verbose", parse(from_occurrences))]
     verbose: u8,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+/// Offline jobs that run against stored data instead of starting the bot
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Train the in-process ML trend model from stored candles and write it to disk
+    TrainTrendModel {
+        #[structopt(long, default_value = "BTCUSDT")]
+        symbol: String,
+        #[structopt(long, default_value = "4h")]
+        timeframe: String,
+        /// Maximum number of stored candles to train on
+        #[structopt(long, default_value = "5000")]
+        limit: i64,
+        /// Candles ahead the label looks at
+        #[structopt(long, default_value = "6")]
+        horizon: usize,
+        #[structopt(long, default_value = "models/trend_model.json")]
+        output: String,
+    },
+    /// Export stored candles as a labeled feature dataset (CSV)
+    ExportDataset {
+        #[structopt(long, default_value = "BTCUSDT")]
+        symbol: String,
+        #[structopt(long, default_value = "1h")]
+        timeframe: String,
+        /// Maximum number of stored candles to export
+        #[structopt(long, default_value = "5000")]
+        limit: i64,
+        /// Comma-separated feature specs, e.g. "rsi_14,macd_12_26_9,atr_14"
+        #[structopt(long)]
+        features: Option<String>,
+        /// Comma-separated forward horizons in candles
+        #[structopt(long, default_value = "1,6,24")]
+        horizons: String,
+        /// Forward returns within ±threshold are labeled 0
+        #[structopt(long, default_value = "0.002")]
+        label_threshold: f64,
+        /// Defaults to datasets/<symbol>_<timeframe>.csv
+        #[structopt(long)]
+        output: Option<String>,
+    },
 }
 
 #[tokio::main]
@@ -84,6 +129,10 @@ async fn main() -> Result<()> {
     // Initialize storage
     let storage = storage::Storage::new(&config.database).await?;
 
+    if let Some(command) = opt.command {
+        return run_command(command, &storage).await;
+    }
+
     // Initialize market data processor
     let mut market_data_processor = MarketDataProcessor::new(
         config.binance.clone(),
@@ -301,6 +350,40 @@ async fn main() -> Result<()> {
     )
     .await?;
 
+    // Named paper portfolios running next to the default one, each from its own YAML:
+    // PAPER_TRADING_PORTFOLIOS="conservative=config/conservative.yml,aggressive=..."
+    if let Ok(portfolios) = std::env::var("PAPER_TRADING_PORTFOLIOS") {
+        for entry in portfolios
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            let Some((name, yaml_path)) = entry.split_once('=') else {
+                tracing::warn!("⚠️ Ignoring paper portfolio entry without '=': {}", entry);
+                continue;
+            };
+            let (name, yaml_path) = (name.trim(), yaml_path.trim());
+            let settings = match PaperTradingSettings::from_yaml(yaml_path) {
+                Ok(settings) => settings,
+                Err(e) => {
+                    tracing::warn!("⚠️ Failed to load paper portfolio '{}': {}", name, e);
+                    continue;
+                },
+            };
+            match api_server
+                .paper_portfolios()
+                .create(name, Some(settings))
+                .await
+            {
+                Ok(engine) => match engine.start().await {
+                    Ok(()) => info!("📁 Paper portfolio '{}' started from {}", name, yaml_path),
+                    Err(e) => tracing::warn!("Paper portfolio '{}' failed to start: {}", name, e),
+                },
+                Err(e) => tracing::warn!("⚠️ Failed to create paper portfolio '{}': {}", name, e),
+            }
+        }
+    }
+
     // Start all components
     let market_data_handle = tokio::spawn(async move { market_data_processor.start().await });
 
@@ -325,3 +408,97 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+async fn run_command(command: Command, storage: &storage::Storage) -> Result<()> {
+    match command {
+        Command::TrainTrendModel {
+            symbol,
+            timeframe,
+            limit,
+            horizon,
+            output,
+        } => {
+            let candles = load_stored_candles(storage, &symbol, &timeframe, limit).await?;
+            info!(
+                "Training trend model on {} {} {} candles",
+                candles.len(),
+                symbol,
+                timeframe
+            );
+
+            let config = strategies::trend_model::TrendModelConfig {
+                horizon,
+                ..Default::default()
+            };
+            let model = strategies::trend_model::TrendModel::train(&candles, &config)
+                .map_err(anyhow::Error::msg)?;
+            create_parent_dir(&output)?;
+            model.save(&output).map_err(anyhow::Error::msg)?;
+            info!(
+                "Trend model saved to {} ({} samples, validation accuracy {:?})",
+                output, model.training_samples, model.validation_accuracy
+            );
+            Ok(())
+        },
+        Command::ExportDataset {
+            symbol,
+            timeframe,
+            limit,
+            features,
+            horizons,
+            label_threshold,
+            output,
+        } => {
+            use strategies::dataset::{build_dataset, parse_feature_list, DatasetConfig};
+
+            let config = DatasetConfig {
+                features: match features {
+                    Some(specs) => parse_feature_list(&specs).map_err(anyhow::Error::msg)?,
+                    None => DatasetConfig::default().features,
+                },
+                horizons: horizons
+                    .split(',')
+                    .map(|h| h.trim().parse::<usize>())
+                    .collect::<std::result::Result<_, _>>()
+                    .map_err(|e| anyhow::anyhow!("Invalid horizons '{}': {}", horizons, e))?,
+                label_threshold,
+            };
+            let candles = load_stored_candles(storage, &symbol, &timeframe, limit).await?;
+            let dataset = build_dataset(&candles, &config).map_err(anyhow::Error::msg)?;
+
+            let output = output.unwrap_or_else(|| format!("datasets/{}_{}.csv", symbol, timeframe));
+            create_parent_dir(&output)?;
+            dataset.write_csv(std::io::BufWriter::new(std::fs::File::create(&output)?))?;
+            info!(
+                "Exported {} rows x {} features from {} candles to {}",
+                dataset.rows.len(),
+                dataset.feature_names.len(),
+                candles.len(),
+                output
+            );
+            Ok(())
+        },
+    }
+}
+
+/// Stored candles for `symbol`/`timeframe`, oldest first
+async fn load_stored_candles(
+    storage: &storage::Storage,
+    symbol: &str,
+    timeframe: &str,
+    limit: i64,
+) -> Result<Vec<market_data::cache::CandleData>> {
+    // Stored klines come back newest first
+    let mut klines = storage
+        .get_market_data(symbol, timeframe, Some(limit))
+        .await?;
+    klines.reverse();
+    Ok(klines.iter().map(Into::into).collect())
+}
+
+fn create_parent_dir(path: &str) -> Result<()> {
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    Ok(())
+}
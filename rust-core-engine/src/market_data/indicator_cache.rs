@@ -0,0 +1,225 @@
+use dashmap::DashMap;
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::market_data::cache::CandleData;
+use crate::strategies::streaming_indicators::{
+    IndicatorValues, StreamingIndicatorConfig, StreamingIndicators, TimeframeIndicators,
+};
+
+// @spec:FR-MARKET-DATA-004 - Market Data Caching
+// @ref:specs/02-design/2.3-api/API-RUST-CORE.md#market-data
+
+/// Incremental indicator state per symbol/timeframe, kept alongside `MarketDataCache`.
+///
+/// Each `sync` only feeds candles that closed since the previous call, so refreshing
+/// indicators on every strategy tick costs O(new candles) instead of O(history).
+pub struct IndicatorCache {
+    // Key: "symbol:timeframe" -> indicator state over closed candles
+    states: Arc<DashMap<String, StreamingIndicators>>,
+    config: StreamingIndicatorConfig,
+}
+
+impl Clone for IndicatorCache {
+    fn clone(&self) -> Self {
+        Self {
+            states: self.states.clone(),
+            config: self.config,
+        }
+    }
+}
+
+impl Default for IndicatorCache {
+    fn default() -> Self {
+        Self::new(StreamingIndicatorConfig::default())
+    }
+}
+
+impl IndicatorCache {
+    pub fn new(config: StreamingIndicatorConfig) -> Self {
+        Self {
+            states: Arc::new(DashMap::new()),
+            config,
+        }
+    }
+
+    fn get_key(symbol: &str, timeframe: &str) -> String {
+        let symbol_upper = symbol.to_uppercase();
+        format!("{symbol_upper}:{timeframe}")
+    }
+
+    pub fn config(&self) -> &StreamingIndicatorConfig {
+        &self.config
+    }
+
+    /// Bring the indicators for `symbol`/`timeframe` up to date with `candles` (oldest
+    /// first) and return a snapshot. The last candle is treated as still forming: it is
+    /// previewed, never committed. State is rebuilt from `candles` when they no longer
+    /// connect to what was committed (gap or history reset).
+    pub fn sync(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        candles: &[CandleData],
+    ) -> Option<TimeframeIndicators> {
+        let (latest, closed) = candles.split_last()?;
+        let key = Self::get_key(symbol, timeframe);
+        let mut state = self
+            .states
+            .entry(key)
+            .or_insert_with(|| StreamingIndicators::new(self.config));
+
+        let connected = match (state.last_open_time(), closed.first()) {
+            (None, _) => true,
+            (Some(last), Some(first)) => {
+                first.open_time <= last
+                    && latest.open_time > last
+                    && closed.iter().any(|candle| candle.open_time == last)
+            },
+            (Some(last), None) => latest.open_time > last,
+        };
+        if !connected {
+            debug!(
+                "Reseeding streaming indicators for {} {} from {} candles",
+                symbol,
+                timeframe,
+                candles.len()
+            );
+            *state = StreamingIndicators::new(self.config);
+        }
+
+        for candle in closed {
+            state.update(candle);
+        }
+        Some(state.snapshot(latest))
+    }
+
+    /// Indicator values over the committed (closed) candles
+    pub fn get_values(&self, symbol: &str, timeframe: &str) -> Option<IndicatorValues> {
+        self.states
+            .get(&Self::get_key(symbol, timeframe))
+            .map(|state| state.values())
+    }
+
+    pub fn remove_symbol(&self, symbol: &str) {
+        let prefix = format!("{}:", symbol.to_uppercase());
+        self.states.retain(|key, _| !key.starts_with(&prefix));
+    }
+
+    pub fn clear(&self) {
+        self.states.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::indicators::{calculate_bollinger_bands, calculate_macd, calculate_rsi};
+
+    fn candles(start: usize, count: usize) -> Vec<CandleData> {
+        (start..start + count)
+            .map(|i| {
+                let close = 50000.0 + (i as f64 * 0.4).sin() * 300.0 + i as f64;
+                CandleData {
+                    open_time: i as i64 * 3_600_000,
+                    close_time: i as i64 * 3_600_000 + 3_599_999,
+                    open: close - 10.0,
+                    high: close + 25.0,
+                    low: close - 30.0,
+                    close,
+                    volume: 100.0,
+                    quote_volume: 0.0,
+                    trades: 5,
+                    is_closed: true,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_sync_matches_batch_on_sliding_window() {
+        let cache = IndicatorCache::default();
+        let all = candles(0, 200);
+
+        // Simulate a 100-candle rolling window that advances one candle per tick. RSI and
+        // MACD keep smoothing state from the first candle seen, so they match the batch
+        // calculation over the full history rather than over the window.
+        for end in 100..=200 {
+            let window = &all[end - 100..end];
+            let history = &all[..end];
+            let snapshot = cache.sync("btcusdt", "1h", window).unwrap();
+
+            let rsi = calculate_rsi(history, 14).unwrap();
+            let (current, previous) = snapshot.rsi(14).unwrap();
+            assert!((current - rsi[rsi.len() - 1]).abs() < 1e-6);
+            assert!((previous - rsi[rsi.len() - 2]).abs() < 1e-6);
+
+            let macd = calculate_macd(history, 12, 26, 9).unwrap();
+            let (current, _) = snapshot.macd(12, 26, 9).unwrap();
+            assert!((current.histogram - macd.histogram.last().unwrap()).abs() < 1e-6);
+
+            let bands = calculate_bollinger_bands(window, 20, 2.0).unwrap();
+            let (current, _) = snapshot.bollinger(20, 2.0).unwrap();
+            assert!((current.upper - bands.upper.last().unwrap()).abs() < 1e-6);
+        }
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_sync_reseeds_after_gap() {
+        let cache = IndicatorCache::default();
+        cache.sync("BTCUSDT", "1h", &candles(0, 100)).unwrap();
+
+        // History jumps forward past the committed candles
+        let later = candles(500, 100);
+        let snapshot = cache.sync("BTCUSDT", "1h", &later).unwrap();
+        let rsi = calculate_rsi(&later, 14).unwrap();
+        assert!((snapshot.rsi(14).unwrap().0 - rsi.last().unwrap()).abs() < 1e-6);
+
+        // History goes backwards (e.g. reloaded from an older snapshot)
+        let earlier = candles(10, 60);
+        let snapshot = cache.sync("BTCUSDT", "1h", &earlier).unwrap();
+        let rsi = calculate_rsi(&earlier, 14).unwrap();
+        assert!((snapshot.rsi(14).unwrap().0 - rsi.last().unwrap()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_forming_candle_is_not_committed() {
+        let cache = IndicatorCache::default();
+        let mut window = candles(0, 50);
+        let first = cache.sync("ETHUSDT", "5m", &window).unwrap();
+
+        // Same forming candle with a new price: previous values stay, current moves
+        window.last_mut().unwrap().close += 500.0;
+        let second = cache.sync("ETHUSDT", "5m", &window).unwrap();
+        assert_eq!(first.previous, second.previous);
+        assert!(second.current.rsi.unwrap() > first.current.rsi.unwrap());
+        assert_eq!(cache.get_values("ethusdt", "5m").unwrap(), second.previous);
+    }
+
+    #[test]
+    fn test_remove_symbol_and_empty_input() {
+        let cache = IndicatorCache::default();
+        assert!(cache.sync("BTCUSDT", "1h", &[]).is_none());
+
+        cache.sync("BTCUSDT", "1h", &candles(0, 30)).unwrap();
+        cache.sync("BTCUSDT", "4h", &candles(0, 30)).unwrap();
+        cache.sync("ETHUSDT", "1h", &candles(0, 30)).unwrap();
+        assert_eq!(cache.len(), 3);
+
+        cache.remove_symbol("btcusdt");
+        assert_eq!(cache.len(), 1);
+        assert!(cache.get_values("BTCUSDT", "1h").is_none());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 pub mod analyzer;
 pub mod cache;
+pub mod indicator_cache;
 pub mod processor;
 
 pub use analyzer::*;
@@ -0,0 +1,782 @@
+use anyhow::Result;
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{debug, info};
+
+use crate::market_data::cache::CandleData;
+use crate::strategies::strategy_engine::StrategyEngine;
+use crate::strategies::{StrategyInput, TradingSignal};
+
+use super::portfolio::{PaperPortfolio, PortfolioMetrics};
+use super::settings::PaperTradingSettings;
+use super::strategy_optimizer::BacktestingResults;
+use super::trade::{CloseReason, PaperTrade, TradeType};
+
+// @spec:FR-STRATEGIES-008 - Candle Replay Backtesting
+// @ref:specs/02-design/2.5-components/COMP-RUST-TRADING.md#strategies
+// @test:TC-BACKTEST-001, TC-BACKTEST-002
+
+/// Number of most recent candles per timeframe passed to the strategies on each bar.
+/// Matches the size of the live `historical_data_cache` window (max 300 per timeframe).
+const DEFAULT_LOOKBACK_CANDLES: usize = 300;
+
+/// Minimum number of primary candles that must be visible before the first signal.
+/// Strategies need ~50 candles for MACD/Bollinger warmup.
+const DEFAULT_WARMUP_CANDLES: usize = 50;
+
+/// Backtest configuration (mirrors the live paper trading execution parameters)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestConfig {
+    /// Starting balance in USDT
+    pub initial_balance: f64,
+
+    /// Timeframe that drives the replay (one step per closed candle)
+    pub primary_timeframe: String,
+
+    /// Leverage applied to every simulated trade
+    pub leverage: u8,
+
+    /// Position size as percentage of equity (margin allocated per trade)
+    pub position_size_pct: f64,
+
+    /// Stop loss percentage (PnL-based, divided by leverage for price distance)
+    pub stop_loss_pct: f64,
+
+    /// Take profit percentage (PnL-based, divided by leverage for price distance)
+    pub take_profit_pct: f64,
+
+    /// Trading fee rate (e.g., 0.0004 for 0.04%)
+    pub trading_fee_rate: f64,
+
+    /// Slippage percentage applied against the entry price
+    pub slippage_pct: f64,
+
+    /// Minimum combined strategy confidence required to open a trade
+    pub min_confidence: f64,
+
+    /// Close the open position when the strategies flip direction
+    pub close_on_reversal: bool,
+
+    /// Candles per timeframe fed to the strategies on each bar
+    pub lookback_candles: usize,
+
+    /// Primary candles skipped before the first signal is evaluated
+    pub warmup_candles: usize,
+
+    /// Minimum number of closed trades for the result to be considered significant
+    pub min_trades: u32,
+}
+
+/// One point of the backtest equity curve (recorded after every replayed bar)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EquityPoint {
+    pub timestamp: DateTime<Utc>,
+    pub equity: f64,
+    pub cash_balance: f64,
+    pub drawdown_pct: f64,
+}
+
+/// Full result of a candle-replay backtest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestReport {
+    pub symbol: String,
+    pub primary_timeframe: String,
+    pub start_time: Option<DateTime<Utc>>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub bars_processed: usize,
+    pub signals_generated: usize,
+    pub initial_balance: f64,
+    pub final_equity: f64,
+    pub metrics: PortfolioMetrics,
+    pub equity_curve: Vec<EquityPoint>,
+    pub trades: Vec<PaperTrade>,
+    /// True when the number of closed trades reaches `BacktestConfig::min_trades`
+    pub meets_min_trades: bool,
+}
+
+/// Replays historical candles bar-by-bar through the `StrategyEngine`
+/// and simulates fills with the same `PaperTrade`/`PaperPortfolio` logic as live paper trading
+pub struct Backtester {
+    config: BacktestConfig,
+    strategy_engine: StrategyEngine,
+}
+
+impl Backtester {
+    /// Create a backtester with the default strategy set
+    pub fn new(config: BacktestConfig) -> Self {
+        Self::with_strategy_engine(config, StrategyEngine::new())
+    }
+
+    /// Create a backtester around a pre-configured strategy engine
+    pub fn with_strategy_engine(config: BacktestConfig, strategy_engine: StrategyEngine) -> Self {
+        Self {
+            config,
+            strategy_engine,
+        }
+    }
+
+    pub fn config(&self) -> &BacktestConfig {
+        &self.config
+    }
+
+    pub fn strategy_engine_mut(&mut self) -> &mut StrategyEngine {
+        &mut self.strategy_engine
+    }
+
+    /// Replay `timeframe_data` for `symbol` and return the simulated results.
+    ///
+    /// Each closed candle of the primary timeframe is one step: stop loss / take profit
+    /// are checked against the bar's high/low first, then the strategies see every
+    /// timeframe truncated to candles closed at or before the bar's close time.
+    pub async fn run(
+        &self,
+        symbol: &str,
+        timeframe_data: &HashMap<String, Vec<CandleData>>,
+    ) -> Result<BacktestReport> {
+        let mut sorted_data: HashMap<String, Vec<CandleData>> = HashMap::new();
+        for (timeframe, candles) in timeframe_data {
+            let mut candles: Vec<CandleData> =
+                candles.iter().filter(|c| c.is_closed).cloned().collect();
+            candles.sort_by_key(|c| c.open_time);
+            sorted_data.insert(timeframe.clone(), candles);
+        }
+
+        let primary = sorted_data
+            .get(&self.config.primary_timeframe)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Missing primary timeframe {} data for {}",
+                    self.config.primary_timeframe,
+                    symbol
+                )
+            })?;
+
+        if primary.len() <= self.config.warmup_candles {
+            return Err(anyhow::anyhow!(
+                "Need more than {} {} candles to backtest {}, got {}",
+                self.config.warmup_candles,
+                self.config.primary_timeframe,
+                symbol,
+                primary.len()
+            ));
+        }
+
+        let mut portfolio = PaperPortfolio::new(self.config.initial_balance);
+        let mut equity_curve = Vec::with_capacity(primary.len());
+        let mut open_trade_id: Option<String> = None;
+        let mut signals_generated = 0;
+        let mut peak_equity = self.config.initial_balance;
+
+        info!(
+            "🔁 Backtesting {} on {} ({} candles)",
+            symbol,
+            self.config.primary_timeframe,
+            primary.len()
+        );
+
+        for (index, bar) in primary.iter().enumerate() {
+            let bar_time = timestamp_to_datetime(bar.close_time);
+
+            // 1. Intrabar stop loss / take profit using the bar's range
+            if let Some(trade_id) = open_trade_id.clone() {
+                if let Some((exit_price, reason)) = portfolio
+                    .get_trade(&trade_id)
+                    .and_then(|trade| intrabar_exit(trade, bar))
+                {
+                    close_at(&mut portfolio, &trade_id, exit_price, reason, bar_time)?;
+                    open_trade_id = None;
+                }
+            }
+
+            mark_to_market(&mut portfolio, symbol, bar.close);
+
+            // 2. Strategy evaluation on data visible at this bar's close
+            if index + 1 > self.config.warmup_candles {
+                let input = self.build_input(symbol, &sorted_data, bar);
+                match self.strategy_engine.analyze_market(&input).await {
+                    Ok(combined) => {
+                        let signal = combined.final_signal;
+                        if signal != TradingSignal::Neutral
+                            && combined.combined_confidence >= self.config.min_confidence
+                        {
+                            signals_generated += 1;
+                            open_trade_id = self.handle_signal(
+                                &mut portfolio,
+                                symbol,
+                                signal,
+                                combined.combined_confidence,
+                                combined.reasoning,
+                                bar,
+                                open_trade_id,
+                            )?;
+                        }
+                    },
+                    Err(e) => {
+                        debug!("Backtest bar {} skipped for {}: {}", index, symbol, e);
+                    },
+                }
+            }
+
+            peak_equity = peak_equity.max(portfolio.equity);
+            let drawdown_pct = if peak_equity > 0.0 {
+                ((peak_equity - portfolio.equity) / peak_equity) * 100.0
+            } else {
+                0.0
+            };
+            equity_curve.push(EquityPoint {
+                timestamp: bar_time,
+                equity: portfolio.equity,
+                cash_balance: portfolio.cash_balance,
+                drawdown_pct,
+            });
+        }
+
+        // Close anything still open at the last close so metrics include it
+        if let (Some(trade_id), Some(last)) = (open_trade_id, primary.last()) {
+            close_at(
+                &mut portfolio,
+                &trade_id,
+                last.close,
+                CloseReason::TimeBasedExit,
+                timestamp_to_datetime(last.close_time),
+            )?;
+            if let Some(point) = equity_curve.last_mut() {
+                point.equity = portfolio.equity;
+                point.cash_balance = portfolio.cash_balance;
+            }
+        }
+
+        let trades: Vec<PaperTrade> = portfolio.get_closed_trades().into_iter().cloned().collect();
+        let meets_min_trades = trades.len() as u32 >= self.config.min_trades;
+
+        info!(
+            "✅ Backtest finished for {}: {} trades, final equity {:.2}",
+            symbol,
+            trades.len(),
+            portfolio.equity
+        );
+
+        Ok(BacktestReport {
+            symbol: symbol.to_string(),
+            primary_timeframe: self.config.primary_timeframe.clone(),
+            start_time: primary.first().map(|c| timestamp_to_datetime(c.open_time)),
+            end_time: primary.last().map(|c| timestamp_to_datetime(c.close_time)),
+            bars_processed: primary.len(),
+            signals_generated,
+            initial_balance: self.config.initial_balance,
+            final_equity: portfolio.equity,
+            metrics: portfolio.metrics.clone(),
+            equity_curve,
+            trades,
+            meets_min_trades,
+        })
+    }
+
+    /// Build the strategy input visible at the close of `bar`
+    fn build_input(
+        &self,
+        symbol: &str,
+        sorted_data: &HashMap<String, Vec<CandleData>>,
+        bar: &CandleData,
+    ) -> StrategyInput {
+        let mut visible: HashMap<String, Vec<CandleData>> = HashMap::new();
+        for (timeframe, candles) in sorted_data {
+            let end = candles.partition_point(|c| c.close_time <= bar.close_time);
+            let start = end.saturating_sub(self.config.lookback_candles);
+            if end > start {
+                visible.insert(timeframe.clone(), candles[start..end].to_vec());
+            }
+        }
+
+        // 24h volume from the primary candles that closed within the last 24 hours
+        let day_ago = bar.close_time - 24 * 60 * 60 * 1000;
+        let volume_24h = visible
+            .get(&self.config.primary_timeframe)
+            .map(|candles| {
+                candles
+                    .iter()
+                    .rev()
+                    .take_while(|c| c.close_time > day_ago)
+                    .map(|c| c.volume)
+                    .sum::<f64>()
+            })
+            .unwrap_or(0.0);
+
+        StrategyInput {
+            symbol: symbol.to_string(),
+            timeframe_data: visible,
+            current_price: bar.close,
+            volume_24h,
+            timestamp: bar.close_time / 1000,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
+        }
+    }
+
+    /// Apply a directional signal: reverse or open a position at the bar close
+    #[allow(clippy::too_many_arguments)]
+    fn handle_signal(
+        &self,
+        portfolio: &mut PaperPortfolio,
+        symbol: &str,
+        signal: TradingSignal,
+        confidence: f64,
+        reasoning: String,
+        bar: &CandleData,
+        open_trade_id: Option<String>,
+    ) -> Result<Option<String>> {
+        let trade_type = match signal {
+            TradingSignal::Long => TradeType::Long,
+            TradingSignal::Short => TradeType::Short,
+            TradingSignal::Neutral => return Ok(open_trade_id),
+        };
+        let bar_time = timestamp_to_datetime(bar.close_time);
+
+        if let Some(trade_id) = open_trade_id {
+            let same_direction = portfolio
+                .get_trade(&trade_id)
+                .is_some_and(|t| t.trade_type == trade_type);
+            if same_direction || !self.config.close_on_reversal {
+                return Ok(Some(trade_id));
+            }
+            close_at(
+                portfolio,
+                &trade_id,
+                bar.close,
+                CloseReason::AISignal,
+                bar_time,
+            )?;
+        }
+
+        let entry_price = match trade_type {
+            TradeType::Long => bar.close * (1.0 + self.config.slippage_pct / 100.0),
+            TradeType::Short => bar.close * (1.0 - self.config.slippage_pct / 100.0),
+        };
+        let leverage = self.config.leverage.max(1);
+        let margin = portfolio.equity * (self.config.position_size_pct / 100.0);
+        let quantity = (margin * leverage as f64) / entry_price;
+        if quantity <= 0.0 || !quantity.is_finite() {
+            return Ok(None);
+        }
+
+        let mut trade = PaperTrade::new(
+            symbol.to_string(),
+            trade_type,
+            entry_price,
+            quantity,
+            leverage,
+            self.config.trading_fee_rate,
+            None,
+            Some(confidence),
+            Some(reasoning),
+        );
+        let lev = leverage as f64;
+        let (stop_loss, take_profit) = match trade_type {
+            TradeType::Long => (
+                entry_price * (1.0 - self.config.stop_loss_pct / (lev * 100.0)),
+                entry_price * (1.0 + self.config.take_profit_pct / (lev * 100.0)),
+            ),
+            TradeType::Short => (
+                entry_price * (1.0 + self.config.stop_loss_pct / (lev * 100.0)),
+                entry_price * (1.0 - self.config.take_profit_pct / (lev * 100.0)),
+            ),
+        };
+        if self.config.stop_loss_pct > 0.0 {
+            trade.stop_loss = Some(stop_loss);
+        }
+        if self.config.take_profit_pct > 0.0 {
+            trade.take_profit = Some(take_profit);
+        }
+        trade.strategy_name = Some("backtest".to_string());
+        trade.slippage = (entry_price - bar.close).abs();
+        trade.open_time = bar_time;
+        trade.execution_timestamp = bar_time;
+
+        let trade_id = trade.id.clone();
+        match portfolio.add_trade(trade) {
+            Ok(()) => Ok(Some(trade_id)),
+            Err(e) => {
+                debug!("Backtest trade rejected for {}: {}", symbol, e);
+                Ok(None)
+            },
+        }
+    }
+}
+
+impl BacktestConfig {
+    /// Build a backtest configuration from the live paper trading settings for `symbol`
+    pub fn from_settings(settings: &PaperTradingSettings, symbol: &str) -> Self {
+        let symbol_settings = settings.get_symbol_settings(symbol);
+        Self {
+            initial_balance: settings.basic.initial_balance,
+            primary_timeframe: settings.strategy.backtesting.data_resolution.clone(),
+            leverage: symbol_settings.leverage,
+            position_size_pct: symbol_settings.position_size_pct,
+            stop_loss_pct: symbol_settings.stop_loss_pct,
+            take_profit_pct: symbol_settings.take_profit_pct,
+            trading_fee_rate: settings.basic.trading_fee_rate,
+            slippage_pct: settings.basic.slippage_pct,
+            min_confidence: settings.strategy.min_ai_confidence,
+            close_on_reversal: settings.risk.enable_signal_reversal,
+            lookback_candles: DEFAULT_LOOKBACK_CANDLES,
+            warmup_candles: DEFAULT_WARMUP_CANDLES,
+            min_trades: settings.strategy.backtesting.min_trades,
+        }
+    }
+}
+
+impl Default for BacktestConfig {
+    fn default() -> Self {
+        Self::from_settings(&PaperTradingSettings::default(), "")
+    }
+}
+
+impl BacktestReport {
+    /// Convert into the optimizer's `BacktestingResults` format
+    pub fn to_backtesting_results(&self) -> BacktestingResults {
+        let mut monthly: Vec<(String, f64)> = Vec::new();
+        let mut trade_distribution: HashMap<String, u32> = HashMap::new();
+
+        for trade in &self.trades {
+            let pnl = trade.realized_pnl.unwrap_or(0.0);
+            let month = trade
+                .close_time
+                .unwrap_or(trade.open_time)
+                .format("%Y-%m")
+                .to_string();
+            match monthly.last_mut() {
+                Some((m, total)) if *m == month => *total += pnl,
+                _ => monthly.push((month, pnl)),
+            }
+
+            if let Some(reason) = &trade.close_reason {
+                *trade_distribution.entry(format!("{reason:?}")).or_insert(0) += 1;
+            }
+        }
+
+        let monthly_returns = monthly
+            .into_iter()
+            .map(|(_, pnl)| {
+                if self.initial_balance > 0.0 {
+                    (pnl / self.initial_balance) * 100.0
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+
+        let total_return = if self.initial_balance > 0.0 {
+            ((self.final_equity - self.initial_balance) / self.initial_balance) * 100.0
+        } else {
+            0.0
+        };
+
+        BacktestingResults {
+            total_trades: self.trades.len() as u32,
+            win_rate: self.metrics.win_rate,
+            total_return,
+            sharpe_ratio: self.metrics.sharpe_ratio,
+            max_drawdown: self.metrics.max_drawdown_percentage,
+            profit_factor: self.metrics.profit_factor,
+            monthly_returns,
+            trade_distribution,
+        }
+    }
+}
+
+/// Exit price and reason if the bar's range touched the trade's stop loss or take profit.
+/// When both are touched inside one bar the stop loss is assumed to fill first (conservative).
+fn intrabar_exit(trade: &PaperTrade, bar: &CandleData) -> Option<(f64, CloseReason)> {
+    let (adverse, favorable) = match trade.trade_type {
+        TradeType::Long => (bar.low, bar.high),
+        TradeType::Short => (bar.high, bar.low),
+    };
+
+    if let Some(stop_loss) = trade.stop_loss {
+        if trade.should_stop_loss(adverse) {
+            // Gaps through the stop fill at the open
+            let fill = match trade.trade_type {
+                TradeType::Long => stop_loss.min(bar.open),
+                TradeType::Short => stop_loss.max(bar.open),
+            };
+            return Some((fill, CloseReason::StopLoss));
+        }
+    }
+
+    if let Some(take_profit) = trade.take_profit {
+        if trade.should_take_profit(favorable) {
+            return Some((take_profit, CloseReason::TakeProfit));
+        }
+    }
+
+    None
+}
+
+fn mark_to_market(portfolio: &mut PaperPortfolio, symbol: &str, price: f64) {
+    let mut prices = HashMap::new();
+    prices.insert(symbol.to_string(), price);
+    portfolio.update_prices(prices, None);
+}
+
+/// Close a trade and stamp it with the simulated (candle) time instead of wall-clock time
+fn close_at(
+    portfolio: &mut PaperPortfolio,
+    trade_id: &str,
+    exit_price: f64,
+    reason: CloseReason,
+    close_time: DateTime<Utc>,
+) -> Result<()> {
+    portfolio.close_trade(trade_id, exit_price, reason)?;
+    if let Some(trade) = portfolio.trades.get_mut(trade_id) {
+        trade.close_time = Some(close_time);
+        trade.duration_ms = Some((close_time - trade.open_time).num_milliseconds());
+    }
+    Ok(())
+}
+
+fn timestamp_to_datetime(timestamp_ms: i64) -> DateTime<Utc> {
+    Utc.timestamp_millis_opt(timestamp_ms)
+        .single()
+        .unwrap_or_else(Utc::now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIVE_MIN_MS: i64 = 5 * 60 * 1000;
+
+    fn make_candle(index: i64, interval_ms: i64, open: f64, close: f64) -> CandleData {
+        let open_time = 1_700_000_000_000 + index * interval_ms;
+        CandleData {
+            open_time,
+            close_time: open_time + interval_ms - 1,
+            open,
+            high: open.max(close) * 1.001,
+            low: open.min(close) * 0.999,
+            close,
+            volume: 1000.0,
+            quote_volume: 1000.0 * close,
+            trades: 100,
+            is_closed: true,
+        }
+    }
+
+    /// Build 5m and 15m candles from a closure that returns the price at each 5m index
+    fn make_series(
+        count: usize,
+        price_at: impl Fn(usize) -> f64,
+    ) -> HashMap<String, Vec<CandleData>> {
+        let candles_5m: Vec<CandleData> = (0..count)
+            .map(|i| {
+                let open = if i == 0 { price_at(0) } else { price_at(i - 1) };
+                make_candle(i as i64, FIVE_MIN_MS, open, price_at(i))
+            })
+            .collect();
+
+        let candles_15m: Vec<CandleData> = candles_5m
+            .chunks(3)
+            .enumerate()
+            .filter(|(_, chunk)| chunk.len() == 3)
+            .map(|(i, chunk)| {
+                let mut candle =
+                    make_candle(i as i64, FIVE_MIN_MS * 3, chunk[0].open, chunk[2].close);
+                candle.high = chunk.iter().map(|c| c.high).fold(f64::MIN, f64::max);
+                candle.low = chunk.iter().map(|c| c.low).fold(f64::MAX, f64::min);
+                candle
+            })
+            .collect();
+
+        let mut data = HashMap::new();
+        data.insert("5m".to_string(), candles_5m);
+        data.insert("15m".to_string(), candles_15m);
+        data
+    }
+
+    fn test_config() -> BacktestConfig {
+        BacktestConfig {
+            primary_timeframe: "5m".to_string(),
+            min_confidence: 0.0,
+            ..BacktestConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_config_from_settings_uses_backtesting_resolution() {
+        let settings = PaperTradingSettings::default();
+        let config = BacktestConfig::from_settings(&settings, "BTCUSDT");
+        assert_eq!(
+            config.primary_timeframe,
+            settings.strategy.backtesting.data_resolution
+        );
+        assert_eq!(config.initial_balance, settings.basic.initial_balance);
+        assert_eq!(config.min_trades, settings.strategy.backtesting.min_trades);
+    }
+
+    #[tokio::test]
+    async fn test_run_missing_primary_timeframe_fails() {
+        let backtester = Backtester::new(test_config());
+        let data = HashMap::new();
+        assert!(backtester.run("BTCUSDT", &data).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_insufficient_candles_fails() {
+        let backtester = Backtester::new(test_config());
+        let data = make_series(20, |_| 100.0);
+        assert!(backtester.run("BTCUSDT", &data).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_produces_equity_curve_per_bar() {
+        let backtester = Backtester::new(test_config());
+        let data = make_series(240, |i| 100.0 + (i as f64 * 0.2).sin() * 5.0);
+
+        let report = backtester.run("BTCUSDT", &data).await.unwrap();
+
+        assert_eq!(report.bars_processed, 240);
+        assert_eq!(report.equity_curve.len(), 240);
+        assert_eq!(report.initial_balance, 10000.0);
+        assert!(report.start_time.unwrap() < report.end_time.unwrap());
+        assert!(report.equity_curve.iter().all(|p| p.drawdown_pct >= 0.0));
+        // All trades are closed when the replay ends
+        assert!(report
+            .trades
+            .iter()
+            .all(|t| t.status == crate::paper_trading::trade::TradeStatus::Closed));
+        let last = report.equity_curve.last().unwrap();
+        assert!((last.equity - report.final_equity).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_trades_use_candle_timestamps() {
+        let backtester = Backtester::new(test_config());
+        let data = make_series(240, |i| 100.0 + (i as f64 * 0.15).sin() * 8.0);
+
+        let report = backtester.run("BTCUSDT", &data).await.unwrap();
+        let start = report.start_time.unwrap();
+        let end = report.end_time.unwrap();
+
+        for trade in &report.trades {
+            assert!(trade.open_time >= start && trade.open_time <= end);
+            let close_time = trade.close_time.unwrap();
+            assert!(close_time >= trade.open_time && close_time <= end);
+        }
+    }
+
+    #[test]
+    fn test_intrabar_exit_long_stop_loss_first() {
+        let mut trade = PaperTrade::new(
+            "BTCUSDT".to_string(),
+            TradeType::Long,
+            100.0,
+            1.0,
+            1,
+            0.0,
+            None,
+            None,
+            None,
+        );
+        trade.stop_loss = Some(95.0);
+        trade.take_profit = Some(105.0);
+
+        // Bar touches both SL and TP → SL assumed first
+        let mut bar = make_candle(0, FIVE_MIN_MS, 100.0, 100.0);
+        bar.high = 106.0;
+        bar.low = 94.0;
+        let (price, reason) = intrabar_exit(&trade, &bar).unwrap();
+        assert_eq!(reason, CloseReason::StopLoss);
+        assert_eq!(price, 95.0);
+
+        // Only TP touched
+        bar.low = 99.0;
+        let (price, reason) = intrabar_exit(&trade, &bar).unwrap();
+        assert_eq!(reason, CloseReason::TakeProfit);
+        assert_eq!(price, 105.0);
+
+        // Neither touched
+        bar.high = 101.0;
+        assert!(intrabar_exit(&trade, &bar).is_none());
+    }
+
+    #[test]
+    fn test_intrabar_exit_short_gap_fills_at_open() {
+        let mut trade = PaperTrade::new(
+            "BTCUSDT".to_string(),
+            TradeType::Short,
+            100.0,
+            1.0,
+            1,
+            0.0,
+            None,
+            None,
+            None,
+        );
+        trade.stop_loss = Some(105.0);
+
+        let mut bar = make_candle(0, FIVE_MIN_MS, 110.0, 111.0);
+        bar.high = 112.0;
+        bar.low = 109.0;
+        let (price, reason) = intrabar_exit(&trade, &bar).unwrap();
+        assert_eq!(reason, CloseReason::StopLoss);
+        assert_eq!(price, 110.0);
+    }
+
+    #[test]
+    fn test_build_input_hides_future_candles() {
+        let backtester = Backtester::new(test_config());
+        let data = make_series(120, |i| 100.0 + i as f64);
+        let bar = data["5m"][59].clone();
+
+        let input = backtester.build_input("BTCUSDT", &data, &bar);
+
+        assert_eq!(input.current_price, bar.close);
+        assert_eq!(input.timeframe_data["5m"].len(), 60);
+        assert!(input
+            .timeframe_data
+            .values()
+            .flatten()
+            .all(|c| c.close_time <= bar.close_time));
+        assert_eq!(input.timeframe_data["15m"].len(), 20);
+    }
+
+    #[test]
+    fn test_report_to_backtesting_results() {
+        let mut trade = PaperTrade::new(
+            "BTCUSDT".to_string(),
+            TradeType::Long,
+            100.0,
+            10.0,
+            1,
+            0.0,
+            None,
+            None,
+            None,
+        );
+        trade.close(110.0, CloseReason::TakeProfit, 0.0).unwrap();
+
+        let report = BacktestReport {
+            symbol: "BTCUSDT".to_string(),
+            primary_timeframe: "5m".to_string(),
+            start_time: None,
+            end_time: None,
+            bars_processed: 0,
+            signals_generated: 1,
+            initial_balance: 1000.0,
+            final_equity: 1100.0,
+            metrics: PortfolioMetrics::default(),
+            equity_curve: Vec::new(),
+            trades: vec![trade],
+            meets_min_trades: false,
+        };
+
+        let results = report.to_backtesting_results();
+        assert_eq!(results.total_trades, 1);
+        assert!((results.total_return - 10.0).abs() < 1e-9);
+        assert_eq!(results.monthly_returns.len(), 1);
+        assert!((results.monthly_returns[0] - 10.0).abs() < 1e-9);
+        assert_eq!(results.trade_distribution.get("TakeProfit"), Some(&1));
+    }
+}
@@ -0,0 +1,89 @@
+//! Clock used by paper trading for every timestamp it records
+//!
+//! The clock follows wall time until it is set to a fixed time; from then on it only
+//! moves when set or advanced. Together with the execution RNG seed this lets a paper
+//! session be replayed exactly from its recorded inputs. Clones share the same time,
+//! so the engine and its portfolio always agree.
+
+use chrono::{DateTime, Duration, Utc};
+use std::sync::{Arc, RwLock};
+
+/// Wall clock, or a manually driven clock once `set` is called
+#[derive(Debug, Clone, Default)]
+pub struct SimulationClock {
+    manual_time: Arc<RwLock<Option<DateTime<Utc>>>>,
+}
+
+impl SimulationClock {
+    /// Clock that follows wall time
+    pub fn system() -> Self {
+        Self::default()
+    }
+
+    /// Clock fixed at `time`
+    pub fn manual(time: DateTime<Utc>) -> Self {
+        let clock = Self::default();
+        clock.set(time);
+        clock
+    }
+
+    /// Current time: the manual time when set, wall time otherwise
+    pub fn now(&self) -> DateTime<Utc> {
+        self.read().unwrap_or_else(Utc::now)
+    }
+
+    /// Whether the clock is driven manually
+    pub fn is_manual(&self) -> bool {
+        self.read().is_some()
+    }
+
+    /// Fix the clock at `time`
+    pub fn set(&self, time: DateTime<Utc>) {
+        *self
+            .manual_time
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(time);
+    }
+
+    /// Move the clock forward by `duration`, fixing it first if it follows wall time
+    pub fn advance(&self, duration: Duration) {
+        self.set(self.now() + duration);
+    }
+
+    /// Follow wall time again
+    pub fn use_system_time(&self) {
+        *self
+            .manual_time
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+    }
+
+    fn read(&self) -> Option<DateTime<Utc>> {
+        *self
+            .manual_time
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_manual_clock_is_shared_by_clones() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let clock = SimulationClock::manual(start);
+        let shared = clock.clone();
+        assert!(shared.is_manual());
+        assert_eq!(shared.now(), start);
+
+        clock.advance(Duration::minutes(5));
+        assert_eq!(shared.now(), start + Duration::minutes(5));
+
+        shared.use_system_time();
+        assert!(!clock.is_manual());
+        assert!(clock.now() > start);
+    }
+}
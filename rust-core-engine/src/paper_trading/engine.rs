@@ -1,8 +1,8 @@
 use anyhow::Result;
 use chrono::{DateTime, Datelike, Utc};
-use rand::Rng;
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde_json;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::{broadcast, Mutex, RwLock};
 use tokio::time::{interval, Duration};
@@ -10,25 +10,38 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use crate::ai::AIService;
+use crate::binance::types::TimeInForce;
 use crate::binance::BinanceClient;
 use crate::market_data::cache::{CandleData, MarketDataCache};
+use crate::market_data::indicator_cache::IndicatorCache;
 use crate::storage::Storage;
-use crate::strategies::strategy_engine::StrategyEngine;
+use crate::strategies::registry::StrategyRegistry;
+use crate::strategies::strategy_engine::{StrategyEngine, StrategyEngineConfig};
 use crate::strategies::TradingSignal;
 
 use super::{
     // @spec:FR-TRADING-015 - Paper Trading Engine
     // @ref:specs/02-design/2.5-components/COMP-RUST-TRADING.md#paper-trading
     // @test:TC-INTEGRATION-025, TC-INTEGRATION-026
-    portfolio::PaperPortfolio,
+    backtester::{BacktestConfig, BacktestReport, Backtester},
+    clock::SimulationClock,
+    monte_carlo::{MonteCarloConfig, MonteCarloReport},
+    order_book::{
+        evaluate_resting_limit, is_buy_side, is_marketable, traded_volume_since, validate_bracket,
+        validate_oco, LimitOrderAction,
+    },
+    portfolio::{PairTradeSummary, PaperPortfolio},
     settings::PaperTradingSettings,
-    strategy_optimizer::StrategyOptimizer,
-    trade::{CloseReason, PaperTrade, TradeType},
+    strategy_optimizer::{
+        parameter_space_from_config, StrategyOptimizer, WalkForwardConfig, WalkForwardReport,
+    },
+    trade::{CloseReason, PaperTrade, TradeStatus, TradeType, LIQUIDITY_KEY, STRATEGY_VOTES_KEY},
     AIMarketBias,
     AITradingSignal,
     MarketAnalysisData,
     OrderStatus,
     OrderType,
+    PairTradeParams,
     PaperTradingEvent,
     PerformanceSummary,
     StopLimitOrder,
@@ -57,7 +70,7 @@ pub struct StrategySignalCache {
 const STRATEGY_REFRESH_TIMEFRAMES: &[&str] = &["5m", "15m"];
 
 /// Full set of timeframes loaded for strategy input (includes 1h for AI bias analysis)
-const STRATEGY_ALL_TIMEFRAMES: &[&str] = &["5m", "15m", "1h"];
+pub(crate) const STRATEGY_ALL_TIMEFRAMES: &[&str] = &["5m", "15m", "1h"];
 
 /// Main paper trading engine
 #[derive(Clone)]
@@ -106,7 +119,8 @@ pub struct PaperTradingEngine {
     pending_stop_limit_orders: Arc<RwLock<Vec<StopLimitOrder>>>,
 
     /// Strategy engine for realtime signal generation from WebSocket kline events
-    strategy_engine: Arc<StrategyEngine>,
+    /// (write-locked only when the enabled strategy set changes at runtime)
+    strategy_engine: Arc<RwLock<StrategyEngine>>,
 
     /// Pre-computed AI market bias (read with zero latency)
     /// Key: symbol (e.g., "BTCUSDT"), Value: AIMarketBias
@@ -132,6 +146,17 @@ pub struct PaperTradingEngine {
     /// Cache of latest strategy signals per symbol (for API display)
     /// Updated every signal loop iteration; read by /api/paper-trading/latest-signals
     latest_strategy_signals: Arc<RwLock<HashMap<String, StrategySignalCache>>>,
+
+    /// Incremental indicator state per symbol/timeframe so each signal loop tick only
+    /// feeds newly closed candles instead of recomputing over the whole history
+    indicator_cache: IndicatorCache,
+
+    /// RNG behind slippage, partial fills and ids, seeded from `execution.random_seed`
+    /// (never held across an await)
+    rng: Arc<std::sync::Mutex<StdRng>>,
+
+    /// Clock behind every timestamp the engine and its portfolio record
+    clock: SimulationClock,
 }
 
 /// Pending trade for execution
@@ -145,6 +170,16 @@ pub struct PendingTrade {
     pub timestamp: DateTime<Utc>,
 }
 
+/// How a limit order is being filled
+#[derive(Debug, Clone, Copy)]
+struct LimitFill {
+    limit_price: f64,
+    /// Filled passively from the book (maker) rather than crossing the spread (taker)
+    maker: bool,
+    /// Fill-or-kill: reject rather than accept a partial fill
+    fill_or_kill: bool,
+}
+
 /// Consecutive wins/losses streak for AI decision-making
 #[derive(Debug, Clone, Copy, Default)]
 struct ConsecutiveStreak {
@@ -152,6 +187,43 @@ struct ConsecutiveStreak {
     losses: u32,
 }
 
+/// Execution RNG seeded with `seed`, or from the OS when unset
+fn execution_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    }
+}
+
+/// Built-in strategies plus the YAML rule strategies
+/// @spec:FR-STRATEGIES-005 - Strategy Parameters
+fn build_strategy_registry(settings: &PaperTradingSettings) -> Result<StrategyRegistry> {
+    settings.validate_rule_strategies()?;
+    let mut registry = StrategyRegistry::with_builtin_strategies();
+    registry.register_rule_strategies(&settings.rule_strategies)?;
+    Ok(registry)
+}
+
+/// Strategy engine with the built-in strategies plus the YAML rule strategies.
+/// Enabled rule strategies are added to the default enabled list.
+/// @spec:FR-STRATEGIES-005 - Strategy Parameters
+fn build_strategy_engine(settings: &PaperTradingSettings) -> Result<StrategyEngine> {
+    let registry = build_strategy_registry(settings)?;
+
+    let mut config = StrategyEngineConfig {
+        signal_combination_mode: settings.strategy.combination_method.into(),
+        ..StrategyEngineConfig::default()
+    };
+    config.enabled_strategies.extend(
+        settings
+            .rule_strategies
+            .iter()
+            .filter(|definition| definition.enabled)
+            .map(|definition| definition.name.trim().to_string()),
+    );
+    Ok(StrategyEngine::with_registry(&registry, config))
+}
+
 impl PaperTradingEngine {
     /// Create a new paper trading engine
     pub async fn new(
@@ -179,12 +251,15 @@ impl PaperTradingEngine {
             settings.symbols.keys().collect::<Vec<_>>()
         );
 
-        let portfolio = Arc::new(RwLock::new(PaperPortfolio::new(
-            settings.basic.initial_balance,
-        )));
+        let clock = SimulationClock::system();
+        let portfolio = Arc::new(RwLock::new(
+            PaperPortfolio::new(settings.basic.initial_balance).with_clock(clock.clone()),
+        ));
         let optimizer = Arc::new(RwLock::new(StrategyOptimizer::new(
             super::strategy_optimizer::OptimizationConfig::default(),
         )));
+        let strategy_engine = build_strategy_engine(&settings)?;
+        let rng = execution_rng(settings.execution.random_seed);
 
         Ok(Self {
             portfolio,
@@ -200,13 +275,16 @@ impl PaperTradingEngine {
             trade_execution_lock: Arc::new(Mutex::new(())),
             historical_data_cache: Arc::new(RwLock::new(HashMap::new())),
             pending_stop_limit_orders: Arc::new(RwLock::new(Vec::new())),
-            strategy_engine: Arc::new(StrategyEngine::new()),
+            strategy_engine: Arc::new(RwLock::new(strategy_engine)),
             ai_market_bias: Arc::new(RwLock::new(HashMap::new())),
             recent_signals: Arc::new(RwLock::new(HashMap::new())),
             signal_flip_tracker: Arc::new(RwLock::new(HashMap::new())),
             market_data_cache: None,
             funding_rates: Arc::new(RwLock::new(HashMap::new())),
             latest_strategy_signals: Arc::new(RwLock::new(HashMap::new())),
+            indicator_cache: IndicatorCache::default(),
+            rng: Arc::new(std::sync::Mutex::new(rng)),
+            clock,
         })
     }
 
@@ -219,6 +297,85 @@ impl PaperTradingEngine {
         );
     }
 
+    /// Create the engine of named portfolio `name` next to this one
+    ///
+    /// The new engine shares this engine's Binance client, AI service and market data
+    /// cache, but has its own settings, portfolio and `{name}_`-prefixed storage
+    /// collections. Its events are forwarded to this engine's broadcaster with a
+    /// `portfolio` field added to their data.
+    pub async fn new_named_portfolio(
+        &self,
+        name: &str,
+        settings: PaperTradingSettings,
+    ) -> Result<Self> {
+        let (portfolio_broadcaster, mut portfolio_events) = broadcast::channel(1000);
+        let mut engine = Self::new(
+            settings,
+            self.binance_client.clone(),
+            self.ai_service.clone(),
+            self.storage.for_paper_portfolio(name),
+            portfolio_broadcaster,
+        )
+        .await?;
+        engine.market_data_cache = self.market_data_cache.clone();
+
+        let shared_broadcaster = self.event_broadcaster.clone();
+        let portfolio_name = name.to_string();
+        tokio::spawn(async move {
+            loop {
+                match portfolio_events.recv().await {
+                    Ok(mut event) => {
+                        if let Some(data) = event.data.as_object_mut() {
+                            data.insert(
+                                "portfolio".to_string(),
+                                serde_json::Value::String(portfolio_name.clone()),
+                            );
+                        }
+                        let _ = shared_broadcaster.send(event);
+                    },
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        Ok(engine)
+    }
+
+    /// Clock behind the engine's timestamps; set it to replay a session deterministically
+    pub fn clock(&self) -> &SimulationClock {
+        &self.clock
+    }
+
+    /// Restart the execution RNG from `execution.random_seed`
+    ///
+    /// With a fixed seed and a manual clock, a session fed the same prices and orders
+    /// after a reseed produces the same fills, prices and ids.
+    pub async fn reseed_rng(&self) {
+        let seed = self.settings.read().await.execution.random_seed;
+        *self.lock_rng() = execution_rng(seed);
+    }
+
+    fn lock_rng(&self) -> std::sync::MutexGuard<'_, StdRng> {
+        self.rng
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Next id from the execution RNG, so a seeded session replays with the same ids
+    fn next_uuid(&self) -> Uuid {
+        let bytes: [u8; 16] = self.lock_rng().random();
+        uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+
+    /// Give a new trade an id from the execution RNG and open it at the engine's clock
+    fn stamp_trade(&self, mut trade: PaperTrade) -> PaperTrade {
+        trade.id = self.next_uuid().to_string();
+        trade.open_time = self.clock.now();
+        trade.execution_timestamp = trade.open_time;
+        trade
+    }
+
     /// Start the paper trading engine
     pub async fn start(&self) -> Result<()> {
         {
@@ -257,8 +414,8 @@ impl PaperTradingEngine {
         // Broadcast start event
         let _ = self.event_broadcaster.send(PaperTradingEvent {
             event_type: "engine_started".to_string(),
-            data: serde_json::json!({ "timestamp": Utc::now() }),
-            timestamp: Utc::now(),
+            data: serde_json::json!({ "timestamp": self.clock.now() }),
+            timestamp: self.clock.now(),
         });
 
         info!(
@@ -299,8 +456,8 @@ impl PaperTradingEngine {
         // Broadcast stop event
         let _ = self.event_broadcaster.send(PaperTradingEvent {
             event_type: "engine_stopped".to_string(),
-            data: serde_json::json!({ "timestamp": Utc::now() }),
-            timestamp: Utc::now(),
+            data: serde_json::json!({ "timestamp": self.clock.now() }),
+            timestamp: self.clock.now(),
         });
 
         info!("Paper Trading Engine stopped");
@@ -464,7 +621,13 @@ impl PaperTradingEngine {
 
                             // Build strategy input from cached data
                             if let Some(input) = engine.build_strategy_input(symbol).await {
-                                match engine.strategy_engine.analyze_market(&input).await {
+                                match engine
+                                    .strategy_engine
+                                    .read()
+                                    .await
+                                    .analyze_market(&input)
+                                    .await
+                                {
                                     Ok(combined_signal) => {
                                         let signal = combined_signal.final_signal;
                                         let confidence = combined_signal.combined_confidence;
@@ -490,13 +653,22 @@ impl PaperTradingEngine {
                                                     symbol: symbol.to_string(),
                                                     signal_type: format!("{:?}", signal),
                                                     confidence,
-                                                    timestamp: Utc::now().to_rfc3339(),
+                                                    timestamp: engine.clock.now().to_rfc3339(),
                                                     strategies_agreeing,
                                                     entry_price: input.current_price,
                                                 },
                                             );
                                         }
 
+                                        // Pair strategies trade both legs themselves
+                                        engine
+                                            .handle_pair_signals(
+                                                symbol,
+                                                &combined_signal.strategy_signals,
+                                                min_confidence,
+                                            )
+                                            .await;
+
                                         // Skip neutral signals
                                         if signal == TradingSignal::Neutral {
                                             continue;
@@ -539,7 +711,7 @@ impl PaperTradingEngine {
                                         {
                                             let mut tracker =
                                                 engine.signal_flip_tracker.write().await;
-                                            let now_ts = Utc::now().timestamp();
+                                            let now_ts = engine.clock.now().timestamp();
                                             let symbol_key = symbol.to_string();
 
                                             let flips = tracker
@@ -580,7 +752,7 @@ impl PaperTradingEngine {
                                                 _ => TradingSignal::Neutral,
                                             }
                                         );
-                                        let now = Utc::now().timestamp();
+                                        let now = engine.clock.now().timestamp();
 
                                         let confirmed = {
                                             let recent = engine.recent_signals.read().await;
@@ -693,7 +865,7 @@ impl PaperTradingEngine {
 
                                         // Convert to AITradingSignal and execute
                                         let ai_signal = AITradingSignal {
-                                            id: Uuid::new_v4().to_string(),
+                                            id: engine.next_uuid().to_string(),
                                             symbol: symbol.clone(),
                                             signal_type: signal,
                                             confidence,
@@ -718,7 +890,7 @@ impl PaperTradingEngine {
                                                 ),
                                                 risk_score: 1.0 - confidence,
                                             },
-                                            timestamp: Utc::now(),
+                                            timestamp: engine.clock.now(),
                                         };
 
                                         // Broadcast strategy signal via WebSocket
@@ -739,7 +911,7 @@ impl PaperTradingEngine {
                                                     }))
                                                     .collect::<Vec<_>>(),
                                             }),
-                                            timestamp: Utc::now(),
+                                            timestamp: engine.clock.now(),
                                         });
 
                                         // Execute the trade
@@ -747,6 +919,14 @@ impl PaperTradingEngine {
                                             Ok(result) => {
                                                 if result.success {
                                                     info!("🎯 Trade executed from strategy signal: {} {:?}", symbol, signal);
+                                                    if let Some(trade_id) = &result.trade_id {
+                                                        engine
+                                                            .attach_strategy_votes(
+                                                                trade_id,
+                                                                &combined_signal.strategy_signals,
+                                                            )
+                                                            .await;
+                                                    }
                                                 } else {
                                                     debug!(
                                                         "Strategy signal not executed for {}: {}",
@@ -783,11 +963,15 @@ impl PaperTradingEngine {
         let cache = self.historical_data_cache.read().await;
 
         let mut timeframe_data: HashMap<String, Vec<CandleData>> = HashMap::new();
+        let mut indicators = HashMap::new();
 
         for timeframe in STRATEGY_ALL_TIMEFRAMES {
             let cache_key = format!("{}_{}", symbol, timeframe);
             if let Some(klines) = cache.get(&cache_key) {
                 let candles: Vec<CandleData> = klines.iter().map(CandleData::from).collect();
+                if let Some(snapshot) = self.indicator_cache.sync(symbol, timeframe, &candles) {
+                    indicators.insert(timeframe.to_string(), snapshot);
+                }
                 if !candles.is_empty() {
                     timeframe_data.insert(timeframe.to_string(), candles);
                 }
@@ -822,12 +1006,33 @@ impl PaperTradingEngine {
             .map(|candles| candles.iter().rev().take(24).map(|c| c.volume).sum::<f64>())
             .unwrap_or(0.0);
 
+        // Candles of other symbols needed by enabled strategies (e.g. pairs hedge leg);
+        // only symbols tracked by the strategy loop have cached klines
+        let related_symbols = self.strategy_engine.read().await.related_symbols();
+        let mut related_data = HashMap::new();
+        for related_symbol in related_symbols.iter().filter(|s| s.as_str() != symbol) {
+            let mut related_timeframes = HashMap::new();
+            for timeframe in STRATEGY_ALL_TIMEFRAMES {
+                if let Some(klines) = cache.get(&format!("{}_{}", related_symbol, timeframe)) {
+                    related_timeframes.insert(
+                        timeframe.to_string(),
+                        klines.iter().map(CandleData::from).collect::<Vec<_>>(),
+                    );
+                }
+            }
+            if !related_timeframes.is_empty() {
+                related_data.insert(related_symbol.clone(), related_timeframes);
+            }
+        }
+
         Some(crate::strategies::StrategyInput {
             symbol: symbol.to_string(),
             timeframe_data,
             current_price,
             volume_24h,
-            timestamp: Utc::now().timestamp(),
+            timestamp: self.clock.now().timestamp(),
+            indicators,
+            related_data,
         })
     }
 
@@ -1041,7 +1246,7 @@ impl PaperTradingEngine {
         let _ = self.event_broadcaster.send(PaperTradingEvent {
             event_type: "price_update".to_string(),
             data: serde_json::to_value(&new_prices)?,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         // @spec:FR-PAPER-003 - Check pending stop-limit orders on every price update
@@ -1240,7 +1445,7 @@ impl PaperTradingEngine {
         }
 
         let mut portfolio = self.portfolio.write().await;
-        let now = Utc::now();
+        let now = self.clock.now();
         let current_equity = portfolio.equity;
 
         // Check if we need to reset (new week or first time)
@@ -1666,7 +1871,7 @@ impl PaperTradingEngine {
             calculated_leverage: leverage,
             stop_loss,
             take_profit,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         };
 
         // Add to execution queue
@@ -1679,7 +1884,7 @@ impl PaperTradingEngine {
         let _ = self.event_broadcaster.send(PaperTradingEvent {
             event_type: "ai_signal_received".to_string(),
             data: serde_json::to_value(&signal)?,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         // Execute the trade
@@ -1724,8 +1929,7 @@ impl PaperTradingEngine {
         drop(settings);
 
         // Random slippage between 0 and max_slippage_pct
-        let mut rng = rand::rng();
-        let slippage_pct = rng.random::<f64>() * max_slippage;
+        let slippage_pct = self.lock_rng().random::<f64>() * max_slippage;
 
         let slipped_price = match trade_type {
             TradeType::Long => price * (1.0 + slippage_pct / 100.0), // Buy at higher price
@@ -1804,11 +2008,14 @@ impl PaperTradingEngine {
         let partial_prob = settings.execution.partial_fill_probability;
         drop(settings);
 
-        let mut rng = rand::rng();
+        let (partial, fill_draw) = {
+            let mut rng = self.lock_rng();
+            (rng.random::<f64>() < partial_prob, rng.random::<f64>())
+        };
 
-        if rng.random::<f64>() < partial_prob {
+        if partial {
             // Partial fill: 30-90% of requested quantity
-            let fill_pct = 0.3 + (rng.random::<f64>() * 0.6);
+            let fill_pct = 0.3 + (fill_draw * 0.6);
             let filled_qty = quantity * fill_pct;
 
             warn!(
@@ -2047,7 +2254,7 @@ impl PaperTradingEngine {
                     "daily_limit_pct": daily_limit_pct,
                     "daily_loss_usd": daily_loss,
                 }),
-                timestamp: Utc::now(),
+                timestamp: self.clock.now(),
             });
 
             return Ok(false); // Block new trades
@@ -2062,8 +2269,8 @@ impl PaperTradingEngine {
         let portfolio = self.portfolio.read().await;
 
         if let Some(cool_down_until) = portfolio.cool_down_until {
-            if Utc::now() < cool_down_until {
-                let remaining = (cool_down_until - Utc::now()).num_minutes();
+            if self.clock.now() < cool_down_until {
+                let remaining = (cool_down_until - self.clock.now()).num_minutes();
                 warn!(
                     "🧊 Cool-down active: {} minutes remaining (consecutive losses: {})",
                     remaining, portfolio.consecutive_losses
@@ -2092,7 +2299,7 @@ impl PaperTradingEngine {
             if portfolio.consecutive_losses >= settings.risk.max_consecutive_losses {
                 let cool_down = settings.risk.cool_down_minutes;
                 portfolio.cool_down_until =
-                    Some(Utc::now() + chrono::Duration::minutes(cool_down as i64));
+                    Some(self.clock.now() + chrono::Duration::minutes(cool_down as i64));
 
                 error!(
                     "🛑 {} consecutive losses reached. Cool-down for {} minutes.",
@@ -2107,7 +2314,7 @@ impl PaperTradingEngine {
                         "cool_down_minutes": cool_down,
                         "cool_down_until": portfolio.cool_down_until,
                     }),
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
                 });
             }
         } else {
@@ -2179,7 +2386,7 @@ impl PaperTradingEngine {
                         "current_ratio": long_ratio,
                         "limit": correlation_limit,
                     }),
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
                 });
 
                 Ok(false)
@@ -2199,7 +2406,7 @@ impl PaperTradingEngine {
                         "current_ratio": short_ratio,
                         "limit": correlation_limit,
                     }),
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
                 });
 
                 Ok(false)
@@ -2272,7 +2479,7 @@ impl PaperTradingEngine {
                     "max_risk_pct": max_portfolio_risk_pct,
                     "open_positions": open_trades.len(),
                 }),
-                timestamp: Utc::now(),
+                timestamp: self.clock.now(),
             });
 
             return Ok(false);
@@ -2490,7 +2697,7 @@ impl PaperTradingEngine {
             calculated_leverage: leverage,
             stop_loss,
             take_profit,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         };
 
         let execution_result = self.execute_trade(pending_trade).await?;
@@ -2515,7 +2722,7 @@ impl PaperTradingEngine {
                     "new_entry_price": execution_result.execution_price,
                     "confidence": new_signal.confidence,
                 }),
-                timestamp: Utc::now(),
+                timestamp: self.clock.now(),
             });
         } else {
             warn!(
@@ -2670,6 +2877,19 @@ impl PaperTradingEngine {
     /// @spec:FR-TRADING-015 - Execution Realism (Phase 1)
     /// @spec:FR-TRADING-015 - Performance Metrics (Phase 4)
     async fn execute_trade(&self, pending_trade: PendingTrade) -> Result<TradeExecutionResult> {
+        self.execute_trade_with(pending_trade, None).await
+    }
+
+    /// Execute a trade, optionally as the fill of a limit order
+    ///
+    /// A maker fill happens at the limit price with no delay, slippage, market impact or
+    /// partial fill, and pays `execution.maker_fee_rate`. A taker fill of a limit order
+    /// runs the normal simulation but never fills worse than the limit price.
+    async fn execute_trade_with(
+        &self,
+        pending_trade: PendingTrade,
+        limit_fill: Option<LimitFill>,
+    ) -> Result<TradeExecutionResult> {
         let signal = &pending_trade.signal;
 
         // Determine trade type
@@ -2689,45 +2909,52 @@ impl PaperTradingEngine {
 
         // ========== PHASE 1: EXECUTION REALISM SIMULATION ==========
 
-        // 1. Simulate execution delay (network latency)
+        let maker_fill = limit_fill.filter(|fill| fill.maker);
         let settings = self.settings.read().await;
         let execution_delay_ms = settings.execution.execution_delay_ms;
-        let trading_fee_rate = settings.basic.trading_fee_rate;
+        let trading_fee_rate = if maker_fill.is_some() {
+            settings.execution.maker_fee_rate
+        } else {
+            settings.basic.trading_fee_rate
+        };
+        let margin_mode = settings.risk.margin_mode;
         drop(settings);
 
-        if execution_delay_ms > 0 {
-            debug!("⏳ Simulating execution delay: {}ms", execution_delay_ms);
-            tokio::time::sleep(Duration::from_millis(execution_delay_ms as u64)).await;
-        }
-
-        // 2. Re-fetch current price after delay (price may have moved!)
-        let current_price = self
-            .current_prices
-            .read()
-            .await
-            .get(&signal.symbol)
-            .copied()
-            .unwrap_or(signal.entry_price);
-
-        // 3. Calculate market impact based on order size
-        let market_impact_pct = self
-            .calculate_market_impact(
-                &signal.symbol,
-                pending_trade.calculated_quantity,
-                current_price,
-            )
-            .await;
-
-        // 4. Apply market impact to price
-        let price_with_impact = current_price * (1.0 + market_impact_pct / 100.0);
+        // Resting orders fill passively at their limit price
+        let (current_price, market_impact_pct, execution_price, filled_quantity) =
+            if let Some(fill) = maker_fill {
+                (
+                    fill.limit_price,
+                    0.0,
+                    fill.limit_price,
+                    pending_trade.calculated_quantity,
+                )
+            } else {
+                self.simulate_execution(&pending_trade, trade_type, execution_delay_ms)
+                    .await
+            };
 
-        // 5. Apply slippage simulation
-        let execution_price = self.apply_slippage(price_with_impact, trade_type).await;
+        if limit_fill.is_some_and(|fill| fill.fill_or_kill)
+            && filled_quantity < pending_trade.calculated_quantity
+        {
+            return Ok(TradeExecutionResult {
+                success: false,
+                trade_id: None,
+                error_message: Some(format!(
+                    "FOK limit order cancelled: only {} of {} could be filled",
+                    filled_quantity, pending_trade.calculated_quantity
+                )),
+                execution_price: None,
+                fees_paid: None,
+            });
+        }
 
-        // 6. Simulate partial fills
-        let (filled_quantity, _is_partial) = self
-            .simulate_partial_fill(pending_trade.calculated_quantity)
-            .await;
+        // A marketable limit order never fills beyond its limit
+        let execution_price = match (limit_fill, trade_type) {
+            (Some(fill), TradeType::Long) => execution_price.min(fill.limit_price),
+            (Some(fill), TradeType::Short) => execution_price.max(fill.limit_price),
+            (None, _) => execution_price,
+        };
 
         info!(
             "🎯 Execution simulation complete for {}: base={:.2}, impact={:.4}%, slippage applied, fill={:.1}%",
@@ -2740,23 +2967,36 @@ impl PaperTradingEngine {
         // ========== CREATE PAPER TRADE WITH REALISTIC EXECUTION ==========
 
         // Create paper trade with realistic execution price
-        let mut paper_trade = PaperTrade::new(
-            signal.symbol.clone(),
-            trade_type,
-            execution_price, // Use realistic execution price (not signal price!)
-            filled_quantity, // Use actual filled quantity (may be partial)
-            pending_trade.calculated_leverage,
-            trading_fee_rate,
-            Some(signal.id.clone()),
-            Some(signal.confidence),
-            Some(signal.reasoning.clone()),
+        let mut paper_trade = self.stamp_trade(
+            PaperTrade::new(
+                signal.symbol.clone(),
+                trade_type,
+                execution_price, // Use realistic execution price (not signal price!)
+                filled_quantity, // Use actual filled quantity (may be partial)
+                pending_trade.calculated_leverage,
+                trading_fee_rate,
+                Some(signal.id.clone()),
+                Some(signal.confidence),
+                Some(signal.reasoning.clone()),
+            )
+            .with_margin_mode(margin_mode),
         );
+        if limit_fill.is_some() {
+            let liquidity = if maker_fill.is_some() {
+                "maker"
+            } else {
+                "taker"
+            };
+            paper_trade
+                .metadata
+                .insert(LIQUIDITY_KEY.to_string(), serde_json::json!(liquidity));
+        }
 
         // ========== PHASE 4: PERFORMANCE METRICS ==========
 
         // Set signal timestamp and calculate execution latency
         paper_trade.signal_timestamp = Some(signal.timestamp);
-        paper_trade.execution_timestamp = Utc::now();
+        paper_trade.execution_timestamp = self.clock.now();
 
         if let Some(signal_ts) = paper_trade.signal_timestamp {
             let latency = (paper_trade.execution_timestamp - signal_ts)
@@ -2850,7 +3090,7 @@ impl PaperTradingEngine {
                 "leverage": pending_trade.calculated_leverage,
                 "slippage_pct": ((execution_price - signal.entry_price) / signal.entry_price * 100.0).abs(),
             }),
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         info!(
@@ -2872,13 +3112,69 @@ impl PaperTradingEngine {
         })
     }
 
+    /// Run the delay, market impact, slippage and partial fill simulation
+    ///
+    /// Returns (base price, market impact %, execution price, filled quantity).
+    async fn simulate_execution(
+        &self,
+        pending_trade: &PendingTrade,
+        trade_type: TradeType,
+        execution_delay_ms: u32,
+    ) -> (f64, f64, f64, f64) {
+        let signal = &pending_trade.signal;
+
+        // 1. Simulate execution delay (network latency)
+        if execution_delay_ms > 0 {
+            debug!("⏳ Simulating execution delay: {}ms", execution_delay_ms);
+            tokio::time::sleep(Duration::from_millis(execution_delay_ms as u64)).await;
+        }
+
+        // 2. Re-fetch current price after delay (price may have moved!)
+        let current_price = self
+            .current_prices
+            .read()
+            .await
+            .get(&signal.symbol)
+            .copied()
+            .unwrap_or(signal.entry_price);
+
+        // 3. Calculate market impact based on order size
+        let market_impact_pct = self
+            .calculate_market_impact(
+                &signal.symbol,
+                pending_trade.calculated_quantity,
+                current_price,
+            )
+            .await;
+
+        // 4. Apply market impact to price
+        let price_with_impact = current_price * (1.0 + market_impact_pct / 100.0);
+
+        // 5. Apply slippage simulation
+        let execution_price = self.apply_slippage(price_with_impact, trade_type).await;
+
+        // 6. Simulate partial fills
+        let (filled_quantity, _is_partial) = self
+            .simulate_partial_fill(pending_trade.calculated_quantity)
+            .await;
+
+        (
+            current_price,
+            market_impact_pct,
+            execution_price,
+            filled_quantity,
+        )
+    }
+
     /// Monitor open trades for stop loss/take profit
     /// Uses engine-level close_trade() to persist closures to MongoDB
     async fn monitor_open_trades(&self) -> Result<()> {
         // Step 1: Detect which trades need closing (read-only)
-        let trades_to_close = {
+        let (trades_to_close, take_profit_levels_hit) = {
             let portfolio = self.portfolio.read().await;
             let mut to_close: Vec<(String, CloseReason)> = Vec::new();
+            let mut levels_hit: Vec<(String, usize)> = Vec::new();
+            let liquidations = portfolio.liquidation_candidates();
 
             for trade_id in &portfolio.open_trade_ids {
                 if let Some(trade) = portfolio.trades.get(trade_id) {
@@ -2906,6 +3202,16 @@ impl PaperTradingEngine {
                                 CloseReason::StopLoss
                             };
                             to_close.push((trade_id.clone(), reason));
+                        } else if let Some(level) = trade.take_profit_level_hit(*current_price) {
+                            info!(
+                                "✅ TP LEVEL {} HIT: {} ({} {:?}) price=${:.2}",
+                                level + 1,
+                                trade_id,
+                                trade.symbol,
+                                trade.trade_type,
+                                current_price
+                            );
+                            levels_hit.push((trade_id.clone(), level));
                         } else if trade.should_take_profit(*current_price) {
                             info!(
                                 "✅ TP DETECTED: {} ({} {:?}) price=${:.2} tp=${:.2}",
@@ -2916,21 +3222,63 @@ impl PaperTradingEngine {
                                 trade.take_profit.unwrap_or(0.0)
                             );
                             to_close.push((trade_id.clone(), CloseReason::TakeProfit));
-                        } else if trade.is_at_liquidation_risk(*current_price) {
+                        } else if liquidations.contains(trade_id) {
                             warn!(
-                                "⚠️ LIQUIDATION DETECTED: {} ({} {:?})",
-                                trade_id, trade.symbol, trade.trade_type
+                                "💥 LIQUIDATION: {} ({} {:?} {:?}) price=${:.2} liq=${:.2}",
+                                trade_id,
+                                trade.symbol,
+                                trade.trade_type,
+                                trade.margin_mode,
+                                current_price,
+                                trade.liquidation_price.unwrap_or(0.0)
                             );
-                            to_close.push((trade_id.clone(), CloseReason::MarginCall));
+                            to_close.push((trade_id.clone(), CloseReason::Liquidation));
                         }
                     }
                 }
             }
-            to_close
+            (to_close, levels_hit)
         }; // Drop read lock
 
+        // Take partial profits; a level covering the whole remaining position closes it
+        for (trade_id, level) in take_profit_levels_hit {
+            let quantity = {
+                let mut portfolio = self.portfolio.write().await;
+                match portfolio
+                    .trades
+                    .get_mut(&trade_id)
+                    .and_then(|t| t.take_profit_levels.get_mut(level))
+                {
+                    Some(take_profit_level) => {
+                        take_profit_level.filled = true;
+                        take_profit_level.quantity
+                    },
+                    None => continue,
+                }
+            };
+            if let Err(e) = self
+                .reduce_position(&trade_id, quantity, CloseReason::TakeProfit)
+                .await
+            {
+                error!(
+                    "❌ Failed to take partial profit on trade {}: {}",
+                    trade_id, e
+                );
+            }
+        }
+
         // Step 2: Close via engine-level close_trade() → persists to MongoDB
         for (trade_id, close_reason) in trades_to_close {
+            // Skip legs already closed together with their pair earlier in this pass
+            if !self
+                .portfolio
+                .read()
+                .await
+                .open_trade_ids
+                .contains(&trade_id)
+            {
+                continue;
+            }
             info!(
                 "🔒 Auto-closing trade {} due to {:?}",
                 trade_id, close_reason
@@ -2953,7 +3301,7 @@ impl PaperTradingEngine {
         let _ = self.event_broadcaster.send(PaperTradingEvent {
             event_type: "performance_update".to_string(),
             data: serde_json::to_value(&metrics)?,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         Ok(())
@@ -3079,20 +3427,14 @@ impl PaperTradingEngine {
                             "RiskManagement" => Some(CloseReason::RiskManagement),
                             "MarginCall" => Some(CloseReason::MarginCall),
                             "TimeBasedExit" => Some(CloseReason::TimeBasedExit),
+                            "Liquidation" => Some(CloseReason::Liquidation),
                             _ => None,
                         });
 
                 let notional_value = trade_record.quantity * trade_record.entry_price;
                 let initial_margin = notional_value / trade_record.leverage as f64;
-                let maintenance_margin_rate = match trade_record.leverage {
-                    1..=5 => 0.01,
-                    6..=10 => 0.025,
-                    11..=20 => 0.05,
-                    21..=50 => 0.1,
-                    51..=100 => 0.125,
-                    _ => 0.15,
-                };
-                let maintenance_margin = notional_value * maintenance_margin_rate;
+                let maintenance_margin =
+                    super::margin::maintenance_margin(&trade_record.symbol, notional_value);
 
                 // Calculate SL/TP for open trades from settings
                 let (restored_sl, restored_tp) = if status == super::trade::TradeStatus::Open {
@@ -3154,6 +3496,11 @@ impl PaperTradingEngine {
                     highest_price_achieved: None,
                     trailing_stop_active: false,
                     metadata: std::collections::HashMap::new(),
+                    partial_closes: Vec::new(),
+                    scale_ins: Vec::new(),
+                    take_profit_levels: Vec::new(),
+                    margin_mode: settings.risk.margin_mode,
+                    liquidation_price: None,
                 };
 
                 // Add trade to portfolio
@@ -3352,8 +3699,8 @@ impl PaperTradingEngine {
         // Broadcast start event
         let _ = self.event_broadcaster.send(PaperTradingEvent {
             event_type: "engine_started".to_string(),
-            data: serde_json::json!({ "timestamp": Utc::now() }),
-            timestamp: Utc::now(),
+            data: serde_json::json!({ "timestamp": self.clock.now() }),
+            timestamp: self.clock.now(),
         });
 
         info!("Paper Trading Engine started successfully (async)");
@@ -3405,12 +3752,88 @@ impl PaperTradingEngine {
             .collect()
     }
 
+    /// Names of all strategies loaded in the strategy engine, with whether each one runs
+    /// in the signal loop
+    /// @spec:FR-STRATEGIES-005 - Strategy Parameters
+    pub async fn get_strategy_statuses(&self) -> Vec<(String, bool)> {
+        let strategy_engine = self.strategy_engine.read().await;
+        strategy_engine
+            .get_strategy_names()
+            .into_iter()
+            .map(|name| (name.to_string(), strategy_engine.is_strategy_enabled(name)))
+            .collect()
+    }
+
+    /// Names of the strategies that currently run in the signal loop
+    pub async fn get_enabled_strategies(&self) -> Vec<String> {
+        self.get_strategy_statuses()
+            .await
+            .into_iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Replace the set of strategies that run in the signal loop
+    pub async fn set_enabled_strategies(&self, names: Vec<String>) -> Result<()> {
+        self.strategy_engine
+            .write()
+            .await
+            .set_enabled_strategies(names)?;
+        Ok(())
+    }
+
+    pub async fn enable_strategy(&self, name: &str) -> Result<()> {
+        self.strategy_engine.write().await.enable_strategy(name)?;
+        Ok(())
+    }
+
+    pub async fn disable_strategy(&self, name: &str) -> Result<()> {
+        self.strategy_engine.write().await.disable_strategy(name)?;
+        Ok(())
+    }
+
+    /// Monte Carlo analysis of the closed trades' PnL: confidence intervals for
+    /// drawdown and final equity instead of the single-path portfolio metrics
+    /// @spec:FR-PORTFOLIO-003 - P&L Calculation
+    pub async fn run_monte_carlo(&self, config: &MonteCarloConfig) -> Result<MonteCarloReport> {
+        let (closed_trades, initial_balance) = {
+            let portfolio = self.portfolio.read().await;
+            let closed_trades: Vec<PaperTrade> =
+                portfolio.get_closed_trades().into_iter().cloned().collect();
+            (closed_trades, portfolio.initial_balance)
+        };
+
+        // Up to MAX_SIMULATIONS paths of CPU-bound work: keep it off the async runtime
+        let config = config.clone();
+        tokio::task::spawn_blocking(move || {
+            let trades: Vec<&PaperTrade> = closed_trades.iter().collect();
+            super::monte_carlo::run_monte_carlo(&trades, initial_balance, &config)
+        })
+        .await?
+    }
+
     /// Close a trade with specified reason
     ///
     /// # Arguments
     /// * `trade_id` - ID of the trade to close
     /// * `close_reason` - Reason for closing (Manual, AISignal, StopLoss, etc.)
     pub async fn close_trade(&self, trade_id: &str, close_reason: CloseReason) -> Result<()> {
+        // Closing either leg of a pair trade closes the whole pair
+        let pair_id = {
+            let portfolio = self.portfolio.read().await;
+            portfolio
+                .get_trade(trade_id)
+                .and_then(|t| t.pair_id())
+                .map(str::to_string)
+        };
+        if let Some(pair_id) = pair_id {
+            return self
+                .close_pair_trade(&pair_id, close_reason)
+                .await
+                .map(|_| ());
+        }
+
         let current_price = {
             let portfolio = self.portfolio.read().await;
             if let Some(trade) = portfolio.get_trade(trade_id) {
@@ -3425,6 +3848,7 @@ impl PaperTradingEngine {
             }
         };
 
+        let liquidated = close_reason == CloseReason::Liquidation;
         let mut portfolio = self.portfolio.write().await;
         portfolio.close_trade(trade_id, current_price, close_reason)?;
 
@@ -3433,6 +3857,12 @@ impl PaperTradingEngine {
             .get_trade(trade_id)
             .and_then(|t| t.realized_pnl)
             .unwrap_or(0.0);
+        let strategy_votes = portfolio.get_trade(trade_id).and_then(|t| {
+            let votes = t.metadata.get(STRATEGY_VOTES_KEY)?;
+            let votes: HashMap<String, TradingSignal> =
+                serde_json::from_value(votes.clone()).ok()?;
+            Some((votes, t.trade_type))
+        });
 
         // Get the closed trade and update in database
         if let Some(trade) = portfolio.get_trade(trade_id) {
@@ -3538,7 +3968,7 @@ impl PaperTradingEngine {
                         "close_reason": close_reason_str,
                         "trade_id": trade_id,
                     }),
-                    timestamp: Utc::now(),
+                    timestamp: self.clock.now(),
                 });
             }
 
@@ -3565,6 +3995,35 @@ impl PaperTradingEngine {
         // Update consecutive losses counter and check cool-down
         self.update_consecutive_losses(trade_pnl).await;
 
+        // Feed the outcome back into the learned ensemble weights
+        if let Some((votes, trade_type)) = strategy_votes {
+            let direction = match trade_type {
+                TradeType::Long => TradingSignal::Long,
+                TradeType::Short => TradingSignal::Short,
+            };
+            self.strategy_engine
+                .read()
+                .await
+                .record_signal_outcome(&votes, direction, trade_pnl > 0.0)
+                .await;
+        }
+
+        if liquidated {
+            warn!(
+                "💥 Trade {} liquidated at {:.2}, PnL {:.2}",
+                trade_id, current_price, trade_pnl
+            );
+            let _ = self.event_broadcaster.send(PaperTradingEvent {
+                event_type: "trade_liquidated".to_string(),
+                data: serde_json::json!({
+                    "trade_id": trade_id,
+                    "exit_price": current_price,
+                    "realized_pnl": trade_pnl,
+                }),
+                timestamp: self.clock.now(),
+            });
+        }
+
         // Broadcast trade closure event
         let _ = self.event_broadcaster.send(PaperTradingEvent {
             event_type: "trade_closed".to_string(),
@@ -3572,32 +4031,194 @@ impl PaperTradingEngine {
                 "trade_id": trade_id,
                 "reason": "manual",
             }),
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         Ok(())
     }
 
-    /// Execute a manual order placed by the user
-    /// @spec:FR-PAPER-003 - Manual Order Placement
-    /// @doc:docs/features/paper-trading.md#manual-orders
+    /// Close `fraction` (0, 1] of an open trade at the current price
     ///
-    /// Execute a manual order with the given parameters
-    ///
-    /// # Arguments
-    /// * `params` - Order parameters including symbol, side, type, quantity, prices, etc.
-    pub async fn execute_manual_order(
+    /// Returns the realized PnL of the closed part. Pair trade legs can only be closed
+    /// together, so they reject fractions below 1.
+    pub async fn partial_close_trade(
         &self,
-        params: super::ManualOrderParams,
-    ) -> Result<TradeExecutionResult> {
-        // Extract parameters from struct
-        let symbol = params.symbol;
-        let side = params.side;
-        let order_type = params.order_type;
-        let quantity = params.quantity;
-        let price = params.price;
-        let stop_price = params.stop_price;
-        let leverage = params.leverage;
+        trade_id: &str,
+        fraction: f64,
+        close_reason: CloseReason,
+    ) -> Result<f64> {
+        if !(fraction > 0.0 && fraction <= 1.0) {
+            return Err(anyhow::anyhow!(
+                "Close fraction must be in (0, 1], got {}",
+                fraction
+            ));
+        }
+        let quantity = {
+            let portfolio = self.portfolio.read().await;
+            let trade = portfolio
+                .get_trade(trade_id)
+                .ok_or_else(|| anyhow::anyhow!("Trade not found"))?;
+            if trade.pair_id().is_some() && fraction < 1.0 {
+                return Err(anyhow::anyhow!(
+                    "Pair trade legs can only be closed in full"
+                ));
+            }
+            trade.quantity * fraction
+        };
+        self.reduce_position(trade_id, quantity, close_reason).await
+    }
+
+    /// Close `quantity` of a trade at the current price, or all of it if that covers the
+    /// position, persisting the change and returning the PnL realized by this fill
+    async fn reduce_position(
+        &self,
+        trade_id: &str,
+        quantity: f64,
+        close_reason: CloseReason,
+    ) -> Result<f64> {
+        let (symbol, entry_price, remaining, partial_pnl_before) = {
+            let portfolio = self.portfolio.read().await;
+            let trade = portfolio
+                .get_trade(trade_id)
+                .ok_or_else(|| anyhow::anyhow!("Trade not found"))?;
+            (
+                trade.symbol.clone(),
+                trade.entry_price,
+                trade.quantity,
+                trade.partial_realized_pnl(),
+            )
+        };
+
+        if quantity >= remaining * (1.0 - 1e-9) {
+            self.close_trade(trade_id, close_reason).await?;
+            let portfolio = self.portfolio.read().await;
+            let realized_pnl = portfolio
+                .get_trade(trade_id)
+                .and_then(|t| t.realized_pnl)
+                .unwrap_or(0.0);
+            return Ok(realized_pnl - partial_pnl_before);
+        }
+
+        let exit_price = self
+            .current_prices
+            .read()
+            .await
+            .get(&symbol)
+            .copied()
+            .unwrap_or(entry_price);
+
+        let mut portfolio = self.portfolio.write().await;
+        let realized_pnl =
+            portfolio.reduce_trade(trade_id, quantity, exit_price, close_reason.clone())?;
+        if let Some(trade) = portfolio.get_trade(trade_id) {
+            if let Err(e) = self.storage.update_paper_trade(trade).await {
+                error!(
+                    "❌ Failed to update partially closed trade {} in database: {}",
+                    trade_id, e
+                );
+            }
+            info!(
+                "✂️ Partially closed {}: {} {} @ {:.2}, realized {:.2}, remaining {}",
+                trade_id, quantity, symbol, exit_price, realized_pnl, trade.quantity
+            );
+            let _ = self.event_broadcaster.send(PaperTradingEvent {
+                event_type: "trade_partially_closed".to_string(),
+                data: serde_json::json!({
+                    "trade_id": trade_id,
+                    "symbol": symbol,
+                    "quantity": quantity,
+                    "exit_price": exit_price,
+                    "realized_pnl": realized_pnl,
+                    "remaining_quantity": trade.quantity,
+                    "reason": close_reason,
+                }),
+                timestamp: self.clock.now(),
+            });
+        }
+
+        Ok(realized_pnl)
+    }
+
+    /// Add `quantity` to an open trade at the current price, re-averaging its entry
+    pub async fn scale_in_trade(&self, trade_id: &str, quantity: f64) -> Result<()> {
+        let (symbol, trade_type) = {
+            let portfolio = self.portfolio.read().await;
+            let trade = portfolio
+                .get_trade(trade_id)
+                .ok_or_else(|| anyhow::anyhow!("Trade not found"))?;
+            (trade.symbol.clone(), trade.trade_type)
+        };
+        let Some(current_price) = self.current_prices.read().await.get(&symbol).copied() else {
+            return Err(anyhow::anyhow!("No market price available for {}", symbol));
+        };
+        let price = self.apply_slippage(current_price, trade_type).await;
+        let trading_fee_rate = self.settings.read().await.basic.trading_fee_rate;
+
+        let mut portfolio = self.portfolio.write().await;
+        portfolio.scale_in_trade(trade_id, quantity, price, trading_fee_rate)?;
+        if let Some(trade) = portfolio.get_trade(trade_id) {
+            if let Err(e) = self.storage.update_paper_trade(trade).await {
+                error!(
+                    "❌ Failed to update scaled-in trade {} in database: {}",
+                    trade_id, e
+                );
+            }
+            info!(
+                "➕ Scaled into {}: {} {} @ {:.2}, new entry {:.2}, size {}",
+                trade_id, quantity, symbol, price, trade.entry_price, trade.quantity
+            );
+            let _ = self.event_broadcaster.send(PaperTradingEvent {
+                event_type: "trade_scaled_in".to_string(),
+                data: serde_json::json!({
+                    "trade_id": trade_id,
+                    "symbol": symbol,
+                    "quantity": quantity,
+                    "price": price,
+                    "entry_price": trade.entry_price,
+                    "total_quantity": trade.quantity,
+                }),
+                timestamp: self.clock.now(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Set `(price, fraction)` take profit levels on an open trade
+    pub async fn set_take_profit_levels(
+        &self,
+        trade_id: &str,
+        levels: &[(f64, f64)],
+    ) -> Result<()> {
+        let mut portfolio = self.portfolio.write().await;
+        let trade = portfolio
+            .trades
+            .get_mut(trade_id)
+            .filter(|t| t.status == TradeStatus::Open)
+            .ok_or_else(|| anyhow::anyhow!("Open trade not found: {}", trade_id))?;
+        trade.set_take_profit_levels(levels)
+    }
+
+    /// Execute a manual order placed by the user
+    /// @spec:FR-PAPER-003 - Manual Order Placement
+    /// @doc:docs/features/paper-trading.md#manual-orders
+    ///
+    /// Execute a manual order with the given parameters
+    ///
+    /// # Arguments
+    /// * `params` - Order parameters including symbol, side, type, quantity, prices, etc.
+    pub async fn execute_manual_order(
+        &self,
+        params: super::ManualOrderParams,
+    ) -> Result<TradeExecutionResult> {
+        // Extract parameters from struct
+        let symbol = params.symbol.clone();
+        let side = params.side.clone();
+        let order_type = params.order_type.clone();
+        let quantity = params.quantity;
+        let price = params.price;
+        let stop_price = params.stop_price;
+        let leverage = params.leverage;
         let stop_loss_pct = params.stop_loss_pct;
         let take_profit_pct = params.take_profit_pct;
 
@@ -3624,6 +4245,12 @@ impl PaperTradingEngine {
         // 2. Handle order type - for stop-limit, create pending order instead of executing
         let order_type_lower = order_type.to_lowercase();
 
+        match order_type_lower.as_str() {
+            "bracket" => return self.place_bracket_order(&params, signal_type).await,
+            "oco" => return self.place_oco_order(&params, signal_type).await,
+            _ => {},
+        }
+
         // @spec:FR-PAPER-003 - Stop-Limit Order Handling
         // Stop-limit orders are added to pending queue and executed when stop price is triggered
         if order_type_lower == "stop-limit" {
@@ -3666,7 +4293,7 @@ impl PaperTradingEngine {
             let calculated_leverage = leverage.unwrap_or(default_leverage);
 
             // Create stop-limit order
-            let order_id = format!("stop-limit-{}", Uuid::new_v4());
+            let order_id = format!("stop-limit-{}", self.next_uuid());
             let pending_order = StopLimitOrder {
                 id: order_id.clone(),
                 symbol: symbol.clone(),
@@ -3679,10 +4306,17 @@ impl PaperTradingEngine {
                 stop_loss_pct,
                 take_profit_pct,
                 status: OrderStatus::Pending,
-                created_at: Utc::now(),
+                created_at: self.clock.now(),
                 triggered_at: None,
                 filled_at: None,
                 error_message: None,
+                time_in_force: params.time_in_force.unwrap_or_default(),
+                queue_ahead: 0.0,
+                expires_at: None,
+                volume_mark: None,
+                order_list_id: None,
+                stop_loss_price: params.stop_loss_price,
+                take_profit_price: params.take_profit_price,
             };
 
             // Add to pending orders
@@ -3709,7 +4343,7 @@ impl PaperTradingEngine {
                     "leverage": calculated_leverage,
                     "status": "pending"
                 }),
-                timestamp: Utc::now(),
+                timestamp: self.clock.now(),
             });
 
             return Ok(TradeExecutionResult {
@@ -3721,7 +4355,7 @@ impl PaperTradingEngine {
             });
         }
 
-        // For market and limit orders, get entry price and execute immediately
+        // Market orders execute immediately; limit orders fill now only if marketable
         let entry_price = match order_type_lower.as_str() {
             "market" => {
                 // Get current market price
@@ -3744,7 +4378,7 @@ impl PaperTradingEngine {
                 }
             },
             "limit" => match price {
-                Some(p) if p > 0.0 => p,
+                Some(p) if p > 0.0 => return self.place_limit_order(&params, signal_type, p).await,
                 _ => {
                     return Ok(TradeExecutionResult {
                         success: false,
@@ -3760,7 +4394,7 @@ impl PaperTradingEngine {
                     success: false,
                     trade_id: None,
                     error_message: Some(format!(
-                        "Invalid order type: {}. Must be 'market', 'limit', or 'stop-limit'",
+                        "Invalid order type: {}. Must be 'market', 'limit', 'stop-limit', 'bracket' or 'oco'",
                         order_type
                     )),
                     execution_price: None,
@@ -3769,6 +4403,348 @@ impl PaperTradingEngine {
             },
         };
 
+        self.execute_manual_fill(&params, signal_type, entry_price, None)
+            .await
+    }
+
+    /// Place a limit order
+    ///
+    /// A marketable order fills immediately as a taker, unless it is post-only (GTX).
+    /// Otherwise GTC and GTX orders rest until the traded price crosses them or they
+    /// expire after `execution.order_expiration_minutes`, while IOC and FOK orders are
+    /// cancelled.
+    /// @spec:FR-PAPER-003 - Limit Order Handling
+    async fn place_limit_order(
+        &self,
+        params: &super::ManualOrderParams,
+        signal_type: TradingSignal,
+        limit_price: f64,
+    ) -> Result<TradeExecutionResult> {
+        let time_in_force = params.time_in_force.unwrap_or_default();
+        let is_buy = matches!(signal_type, TradingSignal::Long);
+        let current_price = self
+            .current_prices
+            .read()
+            .await
+            .get(&params.symbol)
+            .copied();
+
+        let rejected = |message: String| {
+            Ok(TradeExecutionResult {
+                success: false,
+                trade_id: None,
+                error_message: Some(message),
+                execution_price: None,
+                fees_paid: None,
+            })
+        };
+
+        if let Some(market_price) = current_price.filter(|p| is_marketable(is_buy, limit_price, *p))
+        {
+            if time_in_force == TimeInForce::Gtx {
+                return rejected(format!(
+                    "Post-only (GTX) limit order at {:.2} would fill immediately against {:.2}",
+                    limit_price, market_price
+                ));
+            }
+            let fill = LimitFill {
+                limit_price,
+                maker: false,
+                fill_or_kill: time_in_force == TimeInForce::Fok,
+            };
+            return self
+                .execute_manual_fill(params, signal_type, market_price, Some(fill))
+                .await;
+        }
+
+        if matches!(time_in_force, TimeInForce::Ioc | TimeInForce::Fok) {
+            return rejected(format!(
+                "{} limit order at {:.2} cancelled: not marketable at the current price",
+                time_in_force, limit_price
+            ));
+        }
+
+        let order_id = format!("limit-{}", self.next_uuid());
+        let resting_order = self
+            .new_resting_limit_order(params, order_id.clone(), limit_price, time_in_force)
+            .await;
+        let leverage = resting_order.leverage;
+        let queue_ahead = resting_order.queue_ahead;
+        let now = resting_order.created_at;
+
+        self.pending_stop_limit_orders
+            .write()
+            .await
+            .push(resting_order);
+
+        info!(
+            "📋 Limit order resting: {} {} qty={} limit={:.2} tif={} queue_ahead={:.4}",
+            params.side, params.symbol, params.quantity, limit_price, time_in_force, queue_ahead
+        );
+
+        let _ = self.event_broadcaster.send(PaperTradingEvent {
+            event_type: "limit_order_created".to_string(),
+            data: serde_json::json!({
+                "order_id": order_id,
+                "symbol": params.symbol,
+                "side": params.side,
+                "quantity": params.quantity,
+                "limit_price": limit_price,
+                "time_in_force": time_in_force,
+                "leverage": leverage,
+                "status": "pending"
+            }),
+            timestamp: now,
+        });
+
+        Ok(TradeExecutionResult {
+            success: true,
+            trade_id: Some(order_id),
+            error_message: None,
+            execution_price: None, // Resting until price crosses the limit
+            fees_paid: None,
+        })
+    }
+
+    /// Build a pending limit order resting at `limit_price`
+    ///
+    /// The queue ahead of it is approximated as a share of the current 1m candle's volume.
+    async fn new_resting_limit_order(
+        &self,
+        params: &super::ManualOrderParams,
+        order_id: String,
+        limit_price: f64,
+        time_in_force: TimeInForce,
+    ) -> StopLimitOrder {
+        let settings = self.settings.read().await;
+        let leverage = params.leverage.unwrap_or(settings.basic.default_leverage);
+        let expiration_minutes = settings.execution.order_expiration_minutes;
+        let queue_volume_fraction = settings.execution.limit_queue_volume_fraction;
+        drop(settings);
+
+        let latest_candle = self
+            .market_data_cache
+            .as_ref()
+            .and_then(|cache| cache.get_latest_candle(&params.symbol, "1m"));
+        let queue_ahead = latest_candle
+            .as_ref()
+            .map_or(0.0, |candle| candle.volume * queue_volume_fraction);
+
+        let now = self.clock.now();
+        StopLimitOrder {
+            id: order_id,
+            symbol: params.symbol.clone(),
+            side: params.side.clone(),
+            order_type: OrderType::Limit,
+            quantity: params.quantity,
+            stop_price: limit_price,
+            limit_price,
+            leverage,
+            stop_loss_pct: params.stop_loss_pct,
+            take_profit_pct: params.take_profit_pct,
+            status: OrderStatus::Pending,
+            created_at: now,
+            triggered_at: None,
+            filled_at: None,
+            error_message: None,
+            time_in_force,
+            queue_ahead,
+            expires_at: (expiration_minutes > 0)
+                .then(|| now + chrono::Duration::minutes(expiration_minutes as i64)),
+            volume_mark: latest_candle.map(|candle| (candle.open_time, candle.volume)),
+            order_list_id: None,
+            stop_loss_price: params.stop_loss_price,
+            take_profit_price: params.take_profit_price,
+        }
+    }
+
+    /// Place a bracket order: an entry with linked stop loss and take profit exits
+    ///
+    /// The entry is a market order, or a limit order when `price` is set, which rests
+    /// like any other limit order until it fills. The exits become the trade's stop loss
+    /// and take profit, so whichever is hit first closes the trade and retires the other.
+    /// @spec:FR-PAPER-003 - Bracket Orders
+    async fn place_bracket_order(
+        &self,
+        params: &super::ManualOrderParams,
+        signal_type: TradingSignal,
+    ) -> Result<TradeExecutionResult> {
+        let rejected = |message: String| {
+            Ok(TradeExecutionResult {
+                success: false,
+                trade_id: None,
+                error_message: Some(message),
+                execution_price: None,
+                fees_paid: None,
+            })
+        };
+
+        let (Some(stop_loss), Some(take_profit)) =
+            (params.stop_loss_price, params.take_profit_price)
+        else {
+            return rejected(
+                "Bracket orders require stop_loss_price and take_profit_price".to_string(),
+            );
+        };
+        let limit_price = params.price.filter(|p| *p > 0.0);
+        let current_price = self
+            .current_prices
+            .read()
+            .await
+            .get(&params.symbol)
+            .copied();
+        let Some(entry_price) = limit_price.or(current_price) else {
+            return rejected(format!(
+                "No market price available for {}. Please wait for price data.",
+                params.symbol
+            ));
+        };
+
+        let is_buy = matches!(signal_type, TradingSignal::Long);
+        if let Err(e) = validate_bracket(is_buy, entry_price, stop_loss, take_profit) {
+            return rejected(e);
+        }
+
+        info!(
+            "📋 Bracket order: {} {} qty={} entry={:.2} sl={:.2} tp={:.2}",
+            params.side, params.symbol, params.quantity, entry_price, stop_loss, take_profit
+        );
+
+        match limit_price {
+            Some(limit_price) => {
+                self.place_limit_order(params, signal_type, limit_price)
+                    .await
+            },
+            None => {
+                self.execute_manual_fill(params, signal_type, entry_price, None)
+                    .await
+            },
+        }
+    }
+
+    /// Place a one-cancels-other pair of entry orders
+    ///
+    /// A limit leg at `price` and a stop-limit leg triggered at `stop_price` (filling at
+    /// `stop_limit_price`) share an order list id. For a buy the limit sits below the
+    /// market and the stop above it, and the reverse for a sell. Once either leg fills or
+    /// triggers the other is cancelled, and cancelling either leg or the list cancels both.
+    /// @spec:FR-PAPER-003 - OCO Orders
+    async fn place_oco_order(
+        &self,
+        params: &super::ManualOrderParams,
+        signal_type: TradingSignal,
+    ) -> Result<TradeExecutionResult> {
+        let rejected = |message: String| {
+            Ok(TradeExecutionResult {
+                success: false,
+                trade_id: None,
+                error_message: Some(message),
+                execution_price: None,
+                fees_paid: None,
+            })
+        };
+
+        let (Some(limit_price), Some(stop_price)) = (
+            params.price.filter(|p| *p > 0.0),
+            params.stop_price.filter(|p| *p > 0.0),
+        ) else {
+            return rejected("OCO orders require a limit price and a stop_price > 0".to_string());
+        };
+        let stop_limit_price = params.stop_limit_price.unwrap_or(stop_price);
+        let current_price = self
+            .current_prices
+            .read()
+            .await
+            .get(&params.symbol)
+            .copied();
+
+        let is_buy = matches!(signal_type, TradingSignal::Long);
+        if let Err(e) = validate_oco(is_buy, limit_price, stop_price, current_price) {
+            return rejected(e);
+        }
+
+        let order_list_id = format!("oco-{}", self.next_uuid());
+        // OCO legs stay open until one of them fills or the list is cancelled
+        let limit_leg = StopLimitOrder {
+            expires_at: None,
+            order_list_id: Some(order_list_id.clone()),
+            ..self
+                .new_resting_limit_order(
+                    params,
+                    format!("{}-limit", order_list_id),
+                    limit_price,
+                    TimeInForce::Gtc,
+                )
+                .await
+        };
+        let stop_leg = StopLimitOrder {
+            id: format!("{}-stop", order_list_id),
+            order_type: OrderType::StopLimit,
+            stop_price,
+            limit_price: stop_limit_price,
+            queue_ahead: 0.0,
+            volume_mark: None,
+            ..limit_leg.clone()
+        };
+        let leverage = limit_leg.leverage;
+
+        {
+            let mut pending_orders = self.pending_stop_limit_orders.write().await;
+            pending_orders.push(limit_leg);
+            pending_orders.push(stop_leg);
+        }
+
+        info!(
+            "📋 OCO order created: {} {} {} qty={} limit={:.2} stop={:.2} stop_limit={:.2}",
+            order_list_id,
+            params.side,
+            params.symbol,
+            params.quantity,
+            limit_price,
+            stop_price,
+            stop_limit_price
+        );
+
+        let _ = self.event_broadcaster.send(PaperTradingEvent {
+            event_type: "oco_order_created".to_string(),
+            data: serde_json::json!({
+                "order_list_id": order_list_id,
+                "symbol": params.symbol,
+                "side": params.side,
+                "quantity": params.quantity,
+                "limit_price": limit_price,
+                "stop_price": stop_price,
+                "stop_limit_price": stop_limit_price,
+                "leverage": leverage,
+                "status": "pending"
+            }),
+            timestamp: self.clock.now(),
+        });
+
+        Ok(TradeExecutionResult {
+            success: true,
+            trade_id: Some(order_list_id),
+            error_message: None,
+            execution_price: None, // Not executed yet
+            fees_paid: None,
+        })
+    }
+
+    /// Build and execute the trade for a manual order at `entry_price`
+    async fn execute_manual_fill(
+        &self,
+        params: &super::ManualOrderParams,
+        signal_type: TradingSignal,
+        entry_price: f64,
+        limit_fill: Option<LimitFill>,
+    ) -> Result<TradeExecutionResult> {
+        let symbol = &params.symbol;
+        let side = &params.side;
+        let quantity = params.quantity;
+        let leverage = params.leverage;
+        let stop_loss_pct = params.stop_loss_pct;
+        let take_profit_pct = params.take_profit_pct;
+
         // 3. Get settings for defaults
         let settings = self.settings.read().await;
         let default_leverage = settings.basic.default_leverage;
@@ -3779,14 +4755,16 @@ impl PaperTradingEngine {
         // 4. Calculate leverage, stop loss, and take profit (PnL-based: pct / leverage)
         let calculated_leverage = leverage.unwrap_or(default_leverage);
         let lev = calculated_leverage as f64;
-        let stop_loss =
-            entry_price * (1.0 - stop_loss_pct.unwrap_or(default_stop_loss_pct) / (lev * 100.0));
-        let take_profit = entry_price
-            * (1.0 + take_profit_pct.unwrap_or(default_take_profit_pct) / (lev * 100.0));
+        let stop_loss = params.stop_loss_price.unwrap_or_else(|| {
+            entry_price * (1.0 - stop_loss_pct.unwrap_or(default_stop_loss_pct) / (lev * 100.0))
+        });
+        let take_profit = params.take_profit_price.unwrap_or_else(|| {
+            entry_price * (1.0 + take_profit_pct.unwrap_or(default_take_profit_pct) / (lev * 100.0))
+        });
 
         // 5. Create AI signal structure for manual order
         let manual_signal = super::AITradingSignal {
-            id: format!("manual-{}", uuid::Uuid::new_v4()),
+            id: format!("manual-{}", self.next_uuid()),
             symbol: symbol.clone(),
             signal_type,
             confidence: 1.0, // Manual orders have 100% confidence (user intent)
@@ -3811,7 +4789,7 @@ impl PaperTradingEngine {
                 volume_analysis: "N/A".to_string(),
                 risk_score: 0.5,
             },
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         };
 
         // 6. Create pending trade
@@ -3821,7 +4799,7 @@ impl PaperTradingEngine {
             calculated_leverage,
             stop_loss,
             take_profit,
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         };
 
         // 7. Execute using existing trade execution logic
@@ -3830,39 +4808,339 @@ impl PaperTradingEngine {
             side, quantity, symbol, entry_price, calculated_leverage
         );
 
-        self.execute_trade(pending_trade).await
+        self.execute_trade_with(pending_trade, limit_fill).await
     }
 
-    /// Update settings
-    /// Result of settings update operation
-    /// Returns (success, database_saved, warning_message)
-    pub async fn update_settings(
-        &self,
-        new_settings: PaperTradingSettings,
-    ) -> Result<(bool, Option<String>)> {
-        new_settings.validate()?;
-
-        let mut settings = self.settings.write().await;
-        *settings = new_settings;
+    /// Open both legs of a pair trade atomically
+    ///
+    /// `symbol` is traded in `direction` and `pair_symbol` in the opposite direction,
+    /// sized so the hedge leg's notional is `hedge_ratio` times the first leg's.
+    /// Both legs are added to the portfolio under one lock, or neither is.
+    pub async fn open_pair_trade(&self, params: PairTradeParams) -> Result<PairTradeSummary> {
+        let _lock = self.trade_execution_lock.lock().await;
 
-        // Save updated settings to database
-        let db_warning = match self.storage.save_paper_trading_settings(&settings).await {
-            Ok(_) => {
-                info!("✅ Settings updated and saved to database");
-                None
+        let (trade_type, pair_trade_type) = match params.direction {
+            TradingSignal::Long => (TradeType::Long, TradeType::Short),
+            TradingSignal::Short => (TradeType::Short, TradeType::Long),
+            TradingSignal::Neutral => {
+                return Err(anyhow::anyhow!(
+                    "Pair trade direction must be long or short"
+                ))
             },
-            Err(e) => {
-                let warning = format!("Settings saved to memory only. Database save failed: {}", e);
-                warn!("⚠️ {}", warning);
-                Some(warning)
+        };
+        if params.symbol == params.pair_symbol {
+            return Err(anyhow::anyhow!("Pair trade needs two different symbols"));
+        }
+        if !(params.quantity.is_finite() && params.quantity > 0.0) {
+            return Err(anyhow::anyhow!("Pair trade quantity must be positive"));
+        }
+        if !(params.hedge_ratio.is_finite() && params.hedge_ratio > 0.0) {
+            return Err(anyhow::anyhow!("Pair trade hedge_ratio must be positive"));
+        }
+
+        let (price, pair_price) = {
+            let prices = self.current_prices.read().await;
+            let lookup = |symbol: &str| {
+                prices
+                    .get(symbol)
+                    .copied()
+                    .ok_or_else(|| anyhow::anyhow!("No market price available for {}", symbol))
+            };
+            (lookup(&params.symbol)?, lookup(&params.pair_symbol)?)
+        };
+
+        let settings = self.settings.read().await;
+        let leverage = params.leverage.unwrap_or(settings.basic.default_leverage);
+        let trading_fee_rate = settings.basic.trading_fee_rate;
+        let margin_mode = settings.risk.margin_mode;
+        drop(settings);
+
+        let pair_quantity = params.quantity * price * params.hedge_ratio / pair_price;
+        let reasoning = params.reasoning.clone().unwrap_or_else(|| {
+            format!(
+                "Pair trade: {:?} {} vs {} (hedge ratio {:.4})",
+                params.direction, params.symbol, params.pair_symbol, params.hedge_ratio
+            )
+        });
+
+        let mut legs = Vec::with_capacity(2);
+        for (symbol, leg_type, leg_price, quantity) in [
+            (&params.symbol, trade_type, price, params.quantity),
+            (
+                &params.pair_symbol,
+                pair_trade_type,
+                pair_price,
+                pair_quantity,
+            ),
+        ] {
+            let execution_price = self.apply_slippage(leg_price, leg_type).await;
+            legs.push(
+                self.stamp_trade(
+                    PaperTrade::new(
+                        symbol.clone(),
+                        leg_type,
+                        execution_price,
+                        quantity,
+                        leverage,
+                        trading_fee_rate,
+                        None,
+                        params.confidence,
+                        Some(reasoning.clone()),
+                    )
+                    .with_margin_mode(margin_mode),
+                ),
+            );
+        }
+
+        let (pair_id, summary) = {
+            let mut portfolio = self.portfolio.write().await;
+            let pair_id = portfolio.add_pair_trade(legs)?;
+            for leg in portfolio.get_pair_legs(&pair_id) {
+                if let Err(e) = self.storage.save_paper_trade(leg).await {
+                    error!(
+                        "❌ Failed to save pair trade leg {} to database: {}",
+                        leg.id, e
+                    );
+                }
+            }
+            if let Err(e) = self.storage.save_portfolio_snapshot(&portfolio).await {
+                error!(
+                    "❌ Failed to save portfolio snapshot after pair trade: {}",
+                    e
+                );
+            }
+            let summary = Self::find_pair_summary(&portfolio, &pair_id)?;
+            (pair_id, summary)
+        };
+
+        info!(
+            "🔗 Opened pair trade {}: {:?} {} {:.6} / {:?} {} {:.6}",
+            pair_id,
+            trade_type,
+            params.symbol,
+            params.quantity,
+            pair_trade_type,
+            params.pair_symbol,
+            pair_quantity
+        );
+        let _ = self.event_broadcaster.send(PaperTradingEvent {
+            event_type: "pair_trade_opened".to_string(),
+            data: serde_json::to_value(&summary)?,
+            timestamp: self.clock.now(),
+        });
+
+        Ok(summary)
+    }
+
+    /// Close every open leg of a pair trade at current prices and report combined PnL
+    pub async fn close_pair_trade(
+        &self,
+        pair_id: &str,
+        close_reason: CloseReason,
+    ) -> Result<PairTradeSummary> {
+        let mut portfolio = self.portfolio.write().await;
+
+        let exit_prices: HashMap<String, f64> = {
+            let prices = self.current_prices.read().await;
+            portfolio
+                .get_pair_legs(pair_id)
+                .into_iter()
+                .map(|leg| {
+                    let price = prices.get(&leg.symbol).copied().unwrap_or(leg.entry_price);
+                    (leg.symbol.clone(), price)
+                })
+                .collect()
+        };
+        let combined_pnl = portfolio.close_pair_trade(pair_id, &exit_prices, close_reason)?;
+
+        for leg in portfolio.get_pair_legs(pair_id) {
+            if let Err(e) = self.storage.update_paper_trade(leg).await {
+                error!(
+                    "❌ Failed to update pair trade leg {} in database: {}",
+                    leg.id, e
+                );
+            }
+        }
+        if let Err(e) = self.storage.save_portfolio_snapshot(&portfolio).await {
+            error!(
+                "❌ Failed to save portfolio snapshot after pair close: {}",
+                e
+            );
+        }
+        let summary = Self::find_pair_summary(&portfolio, pair_id)?;
+        drop(portfolio);
+
+        // The pair is one bet, so it counts once towards the loss streak
+        self.update_consecutive_losses(combined_pnl).await;
+
+        info!(
+            "🔗 Closed pair trade {} with combined PnL {:.2}",
+            pair_id, combined_pnl
+        );
+        let _ = self.event_broadcaster.send(PaperTradingEvent {
+            event_type: "pair_trade_closed".to_string(),
+            data: serde_json::to_value(&summary)?,
+            timestamp: self.clock.now(),
+        });
+
+        Ok(summary)
+    }
+
+    /// Remember which way each strategy voted for a strategy-driven trade, so its
+    /// outcome can be credited to them when it closes
+    async fn attach_strategy_votes(
+        &self,
+        trade_id: &str,
+        strategy_signals: &[crate::strategies::strategy_engine::StrategySignalResult],
+    ) {
+        let votes: HashMap<&str, TradingSignal> = strategy_signals
+            .iter()
+            .map(|s| (s.strategy_name.as_str(), s.signal))
+            .collect();
+        let mut portfolio = self.portfolio.write().await;
+        if let (Some(trade), Ok(votes)) = (
+            portfolio.trades.get_mut(trade_id),
+            serde_json::to_value(votes),
+        ) {
+            trade.metadata.insert(STRATEGY_VOTES_KEY.to_string(), votes);
+        }
+    }
+
+    /// Combination mode in use plus each strategy's recent hit rate and learned weight
+    pub async fn get_strategy_performance(&self) -> serde_json::Value {
+        let strategy_engine = self.strategy_engine.read().await;
+        serde_json::json!({
+            "combination_mode": strategy_engine.config().signal_combination_mode,
+            "performance": strategy_engine.get_strategy_performance().await,
+            "learned_weights": strategy_engine.get_learned_weights().await,
+        })
+    }
+
+    /// Get all pair trades with combined PnL
+    pub async fn get_pair_trades(&self) -> Vec<PairTradeSummary> {
+        self.portfolio.read().await.get_pair_trades()
+    }
+
+    fn find_pair_summary(portfolio: &PaperPortfolio, pair_id: &str) -> Result<PairTradeSummary> {
+        portfolio
+            .get_pair_trades()
+            .into_iter()
+            .find(|p| p.pair_id == pair_id)
+            .ok_or_else(|| anyhow::anyhow!("Pair trade not found: {}", pair_id))
+    }
+
+    /// Open or close pair trades from strategy outputs that carry a hedge leg
+    ///
+    /// Entries need `min_confidence`; a pair already open for the same symbols is
+    /// held until the strategy reports the spread has reverted (`exit_spread`).
+    async fn handle_pair_signals(
+        &self,
+        symbol: &str,
+        strategy_signals: &[crate::strategies::strategy_engine::StrategySignalResult],
+        min_confidence: f64,
+    ) {
+        for result in strategy_signals {
+            let (Some(pair_symbol), Some(hedge_ratio)) = (
+                result.metadata.get("pair_symbol").and_then(|v| v.as_str()),
+                result.metadata.get("hedge_ratio").and_then(|v| v.as_f64()),
+            ) else {
+                continue;
+            };
+            let exit_spread = result
+                .metadata
+                .get("exit_spread")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            let open_pair = self.get_pair_trades().await.into_iter().find(|p| {
+                p.status == TradeStatus::Open
+                    && p.legs.first().map(|l| l.symbol.as_str()) == Some(symbol)
+                    && p.legs.iter().any(|l| l.symbol == pair_symbol)
+            });
+
+            if let Some(pair) = open_pair {
+                if exit_spread {
+                    if let Err(e) = self
+                        .close_pair_trade(&pair.pair_id, CloseReason::AISignal)
+                        .await
+                    {
+                        error!("Failed to close pair trade {}: {}", pair.pair_id, e);
+                    }
+                }
+                continue;
+            }
+
+            if result.signal == TradingSignal::Neutral || result.confidence < min_confidence {
+                continue;
+            }
+
+            // Size the pair so both legs together use the symbol's position size as margin
+            let price = self.current_prices.read().await.get(symbol).copied();
+            let Some(price) = price.filter(|p| *p > 0.0) else {
+                continue;
+            };
+            let settings = self.settings.read().await;
+            let symbol_settings = settings.get_symbol_settings(symbol);
+            drop(settings);
+            let equity = self.portfolio.read().await.equity;
+            let margin = equity * symbol_settings.position_size_pct / 100.0;
+            let quantity = margin * symbol_settings.leverage as f64 / (1.0 + hedge_ratio) / price;
+
+            let params = PairTradeParams {
+                symbol: symbol.to_string(),
+                pair_symbol: pair_symbol.to_string(),
+                direction: result.signal,
+                quantity,
+                hedge_ratio,
+                leverage: Some(symbol_settings.leverage),
+                reasoning: Some(format!("{}: {}", result.strategy_name, result.reasoning)),
+                confidence: Some(result.confidence),
+            };
+            if let Err(e) = self.open_pair_trade(params).await {
+                warn!("Pair trade {} / {} not opened: {}", symbol, pair_symbol, e);
+            }
+        }
+    }
+
+    /// Update settings
+    /// Result of settings update operation
+    /// Returns (success, database_saved, warning_message)
+    pub async fn update_settings(
+        &self,
+        new_settings: PaperTradingSettings,
+    ) -> Result<(bool, Option<String>)> {
+        new_settings.validate()?;
+
+        self.strategy_engine
+            .write()
+            .await
+            .set_combination_mode(new_settings.strategy.combination_method.into());
+
+        let reseed = new_settings.execution.random_seed;
+        let mut settings = self.settings.write().await;
+        if settings.execution.random_seed != reseed {
+            *self.lock_rng() = execution_rng(reseed);
+        }
+        *settings = new_settings;
+
+        // Save updated settings to database
+        let db_warning = match self.storage.save_paper_trading_settings(&settings).await {
+            Ok(_) => {
+                info!("✅ Settings updated and saved to database");
+                None
+            },
+            Err(e) => {
+                let warning = format!("Settings saved to memory only. Database save failed: {}", e);
+                warn!("⚠️ {}", warning);
+                Some(warning)
             },
         };
 
         // Broadcast settings update
         let _ = self.event_broadcaster.send(PaperTradingEvent {
             event_type: "settings_updated".to_string(),
-            data: serde_json::json!({ "timestamp": Utc::now() }),
-            timestamp: Utc::now(),
+            data: serde_json::json!({ "timestamp": self.clock.now() }),
+            timestamp: self.clock.now(),
         });
 
         // Return success with optional warning about database
@@ -3887,6 +5165,145 @@ impl PaperTradingEngine {
         cache.values().cloned().collect()
     }
 
+    /// Replay recent historical candles for a symbol through the strategy engine
+    /// using the current paper trading settings (no effect on the live portfolio)
+    /// @spec:FR-STRATEGIES-008 - Strategy Backtesting
+    pub async fn run_backtest(&self, symbol: &str) -> Result<BacktestReport> {
+        let settings = self.settings.read().await.clone();
+        let config = BacktestConfig::from_settings(&settings, symbol);
+        let timeframe_data = self
+            .fetch_backtest_candles(symbol, &config, settings.strategy.backtesting.period_days)
+            .await?;
+
+        // Same strategies as the live engine, including rule strategies and runtime toggles
+        let mut strategy_engine = build_strategy_engine(&settings)?;
+        strategy_engine.set_enabled_strategies(self.get_enabled_strategies().await)?;
+
+        Backtester::with_strategy_engine(config, strategy_engine)
+            .run(symbol, &timeframe_data)
+            .await
+    }
+
+    /// Optimize a strategy's parameters in-sample and validate them out-of-sample
+    /// over recent history. The recommended parameters are recorded in the optimizer
+    /// but not applied to the live strategy engine.
+    /// @spec:FR-STRATEGIES-007 - Strategy Optimizer
+    pub async fn run_walk_forward(
+        &self,
+        symbol: &str,
+        strategy_name: &str,
+    ) -> Result<WalkForwardReport> {
+        let settings = self.settings.read().await.clone();
+        let backtest_config = BacktestConfig::from_settings(&settings, symbol);
+        let walk_forward_config =
+            WalkForwardConfig::from_backtesting_settings(&settings.strategy.backtesting);
+
+        let registry = build_strategy_registry(&settings)?;
+        let strategy_config = self
+            .strategy_engine
+            .read()
+            .await
+            .get_strategy_config(strategy_name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Strategy '{strategy_name}' not found"))?;
+        let space = parameter_space_from_config(&strategy_config);
+
+        let timeframe_data = self
+            .fetch_backtest_candles(
+                symbol,
+                &backtest_config,
+                settings.strategy.backtesting.period_days,
+            )
+            .await?;
+
+        let report = self
+            .optimizer
+            .read()
+            .await
+            .run_walk_forward(
+                &registry,
+                strategy_name,
+                &space,
+                &walk_forward_config,
+                &backtest_config,
+                &timeframe_data,
+            )
+            .await?;
+        self.optimizer.write().await.record_walk_forward(&report);
+
+        Ok(report)
+    }
+
+    /// Fetch closed historical candles for every strategy timeframe plus the
+    /// backtest primary timeframe, limited to the last `period_days`
+    async fn fetch_backtest_candles(
+        &self,
+        symbol: &str,
+        config: &BacktestConfig,
+        period_days: u32,
+    ) -> Result<HashMap<String, Vec<CandleData>>> {
+        /// Binance kline REST limit per request
+        const KLINES_PER_REQUEST: u16 = 1000;
+        /// Upper bound on requests per timeframe (200k candles)
+        const MAX_KLINE_PAGES: usize = 200;
+
+        let mut timeframes: Vec<&str> = STRATEGY_ALL_TIMEFRAMES.to_vec();
+        if !timeframes.contains(&config.primary_timeframe.as_str()) {
+            timeframes.push(config.primary_timeframe.as_str());
+        }
+
+        let now_ms = self.clock.now().timestamp_millis();
+        let cutoff_ms =
+            (self.clock.now() - chrono::Duration::days(period_days as i64)).timestamp_millis();
+
+        let mut timeframe_data: HashMap<String, Vec<CandleData>> = HashMap::new();
+        for timeframe in timeframes {
+            // Page forward from the cutoff until the present is covered
+            let mut candles: Vec<CandleData> = Vec::new();
+            let mut start_time = cutoff_ms;
+            let mut pages = 0;
+            loop {
+                if pages == MAX_KLINE_PAGES {
+                    return Err(anyhow::anyhow!(
+                        "Backtest of {period_days} days on {timeframe} needs more than {} candles; \
+                         reduce period_days or use a coarser timeframe",
+                        MAX_KLINE_PAGES * KLINES_PER_REQUEST as usize
+                    ));
+                }
+                pages += 1;
+
+                let klines = self
+                    .binance_client
+                    .get_klines_range(
+                        symbol,
+                        timeframe,
+                        Some(start_time),
+                        None,
+                        Some(KLINES_PER_REQUEST),
+                    )
+                    .await?;
+                let Some(last) = klines.last() else {
+                    break;
+                };
+                let (last_open, last_close) = (last.open_time, last.close_time);
+                candles.extend(klines.iter().map(CandleData::from));
+
+                if klines.len() < KLINES_PER_REQUEST as usize || last_close >= now_ms {
+                    break;
+                }
+                start_time = last_open + 1;
+            }
+            timeframe_data.insert(timeframe.to_string(), candles);
+        }
+
+        // The newest kline is usually still forming - only replay closed candles
+        for candles in timeframe_data.values_mut() {
+            candles.retain(|c| c.close_time < now_ms);
+        }
+
+        Ok(timeframe_data)
+    }
+
     /// Add a new symbol to paper trading settings
     /// This is called when user adds a new symbol to track via market data API
     pub async fn add_symbol_to_settings(&self, symbol: String) -> Result<()> {
@@ -3930,9 +5347,9 @@ impl PaperTradingEngine {
             event_type: "symbol_added".to_string(),
             data: serde_json::json!({
                 "symbol": symbol,
-                "timestamp": chrono::Utc::now()
+                "timestamp": self.clock.now()
             }),
-            timestamp: chrono::Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         Ok(())
@@ -3945,7 +5362,9 @@ impl PaperTradingEngine {
         drop(settings);
 
         let mut portfolio = self.portfolio.write().await;
-        *portfolio = PaperPortfolio::new(initial_balance);
+        *portfolio = PaperPortfolio::new(initial_balance).with_clock(self.clock.clone());
+        drop(portfolio);
+        self.reseed_rng().await;
 
         // Clear MongoDB collections so old trades don't reappear on restart
         if let Err(e) = self.storage.delete_all_paper_trades().await {
@@ -3958,8 +5377,8 @@ impl PaperTradingEngine {
         // Broadcast reset event
         let _ = self.event_broadcaster.send(PaperTradingEvent {
             event_type: "portfolio_reset".to_string(),
-            data: serde_json::json!({ "timestamp": Utc::now() }),
-            timestamp: Utc::now(),
+            data: serde_json::json!({ "timestamp": self.clock.now() }),
+            timestamp: self.clock.now(),
         });
 
         info!("Portfolio reset to initial balance: ${}", initial_balance);
@@ -3990,9 +5409,9 @@ impl PaperTradingEngine {
             event_type: "settings_updated".to_string(),
             data: serde_json::json!({
                 "confidence_threshold": threshold,
-                "timestamp": Utc::now()
+                "timestamp": self.clock.now()
             }),
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         info!(
@@ -4026,9 +5445,9 @@ impl PaperTradingEngine {
             event_type: "settings_updated".to_string(),
             data: serde_json::json!({
                 "signal_refresh_interval_minutes": interval_minutes,
-                "timestamp": Utc::now()
+                "timestamp": self.clock.now()
             }),
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         info!(
@@ -4065,9 +5484,9 @@ impl PaperTradingEngine {
             event_type: "settings_updated".to_string(),
             data: serde_json::json!({
                 "data_resolution": timeframe,
-                "timestamp": Utc::now()
+                "timestamp": self.clock.now()
             }),
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         info!(
@@ -4092,7 +5511,13 @@ impl PaperTradingEngine {
 
         for symbol in &symbols {
             if let Some(input) = self.build_strategy_input(symbol).await {
-                match self.strategy_engine.analyze_market(&input).await {
+                match self
+                    .strategy_engine
+                    .read()
+                    .await
+                    .analyze_market(&input)
+                    .await
+                {
                     Ok(combined_signal) => {
                         let signal = combined_signal.final_signal;
                         let confidence = combined_signal.combined_confidence;
@@ -4109,7 +5534,7 @@ impl PaperTradingEngine {
                             };
 
                             let ai_signal = AITradingSignal {
-                                id: Uuid::new_v4().to_string(),
+                                id: self.next_uuid().to_string(),
                                 symbol: symbol.clone(),
                                 signal_type: signal,
                                 confidence,
@@ -4134,7 +5559,7 @@ impl PaperTradingEngine {
                                     ),
                                     risk_score: 1.0 - confidence,
                                 },
-                                timestamp: Utc::now(),
+                                timestamp: self.clock.now(),
                             };
 
                             if let Err(e) = self.process_trading_signal(ai_signal).await {
@@ -4184,7 +5609,7 @@ impl PaperTradingEngine {
 
         // Create AI trading signal
         let ai_signal = AITradingSignal {
-            id: Uuid::new_v4().to_string(),
+            id: self.next_uuid().to_string(),
             symbol: symbol.clone(),
             signal_type,
             confidence,
@@ -4206,7 +5631,7 @@ impl PaperTradingEngine {
                 volume_analysis: "External signal".to_string(),
                 risk_score: 1.0 - confidence,
             },
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         };
 
         // Save signal to database
@@ -4235,7 +5660,7 @@ impl PaperTradingEngine {
                 "entry_price": ai_signal.entry_price,
                 "source": "external",
             }),
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         // Process trading signal if confidence is high enough
@@ -4291,7 +5716,7 @@ impl PaperTradingEngine {
             direction_bias,
             bias_strength,
             bias_confidence,
-            last_updated: Utc::now(),
+            last_updated: self.clock.now(),
             ttl_seconds: ttl_seconds.unwrap_or(600),
         };
 
@@ -4312,7 +5737,7 @@ impl PaperTradingEngine {
                 "bias_strength": bias_strength,
                 "bias_confidence": bias_confidence,
             }),
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         Ok(())
@@ -4336,10 +5761,17 @@ impl PaperTradingEngine {
     // ============================================================================
 
     /// Check and trigger pending stop-limit orders based on current prices
+    /// Also fills or expires resting limit orders.
     /// This should be called whenever prices are updated
     pub async fn check_pending_stop_limit_orders(&self) -> Result<()> {
         let current_prices = self.current_prices.read().await;
         let mut orders_to_execute: Vec<StopLimitOrder> = Vec::new();
+        let mut limit_orders_to_fill: Vec<StopLimitOrder> = Vec::new();
+        let mut expired_orders: Vec<StopLimitOrder> = Vec::new();
+        let mut oco_cancelled_orders: Vec<StopLimitOrder> = Vec::new();
+        // OCO lists with a leg that filled or triggered in this pass
+        let mut completed_order_lists: HashSet<String> = HashSet::new();
+        let now = self.clock.now();
 
         // Check each pending order
         {
@@ -4348,12 +5780,48 @@ impl PaperTradingEngine {
             while i < pending_orders.len() {
                 let order = &pending_orders[i];
 
-                // Skip non-pending orders
-                if order.status != OrderStatus::Pending {
+                // Skip non-pending orders, and OCO legs whose sibling already went through
+                if order.status != OrderStatus::Pending
+                    || order
+                        .order_list_id
+                        .as_ref()
+                        .is_some_and(|id| completed_order_lists.contains(id))
+                {
                     i += 1;
                     continue;
                 }
 
+                // @spec:FR-PAPER-003 - Resting Limit Orders
+                if order.order_type == OrderType::Limit {
+                    let Some(&current_price) = current_prices.get(&order.symbol) else {
+                        i += 1;
+                        continue;
+                    };
+                    let latest_candle = self
+                        .market_data_cache
+                        .as_ref()
+                        .and_then(|cache| cache.get_latest_candle(&order.symbol, "1m"))
+                        .map(|candle| (candle.open_time, candle.volume));
+                    let order = &mut pending_orders[i];
+                    let traded_volume = traded_volume_since(&mut order.volume_mark, latest_candle);
+
+                    match evaluate_resting_limit(order, current_price, traded_volume, now) {
+                        LimitOrderAction::Rest => i += 1,
+                        LimitOrderAction::Expire => {
+                            order.status = OrderStatus::Expired;
+                            expired_orders.push(order.clone());
+                            i += 1;
+                        },
+                        LimitOrderAction::Fill => {
+                            let mut filled_order = pending_orders.remove(i);
+                            filled_order.triggered_at = Some(now);
+                            completed_order_lists.extend(filled_order.order_list_id.clone());
+                            limit_orders_to_fill.push(filled_order);
+                        },
+                    }
+                    continue;
+                }
+
                 // Get current price for the symbol
                 if let Some(&current_price) = current_prices.get(&order.symbol) {
                     // Check if stop price is triggered
@@ -4378,7 +5846,8 @@ impl PaperTradingEngine {
                         // Mark as triggered and prepare for execution
                         let mut triggered_order = order.clone();
                         triggered_order.status = OrderStatus::Triggered;
-                        triggered_order.triggered_at = Some(Utc::now());
+                        triggered_order.triggered_at = Some(self.clock.now());
+                        completed_order_lists.extend(triggered_order.order_list_id.clone());
                         orders_to_execute.push(triggered_order);
 
                         // Remove from pending list
@@ -4389,71 +5858,117 @@ impl PaperTradingEngine {
                 }
                 i += 1;
             }
+
+            // One leg of an OCO list went through: cancel the others
+            for order in pending_orders.iter_mut() {
+                let completed = order
+                    .order_list_id
+                    .as_ref()
+                    .is_some_and(|id| completed_order_lists.contains(id));
+                if completed && order.status == OrderStatus::Pending {
+                    order.status = OrderStatus::Cancelled;
+                    oco_cancelled_orders.push(order.clone());
+                }
+            }
         }
         drop(current_prices);
 
+        for order in &oco_cancelled_orders {
+            info!(
+                "🚫 OCO leg cancelled after its sibling executed: {}",
+                order.id
+            );
+            self.broadcast_order_cancelled(order, "oco");
+        }
+
+        for order in expired_orders {
+            info!("⌛ Limit order expired: {}", order.id);
+            let _ = self.event_broadcaster.send(PaperTradingEvent {
+                event_type: "limit_order_expired".to_string(),
+                data: serde_json::json!({
+                    "order_id": order.id,
+                    "symbol": order.symbol,
+                    "side": order.side,
+                    "quantity": order.quantity,
+                    "limit_price": order.limit_price,
+                    "expires_at": order.expires_at
+                }),
+                timestamp: now,
+            });
+        }
+
         // Execute triggered orders
         for order in orders_to_execute {
             self.execute_triggered_stop_limit_order(order).await?;
         }
 
+        for order in limit_orders_to_fill {
+            self.execute_resting_limit_order(order).await?;
+        }
+
         Ok(())
     }
 
-    /// Execute a triggered stop-limit order
-    async fn execute_triggered_stop_limit_order(&self, order: StopLimitOrder) -> Result<()> {
-        info!(
-            "🚀 Executing triggered stop-limit order: {} {} {} @ limit {:.2}",
-            order.side, order.quantity, order.symbol, order.limit_price
-        );
-
-        // Execute as a limit order at the limit price
+    /// Fill a resting limit order as a maker at its limit price
+    async fn execute_resting_limit_order(&self, order: StopLimitOrder) -> Result<()> {
+        let signal_type = if is_buy_side(&order.side) {
+            TradingSignal::Long
+        } else {
+            TradingSignal::Short
+        };
+        let params = super::ManualOrderParams {
+            symbol: order.symbol.clone(),
+            side: order.side.clone(),
+            order_type: "limit".to_string(),
+            quantity: order.quantity,
+            price: Some(order.limit_price),
+            stop_price: None,
+            leverage: Some(order.leverage),
+            stop_loss_pct: order.stop_loss_pct,
+            take_profit_pct: order.take_profit_pct,
+            time_in_force: Some(order.time_in_force),
+            stop_limit_price: None,
+            stop_loss_price: order.stop_loss_price,
+            take_profit_price: order.take_profit_price,
+        };
+        let fill = LimitFill {
+            limit_price: order.limit_price,
+            maker: true,
+            fill_or_kill: false,
+        };
         let result = self
-            .execute_manual_order(super::ManualOrderParams {
-                symbol: order.symbol.clone(),
-                side: order.side.clone(),
-                order_type: "limit".to_string(),
-                quantity: order.quantity,
-                price: Some(order.limit_price),
-                stop_price: None, // No stop price for limit execution
-                leverage: Some(order.leverage),
-                stop_loss_pct: order.stop_loss_pct,
-                take_profit_pct: order.take_profit_pct,
-            })
+            .execute_manual_fill(&params, signal_type, order.limit_price, Some(fill))
             .await?;
 
-        // Broadcast execution event
         let _ = self.event_broadcaster.send(PaperTradingEvent {
-            event_type: "stop_limit_order_executed".to_string(),
+            event_type: "limit_order_filled".to_string(),
             data: serde_json::json!({
                 "order_id": order.id,
                 "symbol": order.symbol,
                 "side": order.side,
                 "quantity": order.quantity,
-                "stop_price": order.stop_price,
                 "limit_price": order.limit_price,
                 "execution_result": {
                     "success": result.success,
                     "trade_id": result.trade_id,
                     "execution_price": result.execution_price,
+                    "fees_paid": result.fees_paid,
                     "error": result.error_message
                 },
-                "triggered_at": order.triggered_at,
-                "filled_at": Utc::now()
+                "created_at": order.created_at,
+                "filled_at": self.clock.now()
             }),
-            timestamp: Utc::now(),
+            timestamp: self.clock.now(),
         });
 
         if result.success {
             info!(
-                "✅ Stop-limit order filled: {} trade_id={:?} @ {:.2}",
-                order.id,
-                result.trade_id,
-                result.execution_price.unwrap_or(order.limit_price)
+                "✅ Limit order filled as maker: {} trade_id={:?} @ {:.2}",
+                order.id, result.trade_id, order.limit_price
             );
         } else {
             warn!(
-                "❌ Stop-limit order failed: {} error={:?}",
+                "❌ Limit order fill failed: {} error={:?}",
                 order.id, result.error_message
             );
         }
@@ -4461,8 +5976,73 @@ impl PaperTradingEngine {
         Ok(())
     }
 
-    /// Get all pending stop-limit orders
-    pub async fn get_pending_orders(&self) -> Vec<StopLimitOrder> {
+    /// Execute a triggered stop-limit order
+    async fn execute_triggered_stop_limit_order(&self, order: StopLimitOrder) -> Result<()> {
+        info!(
+            "🚀 Executing triggered stop-limit order: {} {} {} @ limit {:.2}",
+            order.side, order.quantity, order.symbol, order.limit_price
+        );
+
+        // Execute as a limit order at the limit price
+        let result = self
+            .execute_manual_order(super::ManualOrderParams {
+                symbol: order.symbol.clone(),
+                side: order.side.clone(),
+                order_type: "limit".to_string(),
+                quantity: order.quantity,
+                price: Some(order.limit_price),
+                stop_price: None, // No stop price for limit execution
+                leverage: Some(order.leverage),
+                stop_loss_pct: order.stop_loss_pct,
+                take_profit_pct: order.take_profit_pct,
+                time_in_force: Some(order.time_in_force),
+                stop_limit_price: None,
+                stop_loss_price: order.stop_loss_price,
+                take_profit_price: order.take_profit_price,
+            })
+            .await?;
+
+        // Broadcast execution event
+        let _ = self.event_broadcaster.send(PaperTradingEvent {
+            event_type: "stop_limit_order_executed".to_string(),
+            data: serde_json::json!({
+                "order_id": order.id,
+                "symbol": order.symbol,
+                "side": order.side,
+                "quantity": order.quantity,
+                "stop_price": order.stop_price,
+                "limit_price": order.limit_price,
+                "execution_result": {
+                    "success": result.success,
+                    "trade_id": result.trade_id,
+                    "execution_price": result.execution_price,
+                    "error": result.error_message
+                },
+                "triggered_at": order.triggered_at,
+                "filled_at": self.clock.now()
+            }),
+            timestamp: self.clock.now(),
+        });
+
+        if result.success {
+            info!(
+                "✅ Stop-limit order filled: {} trade_id={:?} @ {:.2}",
+                order.id,
+                result.trade_id,
+                result.execution_price.unwrap_or(order.limit_price)
+            );
+        } else {
+            warn!(
+                "❌ Stop-limit order failed: {} error={:?}",
+                order.id, result.error_message
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Get all pending stop-limit and resting limit orders
+    pub async fn get_pending_orders(&self) -> Vec<StopLimitOrder> {
         let pending_orders = self.pending_stop_limit_orders.read().await;
         pending_orders
             .iter()
@@ -4477,30 +6057,51 @@ impl PaperTradingEngine {
         pending_orders.clone()
     }
 
-    /// Cancel a pending stop-limit order by ID
+    /// Cancel a pending stop-limit or resting limit order by ID
+    ///
+    /// Cancelling an OCO list, or any one of its legs, cancels every pending leg.
     pub async fn cancel_pending_order(&self, order_id: &str) -> Result<bool> {
         let mut pending_orders = self.pending_stop_limit_orders.write().await;
 
+        let order_list_id = match pending_orders.iter().find(|o| o.id == order_id) {
+            Some(order) => order.order_list_id.clone(),
+            None => pending_orders
+                .iter()
+                .any(|o| o.order_list_id.as_deref() == Some(order_id))
+                .then(|| order_id.to_string()),
+        };
+
+        if let Some(order_list_id) = order_list_id {
+            let mut cancelled = 0;
+            for order in pending_orders.iter_mut() {
+                if order.order_list_id.as_deref() == Some(order_list_id.as_str())
+                    && order.status == OrderStatus::Pending
+                {
+                    order.status = OrderStatus::Cancelled;
+                    self.broadcast_order_cancelled(order, "user");
+                    cancelled += 1;
+                }
+            }
+            if cancelled == 0 {
+                return Err(anyhow::anyhow!(
+                    "Cannot cancel order list {}: no pending orders left",
+                    order_list_id
+                ));
+            }
+            info!(
+                "🚫 OCO order list cancelled: {} ({} legs)",
+                order_list_id, cancelled
+            );
+            return Ok(true);
+        }
+
         for order in pending_orders.iter_mut() {
             if order.id == order_id {
                 if order.status == OrderStatus::Pending {
                     order.status = OrderStatus::Cancelled;
 
-                    info!("🚫 Stop-limit order cancelled: {}", order_id);
-
-                    // Broadcast cancellation event
-                    let _ = self.event_broadcaster.send(PaperTradingEvent {
-                        event_type: "stop_limit_order_cancelled".to_string(),
-                        data: serde_json::json!({
-                            "order_id": order_id,
-                            "symbol": order.symbol,
-                            "side": order.side,
-                            "quantity": order.quantity,
-                            "stop_price": order.stop_price,
-                            "limit_price": order.limit_price
-                        }),
-                        timestamp: Utc::now(),
-                    });
+                    info!("🚫 {} order cancelled: {}", order.order_type, order_id);
+                    self.broadcast_order_cancelled(order, "user");
 
                     return Ok(true);
                 } else {
@@ -4516,6 +6117,27 @@ impl PaperTradingEngine {
         Err(anyhow::anyhow!("Order not found: {}", order_id))
     }
 
+    /// Broadcast the cancellation of a pending order
+    fn broadcast_order_cancelled(&self, order: &StopLimitOrder, reason: &str) {
+        let _ = self.event_broadcaster.send(PaperTradingEvent {
+            event_type: format!(
+                "{}_order_cancelled",
+                order.order_type.to_string().replace('-', "_")
+            ),
+            data: serde_json::json!({
+                "order_id": order.id,
+                "order_list_id": order.order_list_id,
+                "symbol": order.symbol,
+                "side": order.side,
+                "quantity": order.quantity,
+                "stop_price": order.stop_price,
+                "limit_price": order.limit_price,
+                "reason": reason
+            }),
+            timestamp: self.clock.now(),
+        });
+    }
+
     /// Get pending order count for a symbol
     pub async fn get_pending_order_count(&self, symbol: Option<&str>) -> usize {
         let pending_orders = self.pending_stop_limit_orders.read().await;
@@ -4529,13 +6151,15 @@ impl PaperTradingEngine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::paper_trading::margin::MarginMode;
     use crate::paper_trading::settings::{
         AISettings, BasicSettings, ExecutionSettings, IndicatorSettings, NotificationSettings,
-        RiskSettings, SignalGenerationSettings, SignalPipelineSettings, StrategySettings,
-        SymbolSettings,
+        RiskSettings, SignalGenerationSettings, SignalPipelineSettings, StrategyCombinationMethod,
+        StrategySettings, SymbolSettings,
     };
     use crate::paper_trading::trade::TradeStatus;
     use crate::paper_trading::{ManualOrderParams, MarketAnalysisData};
+    use crate::strategies::strategy_engine::StrategySignalResult;
     use std::sync::Arc;
     use tokio::sync::broadcast;
 
@@ -4622,6 +6246,7 @@ mod tests {
             indicators: IndicatorSettings::default(),
             signal: SignalGenerationSettings::default(),
             signal_pipeline: SignalPipelineSettings::default(),
+            rule_strategies: Vec::new(),
         }
     }
 
@@ -5987,6 +7612,11 @@ mod tests {
             highest_price_achieved: Some(50000.0),
             trailing_stop_active: false,
             metadata: std::collections::HashMap::new(),
+            partial_closes: Vec::new(),
+            scale_ins: Vec::new(),
+            take_profit_levels: Vec::new(),
+            margin_mode: MarginMode::Isolated,
+            liquidation_price: None,
         }
     }
 
@@ -6523,6 +8153,220 @@ mod tests {
             .unwrap()
     }
 
+    fn pair_trade_params(direction: TradingSignal) -> PairTradeParams {
+        PairTradeParams {
+            symbol: "ETHUSDT".to_string(),
+            pair_symbol: "BTCUSDT".to_string(),
+            direction,
+            quantity: 1.0,
+            hedge_ratio: 0.5,
+            leverage: Some(10),
+            reasoning: None,
+            confidence: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pair_trade_opens_and_closes_both_legs() {
+        let engine = create_test_paper_engine().await;
+        {
+            let mut prices = engine.current_prices.write().await;
+            prices.insert("ETHUSDT".to_string(), 3000.0);
+            prices.insert("BTCUSDT".to_string(), 50000.0);
+        }
+
+        let opened = engine
+            .open_pair_trade(pair_trade_params(TradingSignal::Long))
+            .await
+            .unwrap();
+        assert_eq!(opened.status, TradeStatus::Open);
+        assert_eq!(opened.legs.len(), 2);
+        assert_eq!(opened.legs[0].symbol, "ETHUSDT");
+        assert_eq!(opened.legs[0].trade_type, TradeType::Long);
+        assert_eq!(opened.legs[1].symbol, "BTCUSDT");
+        assert_eq!(opened.legs[1].trade_type, TradeType::Short);
+        // Hedge leg notional is half the ETH leg's: 1 * 3000 * 0.5 / 50000
+        assert!((opened.legs[1].quantity - 0.03).abs() < 1e-9);
+        assert_eq!(engine.get_open_trades().await.len(), 2);
+
+        {
+            let mut prices = engine.current_prices.write().await;
+            prices.insert("ETHUSDT".to_string(), 3100.0);
+            prices.insert("BTCUSDT".to_string(), 49000.0);
+        }
+
+        // Closing one leg closes the pair
+        engine
+            .close_trade(&opened.legs[1].id, CloseReason::Manual)
+            .await
+            .unwrap();
+        assert!(engine.get_open_trades().await.is_empty());
+
+        let pairs = engine.get_pair_trades().await;
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].status, TradeStatus::Closed);
+        assert!(pairs[0].combined_pnl > 100.0);
+        assert!(engine
+            .close_pair_trade(&opened.pair_id, CloseReason::Manual)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pair_trade_rejects_invalid_requests() {
+        let engine = create_test_paper_engine().await;
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("ETHUSDT".to_string(), 3000.0);
+
+        // No BTC price yet
+        assert!(engine
+            .open_pair_trade(pair_trade_params(TradingSignal::Short))
+            .await
+            .is_err());
+
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 50000.0);
+        assert!(engine
+            .open_pair_trade(pair_trade_params(TradingSignal::Neutral))
+            .await
+            .is_err());
+        assert!(engine
+            .open_pair_trade(PairTradeParams {
+                pair_symbol: "ETHUSDT".to_string(),
+                ..pair_trade_params(TradingSignal::Long)
+            })
+            .await
+            .is_err());
+        assert!(engine
+            .open_pair_trade(PairTradeParams {
+                hedge_ratio: -1.0,
+                ..pair_trade_params(TradingSignal::Long)
+            })
+            .await
+            .is_err());
+        assert!(engine.get_pair_trades().await.is_empty());
+        assert!(engine.get_open_trades().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_strategy_votes_feed_learned_ensemble() {
+        let engine = create_test_paper_engine().await;
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 50000.0);
+
+        let mut settings = engine.get_settings().await;
+        settings.strategy.combination_method = StrategyCombinationMethod::AIEnsemble;
+        engine.update_settings(settings).await.unwrap();
+        let report = engine.get_strategy_performance().await;
+        assert_eq!(report["combination_mode"], "LearnedEnsemble");
+
+        let trade = PaperTrade::new(
+            "BTCUSDT".to_string(),
+            TradeType::Long,
+            50000.0,
+            0.1,
+            10,
+            0.0004,
+            None,
+            None,
+            None,
+        );
+        let trade_id = trade.id.clone();
+        engine.portfolio.write().await.add_trade(trade).unwrap();
+        let vote = |name: &str, signal| StrategySignalResult {
+            strategy_name: name.to_string(),
+            signal,
+            confidence: 0.7,
+            reasoning: String::new(),
+            weight: 1.0,
+            metadata: HashMap::new(),
+        };
+        engine
+            .attach_strategy_votes(
+                &trade_id,
+                &[
+                    vote("RSI Strategy", TradingSignal::Long),
+                    vote("MACD Strategy", TradingSignal::Short),
+                ],
+            )
+            .await;
+
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 51000.0);
+        engine
+            .close_trade(&trade_id, CloseReason::TakeProfit)
+            .await
+            .unwrap();
+
+        let report = engine.get_strategy_performance().await;
+        let performance = report["performance"].as_array().unwrap();
+        assert_eq!(performance.len(), 2);
+        let successes = |name: &str| {
+            performance
+                .iter()
+                .find(|p| p["strategy_name"] == name)
+                .unwrap()["successful_signals"]
+                .as_u64()
+                .unwrap()
+        };
+        assert_eq!(successes("RSI Strategy"), 1);
+        assert_eq!(successes("MACD Strategy"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_build_strategy_input_loads_pair_symbol_data() {
+        let engine = create_test_paper_engine().await;
+        {
+            let mut cache = engine.historical_data_cache.write().await;
+            let klines: Vec<crate::binance::Kline> = (0..50)
+                .map(|i| crate::binance::Kline {
+                    open_time: i * 900000,
+                    open: "100.0".to_string(),
+                    high: "101.0".to_string(),
+                    low: "99.0".to_string(),
+                    close: "100.5".to_string(),
+                    volume: "10.0".to_string(),
+                    close_time: i * 900000 + 899999,
+                    quote_asset_volume: "1000.0".to_string(),
+                    number_of_trades: 10,
+                    taker_buy_base_asset_volume: "5.0".to_string(),
+                    taker_buy_quote_asset_volume: "500.0".to_string(),
+                    ignore: "0".to_string(),
+                })
+                .collect();
+            cache.insert("ETHUSDT_5m".to_string(), klines.clone());
+            cache.insert("ETHUSDT_15m".to_string(), klines.clone());
+            cache.insert("BTCUSDT_15m".to_string(), klines);
+        }
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("ETHUSDT".to_string(), 100.5);
+
+        let input = engine.build_strategy_input("ETHUSDT").await.unwrap();
+        assert!(input.related_data.is_empty());
+
+        engine
+            .enable_strategy("Pairs Spread Strategy")
+            .await
+            .unwrap();
+        let input = engine.build_strategy_input("ETHUSDT").await.unwrap();
+        assert_eq!(input.related_data["BTCUSDT"]["15m"].len(), 50);
+    }
+
     // Tests for execute_manual_order
     #[tokio::test]
     async fn test_execute_manual_order_invalid_side() {
@@ -6538,6 +8382,10 @@ mod tests {
             leverage: None,
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await.unwrap();
@@ -6560,6 +8408,10 @@ mod tests {
             leverage: None,
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await.unwrap();
@@ -6582,6 +8434,10 @@ mod tests {
             leverage: None,
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await.unwrap();
@@ -6604,6 +8460,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await.unwrap();
@@ -6630,6 +8490,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await;
@@ -6652,6 +8516,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await;
@@ -6674,12 +8542,550 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await.unwrap();
         assert!(result.success);
     }
 
+    fn limit_order_params(side: &str, price: f64, tif: Option<TimeInForce>) -> ManualOrderParams {
+        ManualOrderParams {
+            symbol: "BTCUSDT".to_string(),
+            side: side.to_string(),
+            order_type: "limit".to_string(),
+            quantity: 0.01,
+            price: Some(price),
+            stop_price: None,
+            leverage: Some(10),
+            stop_loss_pct: None,
+            take_profit_pct: None,
+            time_in_force: tif,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resting_limit_order_fills_as_maker_when_price_crosses() {
+        let engine = create_test_paper_engine().await;
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 50500.0);
+
+        let result = engine
+            .execute_manual_order(limit_order_params("buy", 50000.0, None))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.execution_price.is_none());
+        let order_id = result.trade_id.unwrap();
+        assert!(order_id.starts_with("limit-"));
+        let pending = engine.get_pending_orders().await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].order_type, OrderType::Limit);
+        assert!(pending[0].expires_at.is_some());
+
+        // Still above the limit: keeps resting
+        engine.check_pending_stop_limit_orders().await.unwrap();
+        assert_eq!(engine.get_pending_order_count(None).await, 1);
+
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 49900.0);
+        engine.check_pending_stop_limit_orders().await.unwrap();
+        assert_eq!(engine.get_pending_order_count(None).await, 0);
+
+        let trades = engine.get_open_trades().await;
+        assert_eq!(trades.len(), 1);
+        let trade = &trades[0];
+        assert_eq!(trade.entry_price, 50000.0);
+        assert_eq!(trade.quantity, 0.01);
+        let maker_fee_rate = engine.settings.read().await.execution.maker_fee_rate;
+        assert!((trade.trading_fees - 50000.0 * 0.01 * maker_fee_rate).abs() < 1e-9);
+        let portfolio = engine.portfolio.read().await;
+        assert_eq!(portfolio.trades[&trade.id].metadata[LIQUIDITY_KEY], "maker");
+    }
+
+    #[tokio::test]
+    async fn test_marketable_limit_order_fills_as_taker_within_limit() {
+        let engine = create_test_paper_engine().await;
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 50000.0);
+
+        let result = engine
+            .execute_manual_order(limit_order_params("sell", 49950.0, None))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.execution_price.unwrap() >= 49950.0);
+        assert_eq!(engine.get_pending_order_count(None).await, 0);
+
+        let trades = engine.get_open_trades().await;
+        let portfolio = engine.portfolio.read().await;
+        assert_eq!(
+            portfolio.trades[&trades[0].id].metadata[LIQUIDITY_KEY],
+            "taker"
+        );
+        drop(portfolio);
+        let taker_fee_rate = engine.settings.read().await.basic.trading_fee_rate;
+        let notional = trades[0].entry_price * trades[0].quantity;
+        assert!((trades[0].trading_fees - notional * taker_fee_rate).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_limit_order_time_in_force() {
+        let engine = create_test_paper_engine().await;
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 50000.0);
+
+        // Post-only order that would cross is rejected
+        let result = engine
+            .execute_manual_order(limit_order_params("buy", 50100.0, Some(TimeInForce::Gtx)))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error_message.unwrap().contains("Post-only"));
+
+        // IOC and FOK orders that cannot fill now are cancelled, not rested
+        for tif in [TimeInForce::Ioc, TimeInForce::Fok] {
+            let result = engine
+                .execute_manual_order(limit_order_params("buy", 49000.0, Some(tif)))
+                .await
+                .unwrap();
+            assert!(!result.success);
+            assert!(result.error_message.unwrap().contains("cancelled"));
+        }
+        assert_eq!(engine.get_pending_order_count(None).await, 0);
+
+        // Non-crossing post-only order rests
+        let result = engine
+            .execute_manual_order(limit_order_params("buy", 49000.0, Some(TimeInForce::Gtx)))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(
+            engine.get_pending_orders().await[0].time_in_force,
+            TimeInForce::Gtx
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resting_limit_order_expires_and_cancels() {
+        let engine = create_test_paper_engine().await;
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 50000.0);
+
+        let first = engine
+            .execute_manual_order(limit_order_params("sell", 51000.0, None))
+            .await
+            .unwrap()
+            .trade_id
+            .unwrap();
+        let second = engine
+            .execute_manual_order(limit_order_params("sell", 52000.0, None))
+            .await
+            .unwrap()
+            .trade_id
+            .unwrap();
+
+        engine.pending_stop_limit_orders.write().await[0].expires_at =
+            Some(Utc::now() - chrono::Duration::seconds(1));
+        engine.check_pending_stop_limit_orders().await.unwrap();
+
+        let orders = engine.get_all_stop_limit_orders().await;
+        assert_eq!(orders[0].id, first);
+        assert_eq!(orders[0].status, OrderStatus::Expired);
+        assert_eq!(engine.get_pending_order_count(None).await, 1);
+
+        assert!(engine.cancel_pending_order(&second).await.unwrap());
+        assert!(engine.cancel_pending_order(&first).await.is_err());
+        assert_eq!(engine.get_pending_order_count(None).await, 0);
+        assert!(engine.get_open_trades().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bracket_order_sets_exit_prices() {
+        let engine = create_test_paper_engine().await;
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 50000.0);
+
+        let bracket = |price: Option<f64>, stop_loss: f64, take_profit: f64| ManualOrderParams {
+            order_type: "bracket".to_string(),
+            price,
+            stop_loss_price: Some(stop_loss),
+            take_profit_price: Some(take_profit),
+            ..limit_order_params("buy", 0.0, None)
+        };
+
+        // Exits on the wrong side of the entry are rejected
+        let result = engine
+            .execute_manual_order(bracket(None, 51000.0, 52000.0))
+            .await
+            .unwrap();
+        assert!(!result.success);
+
+        // Market entry: the trade carries the bracket's exits
+        let result = engine
+            .execute_manual_order(bracket(None, 48000.0, 55000.0))
+            .await
+            .unwrap();
+        assert!(result.success);
+        let trades = engine.get_open_trades().await;
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].stop_loss, Some(48000.0));
+        assert_eq!(trades[0].take_profit, Some(55000.0));
+
+        // Limit entry rests and keeps its exits until it fills
+        let result = engine
+            .execute_manual_order(bracket(Some(49000.0), 47000.0, 53000.0))
+            .await
+            .unwrap();
+        assert!(result.execution_price.is_none());
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 48900.0);
+        engine.check_pending_stop_limit_orders().await.unwrap();
+
+        let trades = engine.get_open_trades().await;
+        let filled = trades.iter().find(|t| t.entry_price == 49000.0).unwrap();
+        assert_eq!(filled.stop_loss, Some(47000.0));
+        assert_eq!(filled.take_profit, Some(53000.0));
+    }
+
+    #[tokio::test]
+    async fn test_oco_order_fill_cancels_other_leg() {
+        let engine = create_test_paper_engine().await;
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 50000.0);
+
+        let oco = ManualOrderParams {
+            order_type: "oco".to_string(),
+            stop_price: Some(51000.0),
+            stop_limit_price: Some(51100.0),
+            ..limit_order_params("buy", 49000.0, None)
+        };
+
+        // Legs on the wrong sides of the market are rejected
+        let inverted = ManualOrderParams {
+            price: Some(51000.0),
+            stop_price: Some(49000.0),
+            ..oco.clone()
+        };
+        assert!(!engine.execute_manual_order(inverted).await.unwrap().success);
+
+        let result = engine.execute_manual_order(oco).await.unwrap();
+        assert!(result.success);
+        let order_list_id = result.trade_id.unwrap();
+        let pending = engine.get_pending_orders().await;
+        assert_eq!(pending.len(), 2);
+        assert!(pending
+            .iter()
+            .all(|o| o.order_list_id.as_deref() == Some(order_list_id.as_str())));
+
+        // Price drops through the limit leg: it fills and the stop leg is cancelled
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), 48500.0);
+        engine.check_pending_stop_limit_orders().await.unwrap();
+
+        assert_eq!(engine.get_pending_order_count(None).await, 0);
+        let orders = engine.get_all_stop_limit_orders().await;
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_type, OrderType::StopLimit);
+        assert_eq!(orders[0].status, OrderStatus::Cancelled);
+        let trades = engine.get_open_trades().await;
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].entry_price, 49000.0);
+    }
+
+    #[tokio::test]
+    async fn test_cancelling_oco_leg_cancels_list() {
+        let engine = create_test_paper_engine().await;
+        let oco = ManualOrderParams {
+            order_type: "oco".to_string(),
+            stop_price: Some(49000.0),
+            ..limit_order_params("sell", 51000.0, None)
+        };
+        let order_list_id = engine
+            .execute_manual_order(oco)
+            .await
+            .unwrap()
+            .trade_id
+            .unwrap();
+
+        let stop_leg_id = format!("{}-stop", order_list_id);
+        assert!(engine.cancel_pending_order(&stop_leg_id).await.unwrap());
+        let orders = engine.get_all_stop_limit_orders().await;
+        assert!(orders.iter().all(|o| o.status == OrderStatus::Cancelled));
+        assert!(engine.cancel_pending_order(&order_list_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_seeded_session_replays_bit_for_bit() {
+        use chrono::TimeZone;
+
+        async fn run_session(engine: &PaperTradingEngine) -> Vec<(String, f64, f64, String)> {
+            let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+            engine.clock().set(start);
+            let first = open_market_long(engine, 50000.0, 0.1).await;
+            engine.clock().advance(chrono::Duration::minutes(3));
+            open_market_long(engine, 50100.0, 0.1).await;
+            engine.clock().advance(chrono::Duration::minutes(3));
+            set_test_price(engine, 50500.0).await;
+            engine
+                .close_trade(&first, CloseReason::Manual)
+                .await
+                .unwrap();
+
+            let mut trades = engine.get_open_trades().await;
+            trades.extend(engine.get_closed_trades().await);
+            trades.sort_by_key(|trade| trade.open_time);
+            trades
+                .into_iter()
+                .map(|trade| {
+                    (
+                        trade.id,
+                        trade.entry_price,
+                        trade.quantity,
+                        format!("{:?}", (trade.open_time, trade.close_time)),
+                    )
+                })
+                .collect()
+        }
+
+        let mut runs = Vec::new();
+        for _ in 0..2 {
+            let engine = create_test_paper_engine().await;
+            {
+                let mut settings = engine.settings.write().await;
+                settings.execution.random_seed = Some(42);
+                settings.execution.simulate_slippage = true;
+                settings.execution.simulate_partial_fills = true;
+                settings.execution.partial_fill_probability = 0.5;
+            }
+            engine.reseed_rng().await;
+            runs.push(run_session(&engine).await);
+        }
+
+        assert_eq!(runs[0].len(), 2);
+        assert_eq!(runs[0], runs[1]);
+        // Slippage was drawn, not skipped
+        assert_ne!(runs[0][0].1, 50000.0);
+    }
+
+    #[tokio::test]
+    async fn test_named_portfolio_is_independent_and_tags_its_events() {
+        let engine = create_test_paper_engine().await;
+        let mut events = engine.event_broadcaster.subscribe();
+        let mut settings = engine.get_settings().await;
+        settings.basic.initial_balance = 5000.0;
+        let named = engine
+            .new_named_portfolio("aggressive", settings)
+            .await
+            .unwrap();
+
+        open_market_long(&engine, 50000.0, 0.1).await;
+        assert_eq!(engine.get_open_trades().await.len(), 1);
+        assert!(named.get_open_trades().await.is_empty());
+        assert_eq!(named.get_settings().await.basic.initial_balance, 5000.0);
+        assert_eq!(
+            named.storage.paper_collection_name("paper_trades"),
+            "aggressive_paper_trades"
+        );
+
+        open_market_long(&named, 50000.0, 0.01).await;
+        assert_eq!(named.get_open_trades().await.len(), 1);
+        let tagged = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let event = events.recv().await.unwrap();
+                if event.data.get("portfolio").is_some() {
+                    return event;
+                }
+            }
+        })
+        .await
+        .expect("named portfolio event was not forwarded");
+        assert_eq!(tagged.data["portfolio"], "aggressive");
+    }
+
+    async fn open_market_long(engine: &PaperTradingEngine, price: f64, quantity: f64) -> String {
+        set_test_price(engine, price).await;
+        let params = ManualOrderParams {
+            order_type: "market".to_string(),
+            price: None,
+            quantity,
+            ..limit_order_params("buy", 0.0, None)
+        };
+        let result = engine.execute_manual_order(params).await.unwrap();
+        assert!(result.success);
+        result.trade_id.unwrap()
+    }
+
+    async fn set_test_price(engine: &PaperTradingEngine, price: f64) {
+        engine
+            .current_prices
+            .write()
+            .await
+            .insert("BTCUSDT".to_string(), price);
+        engine
+            .portfolio
+            .write()
+            .await
+            .current_prices
+            .insert("BTCUSDT".to_string(), price);
+    }
+
+    #[tokio::test]
+    async fn test_partial_close_trade_realizes_fraction() {
+        let engine = create_test_paper_engine().await;
+        let trade_id = open_market_long(&engine, 50000.0, 0.2).await;
+        assert!(engine
+            .partial_close_trade(&trade_id, 1.5, CloseReason::Manual)
+            .await
+            .is_err());
+
+        set_test_price(&engine, 51000.0).await;
+        let realized = engine
+            .partial_close_trade(&trade_id, 0.5, CloseReason::Manual)
+            .await
+            .unwrap();
+        assert!(realized > 0.0);
+
+        let trades = engine.get_open_trades().await;
+        assert_eq!(trades.len(), 1);
+        assert!((trades[0].quantity - 0.1).abs() < 1e-9);
+        assert_eq!(trades[0].partial_closes.len(), 1);
+        assert!((trades[0].partial_realized_pnl - realized).abs() < 1e-9);
+
+        // Closing the rest folds the partial fill into the trade's realized PnL
+        let rest = engine
+            .partial_close_trade(&trade_id, 1.0, CloseReason::Manual)
+            .await
+            .unwrap();
+        assert!(engine.get_open_trades().await.is_empty());
+        let portfolio = engine.portfolio.read().await;
+        let total = portfolio.trades[&trade_id].realized_pnl.unwrap();
+        assert!((total - (realized + rest)).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_level_partially_closes_on_monitor() {
+        let engine = create_test_paper_engine().await;
+        let trade_id = open_market_long(&engine, 50000.0, 0.2).await;
+        assert!(engine
+            .set_take_profit_levels(&trade_id, &[(49000.0, 0.5)])
+            .await
+            .is_err());
+        engine
+            .set_take_profit_levels(&trade_id, &[(51000.0, 0.5), (52000.0, 0.5)])
+            .await
+            .unwrap();
+
+        set_test_price(&engine, 51100.0).await;
+        engine.monitor_open_trades().await.unwrap();
+        let trades = engine.get_open_trades().await;
+        assert_eq!(trades.len(), 1);
+        assert!((trades[0].quantity - 0.1).abs() < 1e-9);
+        assert!(trades[0].take_profit_levels[0].filled);
+        assert!(!trades[0].take_profit_levels[1].filled);
+
+        // The last level covers the remaining position and closes the trade
+        set_test_price(&engine, 52100.0).await;
+        engine.monitor_open_trades().await.unwrap();
+        assert!(engine.get_open_trades().await.is_empty());
+        let portfolio = engine.portfolio.read().await;
+        assert_eq!(
+            portfolio.trades[&trade_id].close_reason,
+            Some(CloseReason::TakeProfit)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scale_in_trade_reaverages_entry() {
+        let engine = create_test_paper_engine().await;
+        let trade_id = open_market_long(&engine, 50000.0, 0.1).await;
+        let first_entry = engine.get_open_trades().await[0].entry_price;
+
+        set_test_price(&engine, 48000.0).await;
+        engine.scale_in_trade(&trade_id, 0.1).await.unwrap();
+
+        let trades = engine.get_open_trades().await;
+        assert!((trades[0].quantity - 0.2).abs() < 1e-9);
+        assert!(trades[0].entry_price < first_entry);
+        assert!(trades[0].entry_price > 48000.0 * 0.99);
+        assert_eq!(trades[0].scale_ins.len(), 1);
+        assert!(engine.scale_in_trade("missing", 0.1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_isolated_trade_liquidated_while_cross_trade_survives() {
+        let engine = create_test_paper_engine().await;
+        engine.settings.write().await.risk.margin_mode = MarginMode::Cross;
+        let cross_id = open_market_long(&engine, 50000.0, 0.1).await;
+        engine.settings.write().await.risk.margin_mode = MarginMode::Isolated;
+        let isolated_id = open_market_long(&engine, 50000.0, 0.1).await;
+
+        let trades = engine.get_open_trades().await;
+        let cross = trades.iter().find(|t| t.id == cross_id).unwrap();
+        let isolated = trades.iter().find(|t| t.id == isolated_id).unwrap();
+        assert_eq!(cross.margin_mode, MarginMode::Cross);
+        // The shared wallet covers the cross position down to a price of zero
+        assert_eq!(cross.liquidation_price, None);
+        let liquidation_price = isolated.liquidation_price.unwrap();
+        assert!(liquidation_price > 45000.0 && liquidation_price < 46000.0);
+
+        // Without stops the drop reaches the isolated liquidation price
+        for trade in engine.portfolio.write().await.trades.values_mut() {
+            trade.stop_loss = None;
+        }
+        let mut receiver = engine.event_broadcaster.subscribe();
+        set_test_price(&engine, 44000.0).await;
+        engine.monitor_open_trades().await.unwrap();
+
+        let open = engine.get_open_trades().await;
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].id, cross_id);
+        let portfolio = engine.portfolio.read().await;
+        assert_eq!(
+            portfolio.trades[&isolated_id].close_reason,
+            Some(CloseReason::Liquidation)
+        );
+        drop(portfolio);
+        let mut liquidated = false;
+        while let Ok(event) = receiver.try_recv() {
+            liquidated |= event.event_type == "trade_liquidated";
+        }
+        assert!(liquidated);
+    }
+
     #[tokio::test]
     async fn test_execute_manual_order_sell_creates_short_signal() {
         let engine = create_test_paper_engine().await;
@@ -6695,6 +9101,10 @@ mod tests {
             leverage: Some(5),
             stop_loss_pct: Some(3.0),
             take_profit_pct: Some(6.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await;
@@ -6803,6 +9213,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         engine.execute_manual_order(params).await.unwrap();
@@ -6825,6 +9239,10 @@ mod tests {
             leverage: Some(5),
             stop_loss_pct: Some(3.0),
             take_profit_pct: Some(6.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         engine.execute_manual_order(params).await.unwrap();
@@ -6853,6 +9271,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         engine.execute_manual_order(params).await.unwrap();
@@ -6884,6 +9306,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let order_result = engine.execute_manual_order(params).await;
@@ -6931,6 +9357,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(5.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let exec_result = engine.execute_manual_order(params).await.unwrap();
@@ -7664,6 +10094,11 @@ mod tests {
                     highest_price_achieved: None,
                     trailing_stop_active: false,
                     metadata: std::collections::HashMap::new(),
+                    partial_closes: Vec::new(),
+                    scale_ins: Vec::new(),
+                    take_profit_levels: Vec::new(),
+                    margin_mode: MarginMode::Isolated,
+                    liquidation_price: None,
                 };
                 portfolio.trades.insert(trade.id.clone(), trade.clone());
                 portfolio.open_trade_ids.push(trade.id.clone());
@@ -7837,6 +10272,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -8463,6 +10903,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -8567,6 +11012,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -8626,6 +11076,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             let trade2 = PaperTrade {
@@ -8668,6 +11123,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             portfolio.trades.insert(trade1.id.clone(), trade1.clone());
@@ -8737,6 +11197,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -8800,6 +11265,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -8863,6 +11333,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -8926,6 +11401,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -8989,6 +11469,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -9158,6 +11643,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -9225,6 +11715,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -9298,6 +11793,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -9819,6 +12319,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -9884,6 +12389,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -9949,6 +12459,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -10014,6 +12529,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -10407,6 +12927,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             // Short trade with lower value
@@ -10450,6 +12975,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             // Add a 2nd long trade to reach 3 positions (threshold for correlation check)
@@ -10493,6 +13023,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             portfolio
@@ -10569,6 +13104,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             let short_trade = PaperTrade {
@@ -10611,6 +13151,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             // 3rd position: another short to make 60% long / 40% short
@@ -10654,6 +13199,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             portfolio
@@ -10737,6 +13287,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             portfolio.trades.insert(trade.id.clone(), trade.clone());
@@ -10798,6 +13353,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             portfolio.trades.insert(trade.id.clone(), trade.clone());
@@ -10932,6 +13492,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -10997,6 +13562,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.closed_trade_ids.push(trade.id.clone());
@@ -11165,6 +13735,13 @@ mod tests {
                 triggered_at: None,
                 filled_at: None,
                 error_message: None,
+                time_in_force: TimeInForce::Gtc,
+                queue_ahead: 0.0,
+                expires_at: None,
+                volume_mark: None,
+                order_list_id: None,
+                stop_loss_price: None,
+                take_profit_price: None,
             });
         }
 
@@ -11197,6 +13774,13 @@ mod tests {
                 triggered_at: None,
                 filled_at: None,
                 error_message: None,
+                time_in_force: TimeInForce::Gtc,
+                queue_ahead: 0.0,
+                expires_at: None,
+                volume_mark: None,
+                order_list_id: None,
+                stop_loss_price: None,
+                take_profit_price: None,
             });
         }
 
@@ -11241,6 +13825,13 @@ mod tests {
                 triggered_at: None,
                 filled_at: None,
                 error_message: None,
+                time_in_force: TimeInForce::Gtc,
+                queue_ahead: 0.0,
+                expires_at: None,
+                volume_mark: None,
+                order_list_id: None,
+                stop_loss_price: None,
+                take_profit_price: None,
             });
             orders.push(StopLimitOrder {
                 id: "order-count-2".to_string(),
@@ -11258,6 +13849,13 @@ mod tests {
                 triggered_at: None,
                 filled_at: None,
                 error_message: None,
+                time_in_force: TimeInForce::Gtc,
+                queue_ahead: 0.0,
+                expires_at: None,
+                volume_mark: None,
+                order_list_id: None,
+                stop_loss_price: None,
+                take_profit_price: None,
             });
         }
 
@@ -11288,6 +13886,13 @@ mod tests {
                 triggered_at: None,
                 filled_at: None,
                 error_message: None,
+                time_in_force: TimeInForce::Gtc,
+                queue_ahead: 0.0,
+                expires_at: None,
+                volume_mark: None,
+                order_list_id: None,
+                stop_loss_price: None,
+                take_profit_price: None,
             });
             orders.push(StopLimitOrder {
                 id: "order-btc-2".to_string(),
@@ -11305,6 +13910,13 @@ mod tests {
                 triggered_at: None,
                 filled_at: None,
                 error_message: None,
+                time_in_force: TimeInForce::Gtc,
+                queue_ahead: 0.0,
+                expires_at: None,
+                volume_mark: None,
+                order_list_id: None,
+                stop_loss_price: None,
+                take_profit_price: None,
             });
             orders.push(StopLimitOrder {
                 id: "order-eth-1".to_string(),
@@ -11322,6 +13934,13 @@ mod tests {
                 triggered_at: None,
                 filled_at: None,
                 error_message: None,
+                time_in_force: TimeInForce::Gtc,
+                queue_ahead: 0.0,
+                expires_at: None,
+                volume_mark: None,
+                order_list_id: None,
+                stop_loss_price: None,
+                take_profit_price: None,
             });
         }
 
@@ -11418,6 +14037,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -11641,6 +14265,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -11755,6 +14384,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -11872,6 +14506,11 @@ mod tests {
                     highest_price_achieved: None,
                     trailing_stop_active: false,
                     metadata: std::collections::HashMap::new(),
+                    partial_closes: Vec::new(),
+                    scale_ins: Vec::new(),
+                    take_profit_levels: Vec::new(),
+                    margin_mode: MarginMode::Isolated,
+                    liquidation_price: None,
                 };
                 portfolio.trades.insert(trade.id.clone(), trade.clone());
                 portfolio.open_trade_ids.push(trade.id.clone());
@@ -11995,6 +14634,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             let id = trade.id.clone();
             portfolio.trades.insert(trade.id.clone(), trade.clone());
@@ -13428,6 +16072,13 @@ mod tests {
             triggered_at: None,
             filled_at: None,
             error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead: 0.0,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
         {
             let mut pending = engine.pending_stop_limit_orders.write().await;
@@ -13464,6 +16115,13 @@ mod tests {
             triggered_at: None,
             filled_at: None,
             error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead: 0.0,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
         {
             let mut pending = engine.pending_stop_limit_orders.write().await;
@@ -13505,6 +16163,13 @@ mod tests {
             triggered_at: None,
             filled_at: None,
             error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead: 0.0,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
         {
             let mut pending = engine.pending_stop_limit_orders.write().await;
@@ -13546,6 +16211,13 @@ mod tests {
             triggered_at: None,
             filled_at: None,
             error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead: 0.0,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
         {
             let mut pending = engine.pending_stop_limit_orders.write().await;
@@ -13586,6 +16258,13 @@ mod tests {
             triggered_at: Some(Utc::now()),
             filled_at: None,
             error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead: 0.0,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
         {
             let mut pending = engine.pending_stop_limit_orders.write().await;
@@ -13861,6 +16540,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
 
             // 2 short trades (83% of 600k total = 500k short)
@@ -14151,6 +16835,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: true, // TRAILING STOP ACTIVE
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -14192,6 +16881,13 @@ mod tests {
             triggered_at: Some(Utc::now()),
             filled_at: None,
             error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead: 0.0,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
         {
             let mut pending = engine.pending_stop_limit_orders.write().await;
@@ -14422,6 +17118,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.cash_balance = 10000.0;
             portfolio.equity = 10000.0;
@@ -14510,6 +17211,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await;
@@ -14538,6 +17243,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await;
@@ -14565,6 +17274,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         let result = engine.execute_manual_order(params).await;
@@ -14607,6 +17320,13 @@ mod tests {
             triggered_at: None,
             filled_at: None,
             error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead: 0.0,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         {
@@ -14646,6 +17366,13 @@ mod tests {
             triggered_at: None,
             filled_at: None,
             error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead: 0.0,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
         {
             let mut pending = engine.pending_stop_limit_orders.write().await;
@@ -14711,6 +17438,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -14821,6 +17553,11 @@ mod tests {
                 highest_price_achieved: Some(51000.0),
                 trailing_stop_active: true,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -14911,6 +17648,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false,
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -15372,6 +18114,11 @@ mod tests {
                 highest_price_achieved: None,
                 trailing_stop_active: false, // Regular SL, NOT trailing stop
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
@@ -15823,6 +18570,13 @@ mod tests {
                 triggered_at: None,
                 filled_at: None,
                 error_message: None,
+                time_in_force: TimeInForce::Gtc,
+                queue_ahead: 0.0,
+                expires_at: None,
+                volume_mark: None,
+                order_list_id: None,
+                stop_loss_price: None,
+                take_profit_price: None,
             });
         }
 
@@ -16009,6 +18763,11 @@ mod tests {
                 highest_price_achieved: Some(55000.0),
                 trailing_stop_active: true, // TRAILING STOP IS ACTIVE
                 metadata: std::collections::HashMap::new(),
+                partial_closes: Vec::new(),
+                scale_ins: Vec::new(),
+                take_profit_levels: Vec::new(),
+                margin_mode: MarginMode::Isolated,
+                liquidation_price: None,
             };
             portfolio.trades.insert(trade.id.clone(), trade.clone());
             portfolio.open_trade_ids.push(trade.id.clone());
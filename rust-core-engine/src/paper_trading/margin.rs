@@ -0,0 +1,204 @@
+//! Binance USDT-M futures margin model for paper trading
+//!
+//! Maintenance margin follows the exchange's tiered notional brackets: each bracket
+//! has its own maintenance margin rate, plus a maintenance amount that keeps the
+//! requirement continuous across bracket boundaries. An isolated position is backed
+//! only by its own margin; cross positions share the wallet balance that is not locked
+//! in isolated positions, so they are liquidated together once that shared balance
+//! falls to their combined maintenance margin.
+
+use super::trade::TradeType;
+use serde::{Deserialize, Serialize};
+
+/// How a position's collateral is accounted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum MarginMode {
+    /// Only the position's own margin backs it; losses stop at that margin
+    #[default]
+    Isolated,
+    /// The shared wallet balance backs every cross position
+    Cross,
+}
+
+/// One tier of the maintenance margin schedule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaintenanceBracket {
+    /// Upper bound of position notional (USDT) covered by this bracket
+    pub notional_cap: f64,
+    /// Maintenance margin rate applied to the whole notional in this bracket
+    pub maintenance_margin_rate: f64,
+    /// Highest leverage the exchange allows in this bracket
+    pub max_leverage: u8,
+}
+
+const fn bracket(
+    notional_cap: f64,
+    maintenance_margin_rate: f64,
+    max_leverage: u8,
+) -> MaintenanceBracket {
+    MaintenanceBracket {
+        notional_cap,
+        maintenance_margin_rate,
+        max_leverage,
+    }
+}
+
+/// BTCUSDT brackets
+const BTC_BRACKETS: &[MaintenanceBracket] = &[
+    bracket(50_000.0, 0.004, 125),
+    bracket(600_000.0, 0.005, 100),
+    bracket(3_000_000.0, 0.01, 75),
+    bracket(12_000_000.0, 0.025, 50),
+    bracket(70_000_000.0, 0.05, 25),
+    bracket(100_000_000.0, 0.1, 20),
+    bracket(230_000_000.0, 0.125, 10),
+    bracket(480_000_000.0, 0.15, 5),
+    bracket(600_000_000.0, 0.25, 4),
+    bracket(f64::INFINITY, 0.5, 1),
+];
+
+/// ETHUSDT brackets
+const ETH_BRACKETS: &[MaintenanceBracket] = &[
+    bracket(50_000.0, 0.005, 125),
+    bracket(600_000.0, 0.0065, 100),
+    bracket(3_000_000.0, 0.01, 75),
+    bracket(12_000_000.0, 0.02, 50),
+    bracket(50_000_000.0, 0.05, 25),
+    bracket(65_000_000.0, 0.1, 20),
+    bracket(150_000_000.0, 0.125, 10),
+    bracket(320_000_000.0, 0.15, 5),
+    bracket(400_000_000.0, 0.25, 4),
+    bracket(f64::INFINITY, 0.5, 1),
+];
+
+/// Brackets for every other symbol, matching a typical altcoin schedule
+const DEFAULT_BRACKETS: &[MaintenanceBracket] = &[
+    bracket(5_000.0, 0.01, 75),
+    bracket(25_000.0, 0.025, 50),
+    bracket(100_000.0, 0.05, 20),
+    bracket(250_000.0, 0.1, 10),
+    bracket(1_000_000.0, 0.125, 5),
+    bracket(10_000_000.0, 0.25, 2),
+    bracket(f64::INFINITY, 0.5, 1),
+];
+
+/// Maintenance margin brackets for `symbol`
+pub fn brackets_for(symbol: &str) -> &'static [MaintenanceBracket] {
+    match symbol {
+        "BTCUSDT" => BTC_BRACKETS,
+        "ETHUSDT" => ETH_BRACKETS,
+        _ => DEFAULT_BRACKETS,
+    }
+}
+
+/// Maintenance margin rate and maintenance amount for a position of `notional`
+///
+/// The maintenance amount of each bracket is accumulated from the rate steps below it,
+/// so `notional * rate - amount` is continuous across bracket caps.
+pub fn maintenance_rate_and_amount(symbol: &str, notional: f64) -> (f64, f64) {
+    let brackets = brackets_for(symbol);
+    let mut amount = 0.0;
+    let mut floor = 0.0;
+    let mut previous_rate = brackets[0].maintenance_margin_rate;
+    for tier in brackets {
+        amount += floor * (tier.maintenance_margin_rate - previous_rate);
+        if notional <= tier.notional_cap {
+            return (tier.maintenance_margin_rate, amount);
+        }
+        floor = tier.notional_cap;
+        previous_rate = tier.maintenance_margin_rate;
+    }
+    (previous_rate, amount)
+}
+
+/// Maintenance margin required for a position of `notional` on `symbol`
+pub fn maintenance_margin(symbol: &str, notional: f64) -> f64 {
+    let (rate, amount) = maintenance_rate_and_amount(symbol, notional);
+    (notional * rate - amount).max(0.0)
+}
+
+/// Highest leverage the bracket for `notional` allows on `symbol`
+pub fn max_leverage(symbol: &str, notional: f64) -> u8 {
+    brackets_for(symbol)
+        .iter()
+        .find(|tier| notional <= tier.notional_cap)
+        .map_or(1, |tier| tier.max_leverage)
+}
+
+/// Price at which a position's margin balance falls to its maintenance margin
+///
+/// `collateral` is the balance backing the position net of its fees but excluding
+/// its own price PnL: the isolated margin, or for cross the shared wallet less other
+/// positions' maintenance margin plus their unrealized PnL. Returns None when the
+/// collateral covers any price move (a long that survives a price of zero).
+pub fn liquidation_price(
+    symbol: &str,
+    trade_type: TradeType,
+    quantity: f64,
+    entry_price: f64,
+    collateral: f64,
+) -> Option<f64> {
+    if quantity <= 0.0 {
+        return None;
+    }
+    let (rate, amount) = maintenance_rate_and_amount(symbol, quantity * entry_price);
+    let price = match trade_type {
+        TradeType::Long => {
+            (quantity * entry_price - collateral - amount) / (quantity * (1.0 - rate))
+        },
+        TradeType::Short => {
+            (quantity * entry_price + collateral + amount) / (quantity * (1.0 + rate))
+        },
+    };
+    (price > 0.0).then_some(price)
+}
+
+/// Whether `price` has reached a position's liquidation price
+pub fn is_liquidated(trade_type: TradeType, liquidation_price: Option<f64>, price: f64) -> bool {
+    match (trade_type, liquidation_price) {
+        (TradeType::Long, Some(liquidation)) => price <= liquidation,
+        (TradeType::Short, Some(liquidation)) => price >= liquidation,
+        (_, None) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maintenance_margin_is_continuous_across_brackets() {
+        let (rate, amount) = maintenance_rate_and_amount("BTCUSDT", 10_000.0);
+        assert_eq!((rate, amount), (0.004, 0.0));
+        let (rate, amount) = maintenance_rate_and_amount("BTCUSDT", 1_000_000.0);
+        assert_eq!(rate, 0.01);
+        assert!((amount - 3_050.0).abs() < 1e-6);
+
+        for tier in &BTC_BRACKETS[..BTC_BRACKETS.len() - 1] {
+            let below = maintenance_margin("BTCUSDT", tier.notional_cap);
+            let above = maintenance_margin("BTCUSDT", tier.notional_cap + 1e-3);
+            assert!((above - below).abs() < 1.0, "jump at {}", tier.notional_cap);
+        }
+        assert_eq!(maintenance_margin("DOGEUSDT", 1_000.0), 10.0);
+        assert_eq!(max_leverage("BTCUSDT", 1_000_000.0), 75);
+    }
+
+    #[test]
+    fn test_isolated_liquidation_price() {
+        // 0.2 BTC long at 50000, 10x: 1000 margin less 4 fees
+        let long = liquidation_price("BTCUSDT", TradeType::Long, 0.2, 50_000.0, 996.0).unwrap();
+        assert!((long - 45_200.8).abs() < 0.1);
+        assert!(is_liquidated(TradeType::Long, Some(long), 45_100.0));
+        assert!(!is_liquidated(TradeType::Long, Some(long), 45_300.0));
+
+        let short = liquidation_price("BTCUSDT", TradeType::Short, 0.2, 50_000.0, 996.0).unwrap();
+        assert!((short - 54_761.0).abs() < 0.1);
+        assert!(is_liquidated(TradeType::Short, Some(short), 55_000.0));
+
+        // A fully collateralized long cannot be liquidated
+        assert_eq!(
+            liquidation_price("BTCUSDT", TradeType::Long, 0.2, 50_000.0, 10_000.0),
+            None
+        );
+    }
+}
@@ -1,17 +1,25 @@
 #![allow(dead_code)]
 
+pub mod backtester;
+pub mod clock;
 pub mod engine;
+pub mod margin;
+pub mod monte_carlo;
+pub mod order_book;
 pub mod portfolio;
+pub mod registry;
 pub mod settings;
 pub mod strategy_optimizer;
 pub mod trade;
 
 pub use engine::PaperTradingEngine;
 pub use portfolio::PaperPortfolio;
+pub use registry::PaperPortfolioRegistry;
 pub use settings::PaperTradingSettings;
 pub use trade::{CloseReason, PaperTrade, TradeType};
 // ManualOrderParams is exported directly from this module (defined below)
 
+use crate::binance::types::TimeInForce;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -78,13 +86,13 @@ pub struct ManualOrderParams {
     pub symbol: String,
     /// Order side: "buy" or "sell"
     pub side: String,
-    /// Order type: "market", "limit", or "stop-limit"
+    /// Order type: "market", "limit", "stop-limit", "bracket" or "oco"
     pub order_type: String,
     /// Quantity to trade
     pub quantity: f64,
-    /// Limit price (required for limit and stop-limit orders)
+    /// Limit price (required for limit, stop-limit and oco orders; makes a bracket entry a limit order)
     pub price: Option<f64>,
-    /// Stop price (required for stop-limit orders)
+    /// Stop price (required for stop-limit and oco orders)
     pub stop_price: Option<f64>,
     /// Leverage (optional, defaults to settings)
     pub leverage: Option<u8>,
@@ -92,6 +100,39 @@ pub struct ManualOrderParams {
     pub stop_loss_pct: Option<f64>,
     /// Take profit percentage (optional)
     pub take_profit_pct: Option<f64>,
+    /// Time in force for limit orders (defaults to GTC)
+    #[serde(default)]
+    pub time_in_force: Option<TimeInForce>,
+    /// Limit price of an OCO order's stop leg (defaults to `stop_price`)
+    #[serde(default)]
+    pub stop_limit_price: Option<f64>,
+    /// Absolute stop loss price (required for bracket orders, overrides `stop_loss_pct`)
+    #[serde(default)]
+    pub stop_loss_price: Option<f64>,
+    /// Absolute take profit price (required for bracket orders, overrides `take_profit_pct`)
+    #[serde(default)]
+    pub take_profit_price: Option<f64>,
+}
+
+/// Parameters for opening a linked pair trade
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairTradeParams {
+    /// Symbol whose direction the pair trade takes (e.g., "ETHUSDT")
+    pub symbol: String,
+    /// Hedge symbol traded in the opposite direction (e.g., "BTCUSDT")
+    pub pair_symbol: String,
+    /// Long = long `symbol` / short `pair_symbol`; Short = the reverse
+    pub direction: crate::strategies::TradingSignal,
+    /// Quantity of `symbol`
+    pub quantity: f64,
+    /// Notional of the hedge leg per unit notional of `symbol`
+    pub hedge_ratio: f64,
+    /// Leverage for both legs (optional, defaults to settings)
+    pub leverage: Option<u8>,
+    /// Why the pair was opened (optional)
+    pub reasoning: Option<String>,
+    /// Signal confidence when opened by a strategy (optional)
+    pub confidence: Option<f64>,
 }
 
 /// Order type for manual orders
@@ -115,7 +156,7 @@ impl std::fmt::Display for OrderType {
 /// Order status for pending orders
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderStatus {
-    /// Order is pending, waiting for stop price to be hit
+    /// Order is pending, waiting for stop price to be hit (or a resting limit order to fill)
     Pending,
     /// Stop price was hit, order is now active (for stop-limit)
     Triggered,
@@ -140,7 +181,9 @@ impl std::fmt::Display for OrderStatus {
 }
 
 /// @spec:FR-PAPER-003 - Stop-Limit Order
-/// A pending order that waits for stop price to be hit before executing at limit price
+/// A pending order that waits for stop price to be hit before executing at limit price.
+/// Resting limit orders (`OrderType::Limit`) share this lifecycle; their `stop_price`
+/// equals `limit_price`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StopLimitOrder {
     /// Unique order ID
@@ -173,6 +216,27 @@ pub struct StopLimitOrder {
     pub filled_at: Option<DateTime<Utc>>,
     /// Error message if order failed
     pub error_message: Option<String>,
+    /// Time in force (resting limit orders)
+    #[serde(default)]
+    pub time_in_force: TimeInForce,
+    /// Estimated volume queued ahead at the limit price (resting limit orders)
+    #[serde(default)]
+    pub queue_ahead: f64,
+    /// When an unfilled resting limit order expires
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Last seen (open_time, volume) of the symbol's 1m candle, for measuring traded volume
+    #[serde(default)]
+    pub volume_mark: Option<(i64, f64)>,
+    /// Order list an OCO leg belongs to; filling or cancelling one leg cancels the others
+    #[serde(default)]
+    pub order_list_id: Option<String>,
+    /// Absolute stop loss price for the resulting trade (bracket orders)
+    #[serde(default)]
+    pub stop_loss_price: Option<f64>,
+    /// Absolute take profit price for the resulting trade (bracket orders)
+    #[serde(default)]
+    pub take_profit_price: Option<f64>,
 }
 
 /// AI market bias for pre-computed directional filter
@@ -562,6 +626,13 @@ mod tests {
             triggered_at: None,
             filled_at: None,
             error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead: 0.0,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         assert_eq!(order.symbol, "BTCUSDT");
@@ -588,6 +659,13 @@ mod tests {
             triggered_at: Some(now),
             filled_at: Some(now),
             error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead: 0.0,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         assert!(order.triggered_at.is_some());
@@ -607,6 +685,10 @@ mod tests {
             leverage: Some(10),
             stop_loss_pct: Some(2.0),
             take_profit_pct: Some(4.0),
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         assert_eq!(params.symbol, "BTCUSDT");
@@ -627,6 +709,10 @@ mod tests {
             leverage: None,
             stop_loss_pct: None,
             take_profit_pct: None,
+            time_in_force: None,
+            stop_limit_price: None,
+            stop_loss_price: None,
+            take_profit_price: None,
         };
 
         assert!(params.price.is_none());
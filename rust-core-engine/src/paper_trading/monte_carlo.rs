@@ -0,0 +1,520 @@
+use anyhow::Result;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::trade::PaperTrade;
+
+// @spec:FR-PORTFOLIO-003 - P&L Calculation (Monte Carlo robustness analysis)
+// @ref:specs/02-design/2.5-components/COMP-RUST-TRADING.md#portfolio
+
+/// Upper bound on `MonteCarloConfig.simulations` per run
+pub const MAX_SIMULATIONS: usize = 100_000;
+
+/// How each simulated trade sequence is drawn from the closed trades
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResamplingMethod {
+    /// Same trades, random order (final equity is unchanged without perturbation)
+    Reorder,
+    /// Draw trades with replacement (bootstrap)
+    Resample,
+}
+
+/// Monte Carlo simulation parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MonteCarloConfig {
+    /// Number of simulated equity paths
+    pub simulations: usize,
+
+    /// Trade sequence generation method
+    pub method: ResamplingMethod,
+
+    /// Starting balance of every path (defaults to the portfolio's initial balance)
+    pub initial_balance: Option<f64>,
+
+    /// Maximum extra adverse slippage per side, as % of notional (0 = disabled)
+    pub slippage_perturbation_pct: f64,
+
+    /// Maximum relative change of each trade's fees, as % (0 = disabled)
+    pub fee_perturbation_pct: f64,
+
+    /// Drawdown from the starting balance counted as ruin (%)
+    pub ruin_threshold_pct: f64,
+
+    /// RNG seed for reproducible runs
+    pub seed: Option<u64>,
+}
+
+impl Default for MonteCarloConfig {
+    fn default() -> Self {
+        Self {
+            simulations: 1000,
+            method: ResamplingMethod::Resample,
+            initial_balance: None,
+            slippage_perturbation_pct: 0.0,
+            fee_perturbation_pct: 0.0,
+            ruin_threshold_pct: 50.0,
+            seed: None,
+        }
+    }
+}
+
+/// Summary statistics and percentiles of a simulated distribution
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Distribution {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p5: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p95: f64,
+}
+
+/// Result of a Monte Carlo run over closed trades
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloReport {
+    pub simulations: usize,
+    pub trades_per_simulation: usize,
+    pub method: ResamplingMethod,
+    pub initial_balance: f64,
+
+    /// Final equity of each path
+    pub final_equity: Distribution,
+
+    /// Total return of each path (%)
+    pub total_return_pct: Distribution,
+
+    /// Maximum peak-to-trough drawdown of each path (%)
+    pub max_drawdown_pct: Distribution,
+
+    /// Share of paths whose equity fell to the ruin threshold (0.0 - 1.0)
+    pub risk_of_ruin: f64,
+
+    /// Share of paths ending below the initial balance (0.0 - 1.0)
+    pub probability_of_loss: f64,
+
+    /// Max drawdown (%) of the actual trade sequence, for comparison
+    pub historical_max_drawdown_pct: f64,
+}
+
+/// PnL contribution of one closed trade, with the inputs needed for perturbation
+#[derive(Debug, Clone, Copy)]
+struct TradeOutcome {
+    pnl: f64,
+    fees: f64,
+    notional: f64,
+}
+
+impl TradeOutcome {
+    fn from_trade(trade: &PaperTrade) -> Option<Self> {
+        let pnl = trade.realized_pnl?;
+        let exit_price = trade.exit_price.unwrap_or(trade.entry_price);
+        Some(Self {
+            pnl,
+            fees: trade.trading_fees,
+            notional: (trade.entry_price + exit_price) * trade.quantity,
+        })
+    }
+}
+
+/// Simulate equity paths from the closed trades' realized PnL.
+///
+/// Trades are used in close order, so the historical drawdown reflects the sequence
+/// that actually happened.
+pub fn run_monte_carlo(
+    closed_trades: &[&PaperTrade],
+    initial_balance: f64,
+    config: &MonteCarloConfig,
+) -> Result<MonteCarloReport> {
+    let initial_balance = config.initial_balance.unwrap_or(initial_balance);
+    if initial_balance <= 0.0 {
+        return Err(anyhow::anyhow!("Initial balance must be positive"));
+    }
+    if config.simulations == 0 {
+        return Err(anyhow::anyhow!("At least one simulation is required"));
+    }
+    if config.simulations > MAX_SIMULATIONS {
+        return Err(anyhow::anyhow!(
+            "At most {} simulations are allowed, got {}",
+            MAX_SIMULATIONS,
+            config.simulations
+        ));
+    }
+    for (name, value) in [
+        ("ruin_threshold_pct", config.ruin_threshold_pct),
+        ("fee_perturbation_pct", config.fee_perturbation_pct),
+        (
+            "slippage_perturbation_pct",
+            config.slippage_perturbation_pct,
+        ),
+    ] {
+        if !(0.0..=100.0).contains(&value) {
+            return Err(anyhow::anyhow!(
+                "{} must be between 0 and 100, got {}",
+                name,
+                value
+            ));
+        }
+    }
+
+    let outcomes: Vec<TradeOutcome> = closed_trades
+        .iter()
+        .filter_map(|trade| TradeOutcome::from_trade(trade))
+        .collect();
+    if outcomes.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No closed trades available for Monte Carlo analysis"
+        ));
+    }
+
+    let mut rng = match config.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+
+    let ruin_equity = initial_balance * (1.0 - config.ruin_threshold_pct / 100.0);
+    let mut final_equities = Vec::with_capacity(config.simulations);
+    let mut drawdowns = Vec::with_capacity(config.simulations);
+    let mut ruined = 0usize;
+
+    let mut sequence: Vec<TradeOutcome> = outcomes.clone();
+    for _ in 0..config.simulations {
+        match config.method {
+            ResamplingMethod::Reorder => {
+                // Fisher-Yates shuffle
+                for i in (1..sequence.len()).rev() {
+                    let j = rng.random_range(0..=i);
+                    sequence.swap(i, j);
+                }
+            },
+            ResamplingMethod::Resample => {
+                for slot in sequence.iter_mut() {
+                    *slot = outcomes[rng.random_range(0..outcomes.len())];
+                }
+            },
+        }
+
+        let pnls = sequence
+            .iter()
+            .map(|outcome| perturbed_pnl(outcome, config, &mut rng));
+        let path = simulate_path(initial_balance, pnls, ruin_equity);
+
+        if path.ruined {
+            ruined += 1;
+        }
+        final_equities.push(path.final_equity);
+        drawdowns.push(path.max_drawdown_pct);
+    }
+
+    let returns: Vec<f64> = final_equities
+        .iter()
+        .map(|equity| (equity - initial_balance) / initial_balance * 100.0)
+        .collect();
+    let losing_paths = final_equities
+        .iter()
+        .filter(|equity| **equity < initial_balance)
+        .count();
+    let historical = simulate_path(
+        initial_balance,
+        outcomes.iter().map(|outcome| outcome.pnl),
+        ruin_equity,
+    );
+
+    let simulations = config.simulations as f64;
+    Ok(MonteCarloReport {
+        simulations: config.simulations,
+        trades_per_simulation: outcomes.len(),
+        method: config.method,
+        initial_balance,
+        final_equity: Distribution::from_samples(final_equities),
+        total_return_pct: Distribution::from_samples(returns),
+        max_drawdown_pct: Distribution::from_samples(drawdowns),
+        risk_of_ruin: ruined as f64 / simulations,
+        probability_of_loss: losing_paths as f64 / simulations,
+        historical_max_drawdown_pct: historical.max_drawdown_pct,
+    })
+}
+
+/// Apply random extra slippage (always adverse) and fee variation to a trade's PnL
+fn perturbed_pnl(outcome: &TradeOutcome, config: &MonteCarloConfig, rng: &mut StdRng) -> f64 {
+    let mut pnl = outcome.pnl;
+    if config.slippage_perturbation_pct > 0.0 {
+        let slippage_pct = rng.random_range(0.0..=config.slippage_perturbation_pct);
+        pnl -= outcome.notional * slippage_pct / 100.0;
+    }
+    if config.fee_perturbation_pct > 0.0 {
+        let change = config.fee_perturbation_pct / 100.0;
+        let factor = rng.random_range(-change..=change);
+        pnl -= outcome.fees * factor;
+    }
+    pnl
+}
+
+struct PathResult {
+    final_equity: f64,
+    max_drawdown_pct: f64,
+    ruined: bool,
+}
+
+fn simulate_path(
+    initial_balance: f64,
+    pnls: impl Iterator<Item = f64>,
+    ruin_equity: f64,
+) -> PathResult {
+    let mut equity = initial_balance;
+    let mut peak = initial_balance;
+    let mut max_drawdown_pct: f64 = 0.0;
+    let mut ruined = false;
+
+    for pnl in pnls {
+        equity += pnl;
+        peak = peak.max(equity);
+        if peak > 0.0 {
+            max_drawdown_pct = max_drawdown_pct.max((peak - equity) / peak * 100.0);
+        }
+        if equity <= ruin_equity {
+            ruined = true;
+        }
+    }
+
+    PathResult {
+        final_equity: equity,
+        max_drawdown_pct,
+        ruined,
+    }
+}
+
+impl Distribution {
+    fn from_samples(mut samples: Vec<f64>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_by(|a, b| a.total_cmp(b));
+
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / n;
+
+        Self {
+            mean,
+            std_dev: variance.sqrt(),
+            min: samples[0],
+            max: samples[samples.len() - 1],
+            p5: percentile(&samples, 5.0),
+            p25: percentile(&samples, 25.0),
+            p50: percentile(&samples, 50.0),
+            p75: percentile(&samples, 75.0),
+            p95: percentile(&samples, 95.0),
+        }
+    }
+}
+
+/// Linear-interpolated percentile of sorted samples
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    let rank = (pct / 100.0) * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    let weight = rank - lower as f64;
+    sorted[lower] * (1.0 - weight) + sorted[upper] * weight
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::paper_trading::trade::TradeType;
+
+    fn closed_trade(entry: f64, exit: f64) -> PaperTrade {
+        let mut trade = PaperTrade::new(
+            "BTCUSDT".to_string(),
+            TradeType::Long,
+            entry,
+            1.0,
+            1,
+            0.0,
+            None,
+            None,
+            None,
+        );
+        trade.exit_price = Some(exit);
+        trade.realized_pnl = Some(exit - entry);
+        trade
+    }
+
+    fn seeded(method: ResamplingMethod) -> MonteCarloConfig {
+        MonteCarloConfig {
+            simulations: 500,
+            method,
+            seed: Some(7),
+            ..MonteCarloConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_percentile_interpolates() {
+        let samples = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(percentile(&samples, 0.0), 1.0);
+        assert_eq!(percentile(&samples, 50.0), 3.0);
+        assert_eq!(percentile(&samples, 100.0), 5.0);
+        assert!((percentile(&samples, 25.0) - 2.0).abs() < 1e-9);
+        assert!((percentile(&samples, 10.0) - 1.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_simulate_path_drawdown_and_ruin() {
+        let path = simulate_path(1000.0, [100.0, -660.0, 50.0].into_iter(), 500.0);
+        assert!((path.final_equity - 490.0).abs() < 1e-9);
+        assert!((path.max_drawdown_pct - 60.0).abs() < 1e-9);
+        assert!(path.ruined);
+
+        let path = simulate_path(1000.0, [100.0, 100.0].into_iter(), 500.0);
+        assert_eq!(path.max_drawdown_pct, 0.0);
+        assert!(!path.ruined);
+    }
+
+    #[test]
+    fn test_reorder_keeps_final_equity() {
+        let trades = [
+            closed_trade(100.0, 150.0),
+            closed_trade(100.0, 80.0),
+            closed_trade(100.0, 130.0),
+            closed_trade(100.0, 60.0),
+        ];
+        let refs: Vec<&PaperTrade> = trades.iter().collect();
+
+        let report = run_monte_carlo(&refs, 1000.0, &seeded(ResamplingMethod::Reorder)).unwrap();
+        assert_eq!(report.trades_per_simulation, 4);
+        assert!((report.final_equity.min - 1020.0).abs() < 1e-9);
+        assert!((report.final_equity.max - 1020.0).abs() < 1e-9);
+        assert_eq!(report.probability_of_loss, 0.0);
+        // Ordering changes the drawdown, and the worst case loses both losers in a row
+        assert!(report.max_drawdown_pct.max >= report.historical_max_drawdown_pct);
+        assert!(report.max_drawdown_pct.max > report.max_drawdown_pct.min);
+    }
+
+    #[test]
+    fn test_resample_produces_spread_and_is_seeded() {
+        let trades = [
+            closed_trade(100.0, 150.0),
+            closed_trade(100.0, 80.0),
+            closed_trade(100.0, 90.0),
+        ];
+        let refs: Vec<&PaperTrade> = trades.iter().collect();
+        let config = seeded(ResamplingMethod::Resample);
+
+        let first = run_monte_carlo(&refs, 1000.0, &config).unwrap();
+        let second = run_monte_carlo(&refs, 1000.0, &config).unwrap();
+        assert!(first.final_equity.p95 > first.final_equity.p5);
+        assert_eq!(first.final_equity.mean, second.final_equity.mean);
+        assert_eq!(first.max_drawdown_pct.p95, second.max_drawdown_pct.p95);
+        assert!(first.final_equity.p5 <= first.final_equity.p50);
+        assert!(first.final_equity.p50 <= first.final_equity.p95);
+    }
+
+    #[test]
+    fn test_risk_of_ruin_counts_paths_below_threshold() {
+        let trades = [closed_trade(1000.0, 400.0), closed_trade(1000.0, 1100.0)];
+        let refs: Vec<&PaperTrade> = trades.iter().collect();
+        let config = MonteCarloConfig {
+            ruin_threshold_pct: 50.0,
+            ..seeded(ResamplingMethod::Reorder)
+        };
+
+        // The -600 trade always takes a 1000 balance through the 500 ruin level
+        let report = run_monte_carlo(&refs, 1000.0, &config).unwrap();
+        assert_eq!(report.risk_of_ruin, 1.0);
+
+        let safe = MonteCarloConfig {
+            ruin_threshold_pct: 90.0,
+            ..config
+        };
+        let report = run_monte_carlo(&refs, 1000.0, &safe).unwrap();
+        assert_eq!(report.risk_of_ruin, 0.0);
+    }
+
+    #[test]
+    fn test_slippage_perturbation_only_lowers_equity() {
+        let trades = [closed_trade(100.0, 110.0), closed_trade(100.0, 105.0)];
+        let refs: Vec<&PaperTrade> = trades.iter().collect();
+        let config = MonteCarloConfig {
+            slippage_perturbation_pct: 1.0,
+            ..seeded(ResamplingMethod::Reorder)
+        };
+
+        let report = run_monte_carlo(&refs, 1000.0, &config).unwrap();
+        assert!(report.final_equity.max <= 1015.0);
+        assert!(report.final_equity.min < 1015.0);
+    }
+
+    #[test]
+    fn test_fee_perturbation_varies_around_actual_fees() {
+        let mut trade = closed_trade(100.0, 110.0);
+        trade.trading_fees = 2.0;
+        let refs = vec![&trade];
+        let config = MonteCarloConfig {
+            fee_perturbation_pct: 50.0,
+            ..seeded(ResamplingMethod::Reorder)
+        };
+
+        let report = run_monte_carlo(&refs, 1000.0, &config).unwrap();
+        assert!(report.final_equity.min >= 1009.0 - 1e-9);
+        assert!(report.final_equity.max <= 1011.0 + 1e-9);
+        assert!(report.final_equity.std_dev > 0.0);
+    }
+
+    #[test]
+    fn test_errors_without_closed_trades_or_balance() {
+        let config = MonteCarloConfig::default();
+        assert!(run_monte_carlo(&[], 1000.0, &config).is_err());
+
+        let mut open_trade = closed_trade(100.0, 110.0);
+        open_trade.realized_pnl = None;
+        assert!(run_monte_carlo(&[&open_trade], 1000.0, &config).is_err());
+
+        let trade = closed_trade(100.0, 110.0);
+        assert!(run_monte_carlo(&[&trade], 0.0, &config).is_err());
+
+        let no_runs = MonteCarloConfig {
+            simulations: 0,
+            ..config
+        };
+        assert!(run_monte_carlo(&[&trade], 1000.0, &no_runs).is_err());
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_config() {
+        let trade = closed_trade(100.0, 110.0);
+        let too_many = MonteCarloConfig {
+            simulations: MAX_SIMULATIONS + 1,
+            ..MonteCarloConfig::default()
+        };
+        assert!(run_monte_carlo(&[&trade], 1000.0, &too_many).is_err());
+
+        let negative_ruin = MonteCarloConfig {
+            ruin_threshold_pct: -5.0,
+            ..MonteCarloConfig::default()
+        };
+        assert!(run_monte_carlo(&[&trade], 1000.0, &negative_ruin).is_err());
+
+        let excessive_fees = MonteCarloConfig {
+            fee_perturbation_pct: 150.0,
+            ..MonteCarloConfig::default()
+        };
+        assert!(run_monte_carlo(&[&trade], 1000.0, &excessive_fees).is_err());
+    }
+
+    #[test]
+    fn test_config_initial_balance_override() {
+        let trade = closed_trade(100.0, 110.0);
+        let config = MonteCarloConfig {
+            initial_balance: Some(500.0),
+            ..seeded(ResamplingMethod::Reorder)
+        };
+        let report = run_monte_carlo(&[&trade], 1000.0, &config).unwrap();
+        assert_eq!(report.initial_balance, 500.0);
+        assert!((report.final_equity.mean - 510.0).abs() < 1e-9);
+    }
+}
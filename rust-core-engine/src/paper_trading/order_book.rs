@@ -0,0 +1,293 @@
+//! Price-crossing simulation for resting paper limit orders
+//!
+//! There is no real order book behind paper trading, so a resting limit order only
+//! fills once the traded price reaches it. If price trades through the limit the
+//! whole queue at that level has been consumed and the order fills; if price only
+//! touches the limit, the order waits until the volume estimated to be queued ahead
+//! of it has traded.
+//!
+//! Also validates the price legs of bracket and OCO orders.
+
+use super::StopLimitOrder;
+use chrono::{DateTime, Utc};
+
+/// Relative distance within which price counts as touching the limit
+const TOUCH_TOLERANCE: f64 = 1e-9;
+
+/// What to do with a resting limit order after a price update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitOrderAction {
+    Rest,
+    Fill,
+    Expire,
+}
+
+/// Whether `side` is the buy side ("buy"/"long")
+pub fn is_buy_side(side: &str) -> bool {
+    matches!(side.to_lowercase().as_str(), "buy" | "long")
+}
+
+/// A limit order at `limit_price` would fill immediately against `market_price`
+pub fn is_marketable(is_buy: bool, limit_price: f64, market_price: f64) -> bool {
+    if is_buy {
+        market_price <= limit_price
+    } else {
+        market_price >= limit_price
+    }
+}
+
+/// Volume traded on the symbol's current 1m candle since `mark`, updating `mark`
+///
+/// A new candle means everything it has traded so far is new. Returns None when no
+/// candle data is available.
+pub fn traded_volume_since(
+    mark: &mut Option<(i64, f64)>,
+    candle: Option<(i64, f64)>,
+) -> Option<f64> {
+    let (open_time, volume) = candle?;
+    let traded = match *mark {
+        Some((mark_time, mark_volume)) if mark_time == open_time => (volume - mark_volume).max(0.0),
+        _ => volume,
+    };
+    *mark = Some((open_time, volume));
+    Some(traded)
+}
+
+/// Decide whether a resting limit order fills, expires or keeps resting at `price`
+///
+/// `traded_volume` is the volume traded since the previous check; without it a touch
+/// of the limit price fills the order.
+pub fn evaluate_resting_limit(
+    order: &mut StopLimitOrder,
+    price: f64,
+    traded_volume: Option<f64>,
+    now: DateTime<Utc>,
+) -> LimitOrderAction {
+    if order.expires_at.is_some_and(|expires_at| now >= expires_at) {
+        return LimitOrderAction::Expire;
+    }
+
+    let is_buy = is_buy_side(&order.side);
+    let touched = (price - order.limit_price).abs() <= order.limit_price * TOUCH_TOLERANCE;
+    if !touched && is_marketable(is_buy, order.limit_price, price) {
+        return LimitOrderAction::Fill;
+    }
+    if !touched {
+        return LimitOrderAction::Rest;
+    }
+
+    match traded_volume {
+        Some(volume) => {
+            order.queue_ahead = (order.queue_ahead - volume).max(0.0);
+            if order.queue_ahead <= 0.0 {
+                LimitOrderAction::Fill
+            } else {
+                LimitOrderAction::Rest
+            }
+        },
+        None => LimitOrderAction::Fill,
+    }
+}
+
+/// Check a bracket's exits sit on the right sides of its entry price
+pub fn validate_bracket(
+    is_buy: bool,
+    entry_price: f64,
+    stop_loss: f64,
+    take_profit: f64,
+) -> Result<(), String> {
+    let valid = if is_buy {
+        stop_loss < entry_price && entry_price < take_profit
+    } else {
+        take_profit < entry_price && entry_price < stop_loss
+    };
+    if valid {
+        Ok(())
+    } else if is_buy {
+        Err(format!(
+            "Buy bracket needs stop_loss_price < entry ({:.2}) < take_profit_price, got sl={:.2} tp={:.2}",
+            entry_price, stop_loss, take_profit
+        ))
+    } else {
+        Err(format!(
+            "Sell bracket needs take_profit_price < entry ({:.2}) < stop_loss_price, got sl={:.2} tp={:.2}",
+            entry_price, stop_loss, take_profit
+        ))
+    }
+}
+
+/// Check an OCO pair's limit and stop legs straddle the market
+///
+/// A buy OCO rests its limit below the market and its stop above it; a sell OCO the
+/// reverse. Without a market price only the ordering of the two legs is checked.
+pub fn validate_oco(
+    is_buy: bool,
+    limit_price: f64,
+    stop_price: f64,
+    market_price: Option<f64>,
+) -> Result<(), String> {
+    let (below, above) = if is_buy {
+        (limit_price, stop_price)
+    } else {
+        (stop_price, limit_price)
+    };
+    let valid = match market_price {
+        Some(price) => below < price && price < above,
+        None => below < above,
+    };
+    if valid {
+        return Ok(());
+    }
+    let market = market_price.map_or("market".to_string(), |p| format!("market ({:.2})", p));
+    if is_buy {
+        Err(format!(
+            "Buy OCO needs limit price ({:.2}) < {} < stop_price ({:.2})",
+            limit_price, market, stop_price
+        ))
+    } else {
+        Err(format!(
+            "Sell OCO needs stop_price ({:.2}) < {} < limit price ({:.2})",
+            stop_price, market, limit_price
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binance::types::TimeInForce;
+    use crate::paper_trading::{OrderStatus, OrderType};
+
+    fn resting(side: &str, limit_price: f64, queue_ahead: f64) -> StopLimitOrder {
+        StopLimitOrder {
+            id: "limit-1".to_string(),
+            symbol: "BTCUSDT".to_string(),
+            side: side.to_string(),
+            order_type: OrderType::Limit,
+            quantity: 0.1,
+            stop_price: limit_price,
+            limit_price,
+            leverage: 10,
+            stop_loss_pct: None,
+            take_profit_pct: None,
+            status: OrderStatus::Pending,
+            created_at: Utc::now(),
+            triggered_at: None,
+            filled_at: None,
+            error_message: None,
+            time_in_force: TimeInForce::Gtc,
+            queue_ahead,
+            expires_at: None,
+            volume_mark: None,
+            order_list_id: None,
+            stop_loss_price: None,
+            take_profit_price: None,
+        }
+    }
+
+    #[test]
+    fn test_is_marketable() {
+        assert!(is_marketable(true, 50000.0, 49990.0));
+        assert!(!is_marketable(true, 50000.0, 50010.0));
+        assert!(is_marketable(false, 50000.0, 50010.0));
+        assert!(!is_marketable(false, 50000.0, 49990.0));
+        assert!(is_buy_side("Long") && !is_buy_side("sell"));
+    }
+
+    #[test]
+    fn test_resting_limit_fills_when_price_trades_through() {
+        let now = Utc::now();
+        let mut buy = resting("buy", 50000.0, 100.0);
+        assert_eq!(
+            evaluate_resting_limit(&mut buy, 50100.0, Some(5.0), now),
+            LimitOrderAction::Rest
+        );
+        assert_eq!(
+            evaluate_resting_limit(&mut buy, 49999.0, Some(0.0), now),
+            LimitOrderAction::Fill
+        );
+
+        let mut sell = resting("sell", 50000.0, 100.0);
+        assert_eq!(
+            evaluate_resting_limit(&mut sell, 50001.0, None, now),
+            LimitOrderAction::Fill
+        );
+    }
+
+    #[test]
+    fn test_resting_limit_waits_for_queue_at_touch() {
+        let now = Utc::now();
+        let mut order = resting("buy", 50000.0, 10.0);
+        assert_eq!(
+            evaluate_resting_limit(&mut order, 50000.0, Some(4.0), now),
+            LimitOrderAction::Rest
+        );
+        assert_eq!(order.queue_ahead, 6.0);
+        // Volume traded away from the limit does not move the queue
+        assert_eq!(
+            evaluate_resting_limit(&mut order, 50050.0, Some(50.0), now),
+            LimitOrderAction::Rest
+        );
+        assert_eq!(order.queue_ahead, 6.0);
+        assert_eq!(
+            evaluate_resting_limit(&mut order, 50000.0, Some(6.0), now),
+            LimitOrderAction::Fill
+        );
+
+        // Without volume data a touch fills
+        let mut order = resting("sell", 50000.0, 10.0);
+        assert_eq!(
+            evaluate_resting_limit(&mut order, 50000.0, None, now),
+            LimitOrderAction::Fill
+        );
+    }
+
+    #[test]
+    fn test_resting_limit_expires() {
+        let now = Utc::now();
+        let mut order = resting("buy", 50000.0, 0.0);
+        order.expires_at = Some(now - chrono::Duration::seconds(1));
+        assert_eq!(
+            evaluate_resting_limit(&mut order, 49000.0, None, now),
+            LimitOrderAction::Expire
+        );
+    }
+
+    #[test]
+    fn test_validate_bracket() {
+        assert!(validate_bracket(true, 100.0, 95.0, 110.0).is_ok());
+        assert!(validate_bracket(true, 100.0, 101.0, 110.0).is_err());
+        assert!(validate_bracket(false, 100.0, 105.0, 90.0).is_ok());
+        assert!(validate_bracket(false, 100.0, 95.0, 110.0).is_err());
+    }
+
+    #[test]
+    fn test_validate_oco() {
+        assert!(validate_oco(true, 95.0, 105.0, Some(100.0)).is_ok());
+        assert!(validate_oco(true, 95.0, 105.0, Some(106.0)).is_err());
+        assert!(validate_oco(true, 95.0, 105.0, None).is_ok());
+        assert!(validate_oco(true, 105.0, 95.0, None).is_err());
+        assert!(validate_oco(false, 105.0, 95.0, Some(100.0)).is_ok());
+        assert!(validate_oco(false, 95.0, 105.0, Some(100.0)).is_err());
+    }
+
+    #[test]
+    fn test_traded_volume_since() {
+        let mut mark = None;
+        assert_eq!(traded_volume_since(&mut mark, None), None);
+        assert_eq!(
+            traded_volume_since(&mut mark, Some((60_000, 12.0))),
+            Some(12.0)
+        );
+        assert_eq!(
+            traded_volume_since(&mut mark, Some((60_000, 15.0))),
+            Some(3.0)
+        );
+        // New candle: all of its volume is new
+        assert_eq!(
+            traded_volume_since(&mut mark, Some((120_000, 2.0))),
+            Some(2.0)
+        );
+        assert_eq!(mark, Some((120_000, 2.0)));
+    }
+}
@@ -4,7 +4,9 @@ use log::{debug, info, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::trade::{CloseReason, PaperTrade, TradeStatus};
+use super::clock::SimulationClock;
+use super::margin::{self, MarginMode};
+use super::trade::{CloseReason, PaperTrade, TradeStatus, TradeSummary};
 
 /// Paper trading portfolio that tracks all positions and performance
 
@@ -85,6 +87,10 @@ pub struct PaperPortfolio {
     /// Used by ATR regime filter to pause trading if weekly drawdown exceeds limit
     #[serde(default)]
     pub week_start_equity: Option<(DateTime<Utc>, f64)>,
+
+    /// Clock behind trade close times and portfolio timestamps
+    #[serde(skip)]
+    clock: SimulationClock,
 }
 
 /// Comprehensive portfolio metrics
@@ -205,6 +211,21 @@ pub struct DailyPerformance {
     pub max_drawdown: f64,
 }
 
+/// Legs of a pair trade with their combined result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PairTradeSummary {
+    pub pair_id: String,
+    /// Open while any leg is still open
+    pub status: TradeStatus,
+    pub legs: Vec<TradeSummary>,
+    /// Realized PnL of closed legs plus unrealized PnL of open ones
+    pub combined_pnl: f64,
+    pub total_fees: f64,
+    pub total_margin: f64,
+    pub open_time: DateTime<Utc>,
+    pub close_time: Option<DateTime<Utc>>,
+}
+
 impl PaperPortfolio {
     /// Create a new paper trading portfolio
     pub fn new(initial_balance: f64) -> Self {
@@ -229,9 +250,18 @@ impl PaperPortfolio {
             consecutive_losses: 0,
             cool_down_until: None,
             week_start_equity: None,
+            clock: SimulationClock::system(),
         }
     }
 
+    /// Take timestamps from `clock` instead of wall time
+    pub fn with_clock(mut self, clock: SimulationClock) -> Self {
+        self.created_at = clock.now();
+        self.last_updated = self.created_at;
+        self.clock = clock;
+        self
+    }
+
     /// Add a new trade to the portfolio
     pub fn add_trade(&mut self, trade: PaperTrade) -> Result<()> {
         // Check if we have enough free margin
@@ -255,11 +285,135 @@ impl PaperPortfolio {
 
         self.update_portfolio_values(); // Update margin_level and free_margin after adding trade
         self.update_metrics();
-        self.last_updated = Utc::now();
+        self.last_updated = self.clock.now();
 
         Ok(())
     }
 
+    /// Add the legs of a pair trade together, linked by a new pair id
+    ///
+    /// Margin for all legs is checked up front so either every leg opens or none does.
+    pub fn add_pair_trade(&mut self, mut legs: Vec<PaperTrade>) -> Result<String> {
+        if legs.len() < 2 {
+            return Err(anyhow::anyhow!("A pair trade needs at least two legs"));
+        }
+
+        let required_margin: f64 = legs.iter().map(|t| t.initial_margin).sum();
+        if required_margin > self.free_margin {
+            return Err(anyhow::anyhow!(
+                "Insufficient free margin for pair trade. Required: {}, Available: {}",
+                required_margin,
+                self.free_margin
+            ));
+        }
+
+        let pair_id = uuid::Uuid::new_v4().to_string();
+        for mut trade in legs.drain(..) {
+            trade.set_pair_id(&pair_id);
+            self.margin_used += trade.initial_margin;
+            self.open_trade_ids.push(trade.id.clone());
+            self.trades.insert(trade.id.clone(), trade);
+        }
+
+        self.update_portfolio_values();
+        self.update_metrics();
+        self.last_updated = self.clock.now();
+
+        Ok(pair_id)
+    }
+
+    /// Close every open leg of a pair trade, returning the legs' combined realized PnL
+    ///
+    /// Prices for all legs are resolved before anything is closed, so a missing price
+    /// leaves the pair untouched.
+    pub fn close_pair_trade(
+        &mut self,
+        pair_id: &str,
+        exit_prices: &HashMap<String, f64>,
+        close_reason: CloseReason,
+    ) -> Result<f64> {
+        let mut exits = Vec::new();
+        for trade in self.get_pair_legs(pair_id) {
+            if trade.status != TradeStatus::Open {
+                continue;
+            }
+            let exit_price = exit_prices.get(&trade.symbol).copied().ok_or_else(|| {
+                anyhow::anyhow!("No exit price for {} leg of pair {pair_id}", trade.symbol)
+            })?;
+            exits.push((trade.id.clone(), exit_price));
+        }
+        if exits.is_empty() {
+            return Err(anyhow::anyhow!("No open pair trade: {pair_id}"));
+        }
+
+        let mut combined_pnl = 0.0;
+        for (trade_id, exit_price) in exits {
+            self.close_trade(&trade_id, exit_price, close_reason.clone())?;
+            combined_pnl += self
+                .trades
+                .get(&trade_id)
+                .and_then(|t| t.realized_pnl)
+                .unwrap_or(0.0);
+        }
+
+        Ok(combined_pnl)
+    }
+
+    /// All legs (open or closed) of a pair trade, oldest first
+    pub fn get_pair_legs(&self, pair_id: &str) -> Vec<&PaperTrade> {
+        let mut legs: Vec<&PaperTrade> = self
+            .trades
+            .values()
+            .filter(|t| t.pair_id() == Some(pair_id))
+            .collect();
+        legs.sort_by_key(|t| t.open_time);
+        legs
+    }
+
+    /// Pair trades with combined PnL, most recently opened first
+    pub fn get_pair_trades(&self) -> Vec<PairTradeSummary> {
+        let mut pairs: HashMap<&str, Vec<&PaperTrade>> = HashMap::new();
+        for trade in self.trades.values() {
+            if let Some(pair_id) = trade.pair_id() {
+                pairs.entry(pair_id).or_default().push(trade);
+            }
+        }
+
+        let mut summaries: Vec<PairTradeSummary> = pairs
+            .into_iter()
+            .map(|(pair_id, mut legs)| {
+                legs.sort_by_key(|t| t.open_time);
+                let any_open = legs.iter().any(|t| t.status == TradeStatus::Open);
+                let total_margin = legs.iter().map(|t| t.initial_margin).sum();
+                let legs: Vec<TradeSummary> = legs.iter().map(|t| t.get_summary()).collect();
+                PairTradeSummary {
+                    pair_id: pair_id.to_string(),
+                    status: if any_open {
+                        TradeStatus::Open
+                    } else {
+                        TradeStatus::Closed
+                    },
+                    combined_pnl: legs.iter().filter_map(|l| l.pnl).sum(),
+                    total_fees: legs.iter().map(|l| l.trading_fees + l.funding_fees).sum(),
+                    total_margin,
+                    open_time: legs
+                        .iter()
+                        .map(|l| l.open_time)
+                        .min()
+                        .unwrap_or_else(Utc::now),
+                    close_time: if any_open {
+                        None
+                    } else {
+                        legs.iter().filter_map(|l| l.close_time).max()
+                    },
+                    legs,
+                }
+            })
+            .collect();
+        summaries.sort_by_key(|p| std::cmp::Reverse(p.open_time));
+        summaries
+    }
+
     /// Close a trade
     pub fn close_trade(
         &mut self,
@@ -278,20 +432,25 @@ impl PaperPortfolio {
 
         // Calculate exit fees (same rate as entry) with division-by-zero protection
         let notional_entry = trade.quantity * trade.entry_price;
-        let exit_fees = if notional_entry > 0.0 {
+        let mut exit_fees = if notional_entry > 0.0 {
             (trade.quantity * exit_price) * (trade.trading_fees / notional_entry)
         } else {
             0.0
         };
+        // A liquidated position also forfeits its remaining margin to the insurance fund
+        if close_reason == CloseReason::Liquidation {
+            exit_fees += trade.liquidation_clearance_fee(exit_price, exit_fees);
+        }
 
         // Close the trade
-        trade.close(exit_price, close_reason, exit_fees)?;
+        trade.close_at(exit_price, close_reason, exit_fees, self.clock.now())?;
 
         // Update portfolio
         // Only adjust cash_balance by realized PnL (profit/loss)
         // The margin was never deducted from cash_balance, so don't add it back
+        // Partial closes were credited when they happened
         if let Some(realized_pnl) = trade.realized_pnl {
-            self.cash_balance += realized_pnl;
+            self.cash_balance += realized_pnl - trade.partial_realized_pnl();
         }
 
         self.margin_used -= trade.initial_margin;
@@ -304,7 +463,78 @@ impl PaperPortfolio {
 
         self.update_portfolio_values();
         self.update_metrics();
-        self.last_updated = Utc::now();
+        self.last_updated = self.clock.now();
+
+        Ok(())
+    }
+
+    /// Close `quantity` of an open trade, returning the realized PnL of that part
+    pub fn reduce_trade(
+        &mut self,
+        trade_id: &str,
+        quantity: f64,
+        exit_price: f64,
+        close_reason: CloseReason,
+    ) -> Result<f64> {
+        let trade = self
+            .trades
+            .get_mut(trade_id)
+            .ok_or_else(|| anyhow::anyhow!("Trade not found: {trade_id}"))?;
+
+        // Exit fees at the entry fee rate, as for a full close
+        let notional_entry = trade.quantity * trade.entry_price;
+        let exit_fees = if notional_entry > 0.0 {
+            (quantity * exit_price) * (trade.trading_fees / notional_entry)
+        } else {
+            0.0
+        };
+
+        let margin_before = trade.initial_margin;
+        let realized_pnl = trade.reduce(
+            quantity,
+            exit_price,
+            close_reason,
+            exit_fees,
+            self.clock.now(),
+        )?;
+        self.margin_used -= margin_before - trade.initial_margin;
+        self.cash_balance += realized_pnl;
+
+        self.update_portfolio_values();
+        self.update_metrics();
+        self.last_updated = self.clock.now();
+
+        Ok(realized_pnl)
+    }
+
+    /// Add `quantity` at `price` to an open trade, re-averaging its entry price
+    pub fn scale_in_trade(
+        &mut self,
+        trade_id: &str,
+        quantity: f64,
+        price: f64,
+        trading_fee_rate: f64,
+    ) -> Result<()> {
+        let free_margin = self.free_margin;
+        let trade = self
+            .trades
+            .get_mut(trade_id)
+            .ok_or_else(|| anyhow::anyhow!("Trade not found: {trade_id}"))?;
+
+        let required_margin = quantity * price / trade.leverage as f64;
+        if required_margin > free_margin {
+            return Err(anyhow::anyhow!(
+                "Insufficient free margin. Required: {}, Available: {}",
+                required_margin,
+                free_margin
+            ));
+        }
+
+        self.margin_used += trade.scale_in(quantity, price, trading_fee_rate, self.clock.now())?;
+
+        self.update_portfolio_values();
+        self.update_metrics();
+        self.last_updated = self.clock.now();
 
         Ok(())
     }
@@ -334,12 +564,85 @@ impl PaperPortfolio {
 
         self.update_portfolio_values();
         self.update_metrics();
-        self.last_updated = Utc::now();
+        self.last_updated = self.clock.now();
+    }
+
+    /// Open trades whose margin has reached maintenance at current prices
+    ///
+    /// An isolated trade is liquidated on its own; once any cross trade reaches its
+    /// liquidation price the shared balance is exhausted and every cross trade goes.
+    pub fn liquidation_candidates(&self) -> Vec<String> {
+        let reached = |trade: &PaperTrade| {
+            self.current_prices
+                .get(&trade.symbol)
+                .is_some_and(|price| trade.should_liquidate(*price))
+        };
+        let open_trades: Vec<&PaperTrade> = self.get_open_trades();
+        let cross_liquidated = open_trades
+            .iter()
+            .any(|t| t.margin_mode == MarginMode::Cross && reached(t));
+
+        open_trades
+            .into_iter()
+            .filter(|t| match t.margin_mode {
+                MarginMode::Isolated => reached(t),
+                MarginMode::Cross => cross_liquidated,
+            })
+            .map(|t| t.id.clone())
+            .collect()
+    }
+
+    /// Wallet balance shared by cross positions: cash not locked in isolated margin
+    pub fn cross_wallet_balance(&self) -> f64 {
+        let isolated_margin: f64 = self
+            .get_open_trades()
+            .iter()
+            .filter(|t| t.margin_mode == MarginMode::Isolated)
+            .map(|t| t.margin_used)
+            .sum();
+        self.cash_balance - isolated_margin
+    }
+
+    /// Recompute maintenance margins and liquidation prices of all open trades
+    fn update_liquidation_prices(&mut self) {
+        let cross_wallet = self.cross_wallet_balance();
+        let (mut cross_maintenance, mut cross_unrealized) = (0.0, 0.0);
+        for trade_id in &self.open_trade_ids {
+            if let Some(trade) = self.trades.get_mut(trade_id) {
+                trade.refresh_margin_requirements();
+                if trade.margin_mode == MarginMode::Cross {
+                    cross_maintenance += trade.maintenance_margin;
+                    cross_unrealized += trade.unrealized_pnl;
+                }
+            }
+        }
+
+        // Each cross position is backed by the shared wallet less what the other cross
+        // positions need for maintenance, plus their unrealized PnL
+        for trade_id in &self.open_trade_ids {
+            if let Some(trade) = self.trades.get_mut(trade_id) {
+                if trade.margin_mode != MarginMode::Cross {
+                    continue;
+                }
+                let collateral = cross_wallet - (cross_maintenance - trade.maintenance_margin)
+                    + (cross_unrealized - trade.unrealized_pnl)
+                    - trade.trading_fees
+                    - trade.funding_fees;
+                trade.liquidation_price = margin::liquidation_price(
+                    &trade.symbol,
+                    trade.trade_type,
+                    trade.quantity,
+                    trade.entry_price,
+                    collateral,
+                );
+            }
+        }
     }
 
     /// Check for automatic trade closures (stop loss, take profit, liquidation)
     pub fn check_automatic_closures(&mut self) -> Vec<String> {
         let mut closed_trades = Vec::new();
+        let liquidations = self.liquidation_candidates();
 
         let open_trades = self.open_trade_ids.clone();
         for trade_id in &open_trades {
@@ -378,18 +681,19 @@ impl PaperPortfolio {
                         should_close = true;
                         close_reason = CloseReason::TakeProfit;
                     }
-                    // Check liquidation risk
-                    else if trade.is_at_liquidation_risk(*current_price) {
+                    // Check liquidation
+                    else if liquidations.contains(trade_id) {
                         warn!(
-                            "⚠️ LIQUIDATION RISK: Trade {} ({} {:?})",
-                            trade_id, trade.symbol, trade.trade_type
+                            "💥 LIQUIDATION: Trade {} ({} {:?} {:?})",
+                            trade_id, trade.symbol, trade.trade_type, trade.margin_mode
                         );
                         warn!(
-                            "   Current price: ${:.2}, Margin ratio too low!",
-                            current_price
+                            "   Current price: ${:.2}, Liquidation price: ${:.2}",
+                            current_price,
+                            trade.liquidation_price.unwrap_or(0.0)
                         );
                         should_close = true;
-                        close_reason = CloseReason::MarginCall;
+                        close_reason = CloseReason::Liquidation;
                     } else {
                         // Log periodic price checks for debugging (only for BTC for now to avoid spam)
                         if trade.symbol == "BTCUSDT" {
@@ -428,6 +732,7 @@ impl PaperPortfolio {
 
         self.equity = self.cash_balance + unrealized_pnl;
         self.free_margin = self.equity - self.margin_used;
+        self.update_liquidation_prices();
 
         // Calculate margin level
         self.margin_level = if self.margin_used > 0.0 {
@@ -678,7 +983,7 @@ impl PaperPortfolio {
             0.0
         };
 
-        metrics.calculated_at = Utc::now();
+        metrics.calculated_at = self.clock.now();
         self.metrics = metrics;
     }
 
@@ -740,7 +1045,7 @@ impl PaperPortfolio {
             self.daily_performance.remove(0);
         }
 
-        let today = Utc::now().date_naive();
+        let today = self.clock.now().date_naive();
 
         // Check if we already have today's performance
         if let Some(last_perf) = self.daily_performance.last() {
@@ -793,7 +1098,7 @@ impl PaperPortfolio {
             .sum();
 
         let performance = DailyPerformance {
-            date: Utc::now(),
+            date: self.clock.now(),
             balance: self.cash_balance,
             equity: self.equity,
             daily_pnl,
@@ -911,6 +1216,69 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_pair_trade_open_and_close_with_combined_pnl() {
+        let mut portfolio = PaperPortfolio::new(10000.0);
+        let eth = create_test_trade("ETHUSDT", TradeType::Long, 3000.0, 1.0, 10);
+        let btc = create_test_trade("BTCUSDT", TradeType::Short, 50000.0, 0.03, 10);
+        let margin = eth.initial_margin + btc.initial_margin;
+
+        let pair_id = portfolio.add_pair_trade(vec![eth, btc]).unwrap();
+        assert_eq!(portfolio.open_trade_ids.len(), 2);
+        assert!((portfolio.margin_used - margin).abs() < 1e-9);
+        let legs = portfolio.get_pair_legs(&pair_id);
+        assert_eq!(legs.len(), 2);
+        assert!(legs.iter().all(|t| t.pair_id() == Some(pair_id.as_str())));
+
+        // ETH rallies, BTC falls: both legs win
+        let prices = HashMap::from([
+            ("ETHUSDT".to_string(), 3100.0),
+            ("BTCUSDT".to_string(), 49000.0),
+        ]);
+        let combined = portfolio
+            .close_pair_trade(&pair_id, &prices, CloseReason::Manual)
+            .unwrap();
+        assert!(combined > 100.0 + 29.0 - 5.0);
+        assert!(portfolio.open_trade_ids.is_empty());
+        assert_eq!(portfolio.margin_used, 0.0);
+
+        let pairs = portfolio.get_pair_trades();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].status, TradeStatus::Closed);
+        assert_eq!(pairs[0].legs.len(), 2);
+        assert!((pairs[0].combined_pnl - combined).abs() < 1e-9);
+        assert!(pairs[0].close_time.is_some());
+    }
+
+    #[test]
+    fn test_pair_trade_is_all_or_nothing() {
+        let mut portfolio = PaperPortfolio::new(500.0);
+        let eth = create_test_trade("ETHUSDT", TradeType::Long, 3000.0, 1.0, 10);
+        let btc = create_test_trade("BTCUSDT", TradeType::Short, 50000.0, 0.06, 10);
+
+        // Each leg fits on its own (300 margin each) but not both
+        assert!(portfolio.add_pair_trade(vec![eth.clone()]).is_err());
+        assert!(portfolio.add_pair_trade(vec![eth, btc]).is_err());
+        assert!(portfolio.trades.is_empty());
+        assert_eq!(portfolio.margin_used, 0.0);
+
+        let mut portfolio = PaperPortfolio::new(10000.0);
+        let eth = create_test_trade("ETHUSDT", TradeType::Long, 3000.0, 1.0, 10);
+        let btc = create_test_trade("BTCUSDT", TradeType::Short, 50000.0, 0.06, 10);
+        let pair_id = portfolio.add_pair_trade(vec![eth, btc]).unwrap();
+
+        // A missing leg price leaves both legs open
+        let prices = HashMap::from([("ETHUSDT".to_string(), 3100.0)]);
+        assert!(portfolio
+            .close_pair_trade(&pair_id, &prices, CloseReason::Manual)
+            .is_err());
+        assert_eq!(portfolio.open_trade_ids.len(), 2);
+        assert_eq!(portfolio.get_pair_trades()[0].status, TradeStatus::Open);
+        assert!(portfolio
+            .close_pair_trade("missing", &prices, CloseReason::Manual)
+            .is_err());
+    }
+
     #[test]
     fn test_close_trade_profit() {
         let mut portfolio = PaperPortfolio::new(10000.0);
@@ -929,6 +1297,62 @@ mod tests {
         assert_eq!(portfolio.margin_used, 0.0);
     }
 
+    #[test]
+    fn test_partial_close_splits_realized_pnl() {
+        let mut portfolio = PaperPortfolio::new(10000.0);
+        let trade = create_test_trade("BTCUSDT", TradeType::Long, 50000.0, 0.1, 10);
+        let trade_id = trade.id.clone();
+        portfolio.add_trade(trade).unwrap();
+
+        // Half off at 55000: 250 profit - 1.0 entry fee share - 1.1 exit fee
+        let partial_pnl = portfolio
+            .reduce_trade(&trade_id, 0.05, 55000.0, CloseReason::TakeProfit)
+            .unwrap();
+        assert!((partial_pnl - 247.9).abs() < 1e-9);
+        assert!((portfolio.cash_balance - 10247.9).abs() < 1e-9);
+        assert!((portfolio.margin_used - 250.0).abs() < 1e-9);
+        assert_eq!(portfolio.open_trade_ids.len(), 1);
+
+        // Rest at 60000: 500 profit - 1.0 entry fee share - 1.2 exit fee
+        portfolio
+            .close_trade(&trade_id, 60000.0, CloseReason::Manual)
+            .unwrap();
+        let summary = portfolio.get_trade(&trade_id).unwrap().get_summary();
+        assert!((summary.pnl.unwrap() - 745.7).abs() < 1e-9);
+        assert!((summary.partial_realized_pnl - 247.9).abs() < 1e-9);
+        assert_eq!(summary.partial_closes.len(), 1);
+        assert!((summary.trading_fees - 2.0).abs() < 1e-9);
+        assert!((portfolio.cash_balance - 10745.7).abs() < 1e-9);
+        assert!(portfolio.margin_used.abs() < 1e-9);
+
+        assert!(portfolio
+            .reduce_trade(&trade_id, 0.01, 60000.0, CloseReason::Manual)
+            .is_err());
+    }
+
+    #[test]
+    fn test_scale_in_reaverages_entry() {
+        let mut portfolio = PaperPortfolio::new(10000.0);
+        let trade = create_test_trade("BTCUSDT", TradeType::Short, 50000.0, 0.1, 10);
+        let trade_id = trade.id.clone();
+        portfolio.add_trade(trade).unwrap();
+
+        portfolio
+            .scale_in_trade(&trade_id, 0.1, 52000.0, 0.0004)
+            .unwrap();
+        let trade = portfolio.get_trade(&trade_id).unwrap();
+        assert!((trade.entry_price - 51000.0).abs() < 1e-9);
+        assert!((trade.quantity - 0.2).abs() < 1e-12);
+        assert!((trade.trading_fees - (2.0 + 2.08)).abs() < 1e-9);
+        assert!((portfolio.margin_used - 1020.0).abs() < 1e-9);
+        assert_eq!(trade.scale_ins.len(), 1);
+
+        // Margin check covers the addition
+        assert!(portfolio
+            .scale_in_trade(&trade_id, 10.0, 52000.0, 0.0004)
+            .is_err());
+    }
+
     #[test]
     fn test_close_trade_loss() {
         let mut portfolio = PaperPortfolio::new(10000.0);
@@ -1324,17 +1748,70 @@ mod tests {
     fn test_liquidation_risk_closure() {
         let mut portfolio = PaperPortfolio::new(10000.0);
         let trade = create_test_trade("BTCUSDT", TradeType::Long, 50000.0, 0.2, 10);
+        let trade_id = trade.id.clone();
 
         portfolio.add_trade(trade).unwrap();
 
-        // Price drops significantly triggering liquidation risk
+        // 1000 isolated margin less 4 fees, 0.4% maintenance: liquidated near 45200
         let mut prices = HashMap::new();
         prices.insert("BTCUSDT".to_string(), 46000.0);
-        portfolio.update_prices(prices, None);
+        portfolio.update_prices(prices.clone(), None);
+        assert!(portfolio.check_automatic_closures().is_empty());
 
+        prices.insert("BTCUSDT".to_string(), 45100.0);
+        portfolio.update_prices(prices, None);
         let closed_trades = portfolio.check_automatic_closures();
 
-        assert_eq!(closed_trades.len(), 1);
+        assert_eq!(closed_trades, vec![trade_id.clone()]);
+        let trade = portfolio.get_trade(&trade_id).unwrap();
+        assert_eq!(trade.close_reason, Some(CloseReason::Liquidation));
+        // The isolated position loses its margin and nothing more
+        assert!((trade.realized_pnl.unwrap() + 1000.0).abs() < 1.0);
+        assert!((portfolio.cash_balance - 9000.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_cross_margin_shares_collateral_and_liquidates_together() {
+        let mut portfolio = PaperPortfolio::new(10000.0);
+        let mut btc = create_test_trade("BTCUSDT", TradeType::Long, 50000.0, 1.0, 10);
+        btc.margin_mode = MarginMode::Cross;
+        let mut eth = create_test_trade("ETHUSDT", TradeType::Short, 3000.0, 10.0, 10);
+        eth.margin_mode = MarginMode::Cross;
+        let (btc_id, eth_id) = (btc.id.clone(), eth.id.clone());
+        portfolio.add_trade(btc).unwrap();
+        portfolio.add_trade(eth).unwrap();
+
+        // Backed by the whole wallet, BTC survives a drop that would wipe out its
+        // 5000 isolated margin
+        let isolated_liquidation =
+            margin::liquidation_price("BTCUSDT", TradeType::Long, 1.0, 50000.0, 5000.0 - 20.0)
+                .unwrap();
+        let cross_liquidation = portfolio
+            .get_trade(&btc_id)
+            .unwrap()
+            .liquidation_price
+            .unwrap();
+        assert!(cross_liquidation < isolated_liquidation);
+
+        let mut prices = HashMap::new();
+        prices.insert("BTCUSDT".to_string(), isolated_liquidation - 100.0);
+        prices.insert("ETHUSDT".to_string(), 3000.0);
+        portfolio.update_prices(prices.clone(), None);
+        assert!(portfolio.check_automatic_closures().is_empty());
+
+        // Once the shared balance is gone both cross positions are liquidated
+        prices.insert("BTCUSDT".to_string(), cross_liquidation - 100.0);
+        portfolio.update_prices(prices, None);
+        let mut closed = portfolio.check_automatic_closures();
+        closed.sort();
+        let mut expected = vec![btc_id, eth_id];
+        expected.sort();
+        assert_eq!(closed, expected);
+        assert!(portfolio
+            .get_closed_trades()
+            .iter()
+            .all(|t| t.close_reason == Some(CloseReason::Liquidation)));
+        assert!(portfolio.cash_balance >= -1e-6);
     }
 
     #[test]
@@ -0,0 +1,177 @@
+//! Named paper portfolios running side by side
+//!
+//! Each named portfolio is a full `PaperTradingEngine` with its own settings, trades
+//! and storage collections. It is created from the default engine so it shares the
+//! market data feed, which makes it possible to A/B test configurations live. The API
+//! serves them under `/api/paper-trading/{portfolio}/...`.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use super::{PaperTradingEngine, PaperTradingSettings};
+
+/// Name the API uses for the default engine's portfolio
+pub const DEFAULT_PORTFOLIO_NAME: &str = "default";
+
+/// Names a portfolio cannot take because they are top-level paper trading routes
+const RESERVED_PORTFOLIO_NAMES: &[&str] = &[
+    DEFAULT_PORTFOLIO_NAME,
+    "ai-settings",
+    "atr-diagnostics",
+    "backtest",
+    "basic-settings",
+    "config-suggestions",
+    "execution-settings",
+    "indicator-settings",
+    "latest-signals",
+    "monte-carlo",
+    "notification-settings",
+    "orders",
+    "pair-trades",
+    "pending-orders",
+    "portfolio",
+    "portfolios",
+    "reset",
+    "settings",
+    "signal-interval",
+    "signals-history",
+    "start",
+    "status",
+    "stop",
+    "strategies",
+    "strategy-settings",
+    "symbols",
+    "trade-analyses",
+    "trades",
+    "trigger-analysis",
+    "walk-forward",
+];
+
+/// Maximum length of a portfolio name
+const MAX_PORTFOLIO_NAME_LEN: usize = 32;
+
+/// Check `name` can be used as a portfolio name, URL segment and collection prefix
+pub fn validate_portfolio_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_PORTFOLIO_NAME_LEN {
+        return Err(format!(
+            "Portfolio name must be 1-{} characters, got {:?}",
+            MAX_PORTFOLIO_NAME_LEN, name
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    {
+        return Err(format!(
+            "Portfolio name {:?} may only contain lowercase letters, digits, '-' and '_'",
+            name
+        ));
+    }
+    if RESERVED_PORTFOLIO_NAMES.contains(&name) {
+        return Err(format!("Portfolio name {:?} is reserved", name));
+    }
+    Ok(())
+}
+
+/// The default paper trading engine plus any named portfolios created next to it
+#[derive(Clone)]
+pub struct PaperPortfolioRegistry {
+    default_engine: Arc<PaperTradingEngine>,
+    portfolios: Arc<RwLock<HashMap<String, Arc<PaperTradingEngine>>>>,
+}
+
+impl PaperPortfolioRegistry {
+    pub fn new(default_engine: Arc<PaperTradingEngine>) -> Self {
+        Self {
+            default_engine,
+            portfolios: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn default_engine(&self) -> &Arc<PaperTradingEngine> {
+        &self.default_engine
+    }
+
+    /// Engine of the named portfolio; "default" is the default engine
+    pub async fn get(&self, name: &str) -> Option<Arc<PaperTradingEngine>> {
+        if name == DEFAULT_PORTFOLIO_NAME {
+            return Some(self.default_engine.clone());
+        }
+        self.portfolios.read().await.get(name).cloned()
+    }
+
+    /// Names of the named portfolios, sorted
+    pub async fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.portfolios.read().await.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Create a named portfolio, with the default engine's current settings unless
+    /// `settings` are given
+    ///
+    /// The new engine is not started.
+    pub async fn create(
+        &self,
+        name: &str,
+        settings: Option<PaperTradingSettings>,
+    ) -> Result<Arc<PaperTradingEngine>> {
+        validate_portfolio_name(name).map_err(|e| anyhow::anyhow!(e))?;
+        if self.portfolios.read().await.contains_key(name) {
+            return Err(anyhow::anyhow!("Portfolio {} already exists", name));
+        }
+
+        let settings = match settings {
+            Some(settings) => settings,
+            None => self.default_engine.get_settings().await,
+        };
+        let engine = Arc::new(
+            self.default_engine
+                .new_named_portfolio(name, settings)
+                .await?,
+        );
+
+        let mut portfolios = self.portfolios.write().await;
+        if portfolios.contains_key(name) {
+            return Err(anyhow::anyhow!("Portfolio {} already exists", name));
+        }
+        portfolios.insert(name.to_string(), engine.clone());
+        info!("📁 Created paper portfolio '{}'", name);
+        Ok(engine)
+    }
+
+    /// Stop and remove a named portfolio; its stored trades are kept
+    pub async fn remove(&self, name: &str) -> Result<()> {
+        let engine = self
+            .portfolios
+            .write()
+            .await
+            .remove(name)
+            .ok_or_else(|| anyhow::anyhow!("Portfolio {} not found", name))?;
+        if engine.is_running().await {
+            engine.stop().await?;
+        }
+        info!("🗑️ Removed paper portfolio '{}'", name);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_portfolio_name() {
+        assert!(validate_portfolio_name("conservative").is_ok());
+        assert!(validate_portfolio_name("ab-test_2").is_ok());
+        assert!(validate_portfolio_name("").is_err());
+        assert!(validate_portfolio_name("Aggressive").is_err());
+        assert!(validate_portfolio_name("a/b").is_err());
+        assert!(validate_portfolio_name(&"x".repeat(33)).is_err());
+        assert!(validate_portfolio_name("default").is_err());
+        assert!(validate_portfolio_name("trades").is_err());
+    }
+}
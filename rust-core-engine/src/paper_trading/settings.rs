@@ -2,6 +2,12 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::engine::STRATEGY_ALL_TIMEFRAMES;
+use super::margin::MarginMode;
+use crate::strategies::registry::StrategyRegistry;
+use crate::strategies::rule_strategy::{RuleStrategy, RuleStrategyDefinition};
+use crate::strategies::strategy_engine::SignalCombinationMode;
+
 /// Complete paper trading configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct PaperTradingSettings {
@@ -42,6 +48,11 @@ pub struct PaperTradingSettings {
     /// @spec:FR-SETTINGS-003 - Signal pipeline configuration
     #[serde(default)]
     pub signal_pipeline: SignalPipelineSettings,
+
+    /// Declarative rule-based strategies (e.g. "RSI(14) < 30 AND close > EMA(50) ON 1h")
+    /// @spec:FR-STRATEGIES-005 - Strategy Parameters
+    #[serde(default)]
+    pub rule_strategies: Vec<RuleStrategyDefinition>,
 }
 
 /// Technical indicator configuration
@@ -430,6 +441,11 @@ pub struct RiskSettings {
     /// Weekly drawdown limit percentage (pause trading if weekly DD exceeds this)
     #[serde(default = "default_weekly_drawdown_limit")]
     pub weekly_drawdown_limit_pct: f64,
+
+    /// Margin mode for new positions: each backed by its own margin (Isolated) or all
+    /// sharing the wallet balance (Cross)
+    #[serde(default)]
+    pub margin_mode: MarginMode,
 }
 
 /// Strategy configuration
@@ -613,6 +629,29 @@ pub struct ExecutionSettings {
 
     /// Price update frequency (seconds)
     pub price_update_frequency_seconds: u32,
+
+    /// Fee rate for resting limit orders filled as maker; immediate fills pay
+    /// `basic.trading_fee_rate` as taker
+    #[serde(default = "default_maker_fee_rate")]
+    pub maker_fee_rate: f64,
+
+    /// Volume queued ahead of a new resting limit order, as a fraction of the latest
+    /// 1m candle's volume; the order fills at its price once that much has traded there
+    #[serde(default = "default_limit_queue_volume_fraction")]
+    pub limit_queue_volume_fraction: f64,
+
+    /// Seed of the RNG behind slippage, partial fills and trade ids; a fixed seed
+    /// makes a paper session reproducible, None seeds from the OS
+    #[serde(default)]
+    pub random_seed: Option<u64>,
+}
+
+fn default_maker_fee_rate() -> f64 {
+    0.0002 // 0.02% Binance Futures maker
+}
+
+fn default_limit_queue_volume_fraction() -> f64 {
+    0.1
 }
 
 /// Notification settings
@@ -693,18 +732,40 @@ pub enum PositionSizingMethod {
 }
 
 /// Strategy combination methods
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Engine mode names (`Consensus`, `BestConfidence`, `LearnedEnsemble`) and the
+/// dashboard's `MaxConfidence` are accepted as aliases so either vocabulary
+/// configures the same combination layer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum StrategyCombinationMethod {
     /// Weighted average of all strategies
     WeightedAverage,
     /// Only execute if majority agrees
+    #[serde(alias = "Consensus")]
     MajorityVoting,
     /// Only execute if all strategies agree
     Unanimous,
     /// Use the most confident strategy
+    #[serde(alias = "BestConfidence", alias = "MaxConfidence")]
     HighestConfidence,
-    /// Use AI to combine strategies
+    /// Ensemble with per-strategy weights learned from recent hit rates
+    #[serde(alias = "LearnedEnsemble")]
     AIEnsemble,
+    /// Only execute if several high-confidence strategies agree
+    Conservative,
+}
+
+impl From<StrategyCombinationMethod> for SignalCombinationMode {
+    fn from(method: StrategyCombinationMethod) -> Self {
+        match method {
+            StrategyCombinationMethod::WeightedAverage => SignalCombinationMode::WeightedAverage,
+            StrategyCombinationMethod::MajorityVoting => SignalCombinationMode::Consensus,
+            StrategyCombinationMethod::Unanimous => SignalCombinationMode::Unanimous,
+            StrategyCombinationMethod::HighestConfidence => SignalCombinationMode::BestConfidence,
+            StrategyCombinationMethod::AIEnsemble => SignalCombinationMode::LearnedEnsemble,
+            StrategyCombinationMethod::Conservative => SignalCombinationMode::Conservative,
+        }
+    }
 }
 
 /// Notification channels
@@ -789,6 +850,7 @@ impl Default for RiskSettings {
             consecutive_loss_reduction_pct: default_consecutive_loss_reduction(),
             consecutive_loss_reduction_threshold: default_consecutive_loss_threshold(),
             weekly_drawdown_limit_pct: default_weekly_drawdown_limit(),
+            margin_mode: MarginMode::Isolated,
         }
     }
 }
@@ -848,6 +910,9 @@ impl Default for ExecutionSettings {
             simulate_market_impact: false,
             market_impact_factor: 0.001,
             price_update_frequency_seconds: 1,
+            maker_fee_rate: default_maker_fee_rate(),
+            limit_queue_volume_fraction: default_limit_queue_volume_fraction(),
+            random_seed: None,
         }
     }
 }
@@ -908,6 +973,39 @@ impl PaperTradingSettings {
         Ok(())
     }
 
+    /// Rule strategies must compile, have unique names that don't shadow a built-in
+    /// strategy, and run on a timeframe the engine loads candles for
+    /// @spec:FR-STRATEGIES-005 - Strategy Parameters
+    pub fn validate_rule_strategies(&self) -> Result<()> {
+        let builtin = StrategyRegistry::with_builtin_strategies();
+        let mut rule_names = std::collections::HashSet::new();
+        for definition in &self.rule_strategies {
+            let strategy = RuleStrategy::compile(definition)?;
+            let name = definition.name.trim();
+            if builtin.contains(name) {
+                return Err(anyhow::anyhow!(
+                    "Rule strategy name '{}' is reserved by a built-in strategy",
+                    name
+                ));
+            }
+            if !rule_names.insert(name) {
+                return Err(anyhow::anyhow!(
+                    "Duplicate rule strategy name '{}'",
+                    definition.name
+                ));
+            }
+            if !STRATEGY_ALL_TIMEFRAMES.contains(&strategy.timeframe()) {
+                return Err(anyhow::anyhow!(
+                    "Rule strategy '{}' runs on {}, but only {:?} candles are loaded",
+                    name,
+                    strategy.timeframe(),
+                    STRATEGY_ALL_TIMEFRAMES
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Validate settings
     pub fn validate(&self) -> Result<()> {
         // Validate basic settings
@@ -925,6 +1023,16 @@ impl PaperTradingSettings {
             return Err(anyhow::anyhow!("Trading fee rate must be between 0 and 1%"));
         }
 
+        if !(0.0..=0.01).contains(&self.execution.maker_fee_rate) {
+            return Err(anyhow::anyhow!("Maker fee rate must be between 0 and 1%"));
+        }
+
+        if !(0.0..=1.0).contains(&self.execution.limit_queue_volume_fraction) {
+            return Err(anyhow::anyhow!(
+                "Limit queue volume fraction must be between 0 and 1"
+            ));
+        }
+
         // Validate risk settings
         if self.risk.max_risk_per_trade_pct <= 0.0 || self.risk.max_risk_per_trade_pct > 50.0 {
             return Err(anyhow::anyhow!(
@@ -951,6 +1059,8 @@ impl PaperTradingSettings {
             return Err(anyhow::anyhow!("AI confidence must be between 0 and 1"));
         }
 
+        self.validate_rule_strategies()?;
+
         // Validate AI settings
         if self.ai.request_timeout_seconds == 0 {
             return Err(anyhow::anyhow!("Request timeout must be positive"));
@@ -1216,6 +1326,45 @@ mod tests {
         assert_eq!(parsed.risk.max_leverage, defaults.risk.max_leverage);
     }
 
+    #[test]
+    fn test_validate_rule_strategies() {
+        let mut settings = PaperTradingSettings {
+            rule_strategies: serde_yaml::from_str(
+                r#"
+- name: "RSI Dip Buyer"
+  long_when: "RSI(14) < 30 AND close > EMA(50) ON 1h"
+- name: "Band Fade"
+  short_when: "close > BB_UPPER(20, 2)"
+"#,
+            )
+            .unwrap(),
+            ..PaperTradingSettings::default()
+        };
+        assert!(settings.validate().is_ok());
+
+        settings.rule_strategies[1].name = "RSI Dip Buyer".to_string();
+        let err = settings.validate().unwrap_err().to_string();
+        assert!(err.contains("Duplicate rule strategy"));
+
+        settings.rule_strategies[1].name = "RSI Strategy".to_string();
+        let err = settings.validate().unwrap_err().to_string();
+        assert!(err.contains("reserved by a built-in"));
+
+        settings.rule_strategies[1].name = "Band Fade".to_string();
+        settings.rule_strategies[1].timeframe = "4h".to_string();
+        let err = settings.validate().unwrap_err().to_string();
+        assert!(err.contains("runs on 4h"));
+
+        settings.rule_strategies[1].timeframe = "15m".to_string();
+        settings.rule_strategies[1].short_when = Some("close > BB_UPPER(20, 2) ON 1d".to_string());
+        assert!(settings.validate().is_err());
+
+        settings.rule_strategies[1].short_when = Some("close > BB_UPPER(".to_string());
+        let err = settings.validate().unwrap_err().to_string();
+        assert!(err.contains("Invalid configuration"));
+        assert!(err.contains("Band Fade"));
+    }
+
     #[test]
     fn test_from_yaml_missing_file_fails() {
         let result = PaperTradingSettings::from_yaml("/nonexistent/path.yml");
@@ -2378,6 +2527,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_strategy_combination_method_maps_to_engine_mode() {
+        let cases = [
+            (
+                "\"WeightedAverage\"",
+                SignalCombinationMode::WeightedAverage,
+            ),
+            ("\"MajorityVoting\"", SignalCombinationMode::Consensus),
+            ("\"Consensus\"", SignalCombinationMode::Consensus),
+            ("\"Unanimous\"", SignalCombinationMode::Unanimous),
+            (
+                "\"HighestConfidence\"",
+                SignalCombinationMode::BestConfidence,
+            ),
+            ("\"BestConfidence\"", SignalCombinationMode::BestConfidence),
+            ("\"MaxConfidence\"", SignalCombinationMode::BestConfidence),
+            ("\"AIEnsemble\"", SignalCombinationMode::LearnedEnsemble),
+            (
+                "\"LearnedEnsemble\"",
+                SignalCombinationMode::LearnedEnsemble,
+            ),
+            ("\"Conservative\"", SignalCombinationMode::Conservative),
+        ];
+
+        for (json, expected) in cases {
+            let method: StrategyCombinationMethod = serde_json::from_str(json).unwrap();
+            assert_eq!(SignalCombinationMode::from(method), expected, "{json}");
+        }
+        assert!(serde_json::from_str::<StrategyCombinationMethod>("\"Random\"").is_err());
+    }
+
     #[test]
     fn test_strategy_combination_method_serialization() {
         let methods = vec![
@@ -1,10 +1,19 @@
 use anyhow::Result;
 use chrono::{DateTime, Duration, Utc};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use tracing::{debug, info};
 
 // Removed unused imports
+use super::backtester::{BacktestConfig, Backtester};
 use super::portfolio::PortfolioMetrics;
+use super::settings::BacktestingSettings;
+use crate::market_data::cache::CandleData;
+use crate::strategies::registry::StrategyRegistry;
+use crate::strategies::strategy_engine::{StrategyEngine, StrategyEngineConfig};
+use crate::strategies::StrategyConfig;
 // Removed unused imports
 
 /// Strategy optimization engine
@@ -212,6 +221,86 @@ pub struct OptimizationRiskAssessment {
     pub recommendation: String,
 }
 
+/// Search space for a single strategy parameter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ParameterRange {
+    /// Explicit list of candidate values
+    Values(Vec<serde_json::Value>),
+    /// Integer range (inclusive)
+    Int { min: i64, max: i64, step: i64 },
+    /// Float range (inclusive)
+    Float { min: f64, max: f64, step: f64 },
+}
+
+/// Parameter search space keyed by `StrategyConfig.parameters` name
+/// (ordered so grid enumeration is deterministic)
+pub type ParameterSpace = BTreeMap<String, ParameterRange>;
+
+/// How candidate parameter sets are generated from a `ParameterSpace`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ParameterSearchMethod {
+    /// Exhaustive cartesian product, sampled evenly when larger than `max_evaluations`
+    Grid,
+    /// Uniform random sampling with a fixed seed for reproducibility
+    Random { samples: usize, seed: u64 },
+}
+
+/// Walk-forward / out-of-sample optimization configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardConfig {
+    /// Rolling windows when true, a single in-sample/out-of-sample split otherwise
+    pub walk_forward: bool,
+
+    /// Number of rolling windows (ignored for a single split)
+    pub windows: usize,
+
+    /// Out-of-sample percentage of each window (0-100)
+    pub out_of_sample_pct: f64,
+
+    /// Candidate generation method
+    pub search_method: ParameterSearchMethod,
+
+    /// Upper bound on parameter sets evaluated per window
+    pub max_evaluations: usize,
+
+    /// Metric used to rank parameter sets in-sample
+    pub metric: OptimizationMetric,
+}
+
+/// Result of one in-sample/out-of-sample window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardWindow {
+    pub index: usize,
+    pub in_sample_start: Option<DateTime<Utc>>,
+    pub in_sample_end: Option<DateTime<Utc>>,
+    pub out_of_sample_start: Option<DateTime<Utc>>,
+    pub out_of_sample_end: Option<DateTime<Utc>>,
+    pub candidates_evaluated: usize,
+    pub best_parameters: HashMap<String, serde_json::Value>,
+    pub in_sample_score: f64,
+    pub out_of_sample_score: f64,
+    pub in_sample_results: BacktestingResults,
+    pub out_of_sample_results: BacktestingResults,
+}
+
+/// Aggregated walk-forward report for one strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalkForwardReport {
+    pub strategy_name: String,
+    pub metric: OptimizationMetric,
+    pub windows: Vec<WalkForwardWindow>,
+    pub average_in_sample_score: f64,
+    pub average_out_of_sample_score: f64,
+    /// Out-of-sample score relative to in-sample score (1.0 = no degradation)
+    pub walk_forward_efficiency: f64,
+    /// Per-parameter stability across windows (1.0 = same value chosen every window)
+    pub parameter_stability: HashMap<String, f64>,
+    /// Parameters selected most often across windows
+    pub recommended_parameters: HashMap<String, serde_json::Value>,
+    /// Overfitting risk derived from efficiency and stability (0.0 - 1.0)
+    pub overfitting_risk: f64,
+}
+
 impl StrategyOptimizer {
     /// Create a new strategy optimizer
     pub fn new(config: OptimizationConfig) -> Self {
@@ -767,6 +856,567 @@ impl StrategyOptimizer {
     }
 }
 
+impl StrategyOptimizer {
+    /// Run in-sample parameter search and out-of-sample validation over historical candles.
+    ///
+    /// With `walk_forward` enabled the candles are split into rolling windows whose
+    /// out-of-sample segments are contiguous; otherwise a single split is used.
+    /// Only `strategy_name` is enabled during the search so its parameters are isolated;
+    /// it is looked up in `registry`, so rule strategies can be optimized too.
+    pub async fn run_walk_forward(
+        &self,
+        registry: &StrategyRegistry,
+        strategy_name: &str,
+        space: &ParameterSpace,
+        config: &WalkForwardConfig,
+        backtest_config: &BacktestConfig,
+        timeframe_data: &HashMap<String, Vec<CandleData>>,
+    ) -> Result<WalkForwardReport> {
+        let base_config = registry
+            .create(strategy_name)
+            .map_err(|_| anyhow::anyhow!("Strategy '{strategy_name}' not found"))?
+            .config()
+            .clone();
+
+        let mut primary: Vec<CandleData> = timeframe_data
+            .get(&backtest_config.primary_timeframe)
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Missing primary timeframe {} data",
+                    backtest_config.primary_timeframe
+                )
+            })?;
+        primary.sort_by_key(|c| c.open_time);
+
+        let ranges = walk_forward_ranges(
+            primary.len(),
+            if config.walk_forward {
+                config.windows
+            } else {
+                1
+            },
+            config.out_of_sample_pct,
+        );
+        if ranges.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Not enough candles ({}) for walk-forward optimization",
+                primary.len()
+            ));
+        }
+
+        let candidates = generate_candidates(space, config);
+        let warmup = backtest_config.warmup_candles;
+        let mut windows = Vec::with_capacity(ranges.len());
+
+        for (index, (is_range, oos_range)) in ranges.into_iter().enumerate() {
+            // In-sample: warm up inside the window itself
+            let is_data = slice_timeframes(timeframe_data, &primary, is_range.clone());
+            let is_config = backtest_config.clone();
+
+            let mut best: Option<(f64, HashMap<String, serde_json::Value>, BacktestingResults)> =
+                None;
+            for candidate in &candidates {
+                let mut params = base_config.parameters.clone();
+                params.extend(candidate.clone());
+                let results = run_candidate(
+                    registry,
+                    strategy_name,
+                    &base_config,
+                    &params,
+                    &is_config,
+                    &is_data,
+                )
+                .await;
+                let Some(results) = results else { continue };
+                let score = score_results(&results, &config.metric);
+                if best
+                    .as_ref()
+                    .is_none_or(|(best_score, _, _)| score > *best_score)
+                {
+                    best = Some((score, params, results));
+                }
+            }
+
+            let Some((in_sample_score, best_parameters, in_sample_results)) = best else {
+                debug!("Walk-forward window {} produced no valid backtests", index);
+                continue;
+            };
+
+            // Out-of-sample: prepend the tail of the in-sample data as warmup only
+            let oos_start = oos_range.start.saturating_sub(warmup);
+            let oos_data = slice_timeframes(timeframe_data, &primary, oos_start..oos_range.end);
+            let mut oos_config = backtest_config.clone();
+            oos_config.warmup_candles = oos_range.start - oos_start;
+            let out_of_sample_results = run_candidate(
+                registry,
+                strategy_name,
+                &base_config,
+                &best_parameters,
+                &oos_config,
+                &oos_data,
+            )
+            .await
+            .unwrap_or_else(empty_backtesting_results);
+            let out_of_sample_score = score_results(&out_of_sample_results, &config.metric);
+
+            info!(
+                "📈 Walk-forward {} window {}: IS score {:.3}, OOS score {:.3}",
+                strategy_name, index, in_sample_score, out_of_sample_score
+            );
+
+            windows.push(WalkForwardWindow {
+                index,
+                in_sample_start: candle_time(&primary, is_range.start, false),
+                in_sample_end: candle_time(&primary, is_range.end - 1, true),
+                out_of_sample_start: candle_time(&primary, oos_range.start, false),
+                out_of_sample_end: candle_time(&primary, oos_range.end - 1, true),
+                candidates_evaluated: candidates.len(),
+                best_parameters,
+                in_sample_score,
+                out_of_sample_score,
+                in_sample_results,
+                out_of_sample_results,
+            });
+        }
+
+        Ok(summarize_walk_forward(
+            strategy_name,
+            config.metric.clone(),
+            windows,
+        ))
+    }
+
+    /// Store the recommended parameters of a walk-forward report as the best known set
+    pub fn record_walk_forward(&mut self, report: &WalkForwardReport) {
+        if !report.recommended_parameters.is_empty() {
+            self.best_parameters.insert(
+                report.strategy_name.clone(),
+                report.recommended_parameters.clone(),
+            );
+        }
+    }
+
+    /// Best parameters found so far for a strategy
+    pub fn get_best_parameters(
+        &self,
+        strategy_name: &str,
+    ) -> Option<&HashMap<String, serde_json::Value>> {
+        self.best_parameters.get(strategy_name)
+    }
+}
+
+impl ParameterRange {
+    /// Expand the range into concrete candidate values
+    pub fn candidates(&self) -> Vec<serde_json::Value> {
+        match self {
+            ParameterRange::Values(values) => values.clone(),
+            ParameterRange::Int { min, max, step } => {
+                let step = (*step).max(1);
+                (*min..=*max)
+                    .step_by(step as usize)
+                    .map(serde_json::Value::from)
+                    .collect()
+            },
+            ParameterRange::Float { min, max, step } => {
+                if *step <= 0.0 || max < min {
+                    return vec![serde_json::Value::from(*min)];
+                }
+                let count = ((max - min) / step + 1e-9).floor() as usize + 1;
+                (0..count)
+                    .map(|i| serde_json::Value::from(min + step * i as f64))
+                    .collect()
+            },
+        }
+    }
+
+    /// Build a range of `steps` values spread ±`spread_pct`% around a numeric parameter.
+    /// Integer parameters stay integers; non-numeric values return `None`.
+    pub fn around(value: &serde_json::Value, spread_pct: f64, steps: usize) -> Option<Self> {
+        let steps = steps.max(2);
+        if let Some(int_value) = value.as_i64().filter(|_| value.is_i64() || value.is_u64()) {
+            let spread = ((int_value as f64) * spread_pct / 100.0).round().max(1.0) as i64;
+            let min = (int_value - spread).max(1);
+            let max = int_value + spread;
+            let step = ((max - min) / (steps as i64 - 1)).max(1);
+            return Some(ParameterRange::Int { min, max, step });
+        }
+        let float_value = value.as_f64()?;
+        let spread = float_value.abs() * spread_pct / 100.0;
+        if spread <= 0.0 {
+            return Some(ParameterRange::Values(vec![value.clone()]));
+        }
+        Some(ParameterRange::Float {
+            min: float_value - spread,
+            max: float_value + spread,
+            step: (2.0 * spread) / (steps - 1) as f64,
+        })
+    }
+}
+
+impl WalkForwardConfig {
+    /// Build from the paper trading `BacktestingSettings`
+    pub fn from_backtesting_settings(settings: &BacktestingSettings) -> Self {
+        Self {
+            walk_forward: settings.walk_forward_optimization,
+            out_of_sample_pct: settings.out_of_sample_pct,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for WalkForwardConfig {
+    fn default() -> Self {
+        Self {
+            walk_forward: true,
+            windows: 4,
+            out_of_sample_pct: 20.0,
+            search_method: ParameterSearchMethod::Grid,
+            max_evaluations: 50,
+            metric: OptimizationMetric::SharpeRatio,
+        }
+    }
+}
+
+/// Default search space: every numeric parameter of the strategy ±20% in 3 steps
+pub fn parameter_space_from_config(config: &StrategyConfig) -> ParameterSpace {
+    config
+        .parameters
+        .iter()
+        .filter_map(|(name, value)| {
+            ParameterRange::around(value, 20.0, 3).map(|range| (name.clone(), range))
+        })
+        .collect()
+}
+
+/// In-sample / out-of-sample index ranges for `windows` rolling windows over `len` candles.
+/// Out-of-sample segments are contiguous and end at the last candle.
+fn walk_forward_ranges(
+    len: usize,
+    windows: usize,
+    out_of_sample_pct: f64,
+) -> Vec<(Range<usize>, Range<usize>)> {
+    let windows = windows.max(1);
+    let oos_fraction = (out_of_sample_pct / 100.0).clamp(0.01, 0.99);
+    // len = in_sample + windows * out_of_sample, with oos / (is + oos) = oos_fraction
+    let oos_len = (len as f64 / ((1.0 - oos_fraction) / oos_fraction + windows as f64)) as usize;
+    if oos_len == 0 {
+        return Vec::new();
+    }
+    let is_len = len - windows * oos_len;
+    if is_len == 0 {
+        return Vec::new();
+    }
+
+    (0..windows)
+        .map(|i| {
+            let is_start = i * oos_len;
+            let oos_start = is_start + is_len;
+            (is_start..oos_start, oos_start..oos_start + oos_len)
+        })
+        .collect()
+}
+
+/// Enumerate candidate parameter overrides for the configured search method
+fn generate_candidates(
+    space: &ParameterSpace,
+    config: &WalkForwardConfig,
+) -> Vec<HashMap<String, serde_json::Value>> {
+    let axes: Vec<(&String, Vec<serde_json::Value>)> = space
+        .iter()
+        .map(|(name, range)| (name, range.candidates()))
+        .filter(|(_, values)| !values.is_empty())
+        .collect();
+
+    if axes.is_empty() {
+        return vec![HashMap::new()];
+    }
+
+    let max_evaluations = config.max_evaluations.max(1);
+    match &config.search_method {
+        ParameterSearchMethod::Grid => {
+            let grid_size = axes
+                .iter()
+                .try_fold(1usize, |size, (_, values)| size.checked_mul(values.len()));
+            if grid_size.is_some_and(|size| size <= max_evaluations) {
+                let mut combos: Vec<HashMap<String, serde_json::Value>> = vec![HashMap::new()];
+                for (name, values) in &axes {
+                    let mut next = Vec::with_capacity(combos.len() * values.len());
+                    for combo in &combos {
+                        for value in values {
+                            let mut extended = combo.clone();
+                            extended.insert((*name).clone(), value.clone());
+                            next.push(extended);
+                        }
+                    }
+                    combos = next;
+                }
+                return combos;
+            }
+
+            // Grid too large: spread `max_evaluations` points over it so every value of
+            // every axis is tried about equally often. Truncating the cartesian product
+            // instead would pin the slowest-varying parameters to their first values.
+            let strides = coprime_strides(axes.len(), max_evaluations);
+            let mut seen = std::collections::HashSet::new();
+            (0..max_evaluations)
+                .filter_map(|k| {
+                    let indices: Vec<usize> = axes
+                        .iter()
+                        .zip(&strides)
+                        .map(|((_, values), stride)| {
+                            let slot = (k * stride) % max_evaluations;
+                            slot * values.len() / max_evaluations
+                        })
+                        .collect();
+                    if !seen.insert(indices.clone()) {
+                        return None;
+                    }
+                    Some(
+                        axes.iter()
+                            .zip(indices)
+                            .map(|((name, values), index)| ((*name).clone(), values[index].clone()))
+                            .collect(),
+                    )
+                })
+                .collect()
+        },
+        ParameterSearchMethod::Random { samples, seed } => {
+            let mut rng = StdRng::seed_from_u64(*seed);
+            (0..(*samples).min(max_evaluations))
+                .map(|_| {
+                    axes.iter()
+                        .map(|(name, values)| {
+                            (
+                                (*name).clone(),
+                                values[rng.random_range(0..values.len())].clone(),
+                            )
+                        })
+                        .collect()
+                })
+                .collect()
+        },
+    }
+}
+
+/// One multiplier per axis, each coprime with `modulus` so `k * stride % modulus`
+/// visits every slot once; distinct multipliers decorrelate the axes
+fn coprime_strides(axes: usize, modulus: usize) -> Vec<usize> {
+    fn gcd(a: usize, b: usize) -> usize {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    let mut strides = vec![1];
+    let mut candidate = 2;
+    while strides.len() < axes {
+        if gcd(candidate, modulus) == 1 {
+            strides.push(candidate);
+        }
+        candidate += 1;
+    }
+    strides.truncate(axes);
+    strides
+}
+
+/// Backtest one parameter set with only `strategy_name` enabled
+async fn run_candidate(
+    registry: &StrategyRegistry,
+    strategy_name: &str,
+    base_config: &StrategyConfig,
+    parameters: &HashMap<String, serde_json::Value>,
+    backtest_config: &BacktestConfig,
+    timeframe_data: &HashMap<String, Vec<CandleData>>,
+) -> Option<BacktestingResults> {
+    let mut engine = StrategyEngine::with_registry(
+        registry,
+        StrategyEngineConfig {
+            enabled_strategies: vec![strategy_name.to_string()],
+            min_strategies_agreement: 1,
+            ..StrategyEngineConfig::default()
+        },
+    );
+    let strategy_config = StrategyConfig {
+        parameters: parameters.clone(),
+        ..base_config.clone()
+    };
+    engine
+        .update_strategy_config(strategy_name, strategy_config)
+        .ok()?;
+
+    let backtester = Backtester::with_strategy_engine(backtest_config.clone(), engine);
+    match backtester.run("WALK_FORWARD", timeframe_data).await {
+        Ok(report) => Some(report.to_backtesting_results()),
+        Err(e) => {
+            debug!("Walk-forward candidate skipped: {}", e);
+            None
+        },
+    }
+}
+
+/// Restrict every timeframe to candles closed before the end of `range` on the primary series,
+/// and the primary series to `range` itself
+fn slice_timeframes(
+    timeframe_data: &HashMap<String, Vec<CandleData>>,
+    primary: &[CandleData],
+    range: Range<usize>,
+) -> HashMap<String, Vec<CandleData>> {
+    let start_time = primary[range.start].open_time;
+    let end_time = primary[range.end - 1].close_time;
+    timeframe_data
+        .iter()
+        .map(|(timeframe, candles)| {
+            let visible: Vec<CandleData> = candles
+                .iter()
+                .filter(|c| c.open_time >= start_time && c.close_time <= end_time)
+                .cloned()
+                .collect();
+            (timeframe.clone(), visible)
+        })
+        .collect()
+}
+
+/// Score backtest results for ranking (higher is better)
+fn score_results(results: &BacktestingResults, metric: &OptimizationMetric) -> f64 {
+    match metric {
+        OptimizationMetric::TotalReturn => results.total_return,
+        OptimizationMetric::SharpeRatio | OptimizationMetric::SortinoRatio => results.sharpe_ratio,
+        OptimizationMetric::CalmarRatio => {
+            if results.max_drawdown > 0.0 {
+                results.total_return / results.max_drawdown
+            } else {
+                results.total_return
+            }
+        },
+        OptimizationMetric::MaxDrawdown => -results.max_drawdown,
+        OptimizationMetric::WinRate => results.win_rate,
+        OptimizationMetric::ProfitFactor => results.profit_factor,
+        OptimizationMetric::RiskAdjustedReturn => {
+            results.total_return / (1.0 + results.max_drawdown)
+        },
+        OptimizationMetric::Consistency => {
+            let n = results.monthly_returns.len();
+            if n < 2 {
+                return 0.0;
+            }
+            let mean = results.monthly_returns.iter().sum::<f64>() / n as f64;
+            let variance = results
+                .monthly_returns
+                .iter()
+                .map(|r| (r - mean).powi(2))
+                .sum::<f64>()
+                / (n - 1) as f64;
+            -variance.sqrt()
+        },
+    }
+}
+
+fn summarize_walk_forward(
+    strategy_name: &str,
+    metric: OptimizationMetric,
+    windows: Vec<WalkForwardWindow>,
+) -> WalkForwardReport {
+    let count = windows.len().max(1) as f64;
+    let average_in_sample_score = windows.iter().map(|w| w.in_sample_score).sum::<f64>() / count;
+    let average_out_of_sample_score =
+        windows.iter().map(|w| w.out_of_sample_score).sum::<f64>() / count;
+    let walk_forward_efficiency = if average_in_sample_score > 0.0 {
+        average_out_of_sample_score / average_in_sample_score
+    } else {
+        0.0
+    };
+
+    // Stability: 1 - coefficient of variation of the chosen numeric values
+    let mut parameter_stability = HashMap::new();
+    let mut recommended_parameters = HashMap::new();
+    let names: std::collections::BTreeSet<&String> = windows
+        .iter()
+        .flat_map(|w| w.best_parameters.keys())
+        .collect();
+    for name in names {
+        let chosen: Vec<&serde_json::Value> = windows
+            .iter()
+            .filter_map(|w| w.best_parameters.get(name))
+            .collect();
+
+        let numeric: Vec<f64> = chosen.iter().filter_map(|v| v.as_f64()).collect();
+        let stability = if numeric.len() == chosen.len() && !numeric.is_empty() {
+            let mean = numeric.iter().sum::<f64>() / numeric.len() as f64;
+            let std = (numeric.iter().map(|v| (v - mean).powi(2)).sum::<f64>()
+                / numeric.len() as f64)
+                .sqrt();
+            if mean.abs() > f64::EPSILON {
+                (1.0 - std / mean.abs()).clamp(0.0, 1.0)
+            } else if std > 0.0 {
+                0.0
+            } else {
+                1.0
+            }
+        } else {
+            let distinct: std::collections::HashSet<String> =
+                chosen.iter().map(|v| v.to_string()).collect();
+            1.0 / distinct.len().max(1) as f64
+        };
+        parameter_stability.insert(name.clone(), stability);
+
+        // Most frequently selected value; ties go to the most recent window
+        let mut counts: Vec<(&serde_json::Value, usize)> = Vec::new();
+        for value in &chosen {
+            match counts.iter_mut().find(|(v, _)| v == value) {
+                Some((_, c)) => *c += 1,
+                None => counts.push((value, 1)),
+            }
+        }
+        if let Some((value, _)) = counts.iter().rev().max_by_key(|(_, c)| *c) {
+            recommended_parameters.insert(name.clone(), (*value).clone());
+        }
+    }
+
+    let average_stability = if parameter_stability.is_empty() {
+        1.0
+    } else {
+        parameter_stability.values().sum::<f64>() / parameter_stability.len() as f64
+    };
+    let overfitting_risk = (1.0
+        - (walk_forward_efficiency.clamp(0.0, 1.0) * 0.6 + average_stability * 0.4))
+        .clamp(0.0, 1.0);
+
+    WalkForwardReport {
+        strategy_name: strategy_name.to_string(),
+        metric,
+        windows,
+        average_in_sample_score,
+        average_out_of_sample_score,
+        walk_forward_efficiency,
+        parameter_stability,
+        recommended_parameters,
+        overfitting_risk,
+    }
+}
+
+fn candle_time(candles: &[CandleData], index: usize, close: bool) -> Option<DateTime<Utc>> {
+    candles.get(index).and_then(|c| {
+        let ts = if close { c.close_time } else { c.open_time };
+        DateTime::from_timestamp_millis(ts)
+    })
+}
+
+fn empty_backtesting_results() -> BacktestingResults {
+    BacktestingResults {
+        total_trades: 0,
+        win_rate: 0.0,
+        total_return: 0.0,
+        sharpe_ratio: 0.0,
+        max_drawdown: 0.0,
+        profit_factor: 0.0,
+        monthly_returns: Vec::new(),
+        trade_distribution: HashMap::new(),
+    }
+}
+
 /// Strategy analysis for optimization
 #[derive(Debug, Clone)]
 pub struct StrategyAnalysis {
@@ -876,6 +1526,7 @@ impl Default for OptimizationConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::strategies::rule_strategy::RuleStrategyDefinition;
 
     #[test]
     fn test_optimizer_creation() {
@@ -2765,4 +3416,423 @@ mod tests {
             optimizer.generate_parameter_variations("MACD Strategy", &base_params, &analysis);
         assert!(result.is_ok());
     }
+
+    fn make_walk_forward_series(count: usize) -> HashMap<String, Vec<CandleData>> {
+        const FIVE_MIN_MS: i64 = 300_000;
+        let price_at = |i: usize| 100.0 + 5.0 * ((i as f64) / 12.0).sin() + i as f64 * 0.01;
+        let candle = |index: usize, interval_ms: i64, open: f64, close: f64| {
+            let open_time = 1_700_000_000_000 + index as i64 * interval_ms;
+            CandleData {
+                open_time,
+                close_time: open_time + interval_ms - 1,
+                open,
+                high: open.max(close) * 1.001,
+                low: open.min(close) * 0.999,
+                close,
+                volume: 1000.0,
+                quote_volume: 1000.0 * close,
+                trades: 100,
+                is_closed: true,
+            }
+        };
+
+        let candles_5m: Vec<CandleData> = (0..count)
+            .map(|i| candle(i, FIVE_MIN_MS, price_at(i.saturating_sub(1)), price_at(i)))
+            .collect();
+        let candles_15m: Vec<CandleData> = candles_5m
+            .chunks_exact(3)
+            .enumerate()
+            .map(|(i, chunk)| candle(i, FIVE_MIN_MS * 3, chunk[0].open, chunk[2].close))
+            .collect();
+
+        let mut data = HashMap::new();
+        data.insert("5m".to_string(), candles_5m);
+        data.insert("15m".to_string(), candles_15m);
+        data
+    }
+
+    fn make_window(index: usize, period: i64, is_score: f64, oos_score: f64) -> WalkForwardWindow {
+        let mut best_parameters = HashMap::new();
+        best_parameters.insert("rsi_period".to_string(), serde_json::json!(period));
+        WalkForwardWindow {
+            index,
+            in_sample_start: None,
+            in_sample_end: None,
+            out_of_sample_start: None,
+            out_of_sample_end: None,
+            candidates_evaluated: 3,
+            best_parameters,
+            in_sample_score: is_score,
+            out_of_sample_score: oos_score,
+            in_sample_results: empty_backtesting_results(),
+            out_of_sample_results: empty_backtesting_results(),
+        }
+    }
+
+    #[test]
+    fn test_parameter_range_candidates() {
+        let int_range = ParameterRange::Int {
+            min: 10,
+            max: 20,
+            step: 5,
+        };
+        assert_eq!(
+            int_range.candidates(),
+            vec![
+                serde_json::json!(10),
+                serde_json::json!(15),
+                serde_json::json!(20)
+            ]
+        );
+
+        let float_range = ParameterRange::Float {
+            min: 1.0,
+            max: 2.0,
+            step: 0.5,
+        };
+        let values: Vec<f64> = float_range
+            .candidates()
+            .iter()
+            .filter_map(|v| v.as_f64())
+            .collect();
+        assert_eq!(values, vec![1.0, 1.5, 2.0]);
+
+        let list = ParameterRange::Values(vec![serde_json::json!("a"), serde_json::json!("b")]);
+        assert_eq!(list.candidates().len(), 2);
+    }
+
+    #[test]
+    fn test_parameter_range_around_keeps_integers() {
+        let range = ParameterRange::around(&serde_json::json!(14), 20.0, 3).unwrap();
+        let values = range.candidates();
+        assert!(values.iter().all(|v| v.is_i64()));
+        assert!(values.contains(&serde_json::json!(14)));
+
+        let range = ParameterRange::around(&serde_json::json!(2.0), 50.0, 3).unwrap();
+        let values: Vec<f64> = range
+            .candidates()
+            .iter()
+            .filter_map(|v| v.as_f64())
+            .collect();
+        assert_eq!(values.len(), 3);
+        assert!((values[0] - 1.0).abs() < 1e-9);
+        assert!((values[2] - 3.0).abs() < 1e-9);
+
+        assert!(ParameterRange::around(&serde_json::json!("text"), 20.0, 3).is_none());
+    }
+
+    #[test]
+    fn test_parameter_space_from_config_skips_non_numeric() {
+        let mut config = StrategyConfig::default();
+        config
+            .parameters
+            .insert("rsi_period".to_string(), serde_json::json!(14));
+        config
+            .parameters
+            .insert("mode".to_string(), serde_json::json!("fast"));
+
+        let space = parameter_space_from_config(&config);
+        assert!(space.contains_key("rsi_period"));
+        assert!(!space.contains_key("mode"));
+    }
+
+    #[test]
+    fn test_walk_forward_ranges_single_split() {
+        let ranges = walk_forward_ranges(100, 1, 20.0);
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].0, 0..80);
+        assert_eq!(ranges[0].1, 80..100);
+    }
+
+    #[test]
+    fn test_walk_forward_ranges_rolling_windows_are_contiguous() {
+        let ranges = walk_forward_ranges(1000, 4, 25.0);
+        assert_eq!(ranges.len(), 4);
+        for pair in ranges.windows(2) {
+            assert_eq!(pair[0].1.end, pair[1].1.start);
+            assert_eq!(pair[0].0.len(), pair[1].0.len());
+        }
+        for (is_range, oos_range) in &ranges {
+            assert_eq!(is_range.end, oos_range.start);
+        }
+        assert!(ranges.last().unwrap().1.end <= 1000);
+        assert!(walk_forward_ranges(2, 4, 20.0).is_empty());
+    }
+
+    #[test]
+    fn test_generate_candidates_grid_and_limit() {
+        let mut space = ParameterSpace::new();
+        space.insert(
+            "a".to_string(),
+            ParameterRange::Int {
+                min: 1,
+                max: 3,
+                step: 1,
+            },
+        );
+        space.insert(
+            "b".to_string(),
+            ParameterRange::Values(vec![serde_json::json!(true), serde_json::json!(false)]),
+        );
+
+        let config = WalkForwardConfig::default();
+        assert_eq!(generate_candidates(&space, &config).len(), 6);
+
+        let limited = WalkForwardConfig {
+            max_evaluations: 4,
+            ..WalkForwardConfig::default()
+        };
+        assert_eq!(generate_candidates(&space, &limited).len(), 4);
+
+        assert_eq!(
+            generate_candidates(&ParameterSpace::new(), &config),
+            vec![HashMap::new()]
+        );
+    }
+
+    #[test]
+    fn test_generate_candidates_large_grid_covers_every_value() {
+        // Five parameters x 3 values = 243 combinations, capped at 50
+        let space: ParameterSpace = ["a", "b", "c", "d", "e"]
+            .into_iter()
+            .map(|name| {
+                let range = ParameterRange::Int {
+                    min: 0,
+                    max: 2,
+                    step: 1,
+                };
+                (name.to_string(), range)
+            })
+            .collect();
+        let config = WalkForwardConfig {
+            max_evaluations: 50,
+            ..WalkForwardConfig::default()
+        };
+
+        let candidates = generate_candidates(&space, &config);
+        assert!(candidates.len() > 40 && candidates.len() <= 50);
+        for (name, range) in &space {
+            for value in range.candidates() {
+                assert!(
+                    candidates.iter().any(|c| c[name] == value),
+                    "{name}={value} never evaluated"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_generate_candidates_random_is_seeded() {
+        let mut space = ParameterSpace::new();
+        space.insert(
+            "period".to_string(),
+            ParameterRange::Int {
+                min: 5,
+                max: 50,
+                step: 1,
+            },
+        );
+        let config = WalkForwardConfig {
+            search_method: ParameterSearchMethod::Random {
+                samples: 10,
+                seed: 42,
+            },
+            ..WalkForwardConfig::default()
+        };
+
+        let first = generate_candidates(&space, &config);
+        let second = generate_candidates(&space, &config);
+        assert_eq!(first.len(), 10);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_score_results_by_metric() {
+        let results = BacktestingResults {
+            total_trades: 10,
+            win_rate: 0.6,
+            total_return: 20.0,
+            sharpe_ratio: 1.5,
+            max_drawdown: 10.0,
+            profit_factor: 1.8,
+            monthly_returns: vec![5.0, 5.0, 10.0],
+            trade_distribution: HashMap::new(),
+        };
+
+        assert_eq!(
+            score_results(&results, &OptimizationMetric::TotalReturn),
+            20.0
+        );
+        assert_eq!(
+            score_results(&results, &OptimizationMetric::CalmarRatio),
+            2.0
+        );
+        assert_eq!(
+            score_results(&results, &OptimizationMetric::MaxDrawdown),
+            -10.0
+        );
+        assert_eq!(
+            score_results(&results, &OptimizationMetric::ProfitFactor),
+            1.8
+        );
+        assert!(score_results(&results, &OptimizationMetric::Consistency) < 0.0);
+    }
+
+    #[test]
+    fn test_summarize_walk_forward_stability_and_recommendation() {
+        let windows = vec![
+            make_window(0, 14, 2.0, 1.0),
+            make_window(1, 14, 2.0, 1.0),
+            make_window(2, 21, 2.0, 1.0),
+        ];
+        let report =
+            summarize_walk_forward("RSI Strategy", OptimizationMetric::SharpeRatio, windows);
+
+        assert_eq!(report.windows.len(), 3);
+        assert!((report.walk_forward_efficiency - 0.5).abs() < 1e-9);
+        assert_eq!(
+            report.recommended_parameters.get("rsi_period"),
+            Some(&serde_json::json!(14))
+        );
+        let stability = report.parameter_stability["rsi_period"];
+        assert!(stability > 0.0 && stability < 1.0);
+        assert!(report.overfitting_risk > 0.0 && report.overfitting_risk < 1.0);
+
+        let stable = summarize_walk_forward(
+            "RSI Strategy",
+            OptimizationMetric::SharpeRatio,
+            vec![make_window(0, 14, 1.0, 1.0), make_window(1, 14, 1.0, 1.0)],
+        );
+        assert_eq!(stable.parameter_stability["rsi_period"], 1.0);
+        assert!(stable.overfitting_risk.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_walk_forward_config_from_backtesting_settings() {
+        let settings = BacktestingSettings {
+            walk_forward_optimization: false,
+            out_of_sample_pct: 30.0,
+            ..BacktestingSettings::default()
+        };
+        let config = WalkForwardConfig::from_backtesting_settings(&settings);
+        assert!(!config.walk_forward);
+        assert_eq!(config.out_of_sample_pct, 30.0);
+    }
+
+    #[test]
+    fn test_record_walk_forward_stores_best_parameters() {
+        let mut optimizer = StrategyOptimizer::new(OptimizationConfig::default());
+        let report = summarize_walk_forward(
+            "RSI Strategy",
+            OptimizationMetric::SharpeRatio,
+            vec![make_window(0, 21, 1.0, 0.8)],
+        );
+        optimizer.record_walk_forward(&report);
+
+        let best = optimizer.get_best_parameters("RSI Strategy").unwrap();
+        assert_eq!(best.get("rsi_period"), Some(&serde_json::json!(21)));
+        assert!(optimizer.get_best_parameters("MACD Strategy").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_run_walk_forward_produces_windows() {
+        let optimizer = StrategyOptimizer::new(OptimizationConfig::default());
+        let data = make_walk_forward_series(600);
+        let backtest_config = BacktestConfig {
+            primary_timeframe: "5m".to_string(),
+            min_confidence: 0.0,
+            ..BacktestConfig::default()
+        };
+        let mut space = ParameterSpace::new();
+        space.insert(
+            "rsi_period".to_string(),
+            ParameterRange::Values(vec![serde_json::json!(10), serde_json::json!(14)]),
+        );
+        let config = WalkForwardConfig {
+            windows: 2,
+            out_of_sample_pct: 25.0,
+            ..WalkForwardConfig::default()
+        };
+
+        let report = optimizer
+            .run_walk_forward(
+                &StrategyRegistry::with_builtin_strategies(),
+                "RSI Strategy",
+                &space,
+                &config,
+                &backtest_config,
+                &data,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.strategy_name, "RSI Strategy");
+        assert_eq!(report.windows.len(), 2);
+        for window in &report.windows {
+            assert_eq!(window.candidates_evaluated, 2);
+            assert!(window.in_sample_end < window.out_of_sample_start);
+            assert!(window.best_parameters.contains_key("rsi_period"));
+        }
+        assert!(report.recommended_parameters.contains_key("rsi_period"));
+    }
+
+    #[tokio::test]
+    async fn test_run_walk_forward_rejects_unknown_strategy() {
+        let optimizer = StrategyOptimizer::new(OptimizationConfig::default());
+        let result = optimizer
+            .run_walk_forward(
+                &StrategyRegistry::with_builtin_strategies(),
+                "Unknown Strategy",
+                &ParameterSpace::new(),
+                &WalkForwardConfig::default(),
+                &BacktestConfig::default(),
+                &make_walk_forward_series(100),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_walk_forward_rule_strategy() {
+        let definition: RuleStrategyDefinition = serde_yaml::from_str(
+            r#"
+name: "Trend Rule"
+long_when: "close > EMA(20) ON 5m"
+short_when: "close < EMA(20) ON 5m"
+"#,
+        )
+        .unwrap();
+        let mut registry = StrategyRegistry::with_builtin_strategies();
+        registry.register_rule_strategies(&[definition]).unwrap();
+
+        let mut space = ParameterSpace::new();
+        space.insert(
+            "confidence".to_string(),
+            ParameterRange::Values(vec![serde_json::json!(0.6), serde_json::json!(0.8)]),
+        );
+        let backtest_config = BacktestConfig {
+            primary_timeframe: "5m".to_string(),
+            min_confidence: 0.0,
+            ..BacktestConfig::default()
+        };
+        let config = WalkForwardConfig {
+            windows: 1,
+            ..WalkForwardConfig::default()
+        };
+
+        let report = StrategyOptimizer::new(OptimizationConfig::default())
+            .run_walk_forward(
+                &registry,
+                "Trend Rule",
+                &space,
+                &config,
+                &backtest_config,
+                &make_walk_forward_series(300),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.strategy_name, "Trend Rule");
+        assert!(report.recommended_parameters.contains_key("long_when"));
+    }
 }
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use uuid::Uuid;
 
+use super::margin::{self, MarginMode};
+
 /// Trade types supported
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
 pub enum TradeType {
@@ -39,6 +41,8 @@ pub enum CloseReason {
     RiskManagement,
     MarginCall,
     TimeBasedExit,
+    /// Forced close once the margin balance reached the maintenance margin
+    Liquidation,
 }
 
 /// Paper trading position that simulates Binance Futures
@@ -163,8 +167,70 @@ pub struct PaperTrade {
 
     /// Custom metadata
     pub metadata: std::collections::HashMap<String, serde_json::Value>,
+
+    /// Partial closes of the position, oldest first
+    #[serde(default)]
+    pub partial_closes: Vec<PartialClose>,
+
+    /// Additions to the position after it was opened, oldest first
+    #[serde(default)]
+    pub scale_ins: Vec<ScaleIn>,
+
+    /// Take profit levels that each close part of the position
+    #[serde(default)]
+    pub take_profit_levels: Vec<TakeProfitLevel>,
+
+    /// Whether the position is backed by its own margin or the shared wallet
+    #[serde(default)]
+    pub margin_mode: MarginMode,
+
+    /// Price at which the position is liquidated, if any price can liquidate it
+    #[serde(default)]
+    pub liquidation_price: Option<f64>,
+}
+
+/// Part of a position closed before the rest of the trade
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PartialClose {
+    pub quantity: f64,
+    pub exit_price: f64,
+    /// PnL of this fill net of its share of entry and funding fees and its exit fee
+    pub realized_pnl: f64,
+    /// Share of the entry fees attributed to the closed quantity
+    pub entry_fees: f64,
+    pub exit_fees: f64,
+    pub funding_fees: f64,
+    pub reason: CloseReason,
+    pub time: DateTime<Utc>,
+}
+
+/// Quantity added to an open position
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScaleIn {
+    pub quantity: f64,
+    pub price: f64,
+    pub fees: f64,
+    pub time: DateTime<Utc>,
 }
 
+/// Take profit level closing `quantity` of the position when price reaches `price`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TakeProfitLevel {
+    pub price: f64,
+    pub quantity: f64,
+    #[serde(default)]
+    pub filled: bool,
+}
+
+/// Metadata key linking the legs of a pair trade
+pub const PAIR_ID_KEY: &str = "pair_id";
+
+/// Metadata key holding each strategy's vote for a strategy-driven trade
+pub const STRATEGY_VOTES_KEY: &str = "strategy_votes";
+
+/// Metadata key recording whether a limit order filled as "maker" or "taker"
+pub const LIQUIDITY_KEY: &str = "liquidity";
+
 impl PaperTrade {
     /// Create a new paper trade
     #[allow(clippy::too_many_arguments)]
@@ -184,17 +250,15 @@ impl PaperTrade {
         let initial_margin = notional_value / leverage as f64;
         let trading_fees = notional_value * trading_fee_rate;
 
-        // Binance Futures maintenance margin rates (simplified)
-        let maintenance_margin_rate = match leverage {
-            1..=5 => 0.01,     // 1%
-            6..=10 => 0.025,   // 2.5%
-            11..=20 => 0.05,   // 5%
-            21..=50 => 0.1,    // 10%
-            51..=100 => 0.125, // 12.5%
-            _ => 0.15,         // 15%
-        };
-
-        let maintenance_margin = notional_value * maintenance_margin_rate;
+        // Binance Futures tiered maintenance margin for the position's notional
+        let maintenance_margin = margin::maintenance_margin(&symbol, notional_value);
+        let liquidation_price = margin::liquidation_price(
+            &symbol,
+            trade_type,
+            quantity,
+            entry_price,
+            initial_margin - trading_fees,
+        );
 
         Self {
             id,
@@ -236,6 +300,11 @@ impl PaperTrade {
             highest_price_achieved: None,
             trailing_stop_active: false,
             metadata: std::collections::HashMap::new(),
+            partial_closes: Vec::new(),
+            scale_ins: Vec::new(),
+            take_profit_levels: Vec::new(),
+            margin_mode: MarginMode::Isolated,
+            liquidation_price,
         }
     }
 
@@ -467,6 +536,17 @@ impl PaperTrade {
         exit_price: f64,
         close_reason: CloseReason,
         additional_fees: f64,
+    ) -> Result<()> {
+        self.close_at(exit_price, close_reason, additional_fees, Utc::now())
+    }
+
+    /// Close the trade at `close_time`
+    pub fn close_at(
+        &mut self,
+        exit_price: f64,
+        close_reason: CloseReason,
+        additional_fees: f64,
+        close_time: DateTime<Utc>,
     ) -> Result<()> {
         if self.status != TradeStatus::Open {
             return Err(anyhow::anyhow!("Trade is not open"));
@@ -475,7 +555,7 @@ impl PaperTrade {
         self.exit_price = Some(exit_price);
         self.status = TradeStatus::Closed;
         self.close_reason = Some(close_reason);
-        self.close_time = Some(Utc::now());
+        self.close_time = Some(close_time);
 
         // Calculate final PnL
         let price_diff = match self.trade_type {
@@ -483,8 +563,10 @@ impl PaperTrade {
             TradeType::Short => self.entry_price - exit_price,
         };
 
+        // Realized PnL covers the whole trade, including earlier partial closes
         self.realized_pnl = Some(
-            price_diff * self.quantity - self.trading_fees - self.funding_fees - additional_fees,
+            price_diff * self.quantity - self.trading_fees - self.funding_fees - additional_fees
+                + self.partial_realized_pnl(),
         );
 
         // Calculate duration
@@ -495,6 +577,208 @@ impl PaperTrade {
         Ok(())
     }
 
+    /// PnL already realized by partial closes
+    pub fn partial_realized_pnl(&self) -> f64 {
+        self.partial_closes.iter().map(|p| p.realized_pnl).sum()
+    }
+
+    /// Close `quantity` of the position at `exit_price` and `time`, keeping the rest open
+    ///
+    /// Entry and funding fees are split pro rata between the closed and remaining
+    /// quantity, as are the margins. Returns the realized PnL of the closed part.
+    pub fn reduce(
+        &mut self,
+        quantity: f64,
+        exit_price: f64,
+        close_reason: CloseReason,
+        exit_fees: f64,
+        time: DateTime<Utc>,
+    ) -> Result<f64> {
+        if self.status != TradeStatus::Open {
+            return Err(anyhow::anyhow!("Trade is not open"));
+        }
+        if quantity <= 0.0 || quantity >= self.quantity {
+            return Err(anyhow::anyhow!(
+                "Partial close quantity must be between 0 and the position size {}, got {}",
+                self.quantity,
+                quantity
+            ));
+        }
+
+        let fraction = quantity / self.quantity;
+        let entry_fees = self.trading_fees * fraction;
+        let funding_fees = self.funding_fees * fraction;
+        let price_diff = match self.trade_type {
+            TradeType::Long => exit_price - self.entry_price,
+            TradeType::Short => self.entry_price - exit_price,
+        };
+        let realized_pnl = price_diff * quantity - entry_fees - funding_fees - exit_fees;
+
+        self.quantity -= quantity;
+        self.trading_fees -= entry_fees;
+        self.funding_fees -= funding_fees;
+        self.initial_margin *= 1.0 - fraction;
+        self.margin_used *= 1.0 - fraction;
+        self.refresh_margin_requirements();
+        self.partial_closes.push(PartialClose {
+            quantity,
+            exit_price,
+            realized_pnl,
+            entry_fees,
+            exit_fees,
+            funding_fees,
+            reason: close_reason,
+            time,
+        });
+        self.update_with_price(exit_price, None);
+
+        Ok(realized_pnl)
+    }
+
+    /// Add `quantity` at `price` and `time` to the position, re-averaging the entry price
+    ///
+    /// Returns the additional initial margin required.
+    pub fn scale_in(
+        &mut self,
+        quantity: f64,
+        price: f64,
+        trading_fee_rate: f64,
+        time: DateTime<Utc>,
+    ) -> Result<f64> {
+        if self.status != TradeStatus::Open {
+            return Err(anyhow::anyhow!("Trade is not open"));
+        }
+        if quantity <= 0.0 || price <= 0.0 {
+            return Err(anyhow::anyhow!(
+                "Scale-in quantity and price must be positive"
+            ));
+        }
+
+        let notional = quantity * price;
+        let added_margin = notional / self.leverage as f64;
+        let fees = notional * trading_fee_rate;
+
+        self.entry_price =
+            (self.entry_price * self.quantity + notional) / (self.quantity + quantity);
+        self.quantity += quantity;
+        self.trading_fees += fees;
+        self.initial_margin += added_margin;
+        self.margin_used += added_margin;
+        self.refresh_margin_requirements();
+        self.scale_ins.push(ScaleIn {
+            quantity,
+            price,
+            fees,
+            time,
+        });
+        self.update_with_price(price, None);
+
+        Ok(added_margin)
+    }
+
+    /// Account the position in `margin_mode`
+    pub fn with_margin_mode(mut self, margin_mode: MarginMode) -> Self {
+        self.margin_mode = margin_mode;
+        if margin_mode == MarginMode::Cross {
+            // Set by the portfolio once it knows the shared wallet balance
+            self.liquidation_price = None;
+        }
+        self
+    }
+
+    /// Balance backing an isolated position apart from its own price PnL
+    pub fn isolated_collateral(&self) -> f64 {
+        self.margin_used - self.trading_fees - self.funding_fees
+    }
+
+    /// Recompute the maintenance margin bracket and, for isolated positions, the
+    /// liquidation price after the position size or collateral changed
+    ///
+    /// Cross positions get their liquidation price from the portfolio, which knows the
+    /// shared wallet balance.
+    pub fn refresh_margin_requirements(&mut self) {
+        let notional = self.quantity * self.entry_price;
+        self.maintenance_margin = margin::maintenance_margin(&self.symbol, notional);
+        if self.margin_mode == MarginMode::Isolated {
+            self.liquidation_price = margin::liquidation_price(
+                &self.symbol,
+                self.trade_type,
+                self.quantity,
+                self.entry_price,
+                self.isolated_collateral(),
+            );
+        }
+    }
+
+    /// Whether `current_price` has reached the liquidation price
+    pub fn should_liquidate(&self, current_price: f64) -> bool {
+        margin::is_liquidated(self.trade_type, self.liquidation_price, current_price)
+    }
+
+    /// Fee forfeited to the insurance fund when the position is liquidated at `exit_price`
+    ///
+    /// Whatever margin balance the position has left, up to its maintenance margin, is
+    /// lost; a position that gapped past its bankruptcy price pays nothing extra.
+    pub fn liquidation_clearance_fee(&self, exit_price: f64, exit_fees: f64) -> f64 {
+        let price_diff = match self.trade_type {
+            TradeType::Long => exit_price - self.entry_price,
+            TradeType::Short => self.entry_price - exit_price,
+        };
+        let margin_balance = self.margin_used + price_diff * self.quantity
+            - self.trading_fees
+            - self.funding_fees
+            - exit_fees;
+        margin_balance.clamp(0.0, self.maintenance_margin)
+    }
+
+    /// Replace the take profit levels with `(price, fraction)` pairs
+    ///
+    /// Each fraction is of the current position size, so `[(tp1, 0.5)]` takes half the
+    /// position off at TP1 and leaves the rest to the stop loss, trailing stop or
+    /// `take_profit`. Fractions must sum to at most 1.
+    pub fn set_take_profit_levels(&mut self, levels: &[(f64, f64)]) -> Result<()> {
+        let total_fraction: f64 = levels.iter().map(|(_, fraction)| fraction).sum();
+        if levels.iter().any(|(_, fraction)| *fraction <= 0.0) || total_fraction > 1.0 + 1e-9 {
+            return Err(anyhow::anyhow!(
+                "Take profit fractions must be positive and sum to at most 1"
+            ));
+        }
+        for (price, _) in levels {
+            let beyond_entry = match self.trade_type {
+                TradeType::Long => *price > self.entry_price,
+                TradeType::Short => *price < self.entry_price,
+            };
+            if !beyond_entry {
+                return Err(anyhow::anyhow!(
+                    "Take profit level {} is not on the profitable side of entry {}",
+                    price,
+                    self.entry_price
+                ));
+            }
+        }
+
+        self.take_profit_levels = levels
+            .iter()
+            .map(|(price, fraction)| TakeProfitLevel {
+                price: *price,
+                quantity: self.quantity * fraction,
+                filled: false,
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Index of the first unfilled take profit level reached at `current_price`
+    pub fn take_profit_level_hit(&self, current_price: f64) -> Option<usize> {
+        self.take_profit_levels.iter().position(|level| {
+            !level.filled
+                && match self.trade_type {
+                    TradeType::Long => current_price >= level.price,
+                    TradeType::Short => current_price <= level.price,
+                }
+        })
+    }
+
     /// Cancel the trade
     pub fn cancel(&mut self, reason: String) -> Result<()> {
         if self.status != TradeStatus::Open {
@@ -560,6 +844,19 @@ impl PaperTrade {
         Ok(())
     }
 
+    /// Link this trade to the other leg(s) of a pair trade
+    pub fn set_pair_id(&mut self, pair_id: &str) {
+        self.metadata.insert(
+            PAIR_ID_KEY.to_string(),
+            serde_json::Value::String(pair_id.to_string()),
+        );
+    }
+
+    /// Pair this trade is a leg of, if any
+    pub fn pair_id(&self) -> Option<&str> {
+        self.metadata.get(PAIR_ID_KEY).and_then(|v| v.as_str())
+    }
+
     /// Get trade summary for display
     pub fn get_summary(&self) -> TradeSummary {
         TradeSummary {
@@ -579,7 +876,13 @@ impl PaperTrade {
                 Some(self.unrealized_pnl)
             },
             pnl_percentage: self.pnl_percentage,
-            trading_fees: self.trading_fees,
+            // Entry fees of the whole position, including parts already closed
+            trading_fees: self.trading_fees
+                + self
+                    .partial_closes
+                    .iter()
+                    .map(|p| p.entry_fees)
+                    .sum::<f64>(),
             funding_fees: self.funding_fees,
             duration_ms: self.duration_ms,
             open_time: self.open_time,
@@ -587,6 +890,12 @@ impl PaperTrade {
             close_reason: self.close_reason.clone(),
             ai_signal_id: self.ai_signal_id.clone(),
             ai_confidence: self.ai_confidence,
+            partial_realized_pnl: self.partial_realized_pnl(),
+            partial_closes: self.partial_closes.clone(),
+            scale_ins: self.scale_ins.clone(),
+            take_profit_levels: self.take_profit_levels.clone(),
+            margin_mode: self.margin_mode,
+            liquidation_price: self.liquidation_price,
         }
     }
 }
@@ -614,6 +923,19 @@ pub struct TradeSummary {
     pub close_reason: Option<CloseReason>,
     pub ai_signal_id: Option<String>,
     pub ai_confidence: Option<f64>,
+    /// PnL realized by partial closes (included in `pnl` once the trade is closed)
+    #[serde(default)]
+    pub partial_realized_pnl: f64,
+    #[serde(default)]
+    pub partial_closes: Vec<PartialClose>,
+    #[serde(default)]
+    pub scale_ins: Vec<ScaleIn>,
+    #[serde(default)]
+    pub take_profit_levels: Vec<TakeProfitLevel>,
+    #[serde(default)]
+    pub margin_mode: MarginMode,
+    #[serde(default)]
+    pub liquidation_price: Option<f64>,
 }
 
 impl TradeType {
@@ -943,6 +1265,46 @@ mod tests {
         assert!(trade.set_take_profit(45000.0).is_ok());
     }
 
+    #[test]
+    fn test_take_profit_levels() {
+        let mut trade = PaperTrade::new(
+            "BTCUSDT".to_string(),
+            TradeType::Long,
+            50000.0,
+            0.2,
+            10,
+            0.0004,
+            None,
+            None,
+            None,
+        );
+
+        assert!(trade.set_take_profit_levels(&[(49000.0, 0.5)]).is_err());
+        assert!(trade
+            .set_take_profit_levels(&[(51000.0, 0.6), (52000.0, 0.6)])
+            .is_err());
+        trade
+            .set_take_profit_levels(&[(51000.0, 0.5), (52000.0, 0.25)])
+            .unwrap();
+        assert!((trade.take_profit_levels[0].quantity - 0.1).abs() < 1e-12);
+
+        assert_eq!(trade.take_profit_level_hit(50500.0), None);
+        assert_eq!(trade.take_profit_level_hit(52500.0), Some(0));
+        trade.take_profit_levels[0].filled = true;
+        assert_eq!(trade.take_profit_level_hit(52500.0), Some(1));
+
+        let pnl = trade
+            .reduce(0.1, 51000.0, CloseReason::TakeProfit, 0.0, Utc::now())
+            .unwrap();
+        // 100 profit less half the 4.0 entry fee
+        assert!((pnl - 98.0).abs() < 1e-9);
+        assert!((trade.quantity - 0.1).abs() < 1e-12);
+        assert!((trade.initial_margin - 500.0).abs() < 1e-9);
+        assert!(trade
+            .reduce(0.1, 51000.0, CloseReason::Manual, 0.0, Utc::now())
+            .is_err());
+    }
+
     #[test]
     fn test_close_trade() {
         let mut trade = PaperTrade::new(
@@ -1054,7 +1416,7 @@ mod tests {
 
         // Initial margin at 125x: (0.1 * 50000) / 125 = 40
         assert!((trade.initial_margin - 40.0).abs() < 0.01);
-        assert_eq!(trade.maintenance_margin, 750.0); // 5000 * 0.15
+        assert_eq!(trade.maintenance_margin, 20.0); // 5000 * 0.4%
     }
 
     #[test]
@@ -1274,97 +1636,28 @@ mod tests {
 
     #[test]
     fn test_maintenance_margin_rates() {
-        // Test different leverage tiers for maintenance margin calculation
-        let trade_1x = PaperTrade::new(
-            "BTCUSDT".to_string(),
-            TradeType::Long,
-            50000.0,
-            0.1,
-            1,
-            0.0004,
-            None,
-            None,
-            None,
-        );
-        assert_eq!(trade_1x.maintenance_margin, 5000.0 * 0.01); // 1%
-
-        let trade_5x = PaperTrade::new(
-            "BTCUSDT".to_string(),
-            TradeType::Long,
-            50000.0,
-            0.1,
-            5,
-            0.0004,
-            None,
-            None,
-            None,
-        );
-        assert_eq!(trade_5x.maintenance_margin, 5000.0 * 0.01); // 1%
-
-        let trade_10x = PaperTrade::new(
-            "BTCUSDT".to_string(),
-            TradeType::Long,
-            50000.0,
-            0.1,
-            10,
-            0.0004,
-            None,
-            None,
-            None,
-        );
-        assert_eq!(trade_10x.maintenance_margin, 5000.0 * 0.025); // 2.5%
-
-        let trade_20x = PaperTrade::new(
-            "BTCUSDT".to_string(),
-            TradeType::Long,
-            50000.0,
-            0.1,
-            20,
-            0.0004,
-            None,
-            None,
-            None,
-        );
-        assert_eq!(trade_20x.maintenance_margin, 5000.0 * 0.05); // 5%
-
-        let trade_50x = PaperTrade::new(
-            "BTCUSDT".to_string(),
-            TradeType::Long,
-            50000.0,
-            0.1,
-            50,
-            0.0004,
-            None,
-            None,
-            None,
-        );
-        assert_eq!(trade_50x.maintenance_margin, 5000.0 * 0.1); // 10%
+        // Maintenance margin follows the notional bracket, not the leverage
+        let trade = |quantity: f64, leverage: u8| {
+            PaperTrade::new(
+                "BTCUSDT".to_string(),
+                TradeType::Long,
+                50000.0,
+                quantity,
+                leverage,
+                0.0004,
+                None,
+                None,
+                None,
+            )
+        };
+        assert_eq!(trade(0.1, 1).maintenance_margin, 5000.0 * 0.004); // 0.4%
+        assert_eq!(trade(0.1, 125).maintenance_margin, 5000.0 * 0.004);
 
-        let trade_100x = PaperTrade::new(
-            "BTCUSDT".to_string(),
-            TradeType::Long,
-            50000.0,
-            0.1,
-            100,
-            0.0004,
-            None,
-            None,
-            None,
-        );
-        assert_eq!(trade_100x.maintenance_margin, 5000.0 * 0.125); // 12.5%
+        // 100000 notional: 0.5% bracket less its 50 maintenance amount
+        assert!((trade(2.0, 10).maintenance_margin - 450.0).abs() < 1e-9);
 
-        let trade_125x = PaperTrade::new(
-            "BTCUSDT".to_string(),
-            TradeType::Long,
-            50000.0,
-            0.1,
-            125,
-            0.0004,
-            None,
-            None,
-            None,
-        );
-        assert_eq!(trade_125x.maintenance_margin, 5000.0 * 0.15); // 15%
+        // 1,000,000 notional: 1% bracket less its 3050 maintenance amount
+        assert!((trade(20.0, 5).maintenance_margin - 6950.0).abs() < 1e-9);
     }
 
     #[test]
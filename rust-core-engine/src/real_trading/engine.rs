@@ -2578,7 +2578,7 @@ impl RealTradingEngine {
             }
         }
         // Sort by time descending (newest first)
-        all_trades.sort_by(|a, b| b.time.cmp(&a.time));
+        all_trades.sort_by_key(|t| std::cmp::Reverse(t.time));
         all_trades
     }
 
@@ -2602,7 +2602,7 @@ impl RealTradingEngine {
             }
         }
         // Sort by time descending (newest first)
-        all_orders.sort_by(|a, b| b.time.cmp(&a.time));
+        all_orders.sort_by_key(|o| std::cmp::Reverse(o.time));
         all_orders
     }
 
@@ -4243,6 +4243,8 @@ impl RealTradingEngine {
             current_price,
             volume_24h,
             timestamp: Utc::now().timestamp(),
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         })
     }
 
@@ -4265,6 +4267,7 @@ impl RealTradingEngine {
     }
 
     /// Process an external AI signal for real trading
+    #[allow(clippy::too_many_arguments)]
     pub async fn process_external_ai_signal(
         &self,
         symbol: String,
@@ -20,6 +20,9 @@ pub struct Storage {
     #[cfg(feature = "database")]
     db: Option<Database>,
 
+    /// Prefix of the paper trading collections, set for named paper portfolios
+    paper_collection_prefix: String,
+
     // In-memory fallback storage
     #[cfg(not(feature = "database"))]
     _phantom: std::marker::PhantomData<()>,
@@ -43,7 +46,10 @@ impl Storage {
 
                 info!("MongoDB connected successfully to: {}", config.url);
 
-                let storage = Self { db: Some(db) };
+                let storage = Self {
+                    db: Some(db),
+                    paper_collection_prefix: String::new(),
+                };
 
                 // Create indexes for better query performance
                 if let Err(e) = storage.ensure_indexes().await {
@@ -56,7 +62,10 @@ impl Storage {
                     "Database URL not recognized as MongoDB connection string: {}",
                     config.url
                 );
-                Ok(Self { db: None })
+                Ok(Self {
+                    db: None,
+                    paper_collection_prefix: String::new(),
+                })
             }
         }
 
@@ -64,6 +73,7 @@ impl Storage {
         {
             info!("Database feature disabled, using in-memory storage");
             Ok(Self {
+                paper_collection_prefix: String::new(),
                 _phantom: std::marker::PhantomData,
             })
         }
@@ -584,12 +594,28 @@ impl Storage {
         None
     }
 
+    /// Storage for the named paper portfolio `name`
+    ///
+    /// Shares the database connection; paper trades, portfolio history, performance
+    /// metrics and settings go to `{name}_`-prefixed collections.
+    pub fn for_paper_portfolio(&self, name: &str) -> Self {
+        Self {
+            paper_collection_prefix: format!("{}_", name),
+            ..self.clone()
+        }
+    }
+
+    /// Name of paper trading collection `name` for this storage's portfolio
+    pub fn paper_collection_name(&self, name: &str) -> String {
+        format!("{}{}", self.paper_collection_prefix, name)
+    }
+
     /// Get paper trading collection
     pub fn paper_trades(&self) -> Result<Collection<PaperTradingRecord>> {
         self.db
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Database not initialized"))
-            .map(|db| db.collection("paper_trades"))
+            .map(|db| db.collection(&self.paper_collection_name("paper_trades")))
     }
 
     /// Get portfolio history collection
@@ -597,7 +623,7 @@ impl Storage {
         self.db
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Database not initialized"))
-            .map(|db| db.collection("portfolio_history"))
+            .map(|db| db.collection(&self.paper_collection_name("portfolio_history")))
     }
 
     /// Get AI signals collection
@@ -613,7 +639,7 @@ impl Storage {
         self.db
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Database not initialized"))
-            .map(|db| db.collection("performance_metrics"))
+            .map(|db| db.collection(&self.paper_collection_name("performance_metrics")))
     }
 
     /// Get paper trading settings collection
@@ -621,7 +647,7 @@ impl Storage {
         self.db
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Database not initialized"))
-            .map(|db| db.collection("paper_trading_settings"))
+            .map(|db| db.collection(&self.paper_collection_name("paper_trading_settings")))
     }
 
     /// Save paper trade to database
@@ -658,6 +684,9 @@ impl Storage {
         let update = doc! {
             "$set": {
                 "status": format!("{:?}", trade.status),
+                "entry_price": trade.entry_price,
+                "quantity": trade.quantity,
+                "trading_fees": trade.trading_fees,
                 "exit_price": trade.exit_price,
                 "pnl": trade.realized_pnl,
                 "pnl_percentage": trade.pnl_percentage,
@@ -2604,6 +2633,31 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_paper_portfolio_collections_are_prefixed() {
+        let config = crate::config::DatabaseConfig {
+            url: "invalid://test".to_string(),
+            database_name: None,
+            max_connections: 10,
+            enable_logging: false,
+        };
+
+        let storage = Storage::new(&config).await.unwrap();
+        assert_eq!(
+            storage.paper_collection_name("paper_trades"),
+            "paper_trades"
+        );
+        let aggressive = storage.for_paper_portfolio("aggressive");
+        assert_eq!(
+            aggressive.paper_collection_name("paper_trades"),
+            "aggressive_paper_trades"
+        );
+        assert_eq!(
+            aggressive.paper_collection_name("paper_trading_settings"),
+            "aggressive_paper_trading_settings"
+        );
+    }
+
     #[tokio::test]
     async fn test_portfolio_history_collection_without_db() {
         let config = crate::config::DatabaseConfig {
@@ -1,5 +1,6 @@
 use super::*;
 use crate::strategies::indicators::calculate_bollinger_bands;
+use crate::strategies::streaming_indicators::BollingerValue;
 use async_trait::async_trait;
 use serde_json::json;
 
@@ -61,6 +62,41 @@ impl BollingerStrategy {
             .and_then(|v| v.as_f64())
             .unwrap_or(0.02)
     }
+
+    /// Uses the streaming snapshot when it was built with the same period and multiplier
+    fn current_and_previous_bands(
+        &self,
+        data: &StrategyInput,
+        timeframe: &str,
+        candles: &[CandleData],
+        bb_period: usize,
+        bb_multiplier: f64,
+    ) -> Result<(BollingerValue, BollingerValue), StrategyError> {
+        if let Some(values) = data
+            .indicators
+            .get(timeframe)
+            .and_then(|indicators| indicators.bollinger(bb_period, bb_multiplier))
+        {
+            return Ok(values);
+        }
+
+        let bands = calculate_bollinger_bands(candles, bb_period, bb_multiplier)
+            .map_err(StrategyError::CalculationError)?;
+        let value_at = |index: usize| BollingerValue {
+            upper: bands.upper[index],
+            middle: bands.middle[index],
+            lower: bands.lower[index],
+        };
+        let len = bands.upper.len();
+        if len == 0 {
+            return Err(StrategyError::InsufficientData(
+                "No Bollinger Bands calculated".to_string(),
+            ));
+        }
+        let current = value_at(len - 1);
+        let previous = if len > 1 { value_at(len - 2) } else { current };
+        Ok((current, previous))
+    }
 }
 
 #[async_trait]
@@ -99,48 +135,34 @@ impl Strategy for BollingerStrategy {
         let bb_period = self.get_bb_period();
         let bb_multiplier = self.get_bb_multiplier();
 
-        // Calculate Bollinger Bands for both timeframes
-        let primary_bb = calculate_bollinger_bands(primary_candles, bb_period, bb_multiplier)
-            .map_err(StrategyError::CalculationError)?;
-
-        let confirmation_bb =
-            calculate_bollinger_bands(confirmation_candles, bb_period, bb_multiplier)
-                .map_err(StrategyError::CalculationError)?;
-
-        if primary_bb.upper.is_empty() || confirmation_bb.upper.is_empty() {
-            return Err(StrategyError::InsufficientData(
-                "No Bollinger Bands calculated".to_string(),
-            ));
-        }
+        // Current and previous Bollinger Bands for both timeframes
+        let (current_1h, prev_1h) = self.current_and_previous_bands(
+            data,
+            primary_timeframe,
+            primary_candles,
+            bb_period,
+            bb_multiplier,
+        )?;
+        let (current_4h, _) = self.current_and_previous_bands(
+            data,
+            confirmation_timeframe,
+            confirmation_candles,
+            bb_period,
+            bb_multiplier,
+        )?;
 
         let current_price = data.current_price;
 
-        // Current BB values with proper error handling
-        let upper_1h = *primary_bb
-            .upper
-            .last()
-            .ok_or_else(|| StrategyError::InsufficientData("No upper BB values".to_string()))?;
-        let middle_1h = *primary_bb
-            .middle
-            .last()
-            .ok_or_else(|| StrategyError::InsufficientData("No middle BB values".to_string()))?;
-        let lower_1h = *primary_bb
-            .lower
-            .last()
-            .ok_or_else(|| StrategyError::InsufficientData("No lower BB values".to_string()))?;
-
-        let upper_4h = *confirmation_bb
-            .upper
-            .last()
-            .ok_or_else(|| StrategyError::InsufficientData("No 4h upper BB values".to_string()))?;
-        let middle_4h = *confirmation_bb
-            .middle
-            .last()
-            .ok_or_else(|| StrategyError::InsufficientData("No 4h middle BB values".to_string()))?;
-        let lower_4h = *confirmation_bb
-            .lower
-            .last()
-            .ok_or_else(|| StrategyError::InsufficientData("No 4h lower BB values".to_string()))?;
+        let BollingerValue {
+            upper: upper_1h,
+            middle: middle_1h,
+            lower: lower_1h,
+        } = current_1h;
+        let BollingerValue {
+            upper: upper_4h,
+            middle: middle_4h,
+            lower: lower_4h,
+        } = current_4h;
 
         // Calculate price position within bands
         let bb_width_1h = (upper_1h - lower_1h) / middle_1h;
@@ -154,14 +176,7 @@ impl Strategy for BollingerStrategy {
         let is_squeeze_4h = bb_width_4h < squeeze_threshold;
 
         // Get previous BB width for trend analysis
-        let prev_bb_width_1h = if primary_bb.upper.len() > 1 {
-            let prev_upper = primary_bb.upper[primary_bb.upper.len() - 2];
-            let prev_lower = primary_bb.lower[primary_bb.lower.len() - 2];
-            let prev_middle = primary_bb.middle[primary_bb.middle.len() - 2];
-            (prev_upper - prev_lower) / prev_middle
-        } else {
-            bb_width_1h
-        };
+        let prev_bb_width_1h = (prev_1h.upper - prev_1h.lower) / prev_1h.middle;
 
         let bb_expanding_1h = bb_width_1h > prev_bb_width_1h * 1.05;
         let bb_contracting_1h = bb_width_1h < prev_bb_width_1h * 0.95;
@@ -384,6 +399,7 @@ impl Default for BollingerStrategy {
 mod tests {
     use super::*;
     use crate::market_data::cache::CandleData;
+    use crate::market_data::indicator_cache::IndicatorCache;
     use std::collections::HashMap;
 
     fn create_test_candles(prices: Vec<f64>) -> Vec<CandleData> {
@@ -420,7 +436,54 @@ mod tests {
             current_price,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
+        }
+    }
+
+    /// Attach snapshots the way the paper engine does via `IndicatorCache::sync`
+    fn with_streaming_indicators(mut input: StrategyInput) -> StrategyInput {
+        let cache = IndicatorCache::default();
+        for (timeframe, candles) in &input.timeframe_data {
+            if let Some(snapshot) = cache.sync(&input.symbol, timeframe, candles) {
+                input.indicators.insert(timeframe.clone(), snapshot);
+            }
+        }
+        input
+    }
+
+    #[tokio::test]
+    async fn test_bollinger_strategy_streaming_indicators_match_batch() {
+        let strategy = BollingerStrategy::new();
+        let prices_1h: Vec<f64> = (0..100)
+            .map(|i| 100.0 + (i as f64 * 0.3).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let prices_4h: Vec<f64> = (0..100)
+            .map(|i| 100.0 + (i as f64 * 0.15).cos() * 6.0 - i as f64 * 0.02)
+            .collect();
+
+        let batch_input = create_test_input(prices_1h.clone(), prices_4h.clone(), 101.0);
+        let streaming_input = with_streaming_indicators(batch_input.clone());
+        for snapshot in streaming_input.indicators.values() {
+            assert!(snapshot.bollinger(20, 2.0).is_some());
         }
+
+        let batch = strategy.analyze(&batch_input).await.unwrap();
+        let streaming = strategy.analyze(&streaming_input).await.unwrap();
+        assert_eq!(batch.signal, streaming.signal);
+        assert!((batch.confidence - streaming.confidence).abs() < 1e-9);
+
+        // The streaming result comes from the snapshot, not a batch recalculation
+        let reported = streaming.metadata["bb_upper_5m"].as_f64().unwrap();
+        assert_eq!(
+            reported,
+            streaming_input.indicators["5m"]
+                .bollinger(20, 2.0)
+                .unwrap()
+                .0
+                .upper
+        );
+        assert!((reported - batch.metadata["bb_upper_5m"].as_f64().unwrap()).abs() < 1e-6);
     }
 
     #[tokio::test]
@@ -796,6 +859,8 @@ mod tests {
             current_price: 100.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let result = strategy.validate_data(&input);
@@ -0,0 +1,473 @@
+use crate::market_data::cache::{CandleData, MarketDataCache};
+use crate::strategies::indicators::{
+    align_to_candles, calculate_adx, calculate_atr, calculate_bollinger_bands, calculate_cci,
+    calculate_ema, calculate_macd, calculate_rsi, calculate_sma, calculate_stochastic,
+    calculate_williams_r,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+/// One engineered feature column
+///
+/// Each feature's column name is also its spec string (e.g. `rsi_14`, `macd_12_26_9`),
+/// so a dataset's header can be fed back in to rebuild the same feature set.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum DatasetFeature {
+    /// Log return over `period` candles
+    Return(usize),
+    /// RSI rescaled to -0.5..0.5
+    Rsi(usize),
+    /// MACD histogram relative to close
+    MacdHistogram(usize, usize, usize),
+    /// Position within the Bollinger Bands, -0.5 at the lower band and 0.5 at the upper
+    BollingerPercentB(usize, f64),
+    /// Fast EMA over slow EMA, minus 1
+    EmaSpread(usize, usize),
+    /// ATR relative to close
+    Atr(usize),
+    /// Log of volume over its SMA
+    VolumeRatio(usize),
+    Adx(usize),
+    Cci(usize),
+    WilliamsR(usize),
+    /// Stochastic %K
+    Stochastic(usize, usize),
+}
+
+impl DatasetFeature {
+    /// Features the export uses when none are configured
+    pub fn default_set() -> Vec<Self> {
+        vec![
+            Self::Return(1),
+            Self::Return(5),
+            Self::Return(20),
+            Self::Rsi(14),
+            Self::MacdHistogram(12, 26, 9),
+            Self::BollingerPercentB(20, 2.0),
+            Self::EmaSpread(20, 50),
+            Self::Atr(14),
+            Self::VolumeRatio(20),
+        ]
+    }
+
+    /// Feature value per candle, `None` until the indicator has warmed up
+    fn compute(&self, candles: &[CandleData]) -> Result<Vec<Option<f64>>, String> {
+        let n = candles.len();
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let relative = |values: Vec<Option<f64>>| -> Vec<Option<f64>> {
+            values
+                .into_iter()
+                .zip(&closes)
+                .map(|(v, close)| v.map(|v| v / close))
+                .collect()
+        };
+
+        Ok(match *self {
+            Self::Return(period) => (0..n)
+                .map(|i| {
+                    let past = closes.get(i.checked_sub(period)?)?;
+                    Some((closes[i] / past).ln())
+                })
+                .collect(),
+            Self::Rsi(period) => align_to_candles(calculate_rsi(candles, period)?, n)
+                .into_iter()
+                .map(|v| v.map(|rsi| rsi / 100.0 - 0.5))
+                .collect(),
+            Self::MacdHistogram(fast, slow, signal) => relative(align_to_candles(
+                calculate_macd(candles, fast, slow, signal)?.histogram,
+                n,
+            )),
+            Self::BollingerPercentB(period, multiplier) => {
+                let bands = calculate_bollinger_bands(candles, period, multiplier)?;
+                let upper = align_to_candles(bands.upper, n);
+                let lower = align_to_candles(bands.lower, n);
+                (0..n)
+                    .map(|i| {
+                        let (upper, lower) = (upper[i]?, lower[i]?);
+                        let width = upper - lower;
+                        Some(if width > 0.0 {
+                            (closes[i] - lower) / width - 0.5
+                        } else {
+                            0.0
+                        })
+                    })
+                    .collect()
+            },
+            Self::EmaSpread(fast, slow) => {
+                let fast = align_to_candles(calculate_ema(&closes, fast)?, n);
+                let slow = align_to_candles(calculate_ema(&closes, slow)?, n);
+                (0..n).map(|i| Some(fast[i]? / slow[i]? - 1.0)).collect()
+            },
+            Self::Atr(period) => relative(align_to_candles(calculate_atr(candles, period)?, n)),
+            Self::VolumeRatio(period) => {
+                let volumes: Vec<f64> = candles.iter().map(|c| c.volume).collect();
+                let sma = align_to_candles(calculate_sma(&volumes, period)?, n);
+                (0..n)
+                    .map(|i| Some(((volumes[i] + 1e-9) / (sma[i]? + 1e-9)).ln()))
+                    .collect()
+            },
+            Self::Adx(period) => align_to_candles(calculate_adx(candles, period)?.adx, n),
+            Self::Cci(period) => align_to_candles(calculate_cci(candles, period)?, n),
+            Self::WilliamsR(period) => align_to_candles(calculate_williams_r(candles, period)?, n),
+            Self::Stochastic(k, d) => {
+                align_to_candles(calculate_stochastic(candles, k, d)?.k_percent, n)
+            },
+        })
+    }
+}
+
+impl fmt::Display for DatasetFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Return(p) => write!(f, "return_{}", p),
+            Self::Rsi(p) => write!(f, "rsi_{}", p),
+            Self::MacdHistogram(fast, slow, signal) => {
+                write!(f, "macd_{}_{}_{}", fast, slow, signal)
+            },
+            Self::BollingerPercentB(p, m) => write!(f, "bb_{}_{}", p, m),
+            Self::EmaSpread(fast, slow) => write!(f, "ema_spread_{}_{}", fast, slow),
+            Self::Atr(p) => write!(f, "atr_{}", p),
+            Self::VolumeRatio(p) => write!(f, "volume_ratio_{}", p),
+            Self::Adx(p) => write!(f, "adx_{}", p),
+            Self::Cci(p) => write!(f, "cci_{}", p),
+            Self::WilliamsR(p) => write!(f, "williams_r_{}", p),
+            Self::Stochastic(k, d) => write!(f, "stoch_{}_{}", k, d),
+        }
+    }
+}
+
+impl FromStr for DatasetFeature {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let spec = spec.trim();
+        let (name, params) = match spec.find(|c: char| c.is_ascii_digit()) {
+            Some(i) if i > 0 => (spec[..i].trim_end_matches('_'), &spec[i..]),
+            _ => return Err(format!("Feature '{}' is missing its parameters", spec)),
+        };
+        let params: Vec<&str> = params.split('_').collect();
+        let int = |i: usize| -> Result<usize, String> {
+            params
+                .get(i)
+                .and_then(|p| p.parse::<usize>().ok())
+                .filter(|&p| p > 0)
+                .ok_or_else(|| format!("Feature '{}' has an invalid parameter", spec))
+        };
+        let expected = match name {
+            "macd" => 3,
+            "bb" | "ema_spread" | "stoch" => 2,
+            _ => 1,
+        };
+        if params.len() != expected {
+            return Err(format!(
+                "Feature '{}' takes {} parameter(s), got {}",
+                spec,
+                expected,
+                params.len()
+            ));
+        }
+
+        let feature = match name {
+            "return" => Self::Return(int(0)?),
+            "rsi" => Self::Rsi(int(0)?),
+            "macd" => Self::MacdHistogram(int(0)?, int(1)?, int(2)?),
+            "bb" => {
+                let multiplier = params[1]
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|m| *m > 0.0)
+                    .ok_or_else(|| format!("Feature '{}' has an invalid multiplier", spec))?;
+                Self::BollingerPercentB(int(0)?, multiplier)
+            },
+            "ema_spread" => Self::EmaSpread(int(0)?, int(1)?),
+            "atr" => Self::Atr(int(0)?),
+            "volume_ratio" => Self::VolumeRatio(int(0)?),
+            "adx" => Self::Adx(int(0)?),
+            "cci" => Self::Cci(int(0)?),
+            "williams_r" => Self::WilliamsR(int(0)?),
+            "stoch" => Self::Stochastic(int(0)?, int(1)?),
+            _ => return Err(format!("Unknown feature '{}'", spec)),
+        };
+        Ok(feature)
+    }
+}
+
+impl TryFrom<String> for DatasetFeature {
+    type Error = String;
+
+    fn try_from(spec: String) -> Result<Self, Self::Error> {
+        spec.parse()
+    }
+}
+
+impl From<DatasetFeature> for String {
+    fn from(feature: DatasetFeature) -> Self {
+        feature.to_string()
+    }
+}
+
+/// Parse a comma-separated feature list such as `rsi_14,atr_14,return_5`
+pub fn parse_feature_list(specs: &str) -> Result<Vec<DatasetFeature>, String> {
+    specs
+        .split(',')
+        .filter(|s| !s.trim().is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Which features and labels a dataset export produces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetConfig {
+    pub features: Vec<DatasetFeature>,
+    /// Forward horizons, in candles, to label
+    pub horizons: Vec<usize>,
+    /// Forward log returns within ±threshold are labeled 0, above +1, below -1
+    pub label_threshold: f64,
+}
+
+impl Default for DatasetConfig {
+    fn default() -> Self {
+        Self {
+            features: DatasetFeature::default_set(),
+            horizons: vec![1, 6, 24],
+            label_threshold: 0.002,
+        }
+    }
+}
+
+/// Labeled feature table, one row per candle with complete features and labels
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    pub feature_names: Vec<String>,
+    pub horizons: Vec<usize>,
+    pub rows: Vec<DatasetRow>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DatasetRow {
+    pub open_time: i64,
+    pub close: f64,
+    pub features: Vec<f64>,
+    pub forward_returns: Vec<f64>,
+    pub labels: Vec<i8>,
+}
+
+/// Compute features and forward-return labels for a chronologically ordered series
+///
+/// Candles are dropped while any feature is warming up and where any horizon runs
+/// past the end of the series, so every row is complete.
+pub fn build_dataset(candles: &[CandleData], config: &DatasetConfig) -> Result<Dataset, String> {
+    if config.features.is_empty() {
+        return Err("Dataset needs at least one feature".to_string());
+    }
+    if config.horizons.is_empty() || config.horizons.contains(&0) {
+        return Err("Dataset horizons must be non-empty and positive".to_string());
+    }
+    if config.label_threshold < 0.0 {
+        return Err("label_threshold must not be negative".to_string());
+    }
+
+    let columns = config
+        .features
+        .iter()
+        .map(|f| f.compute(candles))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let rows = (0..candles.len())
+        .filter_map(|i| {
+            let features = columns
+                .iter()
+                .map(|column| column[i].filter(|v| v.is_finite()))
+                .collect::<Option<Vec<f64>>>()?;
+            let forward_returns = config
+                .horizons
+                .iter()
+                .map(|&h| {
+                    let future = candles.get(i + h)?;
+                    Some((future.close / candles[i].close).ln()).filter(|r| r.is_finite())
+                })
+                .collect::<Option<Vec<f64>>>()?;
+            let labels = forward_returns
+                .iter()
+                .map(|&r| {
+                    if r > config.label_threshold {
+                        1
+                    } else if r < -config.label_threshold {
+                        -1
+                    } else {
+                        0
+                    }
+                })
+                .collect();
+            Some(DatasetRow {
+                open_time: candles[i].open_time,
+                close: candles[i].close,
+                features,
+                forward_returns,
+                labels,
+            })
+        })
+        .collect();
+
+    Ok(Dataset {
+        feature_names: config.features.iter().map(|f| f.to_string()).collect(),
+        horizons: config.horizons.clone(),
+        rows,
+    })
+}
+
+/// Build a dataset from the candles currently held in the market data cache
+pub fn build_dataset_from_cache(
+    cache: &MarketDataCache,
+    symbol: &str,
+    timeframe: &str,
+    config: &DatasetConfig,
+) -> Result<Dataset, String> {
+    build_dataset(&cache.get_all_candles(symbol, timeframe), config)
+}
+
+impl Dataset {
+    pub fn header(&self) -> Vec<String> {
+        let mut header = vec!["open_time".to_string(), "close".to_string()];
+        header.extend(self.feature_names.iter().cloned());
+        for h in &self.horizons {
+            header.push(format!("fwd_return_{}", h));
+        }
+        for h in &self.horizons {
+            header.push(format!("label_{}", h));
+        }
+        header
+    }
+
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writeln!(writer, "{}", self.header().join(","))?;
+        for row in &self.rows {
+            let mut fields = vec![row.open_time.to_string(), row.close.to_string()];
+            fields.extend(row.features.iter().map(f64::to_string));
+            fields.extend(row.forward_returns.iter().map(f64::to_string));
+            fields.extend(row.labels.iter().map(i8::to_string));
+            writeln!(writer, "{}", fields.join(","))?;
+        }
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles(count: usize) -> Vec<CandleData> {
+        (0..count)
+            .map(|i| {
+                let close = 100.0 + (i as f64 * 0.3).sin() * 5.0 + i as f64 * 0.1;
+                CandleData {
+                    open_time: i as i64 * 60_000,
+                    close_time: i as i64 * 60_000 + 59_999,
+                    open: close - 0.2,
+                    high: close + 0.5,
+                    low: close - 0.7,
+                    close,
+                    volume: 100.0 + (i % 5) as f64 * 10.0,
+                    quote_volume: 100.0 * close,
+                    trades: 10,
+                    is_closed: true,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_feature_spec_roundtrip() {
+        let specs = "return_5,rsi_14,macd_12_26_9,bb_20_2.5,ema_spread_20_50,atr_14,\
+                     volume_ratio_20,adx_14,cci_20,williams_r_14,stoch_14_3";
+        let features = parse_feature_list(specs).unwrap();
+        assert_eq!(features.len(), 11);
+        assert_eq!(features[3], DatasetFeature::BollingerPercentB(20, 2.5));
+        let names: Vec<String> = features.iter().map(|f| f.to_string()).collect();
+        assert_eq!(names.join(","), specs.replace(' ', ""));
+
+        for bad in [
+            "rsi",
+            "rsi_0",
+            "macd_12_26",
+            "atr_14_2",
+            "foo_3",
+            "bb_20_-1",
+        ] {
+            assert!(bad.parse::<DatasetFeature>().is_err(), "{bad}");
+        }
+    }
+
+    #[test]
+    fn test_build_dataset_rows_are_complete() {
+        let data = candles(200);
+        let config = DatasetConfig {
+            features: vec![DatasetFeature::Rsi(14), DatasetFeature::EmaSpread(5, 50)],
+            horizons: vec![1, 10],
+            label_threshold: 0.001,
+        };
+        let dataset = build_dataset(&data, &config).unwrap();
+
+        // EMA 50 warms up at index 49, the 10-candle horizon ends at index 189
+        assert_eq!(dataset.rows.len(), 190 - 49);
+        assert_eq!(dataset.rows[0].open_time, data[49].open_time);
+
+        let row = &dataset.rows[0];
+        let expected = (data[59].close / data[49].close).ln();
+        assert!((row.forward_returns[1] - expected).abs() < 1e-12);
+        let label = if expected.abs() > 0.001 {
+            expected.signum() as i8
+        } else {
+            0
+        };
+        assert_eq!(row.labels[1], label);
+    }
+
+    #[test]
+    fn test_build_dataset_rejects_bad_config() {
+        let data = candles(100);
+        let no_features = DatasetConfig {
+            features: vec![],
+            ..Default::default()
+        };
+        assert!(build_dataset(&data, &no_features).is_err());
+        let zero_horizon = DatasetConfig {
+            horizons: vec![0],
+            ..Default::default()
+        };
+        assert!(build_dataset(&data, &zero_horizon).is_err());
+        // Not enough candles for EMA 50
+        assert!(build_dataset(&data[..30], &DatasetConfig::default()).is_err());
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let dataset = build_dataset(
+            &candles(80),
+            &DatasetConfig {
+                features: vec![DatasetFeature::Return(1)],
+                horizons: vec![2],
+                label_threshold: 0.0,
+            },
+        )
+        .unwrap();
+        let mut out = Vec::new();
+        dataset.write_csv(&mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "open_time,close,return_1,fwd_return_2,label_2");
+        assert_eq!(lines.len(), dataset.rows.len() + 1);
+        assert_eq!(lines[1].split(',').count(), 5);
+    }
+
+    #[test]
+    fn test_build_dataset_from_cache() {
+        let cache = MarketDataCache::new(500);
+        let config = DatasetConfig::default();
+        assert!(build_dataset_from_cache(&cache, "BTCUSDT", "1m", &config).is_err());
+    }
+}
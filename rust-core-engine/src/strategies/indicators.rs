@@ -321,6 +321,511 @@ pub fn calculate_stochastic(
     })
 }
 
+/// ADX with Wilder's directional indicators (DMI)
+#[derive(Debug, Clone)]
+pub struct AdxResult {
+    pub adx: Vec<f64>,
+    pub plus_di: Vec<f64>,
+    pub minus_di: Vec<f64>,
+}
+
+/// Average Directional Index. `plus_di`/`minus_di` start at candle `period`, `adx` at
+/// candle `2 * period - 1`; all series end at the last candle.
+pub fn calculate_adx(candles: &[CandleData], period: usize) -> Result<AdxResult, String> {
+    if period == 0 {
+        return Err("ADX period must be greater than zero".to_string());
+    }
+    if candles.len() < period * 2 {
+        return Err(format!(
+            "Need at least {} candles for ADX calculation",
+            period * 2
+        ));
+    }
+
+    let mut true_ranges = Vec::new();
+    let mut plus_dm = Vec::new();
+    let mut minus_dm = Vec::new();
+
+    for i in 1..candles.len() {
+        let up_move = candles[i].high - candles[i - 1].high;
+        let down_move = candles[i - 1].low - candles[i].low;
+
+        plus_dm.push(if up_move > down_move && up_move > 0.0 {
+            up_move
+        } else {
+            0.0
+        });
+        minus_dm.push(if down_move > up_move && down_move > 0.0 {
+            down_move
+        } else {
+            0.0
+        });
+
+        let high_low = candles[i].high - candles[i].low;
+        let high_close_prev = (candles[i].high - candles[i - 1].close).abs();
+        let low_close_prev = (candles[i].low - candles[i - 1].close).abs();
+        true_ranges.push(high_low.max(high_close_prev).max(low_close_prev));
+    }
+
+    // Wilder smoothing: seed with the sum of the first `period` values
+    let mut smoothed_tr: f64 = true_ranges.iter().take(period).sum();
+    let mut smoothed_plus: f64 = plus_dm.iter().take(period).sum();
+    let mut smoothed_minus: f64 = minus_dm.iter().take(period).sum();
+
+    let mut plus_di = Vec::new();
+    let mut minus_di = Vec::new();
+    let mut dx_values = Vec::new();
+
+    for i in period - 1..true_ranges.len() {
+        if i >= period {
+            smoothed_tr = smoothed_tr - smoothed_tr / period as f64 + true_ranges[i];
+            smoothed_plus = smoothed_plus - smoothed_plus / period as f64 + plus_dm[i];
+            smoothed_minus = smoothed_minus - smoothed_minus / period as f64 + minus_dm[i];
+        }
+
+        let (pdi, mdi) = if smoothed_tr == 0.0 {
+            (0.0, 0.0)
+        } else {
+            (
+                100.0 * smoothed_plus / smoothed_tr,
+                100.0 * smoothed_minus / smoothed_tr,
+            )
+        };
+        let dx = if pdi + mdi == 0.0 {
+            0.0
+        } else {
+            100.0 * (pdi - mdi).abs() / (pdi + mdi)
+        };
+
+        plus_di.push(pdi);
+        minus_di.push(mdi);
+        dx_values.push(dx);
+    }
+
+    // ADX = Wilder-smoothed DX, seeded with the average of the first `period` DX values
+    let mut adx_value = dx_values.iter().take(period).sum::<f64>() / period as f64;
+    let mut adx = vec![adx_value];
+    for &dx in dx_values.iter().skip(period) {
+        adx_value = (adx_value * (period - 1) as f64 + dx) / period as f64;
+        adx.push(adx_value);
+    }
+
+    Ok(AdxResult {
+        adx,
+        plus_di,
+        minus_di,
+    })
+}
+
+/// On-Balance Volume (one value per candle, starting at 0)
+pub fn calculate_obv(candles: &[CandleData]) -> Result<Vec<f64>, String> {
+    if candles.is_empty() {
+        return Err("No data for OBV calculation".to_string());
+    }
+
+    let mut obv = 0.0;
+    let mut obv_values = vec![obv];
+
+    for i in 1..candles.len() {
+        if candles[i].close > candles[i - 1].close {
+            obv += candles[i].volume;
+        } else if candles[i].close < candles[i - 1].close {
+            obv -= candles[i].volume;
+        }
+        obv_values.push(obv);
+    }
+
+    Ok(obv_values)
+}
+
+/// Ichimoku Kinko Hyo lines
+#[derive(Debug, Clone)]
+pub struct IchimokuResult {
+    pub tenkan_sen: Vec<f64>,
+    pub kijun_sen: Vec<f64>,
+    pub senkou_span_a: Vec<f64>,
+    pub senkou_span_b: Vec<f64>,
+}
+
+/// Ichimoku cloud. Values are reported at the candle they are computed from, without
+/// the forward displacement used for charting: the cloud in effect for the latest
+/// candle is the span values `kijun_period` candles back. The chikou span is the close
+/// itself and is not returned.
+pub fn calculate_ichimoku(
+    candles: &[CandleData],
+    tenkan_period: usize,
+    kijun_period: usize,
+    senkou_b_period: usize,
+) -> Result<IchimokuResult, String> {
+    if tenkan_period == 0 || kijun_period == 0 || senkou_b_period == 0 {
+        return Err("Ichimoku periods must be greater than zero".to_string());
+    }
+    let longest = tenkan_period.max(kijun_period).max(senkou_b_period);
+    if candles.len() < longest {
+        return Err(format!(
+            "Need at least {longest} candles for Ichimoku calculation"
+        ));
+    }
+
+    let tenkan_sen = calculate_midpoints(candles, tenkan_period);
+    let kijun_sen = calculate_midpoints(candles, kijun_period);
+    let senkou_span_b = calculate_midpoints(candles, senkou_b_period);
+
+    // Span A needs both lines, so it starts where the longer of the two starts
+    let span_a_len = tenkan_sen.len().min(kijun_sen.len());
+    let senkou_span_a = tenkan_sen[tenkan_sen.len() - span_a_len..]
+        .iter()
+        .zip(&kijun_sen[kijun_sen.len() - span_a_len..])
+        .map(|(tenkan, kijun)| (tenkan + kijun) / 2.0)
+        .collect();
+
+    Ok(IchimokuResult {
+        tenkan_sen,
+        kijun_sen,
+        senkou_span_a,
+        senkou_span_b,
+    })
+}
+
+/// Midpoint of the highest high and lowest low over each `period` window
+fn calculate_midpoints(candles: &[CandleData], period: usize) -> Vec<f64> {
+    candles
+        .windows(period)
+        .map(|window| {
+            let highest_high = window
+                .iter()
+                .map(|c| c.high)
+                .fold(f64::NEG_INFINITY, f64::max);
+            let lowest_low = window.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+            (highest_high + lowest_low) / 2.0
+        })
+        .collect()
+}
+
+/// Keltner Channels calculation
+#[derive(Debug, Clone)]
+pub struct KeltnerChannels {
+    pub upper: Vec<f64>,
+    pub middle: Vec<f64>,
+    pub lower: Vec<f64>,
+}
+
+/// Keltner Channels: EMA of close +/- `multiplier` x ATR
+pub fn calculate_keltner_channels(
+    candles: &[CandleData],
+    ema_period: usize,
+    atr_period: usize,
+    multiplier: f64,
+) -> Result<KeltnerChannels, String> {
+    if ema_period == 0 || atr_period == 0 || candles.len() < ema_period.max(atr_period + 1) {
+        return Err("Insufficient data for Keltner Channels calculation".to_string());
+    }
+
+    let prices: Vec<f64> = candles.iter().map(|c| c.close).collect();
+    let ema = calculate_ema(&prices, ema_period)?;
+    let atr = calculate_atr(candles, atr_period)?;
+
+    // Both series end at the last candle; keep the overlapping tail
+    let len = ema.len().min(atr.len());
+    let middle = ema[ema.len() - len..].to_vec();
+    let atr = &atr[atr.len() - len..];
+
+    let upper = middle
+        .iter()
+        .zip(atr)
+        .map(|(mid, range)| mid + multiplier * range)
+        .collect();
+    let lower = middle
+        .iter()
+        .zip(atr)
+        .map(|(mid, range)| mid - multiplier * range)
+        .collect();
+
+    Ok(KeltnerChannels {
+        upper,
+        middle,
+        lower,
+    })
+}
+
+/// Parabolic SAR (Wilder). One value per candle starting at the second candle.
+pub fn calculate_parabolic_sar(
+    candles: &[CandleData],
+    step: f64,
+    max_step: f64,
+) -> Result<Vec<f64>, String> {
+    if candles.len() < 2 {
+        return Err("Need at least 2 candles for Parabolic SAR calculation".to_string());
+    }
+    if step <= 0.0 || max_step < step {
+        return Err("Parabolic SAR requires 0 < step <= max_step".to_string());
+    }
+
+    // Initial trend from the first two closes
+    let mut is_long = candles[1].close >= candles[0].close;
+    let mut sar = if is_long {
+        candles[0].low
+    } else {
+        candles[0].high
+    };
+    let mut extreme_point = if is_long {
+        candles[0].high
+    } else {
+        candles[0].low
+    };
+    let mut acceleration = step;
+    let mut sar_values = Vec::new();
+
+    for i in 1..candles.len() {
+        let candle = &candles[i];
+        let mut next_sar = sar + acceleration * (extreme_point - sar);
+
+        if is_long {
+            // SAR may not move into the prior two candles' range
+            next_sar = next_sar.min(candles[i - 1].low);
+            if i >= 2 {
+                next_sar = next_sar.min(candles[i - 2].low);
+            }
+
+            if candle.low < next_sar {
+                is_long = false;
+                next_sar = extreme_point;
+                extreme_point = candle.low;
+                acceleration = step;
+            } else if candle.high > extreme_point {
+                extreme_point = candle.high;
+                acceleration = (acceleration + step).min(max_step);
+            }
+        } else {
+            next_sar = next_sar.max(candles[i - 1].high);
+            if i >= 2 {
+                next_sar = next_sar.max(candles[i - 2].high);
+            }
+
+            if candle.high > next_sar {
+                is_long = true;
+                next_sar = extreme_point;
+                extreme_point = candle.high;
+                acceleration = step;
+            } else if candle.low < extreme_point {
+                extreme_point = candle.low;
+                acceleration = (acceleration + step).min(max_step);
+            }
+        }
+
+        sar = next_sar;
+        sar_values.push(sar);
+    }
+
+    Ok(sar_values)
+}
+
+/// Session VWAP: cumulative typical-price VWAP that resets whenever a candle opens in
+/// a new `session_ms` bucket (e.g. 86_400_000 for UTC days). One value per candle.
+pub fn calculate_session_vwap(candles: &[CandleData], session_ms: i64) -> Result<Vec<f64>, String> {
+    if candles.is_empty() {
+        return Err("No data for VWAP calculation".to_string());
+    }
+    if session_ms <= 0 {
+        return Err("VWAP session length must be positive".to_string());
+    }
+
+    let mut vwap_values = Vec::new();
+    let mut session = None;
+    let mut cumulative_pv = 0.0;
+    let mut cumulative_volume = 0.0;
+
+    for candle in candles {
+        let candle_session = candle.open_time.div_euclid(session_ms);
+        if session != Some(candle_session) {
+            session = Some(candle_session);
+            cumulative_pv = 0.0;
+            cumulative_volume = 0.0;
+        }
+
+        let typical_price = (candle.high + candle.low + candle.close) / 3.0;
+        cumulative_pv += typical_price * candle.volume;
+        cumulative_volume += candle.volume;
+
+        vwap_values.push(if cumulative_volume > 0.0 {
+            cumulative_pv / cumulative_volume
+        } else {
+            // No volume traded yet this session
+            typical_price
+        });
+    }
+
+    Ok(vwap_values)
+}
+
+/// Commodity Channel Index
+pub fn calculate_cci(candles: &[CandleData], period: usize) -> Result<Vec<f64>, String> {
+    if period == 0 || candles.len() < period {
+        return Err("Insufficient data for CCI calculation".to_string());
+    }
+
+    let typical_prices: Vec<f64> = candles
+        .iter()
+        .map(|c| (c.high + c.low + c.close) / 3.0)
+        .collect();
+    let sma = calculate_sma(&typical_prices, period)?;
+
+    let cci_values = sma
+        .iter()
+        .enumerate()
+        .map(|(i, &mean)| {
+            let window = &typical_prices[i..i + period];
+            let mean_deviation =
+                window.iter().map(|tp| (tp - mean).abs()).sum::<f64>() / period as f64;
+            if mean_deviation == 0.0 {
+                0.0
+            } else {
+                (window[period - 1] - mean) / (0.015 * mean_deviation)
+            }
+        })
+        .collect();
+
+    Ok(cci_values)
+}
+
+/// Williams %R (-100 to 0)
+pub fn calculate_williams_r(candles: &[CandleData], period: usize) -> Result<Vec<f64>, String> {
+    if period == 0 || candles.len() < period {
+        return Err("Insufficient data for Williams %R calculation".to_string());
+    }
+
+    let williams_r = candles
+        .windows(period)
+        .map(|window| {
+            let highest_high = window
+                .iter()
+                .map(|c| c.high)
+                .fold(f64::NEG_INFINITY, f64::max);
+            let lowest_low = window.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+            let close = window[period - 1].close;
+            if highest_high == lowest_low {
+                -50.0
+            } else {
+                (highest_high - close) / (highest_high - lowest_low) * -100.0
+            }
+        })
+        .collect();
+
+    Ok(williams_r)
+}
+
+/// Supertrend line and direction
+#[derive(Debug, Clone)]
+pub struct SupertrendResult {
+    pub supertrend: Vec<f64>,
+    pub is_uptrend: Vec<bool>,
+}
+
+/// Supertrend: ATR bands around the candle midpoint that only tighten while the trend
+/// holds. Values start at candle `period` (where ATR starts) and end at the last candle.
+pub fn calculate_supertrend(
+    candles: &[CandleData],
+    period: usize,
+    multiplier: f64,
+) -> Result<SupertrendResult, String> {
+    if period == 0 {
+        return Err("Supertrend period must be greater than zero".to_string());
+    }
+    let atr = calculate_atr(candles, period)?;
+    let offset = candles.len() - atr.len();
+
+    let mut supertrend = Vec::with_capacity(atr.len());
+    let mut is_uptrend = Vec::with_capacity(atr.len());
+    let mut final_upper = 0.0;
+    let mut final_lower = 0.0;
+    let mut uptrend = true;
+
+    for (i, &range) in atr.iter().enumerate() {
+        let candle = &candles[offset + i];
+        let midpoint = (candle.high + candle.low) / 2.0;
+        let basic_upper = midpoint + multiplier * range;
+        let basic_lower = midpoint - multiplier * range;
+
+        if i == 0 {
+            final_upper = basic_upper;
+            final_lower = basic_lower;
+            uptrend = candle.close >= midpoint;
+        } else {
+            let prev_close = candles[offset + i - 1].close;
+            // Bands only move toward price unless the previous close broke through them
+            if basic_upper < final_upper || prev_close > final_upper {
+                final_upper = basic_upper;
+            }
+            if basic_lower > final_lower || prev_close < final_lower {
+                final_lower = basic_lower;
+            }
+
+            if uptrend && candle.close < final_lower {
+                uptrend = false;
+            } else if !uptrend && candle.close > final_upper {
+                uptrend = true;
+            }
+        }
+
+        supertrend.push(if uptrend { final_lower } else { final_upper });
+        is_uptrend.push(uptrend);
+    }
+
+    Ok(SupertrendResult {
+        supertrend,
+        is_uptrend,
+    })
+}
+
+/// Donchian Channels calculation
+#[derive(Debug, Clone)]
+pub struct DonchianChannels {
+    pub upper: Vec<f64>,
+    pub middle: Vec<f64>,
+    pub lower: Vec<f64>,
+}
+
+/// Donchian Channels: highest high and lowest low over each `period` window
+pub fn calculate_donchian_channels(
+    candles: &[CandleData],
+    period: usize,
+) -> Result<DonchianChannels, String> {
+    if period == 0 || candles.len() < period {
+        return Err("Insufficient data for Donchian Channels calculation".to_string());
+    }
+
+    let mut upper = Vec::new();
+    let mut middle = Vec::new();
+    let mut lower = Vec::new();
+
+    for window in candles.windows(period) {
+        let highest_high = window
+            .iter()
+            .map(|c| c.high)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let lowest_low = window.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+
+        upper.push(highest_high);
+        middle.push((highest_high + lowest_low) / 2.0);
+        lower.push(lowest_low);
+    }
+
+    Ok(DonchianChannels {
+        upper,
+        middle,
+        lower,
+    })
+}
+
+/// Indicator series end at the last candle; pad the front with `None` so index `i`
+/// lines up with `candles[i]`
+pub fn align_to_candles(values: Vec<f64>, len: usize) -> Vec<Option<f64>> {
+    let offset = len.saturating_sub(values.len());
+    (0..len)
+        .map(|i| i.checked_sub(offset).and_then(|j| values.get(j).copied()))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1908,4 +2413,261 @@ mod tests {
             "POC must be at the price level with maximum volume"
         );
     }
+
+    fn create_wave_candles(count: usize) -> Vec<CandleData> {
+        (0..count)
+            .map(|i| {
+                let t = i as f64;
+                let close = 100.0 + (t * 0.35).sin() * 6.0 + t * 0.1;
+                CandleData {
+                    open: close - 0.4,
+                    high: close + 1.0 + (t * 0.9).cos().abs(),
+                    low: close - 1.0 - (t * 0.6).sin().abs(),
+                    close,
+                    volume: 1000.0 + (t * 0.5).cos() * 300.0,
+                    open_time: (i as i64) * 3_600_000,
+                    close_time: (i as i64) * 3_600_000 + 3_599_999,
+                    quote_volume: 0.0,
+                    trades: 100,
+                    is_closed: true,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_calculate_adx_lengths_and_ranges() {
+        let candles = create_wave_candles(80);
+        let result = calculate_adx(&candles, 14).unwrap();
+
+        assert_eq!(result.plus_di.len(), 80 - 14);
+        assert_eq!(result.minus_di.len(), 80 - 14);
+        assert_eq!(result.adx.len(), 80 - 2 * 14 + 1);
+        for value in result
+            .adx
+            .iter()
+            .chain(&result.plus_di)
+            .chain(&result.minus_di)
+        {
+            assert!((0.0..=100.0).contains(value));
+        }
+    }
+
+    #[test]
+    fn test_calculate_adx_strong_uptrend() {
+        let prices: Vec<f64> = (0..60).map(|i| 100.0 + i as f64 * 2.0).collect();
+        let candles = create_test_candles(prices);
+        let result = calculate_adx(&candles, 14).unwrap();
+
+        // Every candle makes a higher high and higher low: -DM is always zero
+        assert!(result.minus_di.iter().all(|&v| v == 0.0));
+        assert!(*result.plus_di.last().unwrap() > 0.0);
+        assert!((result.adx.last().unwrap() - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_calculate_adx_matches_hand_computed_di() {
+        // period 2: DM/TR sums over the first two moves give the first DI values
+        let candles = create_test_candles_with_range(
+            vec![10.0, 11.0, 10.5, 12.0],
+            vec![10.5, 11.5, 11.0, 12.5],
+            vec![9.5, 10.5, 10.0, 11.5],
+        );
+        let result = calculate_adx(&candles, 2).unwrap();
+
+        // Moves: (+DM 1.0, TR 1.5), (-DM 0.5, TR 1.0)
+        assert!((result.plus_di[0] - 40.0).abs() < 1e-9);
+        assert!((result.minus_di[0] - 20.0).abs() < 1e-9);
+        assert!(calculate_adx(&candles[..3], 2).is_err());
+        assert!(calculate_adx(&candles, 0).is_err());
+    }
+
+    #[test]
+    fn test_calculate_obv() {
+        let mut candles = create_test_candles(vec![10.0, 11.0, 11.0, 10.0, 12.0]);
+        for (candle, volume) in candles.iter_mut().zip([100.0, 200.0, 300.0, 400.0, 500.0]) {
+            candle.volume = volume;
+        }
+
+        let obv = calculate_obv(&candles).unwrap();
+        assert_eq!(obv, vec![0.0, 200.0, 200.0, -200.0, 300.0]);
+        assert!(calculate_obv(&[]).is_err());
+    }
+
+    #[test]
+    fn test_calculate_ichimoku() {
+        let candles = create_wave_candles(60);
+        let result = calculate_ichimoku(&candles, 9, 26, 52).unwrap();
+
+        assert_eq!(result.tenkan_sen.len(), 60 - 9 + 1);
+        assert_eq!(result.kijun_sen.len(), 60 - 26 + 1);
+        assert_eq!(result.senkou_span_a.len(), result.kijun_sen.len());
+        assert_eq!(result.senkou_span_b.len(), 60 - 52 + 1);
+
+        // Cross-check the latest values against a direct window computation
+        let midpoint = |window: &[CandleData]| {
+            let high = window
+                .iter()
+                .map(|c| c.high)
+                .fold(f64::NEG_INFINITY, f64::max);
+            let low = window.iter().map(|c| c.low).fold(f64::INFINITY, f64::min);
+            (high + low) / 2.0
+        };
+        let tenkan = midpoint(&candles[60 - 9..]);
+        let kijun = midpoint(&candles[60 - 26..]);
+        assert!((result.tenkan_sen.last().unwrap() - tenkan).abs() < 1e-9);
+        assert!((result.kijun_sen.last().unwrap() - kijun).abs() < 1e-9);
+        assert!((result.senkou_span_a.last().unwrap() - (tenkan + kijun) / 2.0).abs() < 1e-9);
+        assert!((result.senkou_span_b[0] - midpoint(&candles[..52])).abs() < 1e-9);
+
+        assert!(calculate_ichimoku(&candles[..40], 9, 26, 52).is_err());
+        assert!(calculate_ichimoku(&candles, 0, 26, 52).is_err());
+    }
+
+    #[test]
+    fn test_calculate_keltner_channels() {
+        let candles = create_wave_candles(50);
+        let channels = calculate_keltner_channels(&candles, 20, 10, 2.0).unwrap();
+
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let ema = calculate_ema(&closes, 20).unwrap();
+        let atr = calculate_atr(&candles, 10).unwrap();
+
+        // EMA(20) is the shorter series, so the channel spans it exactly
+        assert_eq!(channels.middle, ema);
+        let last = channels.middle.len() - 1;
+        let last_atr = *atr.last().unwrap();
+        assert!((channels.upper[last] - (ema[last] + 2.0 * last_atr)).abs() < 1e-9);
+        assert!((channels.lower[last] - (ema[last] - 2.0 * last_atr)).abs() < 1e-9);
+
+        assert!(calculate_keltner_channels(&candles[..10], 20, 10, 2.0).is_err());
+    }
+
+    #[test]
+    fn test_calculate_parabolic_sar_trends() {
+        let rising: Vec<f64> = (0..30).map(|i| 100.0 + i as f64).collect();
+        let candles = create_test_candles(rising);
+        let sar = calculate_parabolic_sar(&candles, 0.02, 0.2).unwrap();
+
+        assert_eq!(sar.len(), 29);
+        // In an uptrend SAR trails below every low and keeps rising
+        for (value, candle) in sar.iter().zip(&candles[1..]) {
+            assert!(*value < candle.low);
+        }
+        assert!(sar.windows(2).all(|w| w[1] >= w[0]));
+    }
+
+    #[test]
+    fn test_calculate_parabolic_sar_reversal() {
+        let mut prices: Vec<f64> = (0..15).map(|i| 100.0 + i as f64).collect();
+        prices.extend((0..15).map(|i| 112.0 - i as f64 * 3.0));
+        let candles = create_test_candles(prices);
+        let sar = calculate_parabolic_sar(&candles, 0.02, 0.2).unwrap();
+
+        // After the sell-off the SAR flips above price
+        let last_candle = candles.last().unwrap();
+        assert!(*sar.last().unwrap() > last_candle.high);
+
+        assert!(calculate_parabolic_sar(&candles[..1], 0.02, 0.2).is_err());
+        assert!(calculate_parabolic_sar(&candles, 0.3, 0.2).is_err());
+    }
+
+    #[test]
+    fn test_calculate_session_vwap_resets_each_session() {
+        let mut candles = create_test_candles_with_range(
+            vec![10.0, 20.0, 30.0, 40.0],
+            vec![10.0, 20.0, 30.0, 40.0],
+            vec![10.0, 20.0, 30.0, 40.0],
+        );
+        let session_ms = 120_000;
+        for (candle, volume) in candles.iter_mut().zip([1.0, 3.0, 2.0, 0.0]) {
+            candle.volume = volume;
+        }
+
+        // Candles 0-1 fall in the first 2-minute session, 2-3 in the second
+        let vwap = calculate_session_vwap(&candles, session_ms).unwrap();
+        assert!((vwap[0] - 10.0).abs() < 1e-9);
+        assert!((vwap[1] - (10.0 + 60.0) / 4.0).abs() < 1e-9);
+        assert!((vwap[2] - 30.0).abs() < 1e-9);
+        assert!((vwap[3] - 30.0).abs() < 1e-9);
+
+        assert!(calculate_session_vwap(&[], session_ms).is_err());
+        assert!(calculate_session_vwap(&candles, 0).is_err());
+    }
+
+    #[test]
+    fn test_calculate_cci() {
+        let candles = create_test_candles_with_range(
+            vec![10.0, 12.0, 14.0],
+            vec![10.0, 12.0, 14.0],
+            vec![10.0, 12.0, 14.0],
+        );
+        // Typical prices 10, 12, 14: mean 12, mean deviation 4/3
+        let cci = calculate_cci(&candles, 3).unwrap();
+        assert_eq!(cci.len(), 1);
+        assert!((cci[0] - 2.0 / (0.015 * 4.0 / 3.0)).abs() < 1e-9);
+
+        let flat = create_test_candles(vec![100.0; 25]);
+        assert!(calculate_cci(&flat, 20).unwrap().iter().all(|&v| v == 0.0));
+        assert!(calculate_cci(&flat, 30).is_err());
+    }
+
+    #[test]
+    fn test_calculate_williams_r_matches_stochastic() {
+        let candles = create_wave_candles(60);
+        let williams_r = calculate_williams_r(&candles, 14).unwrap();
+        let stochastic = calculate_stochastic(&candles, 14, 3).unwrap();
+
+        // %R is the stochastic %K shifted down by 100
+        assert_eq!(williams_r.len(), stochastic.k_percent.len());
+        for (r, k) in williams_r.iter().zip(&stochastic.k_percent) {
+            assert!((r - (k - 100.0)).abs() < 1e-9);
+            assert!((-100.0..=0.0).contains(r));
+        }
+        assert!(calculate_williams_r(&candles[..5], 14).is_err());
+    }
+
+    #[test]
+    fn test_calculate_supertrend_follows_trend() {
+        let mut prices: Vec<f64> = (0..30).map(|i| 100.0 + i as f64 * 2.0).collect();
+        prices.extend((0..30).map(|i| 158.0 - i as f64 * 3.0));
+        let candles = create_test_candles(prices);
+        let result = calculate_supertrend(&candles, 10, 3.0).unwrap();
+
+        assert_eq!(result.supertrend.len(), 60 - 10);
+        assert_eq!(result.is_uptrend.len(), result.supertrend.len());
+        // Rally: uptrend with the line below price
+        assert!(result.is_uptrend[15]);
+        assert!(result.supertrend[15] < candles[25].low);
+        // Sell-off: flipped to downtrend with the line above price
+        assert!(!result.is_uptrend.last().unwrap());
+        assert!(result.supertrend.last().unwrap() > &candles.last().unwrap().high);
+    }
+
+    #[test]
+    fn test_calculate_supertrend_lower_band_never_falls_in_uptrend() {
+        let prices: Vec<f64> = (0..50).map(|i| 100.0 + i as f64).collect();
+        let candles = create_test_candles(prices);
+        let result = calculate_supertrend(&candles, 7, 2.0).unwrap();
+
+        assert!(result.is_uptrend.iter().all(|&up| up));
+        assert!(result.supertrend.windows(2).all(|w| w[1] >= w[0]));
+        assert!(calculate_supertrend(&candles[..5], 7, 2.0).is_err());
+        assert!(calculate_supertrend(&candles, 0, 2.0).is_err());
+    }
+
+    #[test]
+    fn test_calculate_donchian_channels() {
+        let candles = create_test_candles_with_range(
+            vec![10.0, 12.0, 11.0, 9.0],
+            vec![10.5, 13.0, 11.5, 9.5],
+            vec![9.5, 11.0, 10.0, 8.0],
+        );
+        let channels = calculate_donchian_channels(&candles, 3).unwrap();
+
+        assert_eq!(channels.upper, vec![13.0, 13.0]);
+        assert_eq!(channels.lower, vec![9.5, 8.0]);
+        assert_eq!(channels.middle, vec![11.25, 10.5]);
+        assert!(calculate_donchian_channels(&candles, 5).is_err());
+    }
 }
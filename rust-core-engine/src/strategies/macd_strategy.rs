@@ -1,5 +1,6 @@
 use super::*;
 use crate::strategies::indicators::calculate_macd;
+use crate::strategies::streaming_indicators::MacdValue;
 use async_trait::async_trait;
 use serde_json::json;
 
@@ -68,6 +69,40 @@ impl MacdStrategy {
             .and_then(|v| v.as_f64())
             .unwrap_or(0.001)
     }
+
+    /// Uses the streaming snapshot when it was built with the same periods
+    fn current_and_previous_macd(
+        &self,
+        data: &StrategyInput,
+        timeframe: &str,
+        candles: &[CandleData],
+        (fast_period, slow_period, signal_period): (usize, usize, usize),
+    ) -> Result<(MacdValue, MacdValue), StrategyError> {
+        if let Some(values) = data
+            .indicators
+            .get(timeframe)
+            .and_then(|indicators| indicators.macd(fast_period, slow_period, signal_period))
+        {
+            return Ok(values);
+        }
+
+        let macd = calculate_macd(candles, fast_period, slow_period, signal_period)
+            .map_err(StrategyError::CalculationError)?;
+
+        // Series are aligned at the end, so offset `back` is the same candle in each
+        let value_at = |back: usize| -> Option<MacdValue> {
+            let at = |series: &[f64]| series.len().checked_sub(back + 1).map(|i| series[i]);
+            Some(MacdValue {
+                macd: at(&macd.macd_line)?,
+                signal: at(&macd.signal_line)?,
+                histogram: at(&macd.histogram)?,
+            })
+        };
+        let current = value_at(0).ok_or_else(|| {
+            StrategyError::InsufficientData(format!("No {timeframe} MACD values"))
+        })?;
+        Ok((current, value_at(1).unwrap_or(current)))
+    }
 }
 
 #[async_trait]
@@ -107,63 +142,31 @@ impl Strategy for MacdStrategy {
         let slow_period = self.get_slow_period();
         let signal_period = self.get_signal_period();
 
-        // Calculate MACD for both timeframes
-        let primary_macd = calculate_macd(primary_candles, fast_period, slow_period, signal_period)
-            .map_err(StrategyError::CalculationError)?;
-
-        let confirmation_macd = calculate_macd(
+        // Current and previous MACD values for both timeframes
+        let (current_1h, prev_1h) = self.current_and_previous_macd(
+            data,
+            primary_timeframe,
+            primary_candles,
+            (fast_period, slow_period, signal_period),
+        )?;
+        let (current_4h, prev_4h) = self.current_and_previous_macd(
+            data,
+            confirmation_timeframe,
             confirmation_candles,
-            fast_period,
-            slow_period,
-            signal_period,
-        )
-        .map_err(StrategyError::CalculationError)?;
-
-        // Get current values with proper error handling
-        let current_macd_1h = *primary_macd
-            .macd_line
-            .last()
-            .ok_or_else(|| StrategyError::InsufficientData("No MACD line values".to_string()))?;
-        let current_signal_1h = *primary_macd.signal_line.last().ok_or_else(|| {
-            StrategyError::InsufficientData("No MACD signal line values".to_string())
-        })?;
-        let current_histogram_1h = *primary_macd.histogram.last().ok_or_else(|| {
-            StrategyError::InsufficientData("No MACD histogram values".to_string())
-        })?;
+            (fast_period, slow_period, signal_period),
+        )?;
 
-        let current_macd_4h = *confirmation_macd
-            .macd_line
-            .last()
-            .ok_or_else(|| StrategyError::InsufficientData("No 4h MACD line values".to_string()))?;
-        let current_signal_4h = *confirmation_macd.signal_line.last().ok_or_else(|| {
-            StrategyError::InsufficientData("No 4h MACD signal line values".to_string())
-        })?;
-        let current_histogram_4h = *confirmation_macd.histogram.last().ok_or_else(|| {
-            StrategyError::InsufficientData("No 4h MACD histogram values".to_string())
-        })?;
+        let current_macd_1h = current_1h.macd;
+        let current_signal_1h = current_1h.signal;
+        let current_histogram_1h = current_1h.histogram;
+        let current_macd_4h = current_4h.macd;
+        let current_signal_4h = current_4h.signal;
+        let current_histogram_4h = current_4h.histogram;
 
-        // Get previous values for trend analysis
-        let prev_macd_1h = if primary_macd.macd_line.len() > 1 {
-            primary_macd.macd_line[primary_macd.macd_line.len() - 2]
-        } else {
-            current_macd_1h
-        };
-        let prev_signal_1h = if primary_macd.signal_line.len() > 1 {
-            primary_macd.signal_line[primary_macd.signal_line.len() - 2]
-        } else {
-            current_signal_1h
-        };
-        let prev_histogram_1h = if primary_macd.histogram.len() > 1 {
-            primary_macd.histogram[primary_macd.histogram.len() - 2]
-        } else {
-            current_histogram_1h
-        };
-
-        let prev_histogram_4h = if confirmation_macd.histogram.len() > 1 {
-            confirmation_macd.histogram[confirmation_macd.histogram.len() - 2]
-        } else {
-            current_histogram_4h
-        };
+        let prev_macd_1h = prev_1h.macd;
+        let prev_signal_1h = prev_1h.signal;
+        let prev_histogram_1h = prev_1h.histogram;
+        let prev_histogram_4h = prev_4h.histogram;
 
         // Determine signal and confidence
         let (signal, confidence, reasoning) = self.analyze_macd_signals(
@@ -367,6 +370,7 @@ impl Default for MacdStrategy {
 mod tests {
     use super::*;
     use crate::market_data::cache::CandleData;
+    use crate::market_data::indicator_cache::IndicatorCache;
     use std::collections::HashMap;
 
     fn create_test_candles(prices: Vec<f64>) -> Vec<CandleData> {
@@ -399,9 +403,56 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         }
     }
 
+    /// Attach snapshots the way the paper engine does via `IndicatorCache::sync`
+    fn with_streaming_indicators(mut input: StrategyInput) -> StrategyInput {
+        let cache = IndicatorCache::default();
+        for (timeframe, candles) in &input.timeframe_data {
+            if let Some(snapshot) = cache.sync(&input.symbol, timeframe, candles) {
+                input.indicators.insert(timeframe.clone(), snapshot);
+            }
+        }
+        input
+    }
+
+    #[tokio::test]
+    async fn test_macd_strategy_streaming_indicators_match_batch() {
+        let strategy = MacdStrategy::new();
+        let prices_1h: Vec<f64> = (0..100)
+            .map(|i| 100.0 + (i as f64 * 0.3).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let prices_4h: Vec<f64> = (0..100)
+            .map(|i| 100.0 + (i as f64 * 0.15).cos() * 6.0 - i as f64 * 0.02)
+            .collect();
+
+        let batch_input = create_test_input(prices_1h.clone(), prices_4h.clone());
+        let streaming_input = with_streaming_indicators(batch_input.clone());
+        for snapshot in streaming_input.indicators.values() {
+            assert!(snapshot.macd(12, 26, 9).is_some());
+        }
+
+        let batch = strategy.analyze(&batch_input).await.unwrap();
+        let streaming = strategy.analyze(&streaming_input).await.unwrap();
+        assert_eq!(batch.signal, streaming.signal);
+        assert!((batch.confidence - streaming.confidence).abs() < 1e-9);
+
+        // The streaming result comes from the snapshot, not a batch recalculation
+        let reported = streaming.metadata["histogram_1h"].as_f64().unwrap();
+        assert_eq!(
+            reported,
+            streaming_input.indicators["5m"]
+                .macd(12, 26, 9)
+                .unwrap()
+                .0
+                .histogram
+        );
+        assert!((reported - batch.metadata["histogram_1h"].as_f64().unwrap()).abs() < 1e-6);
+    }
+
     #[tokio::test]
     async fn test_macd_strategy_new() {
         let strategy = MacdStrategy::new();
@@ -1011,6 +1062,8 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let result = strategy.analyze(&input).await;
@@ -1033,6 +1086,8 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let result = strategy.analyze(&input).await;
@@ -1054,6 +1109,8 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let result = strategy.analyze(&input).await;
@@ -1072,6 +1129,8 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let result = strategy.validate_data(&input);
@@ -1,4 +1,6 @@
+use crate::market_data::cache::CandleData;
 use crate::strategies::trend_filter::TrendDirection;
+use crate::strategies::trend_model::TrendModel;
 use serde::{Deserialize, Serialize};
 
 /// ML-based trend prediction result
@@ -6,17 +8,23 @@ use serde::{Deserialize, Serialize};
 pub struct MLTrendPrediction {
     pub trend: TrendDirection,
     pub confidence: f64, // 0.0 - 1.0
-    pub model: String,   // Model name (e.g., "LSTM", "GRU", "Ensemble")
+    pub model: String,   // Model name (e.g., "LogisticRegression")
     pub timestamp: i64,
 }
 
-/// Configuration for ML trend predictor (retained for interface compatibility)
+/// Configuration for the ML trend predictor
+///
+/// `service_url` and `timeout_ms` are retained for interface compatibility with the
+/// removed Python service; predictions are served from the model at `model_path`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MLPredictorConfig {
     pub service_url: String,
     pub timeout_ms: u64,
     pub min_confidence: f64,
     pub fallback_on_error: bool,
+    /// Trained `TrendModel` file; without one every prediction fails
+    #[serde(default)]
+    pub model_path: Option<String>,
 }
 
 impl Default for MLPredictorConfig {
@@ -26,53 +34,110 @@ impl Default for MLPredictorConfig {
             timeout_ms: 2000,
             min_confidence: 0.65,
             fallback_on_error: true,
+            model_path: None,
         }
     }
 }
 
-/// No-op stub — Python ML service removed.
-/// Trend prediction is now handled by the Rust strategy engine (RSI/MACD/Bollinger).
+/// In-process trend predictor serving a `TrendModel` trained offline
 pub struct MLTrendPredictor {
     config: MLPredictorConfig,
+    model: Option<TrendModel>,
 }
 
 impl MLTrendPredictor {
+    /// Load the model from `config.model_path`, if set; a missing or invalid model
+    /// is logged and leaves the predictor without one
     pub fn new(config: MLPredictorConfig) -> Self {
-        Self { config }
+        let model = config
+            .model_path
+            .as_deref()
+            .and_then(|path| match TrendModel::load(path) {
+                Ok(model) => {
+                    log::info!("Loaded trend model from {}", path);
+                    Some(model)
+                },
+                Err(e) => {
+                    log::warn!("Trend model unavailable: {}", e);
+                    None
+                },
+            });
+        Self { config, model }
+    }
+
+    pub fn with_model(config: MLPredictorConfig, model: TrendModel) -> Self {
+        Self {
+            config,
+            model: Some(model),
+        }
     }
 
-    /// Disabled — Python ML service removed. Always returns an error.
+    /// Predict the trend following the last of `candles`
     pub async fn predict_trend(
         &self,
-        _symbol: &str,
-        _timeframe: &str,
+        symbol: &str,
+        timeframe: &str,
+        candles: &[CandleData],
     ) -> Result<MLTrendPrediction, String> {
-        Err("HTTP request failed: Python ML service disabled".to_string())
-    }
-
-    /// Disabled — always returns None (fallback path).
+        let model = self
+            .model
+            .as_ref()
+            .ok_or_else(|| "No trend model loaded".to_string())?;
+        let (trend, confidence) = model.predict(candles).map_err(|e| {
+            format!(
+                "Trend prediction failed for {} {}: {}",
+                symbol, timeframe, e
+            )
+        })?;
+
+        Ok(MLTrendPrediction {
+            trend,
+            confidence,
+            model: "LogisticRegression".to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        })
+    }
+
+    /// Prediction if it succeeds and clears `min_confidence`, otherwise None
     pub async fn predict_trend_with_fallback(
         &self,
-        _symbol: &str,
-        _timeframe: &str,
+        symbol: &str,
+        timeframe: &str,
+        candles: &[CandleData],
     ) -> Option<MLTrendPrediction> {
-        if self.config.fallback_on_error {
-            log::warn!("ML prediction failed (fallback enabled): Python ML service disabled");
-        } else {
-            log::error!("ML prediction failed (no fallback): Python ML service disabled");
+        match self.predict_trend(symbol, timeframe, candles).await {
+            Ok(prediction) if prediction.confidence >= self.config.min_confidence => {
+                Some(prediction)
+            },
+            Ok(_) => None,
+            Err(e) => {
+                if self.config.fallback_on_error {
+                    log::warn!("ML prediction failed (fallback enabled): {}", e);
+                } else {
+                    log::error!("ML prediction failed (no fallback): {}", e);
+                }
+                None
+            },
         }
-        None
     }
 
-    /// Disabled — always returns an error.
+    /// Ok once a model is loaded
     pub async fn health_check(&self) -> Result<(), String> {
-        Err("Health check failed: Python ML service disabled".to_string())
+        match self.model {
+            Some(_) => Ok(()),
+            None => Err("Health check failed: no trend model loaded".to_string()),
+        }
     }
 
     /// Get configuration
     pub fn config(&self) -> &MLPredictorConfig {
         &self.config
     }
+
+    /// Loaded model, if any
+    pub fn model(&self) -> Option<&TrendModel> {
+        self.model.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -95,6 +160,7 @@ mod tests {
             timeout_ms: 5000,
             min_confidence: 0.75,
             fallback_on_error: false,
+            model_path: None,
         };
 
         assert_eq!(config.service_url, "http://ml-service:9000");
@@ -573,6 +639,7 @@ mod tests {
             timeout_ms: 3500,
             min_confidence: 0.72,
             fallback_on_error: false,
+            model_path: None,
         };
 
         let json = serde_json::to_string(&original).unwrap();
@@ -637,6 +704,7 @@ mod tests {
             timeout_ms: 5000,
             min_confidence: 0.8,
             fallback_on_error: false,
+            model_path: None,
         };
 
         let predictor = MLTrendPredictor::new(config.clone());
@@ -658,6 +726,7 @@ mod tests {
             timeout_ms: 3000,
             min_confidence: 0.7,
             fallback_on_error: true,
+            model_path: None,
         };
 
         let predictor = MLTrendPredictor::new(config.clone());
@@ -676,10 +745,13 @@ mod tests {
             timeout_ms: 100,
             min_confidence: 0.65,
             fallback_on_error: false, // No fallback
+            model_path: None,
         };
 
         let predictor = MLTrendPredictor::new(config);
-        let result = predictor.predict_trend_with_fallback("BTCUSDT", "4h").await;
+        let result = predictor
+            .predict_trend_with_fallback("BTCUSDT", "4h", &[])
+            .await;
 
         // Should return None even with fallback_on_error = false
         assert!(result.is_none());
@@ -708,6 +780,7 @@ mod tests {
             timeout_ms: 4500,
             min_confidence: 0.68,
             fallback_on_error: true,
+            model_path: None,
         };
 
         let json = serde_json::to_string(&original).unwrap();
@@ -814,15 +887,15 @@ mod tests {
         }
     }
 
-    // ========== Stub behavior tests — no HTTP calls made ==========
+    // ========== Behavior without a loaded model ==========
 
     #[tokio::test]
     async fn test_predict_trend_returns_err() {
         let predictor = MLTrendPredictor::new(MLPredictorConfig::default());
-        let result = predictor.predict_trend("BTCUSDT", "1h").await;
+        let result = predictor.predict_trend("BTCUSDT", "1h", &[]).await;
         assert!(result.is_err());
         let err = result.unwrap_err();
-        assert!(err.contains("HTTP request failed"));
+        assert!(err.contains("No trend model loaded"));
     }
 
     #[tokio::test]
@@ -832,7 +905,9 @@ mod tests {
             ..Default::default()
         };
         let predictor = MLTrendPredictor::new(config);
-        let result = predictor.predict_trend_with_fallback("BTCUSDT", "1h").await;
+        let result = predictor
+            .predict_trend_with_fallback("BTCUSDT", "1h", &[])
+            .await;
         assert!(result.is_none());
     }
 
@@ -843,7 +918,9 @@ mod tests {
             ..Default::default()
         };
         let predictor = MLTrendPredictor::new(config);
-        let result = predictor.predict_trend_with_fallback("BTCUSDT", "4h").await;
+        let result = predictor
+            .predict_trend_with_fallback("BTCUSDT", "4h", &[])
+            .await;
         assert!(result.is_none());
     }
 
@@ -855,4 +932,91 @@ mod tests {
         let err = result.unwrap_err();
         assert!(err.contains("Health check failed"));
     }
+    // ========== Local model serving ==========
+
+    fn regime_candles(count: usize) -> Vec<CandleData> {
+        let mut price = 100.0;
+        (0..count)
+            .map(|i| {
+                let drift = if (i / 40) % 2 == 0 { 0.004 } else { -0.004 };
+                let open = price;
+                price *= 1.0 + drift + ((i as f64) * 1.7).sin() * 0.001;
+                CandleData {
+                    open_time: i as i64 * 900_000,
+                    close_time: i as i64 * 900_000 + 899_999,
+                    open,
+                    high: open.max(price) * 1.001,
+                    low: open.min(price) * 0.999,
+                    close: price,
+                    volume: 1000.0 + (i % 7) as f64 * 50.0,
+                    quote_volume: 1000.0 * price,
+                    trades: 100,
+                    is_closed: true,
+                }
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_predict_trend_with_loaded_model() {
+        use crate::strategies::trend_model::TrendModelConfig;
+
+        let candles = regime_candles(1200);
+        let model = TrendModel::train(&candles, &TrendModelConfig::default()).unwrap();
+        let predictor = MLTrendPredictor::with_model(MLPredictorConfig::default(), model);
+        assert!(predictor.health_check().await.is_ok());
+
+        let prediction = predictor
+            .predict_trend("BTCUSDT", "15m", &candles[..110])
+            .await
+            .unwrap();
+        assert_eq!(prediction.trend, TrendDirection::Uptrend);
+        assert_eq!(prediction.model, "LogisticRegression");
+
+        // Too little history for the features
+        assert!(predictor
+            .predict_trend("BTCUSDT", "15m", &candles[..20])
+            .await
+            .is_err());
+
+        let strict = MLTrendPredictor::with_model(
+            MLPredictorConfig {
+                min_confidence: 1.0,
+                ..Default::default()
+            },
+            predictor.model().unwrap().clone(),
+        );
+        assert!(strict
+            .predict_trend_with_fallback("BTCUSDT", "15m", &candles[..110])
+            .await
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_new_loads_model_from_path() {
+        use crate::strategies::trend_model::TrendModelConfig;
+
+        let candles = regime_candles(600);
+        let model = TrendModel::train(&candles, &TrendModelConfig::default()).unwrap();
+        let path = std::env::temp_dir().join(format!("ml_predictor_{}.json", uuid::Uuid::new_v4()));
+        model.save(&path).unwrap();
+
+        let predictor = MLTrendPredictor::new(MLPredictorConfig {
+            model_path: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        });
+        assert!(predictor.model().is_some());
+        assert!(predictor
+            .predict_trend("BTCUSDT", "15m", &candles)
+            .await
+            .is_ok());
+        std::fs::remove_file(&path).unwrap();
+
+        let missing = MLTrendPredictor::new(MLPredictorConfig {
+            model_path: Some(path.to_string_lossy().to_string()),
+            ..Default::default()
+        });
+        assert!(missing.model().is_none());
+        assert!(missing.health_check().await.is_err());
+    }
 }
@@ -1,12 +1,19 @@
 pub mod bollinger_strategy;
+pub mod dataset;
 pub mod hybrid_filter;
 pub mod indicators;
 pub mod macd_strategy;
 pub mod ml_trend_predictor;
+pub mod pairs_strategy;
+pub mod registry;
 pub mod rsi_strategy;
+pub mod rule_strategy;
 pub mod stochastic_strategy;
 pub mod strategy_engine;
+pub mod streaming_indicators;
+pub mod supertrend_strategy;
 pub mod trend_filter;
+pub mod trend_model;
 pub mod types;
 pub mod volume_strategy;
 
@@ -14,6 +21,7 @@ pub mod volume_strategy;
 mod tests;
 
 use crate::market_data::cache::CandleData;
+use crate::strategies::streaming_indicators::TimeframeIndicators;
 use async_trait::async_trait;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::collections::HashMap;
@@ -24,13 +32,13 @@ use std::collections::HashMap;
 #[async_trait]
 pub trait Strategy: Send + Sync {
     /// Unique identifier for the strategy
-    fn name(&self) -> &'static str;
+    fn name(&self) -> &str;
 
     /// Strategy description
-    fn description(&self) -> &'static str;
+    fn description(&self) -> &str;
 
     /// Required timeframes for this strategy
-    fn required_timeframes(&self) -> Vec<&'static str>;
+    fn required_timeframes(&self) -> Vec<&str>;
 
     /// Analyze market data and generate trading signal
     async fn analyze(&self, data: &StrategyInput) -> Result<StrategyOutput, StrategyError>;
@@ -43,6 +51,11 @@ pub trait Strategy: Send + Sync {
 
     /// Validate if strategy can analyze the given data
     fn validate_data(&self, data: &StrategyInput) -> Result<(), StrategyError>;
+
+    /// Other symbols whose candles must be supplied in `StrategyInput::related_data`
+    fn related_symbols(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 /// Input data for strategy analysis
@@ -53,6 +66,14 @@ pub struct StrategyInput {
     pub current_price: f64,
     pub volume_24h: f64,
     pub timestamp: i64,
+    /// Precomputed incremental indicators per timeframe; strategies fall back to
+    /// recomputing from `timeframe_data` when absent or built with other parameters
+    #[serde(default)]
+    pub indicators: HashMap<String, TimeframeIndicators>,
+    /// Candles of other symbols keyed by symbol then timeframe, loaded for
+    /// strategies that declare `related_symbols` (e.g. pairs trading)
+    #[serde(default)]
+    pub related_data: HashMap<String, HashMap<String, Vec<CandleData>>>,
 }
 
 /// Output from strategy analysis
@@ -316,6 +337,8 @@ mod mod_tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         assert_eq!(input.symbol, "BTCUSDT");
@@ -332,6 +355,8 @@ mod mod_tests {
             current_price: 3000.0,
             volume_24h: 500000.0,
             timestamp: 9876543210,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let cloned = input.clone();
@@ -440,6 +465,8 @@ mod mod_tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let serialized = serde_json::to_string(&input).unwrap();
@@ -0,0 +1,552 @@
+use super::*;
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Statistical pairs strategy trading the spread between two correlated symbols
+///
+/// The analyzed symbol is regressed on `pair_symbol` (log prices) over a rolling
+/// window; the z-score of the latest residual drives entries and exits. A Long
+/// signal means "long the analyzed symbol, short `hedge_ratio` of the pair", a
+/// Short signal the opposite.
+
+// @spec:FR-STRATEGIES-005 - Strategy Parameters
+// @ref:specs/02-design/2.5-components/COMP-RUST-TRADING.md#strategies
+
+#[derive(Debug, Clone)]
+pub struct PairsTradingStrategy {
+    config: StrategyConfig,
+}
+
+/// Rolling regression of one symbol's log price on another's
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpreadStats {
+    pub hedge_ratio: f64,
+    pub intercept: f64,
+    pub spread: f64,
+    pub zscore: f64,
+    pub correlation: f64,
+}
+
+impl PairsTradingStrategy {
+    pub const TIMEFRAME: &'static str = "15m";
+
+    pub fn new() -> Self {
+        let mut config = StrategyConfig::default();
+        config
+            .parameters
+            .insert("pair_symbol".to_string(), json!("BTCUSDT"));
+        config
+            .parameters
+            .insert("lookback_period".to_string(), json!(100));
+        config
+            .parameters
+            .insert("entry_zscore".to_string(), json!(2.0));
+        config
+            .parameters
+            .insert("exit_zscore".to_string(), json!(0.5));
+        config
+            .parameters
+            .insert("min_correlation".to_string(), json!(0.7));
+
+        Self { config }
+    }
+
+    pub fn with_config(config: StrategyConfig) -> Self {
+        Self { config }
+    }
+
+    fn get_pair_symbol(&self) -> String {
+        self.config
+            .parameters
+            .get("pair_symbol")
+            .and_then(|v| v.as_str())
+            .unwrap_or("BTCUSDT")
+            .to_uppercase()
+    }
+
+    fn get_lookback_period(&self) -> usize {
+        self.config
+            .parameters
+            .get("lookback_period")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(100) as usize
+    }
+
+    fn get_entry_zscore(&self) -> f64 {
+        self.config
+            .parameters
+            .get("entry_zscore")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(2.0)
+    }
+
+    fn get_exit_zscore(&self) -> f64 {
+        self.config
+            .parameters
+            .get("exit_zscore")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.5)
+    }
+
+    fn get_min_correlation(&self) -> f64 {
+        self.config
+            .parameters
+            .get("min_correlation")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.7)
+    }
+
+    fn pair_candles<'a>(&self, data: &'a StrategyInput) -> Option<&'a Vec<CandleData>> {
+        data.related_data
+            .get(&self.get_pair_symbol())
+            .and_then(|timeframes| timeframes.get(Self::TIMEFRAME))
+    }
+
+    /// Closes of both symbols for candles present in both series, oldest first
+    fn aligned_closes(
+        candles: &[CandleData],
+        pair_candles: &[CandleData],
+        lookback: usize,
+    ) -> (Vec<f64>, Vec<f64>) {
+        let pair_by_time: HashMap<i64, f64> = pair_candles
+            .iter()
+            .map(|c| (c.open_time, c.close))
+            .collect();
+
+        let (mut closes, mut pair_closes): (Vec<f64>, Vec<f64>) = candles
+            .iter()
+            .rev()
+            .filter_map(|c| pair_by_time.get(&c.open_time).map(|&p| (c.close, p)))
+            .take(lookback)
+            .unzip();
+        closes.reverse();
+        pair_closes.reverse();
+        (closes, pair_closes)
+    }
+
+    /// OLS of ln(price) on ln(pair price); the spread is the regression residual
+    pub fn calculate_spread_stats(
+        prices: &[f64],
+        pair_prices: &[f64],
+    ) -> Result<SpreadStats, StrategyError> {
+        if prices.len() != pair_prices.len() || prices.len() < 3 {
+            return Err(StrategyError::InsufficientData(format!(
+                "Need at least 3 aligned prices, got {} and {}",
+                prices.len(),
+                pair_prices.len()
+            )));
+        }
+        if prices.iter().chain(pair_prices).any(|&p| p <= 0.0) {
+            return Err(StrategyError::DataValidation(
+                "Prices must be positive".to_string(),
+            ));
+        }
+
+        let y: Vec<f64> = prices.iter().map(|p| p.ln()).collect();
+        let x: Vec<f64> = pair_prices.iter().map(|p| p.ln()).collect();
+        let n = y.len() as f64;
+        let mean_x = x.iter().sum::<f64>() / n;
+        let mean_y = y.iter().sum::<f64>() / n;
+
+        let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+        for (xi, yi) in x.iter().zip(&y) {
+            cov += (xi - mean_x) * (yi - mean_y);
+            var_x += (xi - mean_x).powi(2);
+            var_y += (yi - mean_y).powi(2);
+        }
+        if var_x <= f64::EPSILON || var_y <= f64::EPSILON {
+            return Err(StrategyError::CalculationError(
+                "Price series has no variance".to_string(),
+            ));
+        }
+
+        let hedge_ratio = cov / var_x;
+        let intercept = mean_y - hedge_ratio * mean_x;
+        let residuals: Vec<f64> = x
+            .iter()
+            .zip(&y)
+            .map(|(xi, yi)| yi - hedge_ratio * xi - intercept)
+            .collect();
+        // OLS residuals have zero mean, so the spread's deviation is their RMS
+        let std_dev = (residuals.iter().map(|r| r * r).sum::<f64>() / n).sqrt();
+        let spread = *residuals.last().unwrap_or(&0.0);
+
+        Ok(SpreadStats {
+            hedge_ratio,
+            intercept,
+            spread,
+            zscore: if std_dev > f64::EPSILON {
+                spread / std_dev
+            } else {
+                0.0
+            },
+            correlation: cov / (var_x.sqrt() * var_y.sqrt()),
+        })
+    }
+
+    fn analyze_spread(&self, stats: &SpreadStats) -> (TradingSignal, f64, String) {
+        let entry = self.get_entry_zscore();
+        let exit = self.get_exit_zscore();
+        let z = stats.zscore;
+
+        if stats.correlation < self.get_min_correlation() || stats.hedge_ratio <= 0.0 {
+            return (
+                TradingSignal::Neutral,
+                0.4,
+                format!(
+                    "Pair correlation {:.2} too weak to trade the spread",
+                    stats.correlation
+                ),
+            );
+        }
+
+        // Confidence grows with how far the spread is stretched beyond the entry band
+        let confidence = (0.65 + 0.05 * (z.abs() - entry)).min(0.9);
+        if z >= entry {
+            return (
+                TradingSignal::Short,
+                confidence,
+                format!("Spread z-score {z:.2} above +{entry:.1}, expecting reversion down"),
+            );
+        }
+        if z <= -entry {
+            return (
+                TradingSignal::Long,
+                confidence,
+                format!("Spread z-score {z:.2} below -{entry:.1}, expecting reversion up"),
+            );
+        }
+        if z.abs() <= exit {
+            return (
+                TradingSignal::Neutral,
+                0.5,
+                format!("Spread z-score {z:.2} reverted inside ±{exit:.1}"),
+            );
+        }
+
+        (
+            TradingSignal::Neutral,
+            0.5,
+            format!("Spread z-score {z:.2} between exit and entry bands"),
+        )
+    }
+}
+
+#[async_trait]
+impl Strategy for PairsTradingStrategy {
+    fn name(&self) -> &'static str {
+        "Pairs Spread Strategy"
+    }
+
+    fn description(&self) -> &'static str {
+        "Statistical arbitrage on the z-score of the spread between two correlated symbols using a rolling hedge ratio"
+    }
+
+    fn required_timeframes(&self) -> Vec<&'static str> {
+        vec![Self::TIMEFRAME]
+    }
+
+    fn related_symbols(&self) -> Vec<String> {
+        vec![self.get_pair_symbol()]
+    }
+
+    async fn analyze(&self, data: &StrategyInput) -> Result<StrategyOutput, StrategyError> {
+        self.validate_data(data)?;
+
+        let candles = data.timeframe_data.get(Self::TIMEFRAME).ok_or_else(|| {
+            StrategyError::InsufficientData(format!("Missing {} data", Self::TIMEFRAME))
+        })?;
+        let pair_candles = self.pair_candles(data).ok_or_else(|| {
+            StrategyError::InsufficientData(format!(
+                "Missing {} data for {}",
+                Self::TIMEFRAME,
+                self.get_pair_symbol()
+            ))
+        })?;
+
+        let lookback = self.get_lookback_period();
+        let (closes, pair_closes) = Self::aligned_closes(candles, pair_candles, lookback);
+        if closes.len() < lookback {
+            return Err(StrategyError::InsufficientData(format!(
+                "Need {} candles aligned with {}, got {}",
+                lookback,
+                self.get_pair_symbol(),
+                closes.len()
+            )));
+        }
+
+        let stats = Self::calculate_spread_stats(&closes, &pair_closes)?;
+        let (signal, confidence, reasoning) = self.analyze_spread(&stats);
+
+        let pair_signal = match signal {
+            TradingSignal::Long => TradingSignal::Short,
+            TradingSignal::Short => TradingSignal::Long,
+            TradingSignal::Neutral => TradingSignal::Neutral,
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("pair_symbol".to_string(), json!(self.get_pair_symbol()));
+        metadata.insert("pair_signal".to_string(), json!(pair_signal));
+        metadata.insert("hedge_ratio".to_string(), json!(stats.hedge_ratio));
+        metadata.insert("spread".to_string(), json!(stats.spread));
+        metadata.insert("zscore".to_string(), json!(stats.zscore));
+        metadata.insert("correlation".to_string(), json!(stats.correlation));
+        metadata.insert(
+            "exit_spread".to_string(),
+            json!(stats.zscore.abs() <= self.get_exit_zscore()),
+        );
+
+        Ok(StrategyOutput {
+            signal,
+            confidence,
+            reasoning,
+            timeframe: Self::TIMEFRAME.to_string(),
+            timestamp: data.timestamp,
+            metadata,
+        })
+    }
+
+    fn config(&self) -> &StrategyConfig {
+        &self.config
+    }
+
+    fn update_config(&mut self, config: StrategyConfig) {
+        self.config = config;
+    }
+
+    fn validate_data(&self, data: &StrategyInput) -> Result<(), StrategyError> {
+        let pair_symbol = self.get_pair_symbol();
+        if data.symbol.eq_ignore_ascii_case(&pair_symbol) {
+            return Err(StrategyError::DataValidation(format!(
+                "{} cannot be paired with itself",
+                data.symbol
+            )));
+        }
+
+        let lookback = self.get_lookback_period();
+        let candles = data.timeframe_data.get(Self::TIMEFRAME).ok_or_else(|| {
+            StrategyError::DataValidation(format!("Missing {} timeframe data", Self::TIMEFRAME))
+        })?;
+        let pair_candles = self.pair_candles(data).ok_or_else(|| {
+            StrategyError::DataValidation(format!(
+                "Missing {} timeframe data for {}",
+                Self::TIMEFRAME,
+                pair_symbol
+            ))
+        })?;
+
+        for (symbol, len) in [
+            (data.symbol.as_str(), candles.len()),
+            (pair_symbol.as_str(), pair_candles.len()),
+        ] {
+            if len < lookback {
+                return Err(StrategyError::InsufficientData(format!(
+                    "Need at least {} candles for {} on {}, got {}",
+                    lookback,
+                    symbol,
+                    Self::TIMEFRAME,
+                    len
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for PairsTradingStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn create_test_candles(prices: &[f64]) -> Vec<CandleData> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| CandleData {
+                open: price,
+                high: price * 1.001,
+                low: price * 0.999,
+                close: price,
+                volume: 1000.0,
+                open_time: (i as i64) * 900000,
+                close_time: (i as i64) * 900000 + 900000,
+                quote_volume: 1000.0 * price,
+                trades: 100,
+                is_closed: true,
+            })
+            .collect()
+    }
+
+    fn create_test_input(prices: &[f64], pair_prices: &[f64]) -> StrategyInput {
+        let mut timeframe_data = HashMap::new();
+        timeframe_data.insert("15m".to_string(), create_test_candles(prices));
+        let mut pair_data = HashMap::new();
+        pair_data.insert("15m".to_string(), create_test_candles(pair_prices));
+        let mut related_data = HashMap::new();
+        related_data.insert("BTCUSDT".to_string(), pair_data);
+
+        StrategyInput {
+            symbol: "ETHUSDT".to_string(),
+            timeframe_data,
+            current_price: *prices.last().unwrap(),
+            volume_24h: 1000000.0,
+            timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data,
+        }
+    }
+
+    /// BTC wanders; ETH tracks it at half the log-return plus a small oscillating spread
+    fn cointegrated_prices(count: usize, last_spread: f64) -> (Vec<f64>, Vec<f64>) {
+        let btc: Vec<f64> = (0..count)
+            .map(|i| 50000.0 * (1.0 + 0.1 * (i as f64 * 0.07).sin()))
+            .collect();
+        let eth = btc
+            .iter()
+            .enumerate()
+            .map(|(i, b)| {
+                let noise = if i + 1 == count {
+                    last_spread
+                } else {
+                    0.002 * (i as f64 * 1.3).sin()
+                };
+                3000.0 * (b / 50000.0).powf(0.5) * noise.exp()
+            })
+            .collect();
+        (eth, btc)
+    }
+
+    #[test]
+    fn test_pairs_strategy_new() {
+        let strategy = PairsTradingStrategy::new();
+        assert_eq!(strategy.name(), "Pairs Spread Strategy");
+        assert_eq!(strategy.required_timeframes(), vec!["15m"]);
+        assert_eq!(strategy.related_symbols(), vec!["BTCUSDT".to_string()]);
+        assert_eq!(strategy.get_lookback_period(), 100);
+        assert_eq!(strategy.get_entry_zscore(), 2.0);
+        assert_eq!(strategy.get_exit_zscore(), 0.5);
+    }
+
+    #[test]
+    fn test_calculate_spread_stats_recovers_hedge_ratio() {
+        let (eth, btc) = cointegrated_prices(100, 0.0);
+        let stats = PairsTradingStrategy::calculate_spread_stats(&eth, &btc).unwrap();
+        assert!((stats.hedge_ratio - 0.5).abs() < 0.05);
+        assert!(stats.correlation > 0.95);
+        assert!(stats.zscore.abs() < 1.0);
+    }
+
+    #[test]
+    fn test_calculate_spread_stats_rejects_bad_input() {
+        assert!(PairsTradingStrategy::calculate_spread_stats(&[1.0, 2.0], &[1.0, 2.0]).is_err());
+        assert!(
+            PairsTradingStrategy::calculate_spread_stats(&[1.0, 2.0, 3.0], &[1.0, 2.0]).is_err()
+        );
+        assert!(
+            PairsTradingStrategy::calculate_spread_stats(&[1.0, 2.0, 3.0], &[5.0, 5.0, 5.0])
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pairs_strategy_rich_spread_shorts() {
+        let strategy = PairsTradingStrategy::new();
+        let (eth, btc) = cointegrated_prices(100, 0.02);
+        let output = strategy
+            .analyze(&create_test_input(&eth, &btc))
+            .await
+            .unwrap();
+
+        assert_eq!(output.signal, TradingSignal::Short);
+        assert!(output.confidence >= 0.65);
+        assert!(output.metadata["zscore"].as_f64().unwrap() > 2.0);
+        assert_eq!(output.metadata["pair_symbol"], json!("BTCUSDT"));
+        assert_eq!(output.metadata["pair_signal"], json!(TradingSignal::Long));
+    }
+
+    #[tokio::test]
+    async fn test_pairs_strategy_cheap_spread_longs() {
+        let strategy = PairsTradingStrategy::new();
+        let (eth, btc) = cointegrated_prices(100, -0.02);
+        let output = strategy
+            .analyze(&create_test_input(&eth, &btc))
+            .await
+            .unwrap();
+
+        assert_eq!(output.signal, TradingSignal::Long);
+        assert_eq!(output.metadata["pair_signal"], json!(TradingSignal::Short));
+    }
+
+    #[tokio::test]
+    async fn test_pairs_strategy_reverted_spread_signals_exit() {
+        let strategy = PairsTradingStrategy::new();
+        let (eth, btc) = cointegrated_prices(100, 0.0);
+        let output = strategy
+            .analyze(&create_test_input(&eth, &btc))
+            .await
+            .unwrap();
+
+        assert_eq!(output.signal, TradingSignal::Neutral);
+        assert_eq!(output.metadata["exit_spread"], json!(true));
+    }
+
+    #[tokio::test]
+    async fn test_pairs_strategy_uncorrelated_pair_neutral() {
+        let strategy = PairsTradingStrategy::new();
+        let btc: Vec<f64> = (0..100).map(|i| 50000.0 + i as f64 * 10.0).collect();
+        let eth: Vec<f64> = (0..100)
+            .map(|i| 3000.0 * (1.0 + 0.05 * (i as f64 * 0.9).sin()))
+            .collect();
+        let output = strategy
+            .analyze(&create_test_input(&eth, &btc))
+            .await
+            .unwrap();
+
+        assert_eq!(output.signal, TradingSignal::Neutral);
+        assert!(output.reasoning.contains("too weak"));
+    }
+
+    #[tokio::test]
+    async fn test_pairs_strategy_missing_pair_data() {
+        let strategy = PairsTradingStrategy::new();
+        let (eth, btc) = cointegrated_prices(100, 0.0);
+        let mut input = create_test_input(&eth, &btc);
+        input.related_data.clear();
+        assert!(strategy.analyze(&input).await.is_err());
+
+        let mut input = create_test_input(&eth, &btc);
+        input.symbol = "BTCUSDT".to_string();
+        assert!(matches!(
+            strategy.validate_data(&input),
+            Err(StrategyError::DataValidation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pairs_strategy_requires_aligned_candles() {
+        let strategy = PairsTradingStrategy::new();
+        let (eth, btc) = cointegrated_prices(100, 0.0);
+        let mut input = create_test_input(&eth, &btc);
+        // Shift the pair's candles so only half of them share open times
+        for candle in input
+            .related_data
+            .get_mut("BTCUSDT")
+            .unwrap()
+            .get_mut("15m")
+            .unwrap()
+        {
+            candle.open_time += 50 * 900000;
+        }
+        assert!(matches!(
+            strategy.analyze(&input).await,
+            Err(StrategyError::InsufficientData(_))
+        ));
+    }
+}
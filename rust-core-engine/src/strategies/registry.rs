@@ -0,0 +1,302 @@
+use crate::strategies::{
+    bollinger_strategy::BollingerStrategy,
+    macd_strategy::MacdStrategy,
+    pairs_strategy::PairsTradingStrategy,
+    rsi_strategy::RsiStrategy,
+    rule_strategy::{RuleStrategy, RuleStrategyDefinition},
+    stochastic_strategy::StochasticStrategy,
+    supertrend_strategy::SupertrendStrategy,
+    volume_strategy::VolumeStrategy,
+    Strategy, StrategyError,
+};
+use std::sync::Arc;
+
+// @spec:FR-STRATEGIES-005 - Strategy Parameters
+// @ref:specs/02-design/2.5-components/COMP-RUST-TRADING.md#strategies
+
+/// Builds a fresh strategy instance with its default configuration
+pub type StrategyFactory = Arc<dyn Fn() -> Box<dyn Strategy> + Send + Sync>;
+
+/// Named strategy factories used to build a `StrategyEngine`.
+///
+/// Strategies are keyed by `Strategy::name()`, which is also the name used in
+/// `StrategyEngineConfig.enabled_strategies`. Registration order is preserved so the
+/// engine evaluates strategies in a stable order.
+#[derive(Clone, Default)]
+pub struct StrategyRegistry {
+    factories: Vec<(String, StrategyFactory)>,
+}
+
+impl StrategyRegistry {
+    /// Empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry containing the built-in strategies
+    pub fn with_builtin_strategies() -> Self {
+        let mut registry = Self::new();
+        for factory in builtin_factories() {
+            // Built-in names are unique, so registration cannot fail
+            let _ = registry.register(factory);
+        }
+        registry
+    }
+
+    /// Register a strategy factory under the name of the strategy it builds
+    pub fn register(&mut self, factory: StrategyFactory) -> Result<(), StrategyError> {
+        let name = factory().name().to_string();
+        if self.contains(&name) {
+            return Err(StrategyError::InvalidConfiguration(format!(
+                "Strategy '{name}' is already registered"
+            )));
+        }
+        self.factories.push((name, factory));
+        Ok(())
+    }
+
+    /// Register a strategy type that can be cloned from a prototype instance
+    pub fn register_strategy<S>(&mut self, prototype: S) -> Result<(), StrategyError>
+    where
+        S: Strategy + Clone + 'static,
+    {
+        self.register(Arc::new(move || Box::new(prototype.clone())))
+    }
+
+    /// Compile and register declarative rule strategies
+    pub fn register_rule_strategies(
+        &mut self,
+        definitions: &[RuleStrategyDefinition],
+    ) -> Result<(), StrategyError> {
+        for definition in definitions {
+            self.register_strategy(RuleStrategy::compile(definition)?)?;
+        }
+        Ok(())
+    }
+
+    /// Remove a strategy, returning whether it was registered
+    pub fn unregister(&mut self, name: &str) -> bool {
+        let before = self.factories.len();
+        self.factories.retain(|(registered, _)| registered != name);
+        self.factories.len() != before
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories
+            .iter()
+            .any(|(registered, _)| registered == name)
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.factories
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.factories.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.factories.is_empty()
+    }
+
+    /// Build a new instance of the named strategy
+    pub fn create(&self, name: &str) -> Result<Box<dyn Strategy>, StrategyError> {
+        self.factories
+            .iter()
+            .find(|(registered, _)| registered == name)
+            .map(|(_, factory)| factory())
+            .ok_or_else(|| {
+                StrategyError::InvalidConfiguration(format!("Strategy '{name}' is not registered"))
+            })
+    }
+
+    /// Build one instance of every registered strategy, in registration order
+    pub fn create_all(&self) -> Vec<Box<dyn Strategy>> {
+        self.factories
+            .iter()
+            .map(|(_, factory)| factory())
+            .collect()
+    }
+
+    /// Build the named strategies, failing on the first unknown name
+    pub fn create_from_names(
+        &self,
+        names: &[String],
+    ) -> Result<Vec<Box<dyn Strategy>>, StrategyError> {
+        names.iter().map(|name| self.create(name)).collect()
+    }
+}
+
+impl std::fmt::Debug for StrategyRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrategyRegistry")
+            .field("strategies", &self.names())
+            .finish()
+    }
+}
+
+fn builtin_factories() -> Vec<StrategyFactory> {
+    vec![
+        Arc::new(|| Box::new(RsiStrategy::new()) as Box<dyn Strategy>),
+        Arc::new(|| Box::new(MacdStrategy::new()) as Box<dyn Strategy>),
+        Arc::new(|| Box::new(BollingerStrategy::new()) as Box<dyn Strategy>),
+        Arc::new(|| Box::new(StochasticStrategy::new()) as Box<dyn Strategy>),
+        Arc::new(|| Box::new(VolumeStrategy::new()) as Box<dyn Strategy>),
+        Arc::new(|| Box::new(SupertrendStrategy::new()) as Box<dyn Strategy>),
+        Arc::new(|| Box::new(PairsTradingStrategy::new()) as Box<dyn Strategy>),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::{StrategyConfig, StrategyInput, StrategyOutput, TradingSignal};
+    use async_trait::async_trait;
+
+    #[derive(Clone)]
+    struct AlwaysLongStrategy {
+        config: StrategyConfig,
+    }
+
+    #[async_trait]
+    impl Strategy for AlwaysLongStrategy {
+        fn name(&self) -> &'static str {
+            "Always Long"
+        }
+
+        fn description(&self) -> &'static str {
+            "Test strategy that always signals long"
+        }
+
+        fn required_timeframes(&self) -> Vec<&'static str> {
+            vec!["1h"]
+        }
+
+        async fn analyze(&self, data: &StrategyInput) -> Result<StrategyOutput, StrategyError> {
+            Ok(StrategyOutput {
+                signal: TradingSignal::Long,
+                confidence: 0.9,
+                reasoning: "Always long".to_string(),
+                timeframe: "1h".to_string(),
+                timestamp: data.timestamp,
+                metadata: Default::default(),
+            })
+        }
+
+        fn config(&self) -> &StrategyConfig {
+            &self.config
+        }
+
+        fn update_config(&mut self, config: StrategyConfig) {
+            self.config = config;
+        }
+
+        fn validate_data(&self, _data: &StrategyInput) -> Result<(), StrategyError> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_builtin_registry_contains_all_strategies() {
+        let registry = StrategyRegistry::with_builtin_strategies();
+        assert_eq!(
+            registry.names(),
+            vec![
+                "RSI Strategy",
+                "MACD Strategy",
+                "Bollinger Bands Strategy",
+                "Stochastic Strategy",
+                "Volume Strategy",
+                "Supertrend Strategy",
+                "Pairs Spread Strategy",
+            ]
+        );
+        assert_eq!(registry.create_all().len(), 7);
+    }
+
+    #[test]
+    fn test_register_custom_strategy() {
+        let mut registry = StrategyRegistry::with_builtin_strategies();
+        registry
+            .register_strategy(AlwaysLongStrategy {
+                config: StrategyConfig::default(),
+            })
+            .unwrap();
+
+        assert_eq!(registry.len(), 8);
+        assert!(registry.contains("Always Long"));
+        assert_eq!(
+            registry.create("Always Long").unwrap().name(),
+            "Always Long"
+        );
+    }
+
+    #[test]
+    fn test_register_duplicate_is_rejected() {
+        let mut registry = StrategyRegistry::with_builtin_strategies();
+        let result = registry.register_strategy(RsiStrategy::new());
+        assert!(matches!(
+            result,
+            Err(StrategyError::InvalidConfiguration(_))
+        ));
+        assert_eq!(registry.len(), 7);
+    }
+
+    #[test]
+    fn test_unregister_and_unknown_names() {
+        let mut registry = StrategyRegistry::with_builtin_strategies();
+        assert!(registry.unregister("MACD Strategy"));
+        assert!(!registry.unregister("MACD Strategy"));
+        assert!(registry.create("MACD Strategy").is_err());
+
+        let names = vec!["RSI Strategy".to_string(), "Unknown".to_string()];
+        assert!(registry.create_from_names(&names).is_err());
+
+        let names = vec!["Volume Strategy".to_string(), "RSI Strategy".to_string()];
+        let strategies = registry.create_from_names(&names).unwrap();
+        assert_eq!(strategies[0].name(), "Volume Strategy");
+        assert_eq!(strategies[1].name(), "RSI Strategy");
+    }
+
+    #[test]
+    fn test_factories_build_independent_instances() {
+        let registry = StrategyRegistry::with_builtin_strategies();
+        let mut first = registry.create("RSI Strategy").unwrap();
+        first.update_config(StrategyConfig {
+            weight: 3.0,
+            ..StrategyConfig::default()
+        });
+
+        let second = registry.create("RSI Strategy").unwrap();
+        assert_eq!(first.config().weight, 3.0);
+        assert_ne!(second.config().weight, 3.0);
+    }
+
+    #[test]
+    fn test_register_rule_strategies() {
+        let mut registry = StrategyRegistry::with_builtin_strategies();
+        let definition: RuleStrategyDefinition = serde_yaml::from_str(
+            r#"
+name: "EMA Trend"
+long_when: "close > EMA(50)"
+"#,
+        )
+        .unwrap();
+
+        registry
+            .register_rule_strategies(std::slice::from_ref(&definition))
+            .unwrap();
+        assert!(registry.contains("EMA Trend"));
+        assert!(registry.register_rule_strategies(&[definition]).is_err());
+    }
+
+    #[test]
+    fn test_empty_registry() {
+        let registry = StrategyRegistry::new();
+        assert!(registry.is_empty());
+        assert!(registry.create_all().is_empty());
+    }
+}
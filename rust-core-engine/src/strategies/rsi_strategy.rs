@@ -86,6 +86,34 @@ impl RsiStrategy {
             .and_then(|v| v.as_f64())
             .unwrap_or(80.0)
     }
+
+    /// Uses the streaming snapshot when it was built with `rsi_period`
+    fn current_and_previous_rsi(
+        &self,
+        data: &StrategyInput,
+        timeframe: &str,
+        candles: &[CandleData],
+        rsi_period: usize,
+    ) -> Result<(f64, f64), StrategyError> {
+        if let Some(values) = data
+            .indicators
+            .get(timeframe)
+            .and_then(|indicators| indicators.rsi(rsi_period))
+        {
+            return Ok(values);
+        }
+
+        let rsi = calculate_rsi(candles, rsi_period).map_err(StrategyError::CalculationError)?;
+        let current = *rsi.last().ok_or_else(|| {
+            StrategyError::InsufficientData(format!("No RSI values calculated for {timeframe}"))
+        })?;
+        let previous = if rsi.len() > 1 {
+            rsi[rsi.len() - 2]
+        } else {
+            current
+        };
+        Ok((current, previous))
+    }
 }
 
 #[async_trait]
@@ -123,31 +151,15 @@ impl Strategy for RsiStrategy {
 
         let rsi_period = self.get_rsi_period();
 
-        // Calculate RSI for both timeframes
-        let primary_rsi =
-            calculate_rsi(primary_candles, rsi_period).map_err(StrategyError::CalculationError)?;
-
-        let confirmation_rsi = calculate_rsi(confirmation_candles, rsi_period)
-            .map_err(StrategyError::CalculationError)?;
-
-        let current_rsi_1h = *primary_rsi.last().ok_or_else(|| {
-            StrategyError::InsufficientData("No RSI values calculated for 1h".to_string())
-        })?;
-        let current_rsi_4h = *confirmation_rsi.last().ok_or_else(|| {
-            StrategyError::InsufficientData("No RSI values calculated for 4h".to_string())
-        })?;
-
-        // Get previous RSI values for trend analysis
-        let prev_rsi_1h = if primary_rsi.len() > 1 {
-            primary_rsi[primary_rsi.len() - 2]
-        } else {
-            current_rsi_1h
-        };
-        let prev_rsi_4h = if confirmation_rsi.len() > 1 {
-            confirmation_rsi[confirmation_rsi.len() - 2]
-        } else {
-            current_rsi_4h
-        };
+        // Current and previous RSI for both timeframes
+        let (current_rsi_1h, prev_rsi_1h) =
+            self.current_and_previous_rsi(data, primary_timeframe, primary_candles, rsi_period)?;
+        let (current_rsi_4h, prev_rsi_4h) = self.current_and_previous_rsi(
+            data,
+            confirmation_timeframe,
+            confirmation_candles,
+            rsi_period,
+        )?;
 
         let oversold = self.get_oversold_threshold();
         let overbought = self.get_overbought_threshold();
@@ -337,6 +349,7 @@ impl Default for RsiStrategy {
 mod tests {
     use super::*;
     use crate::market_data::cache::CandleData;
+    use crate::market_data::indicator_cache::IndicatorCache;
     use std::collections::HashMap;
 
     fn create_test_candles(prices: Vec<f64>) -> Vec<CandleData> {
@@ -369,7 +382,50 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
+        }
+    }
+
+    /// Attach snapshots the way the paper engine does via `IndicatorCache::sync`
+    fn with_streaming_indicators(mut input: StrategyInput) -> StrategyInput {
+        let cache = IndicatorCache::default();
+        for (timeframe, candles) in &input.timeframe_data {
+            if let Some(snapshot) = cache.sync(&input.symbol, timeframe, candles) {
+                input.indicators.insert(timeframe.clone(), snapshot);
+            }
         }
+        input
+    }
+
+    #[tokio::test]
+    async fn test_rsi_strategy_streaming_indicators_match_batch() {
+        let strategy = RsiStrategy::new();
+        let prices_1h: Vec<f64> = (0..100)
+            .map(|i| 100.0 + (i as f64 * 0.3).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let prices_4h: Vec<f64> = (0..100)
+            .map(|i| 100.0 + (i as f64 * 0.15).cos() * 6.0 - i as f64 * 0.02)
+            .collect();
+
+        let batch_input = create_test_input(prices_1h.clone(), prices_4h.clone());
+        let streaming_input = with_streaming_indicators(batch_input.clone());
+        for snapshot in streaming_input.indicators.values() {
+            assert!(snapshot.rsi(14).is_some());
+        }
+
+        let batch = strategy.analyze(&batch_input).await.unwrap();
+        let streaming = strategy.analyze(&streaming_input).await.unwrap();
+        assert_eq!(batch.signal, streaming.signal);
+        assert!((batch.confidence - streaming.confidence).abs() < 1e-9);
+
+        // The streaming result comes from the snapshot, not a batch recalculation
+        let reported = streaming.metadata["rsi_1h"].as_f64().unwrap();
+        assert_eq!(
+            reported,
+            streaming_input.indicators["5m"].rsi(14).unwrap().0
+        );
+        assert!((reported - batch.metadata["rsi_1h"].as_f64().unwrap()).abs() < 1e-6);
     }
 
     #[tokio::test]
@@ -501,6 +557,8 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let result = strategy.validate_data(&input);
@@ -873,6 +931,8 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let result = strategy.analyze(&input).await;
@@ -898,6 +958,8 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let result = strategy.analyze(&input).await;
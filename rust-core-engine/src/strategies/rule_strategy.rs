@@ -0,0 +1,1232 @@
+use super::*;
+use crate::strategies::indicators::{
+    calculate_atr, calculate_bollinger_bands, calculate_ema, calculate_macd, calculate_rsi,
+    calculate_sma, calculate_stochastic,
+};
+use async_trait::async_trait;
+use serde_json::json;
+
+// @spec:FR-STRATEGIES-005 - Strategy Parameters
+// @ref:specs/02-design/2.5-components/COMP-RUST-TRADING.md#strategies
+
+/// Declarative strategy as written in the paper trading YAML
+///
+/// ```yaml
+/// rule_strategies:
+///   - name: "RSI Dip Buyer"
+///     long_when: "RSI(14) < 30 AND close > EMA(50) ON 1h"
+///     short_when: "RSI(14) > 70 AND close < EMA(50) ON 1h"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleStrategyDefinition {
+    /// Unique strategy name, used in `enabled_strategies`
+    pub name: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    /// Timeframe the rules run on, unless an expression ends with `ON <timeframe>`
+    #[serde(default = "default_rule_timeframe")]
+    pub timeframe: String,
+
+    /// Condition that produces a LONG signal
+    #[serde(default)]
+    pub long_when: Option<String>,
+
+    /// Condition that produces a SHORT signal
+    #[serde(default)]
+    pub short_when: Option<String>,
+
+    /// Confidence reported when a condition matches (0.0 - 1.0)
+    #[serde(default = "default_rule_confidence")]
+    pub confidence: f64,
+
+    #[serde(default = "default_rule_enabled")]
+    pub enabled: bool,
+
+    #[serde(default = "default_rule_weight")]
+    pub weight: f64,
+}
+
+fn default_rule_timeframe() -> String {
+    "1h".to_string()
+}
+fn default_rule_confidence() -> f64 {
+    0.7
+}
+fn default_rule_enabled() -> bool {
+    true
+}
+fn default_rule_weight() -> f64 {
+    1.0
+}
+
+/// Strategy compiled from a `RuleStrategyDefinition`
+#[derive(Debug, Clone)]
+pub struct RuleStrategy {
+    name: String,
+    description: String,
+    timeframe: String,
+    long_rule: Option<Condition>,
+    short_rule: Option<Condition>,
+    min_candles: usize,
+    config: StrategyConfig,
+}
+
+impl RuleStrategy {
+    /// Parse and type-check both rules against the indicator library
+    pub fn compile(definition: &RuleStrategyDefinition) -> Result<Self, StrategyError> {
+        let name = definition.name.trim();
+        if name.is_empty() {
+            return Err(StrategyError::InvalidConfiguration(
+                "Rule strategy name cannot be empty".to_string(),
+            ));
+        }
+        if definition.long_when.is_none() && definition.short_when.is_none() {
+            return Err(StrategyError::InvalidConfiguration(format!(
+                "Rule strategy '{name}' needs long_when and/or short_when"
+            )));
+        }
+        if !(0.0..=1.0).contains(&definition.confidence) {
+            return Err(StrategyError::InvalidConfiguration(format!(
+                "Rule strategy '{name}' confidence must be between 0 and 1"
+            )));
+        }
+
+        let mut parameters = HashMap::new();
+        parameters.insert("timeframe".to_string(), json!(definition.timeframe));
+        parameters.insert("confidence".to_string(), json!(definition.confidence));
+        if let Some(rule) = &definition.long_when {
+            parameters.insert("long_when".to_string(), json!(rule));
+        }
+        if let Some(rule) = &definition.short_when {
+            parameters.insert("short_when".to_string(), json!(rule));
+        }
+
+        let mut strategy = Self {
+            name: name.to_string(),
+            description: definition.description.clone(),
+            timeframe: definition.timeframe.clone(),
+            long_rule: None,
+            short_rule: None,
+            min_candles: 0,
+            config: StrategyConfig {
+                enabled: definition.enabled,
+                weight: definition.weight,
+                parameters,
+            },
+        };
+        strategy.compile_rules()?;
+        Ok(strategy)
+    }
+
+    /// (Re)build the rules from the `long_when` / `short_when` / `timeframe` parameters
+    fn compile_rules(&mut self) -> Result<(), StrategyError> {
+        let params = &self.config.parameters;
+        let base_timeframe = params
+            .get("timeframe")
+            .and_then(|v| v.as_str())
+            .unwrap_or(&self.timeframe)
+            .to_string();
+
+        let compile = |key: &str| -> Result<Option<ParsedRule>, StrategyError> {
+            let Some(source) = params.get(key).and_then(|v| v.as_str()) else {
+                return Ok(None);
+            };
+            Parser::new(source)
+                .and_then(|parser| parser.parse_rule())
+                .map(Some)
+                .map_err(|e| {
+                    StrategyError::InvalidConfiguration(format!(
+                        "Rule strategy '{}' {key}: {e}",
+                        self.name
+                    ))
+                })
+        };
+        let long_parsed = compile("long_when")?;
+        let short_parsed = compile("short_when")?;
+
+        // Both rules are evaluated on the same candles, so their `ON` clauses must agree
+        let long_timeframe = long_parsed.as_ref().and_then(|r| r.timeframe.clone());
+        let short_timeframe = short_parsed.as_ref().and_then(|r| r.timeframe.clone());
+        let timeframe = match (long_timeframe, short_timeframe) {
+            (Some(long), Some(short)) if long != short => {
+                return Err(StrategyError::InvalidConfiguration(format!(
+                    "Rule strategy '{}': long_when runs ON {long} but short_when ON {short}",
+                    self.name
+                )));
+            },
+            (Some(timeframe), _) | (None, Some(timeframe)) => timeframe,
+            (None, None) => base_timeframe,
+        };
+        let long_rule = long_parsed.map(|r| r.condition);
+        let short_rule = short_parsed.map(|r| r.condition);
+
+        let lookback = long_rule
+            .iter()
+            .chain(short_rule.iter())
+            .map(Condition::lookback)
+            .max()
+            .unwrap_or(1);
+
+        self.timeframe = timeframe;
+        self.long_rule = long_rule;
+        self.short_rule = short_rule;
+        self.min_candles = lookback;
+        Ok(())
+    }
+
+    fn confidence(&self) -> f64 {
+        self.config
+            .parameters
+            .get("confidence")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.7)
+    }
+
+    /// Minimum candles needed on the rule timeframe
+    pub fn min_candles(&self) -> usize {
+        self.min_candles
+    }
+
+    pub fn timeframe(&self) -> &str {
+        &self.timeframe
+    }
+}
+
+#[async_trait]
+impl Strategy for RuleStrategy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        if self.description.is_empty() {
+            "Declarative rule-based strategy"
+        } else {
+            &self.description
+        }
+    }
+
+    fn required_timeframes(&self) -> Vec<&str> {
+        vec![self.timeframe.as_str()]
+    }
+
+    async fn analyze(&self, data: &StrategyInput) -> Result<StrategyOutput, StrategyError> {
+        self.validate_data(data)?;
+
+        let candles = &data.timeframe_data[&self.timeframe];
+        let long_match = match &self.long_rule {
+            Some(rule) => rule.evaluate(candles)?,
+            None => false,
+        };
+        let short_match = match &self.short_rule {
+            Some(rule) => rule.evaluate(candles)?,
+            None => false,
+        };
+
+        let (signal, confidence, reasoning) = match (long_match, short_match) {
+            (true, false) => (
+                TradingSignal::Long,
+                self.confidence(),
+                "Long rule matched".to_string(),
+            ),
+            (false, true) => (
+                TradingSignal::Short,
+                self.confidence(),
+                "Short rule matched".to_string(),
+            ),
+            (true, true) => (
+                TradingSignal::Neutral,
+                0.5,
+                "Long and short rules both matched".to_string(),
+            ),
+            (false, false) => (TradingSignal::Neutral, 0.5, "No rule matched".to_string()),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("long_rule_matched".to_string(), json!(long_match));
+        metadata.insert("short_rule_matched".to_string(), json!(short_match));
+
+        Ok(StrategyOutput {
+            signal,
+            confidence,
+            reasoning,
+            timeframe: self.timeframe.clone(),
+            timestamp: data.timestamp,
+            metadata,
+        })
+    }
+
+    fn config(&self) -> &StrategyConfig {
+        &self.config
+    }
+
+    fn update_config(&mut self, config: StrategyConfig) {
+        let previous = std::mem::replace(&mut self.config, config);
+        if let Err(e) = self.compile_rules() {
+            warn!("Keeping previous rules for '{}': {}", self.name, e);
+            self.config.parameters = previous.parameters;
+        }
+    }
+
+    fn validate_data(&self, data: &StrategyInput) -> Result<(), StrategyError> {
+        let candles = data.timeframe_data.get(&self.timeframe).ok_or_else(|| {
+            StrategyError::InsufficientData(format!("Missing {} timeframe data", self.timeframe))
+        })?;
+
+        if candles.len() < self.min_candles {
+            return Err(StrategyError::InsufficientData(format!(
+                "Need at least {} candles for {} timeframe, got {}",
+                self.min_candles,
+                self.timeframe,
+                candles.len()
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+// ========================================
+// Expression tree
+// ========================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Compare(CompareOp, Value, Value),
+    Cross(CrossDirection, Value, Value),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CompareOp {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CrossDirection {
+    Above,
+    Below,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Number(f64),
+    Price(PriceField),
+    Indicator(Indicator),
+    Neg(Box<Value>),
+    Arith(ArithOp, Box<Value>, Box<Value>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ArithOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PriceField {
+    Open,
+    High,
+    Low,
+    Close,
+    Volume,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Indicator {
+    Rsi(usize),
+    Sma(usize),
+    Ema(usize),
+    Atr(usize),
+    Macd(usize, usize, usize, MacdLine),
+    Bollinger(usize, f64, Band),
+    Stochastic(usize, usize, StochasticLine),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MacdLine {
+    Macd,
+    Signal,
+    Histogram,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Band {
+    Upper,
+    Middle,
+    Lower,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum StochasticLine {
+    K,
+    D,
+}
+
+impl Condition {
+    fn evaluate(&self, candles: &[CandleData]) -> Result<bool, StrategyError> {
+        self.evaluate_at(candles, 0)
+    }
+
+    /// Evaluate `offset` candles back from the latest one
+    fn evaluate_at(&self, candles: &[CandleData], offset: usize) -> Result<bool, StrategyError> {
+        Ok(match self {
+            Condition::Compare(op, lhs, rhs) => {
+                let (a, b) = (
+                    lhs.value_at(candles, offset)?,
+                    rhs.value_at(candles, offset)?,
+                );
+                match op {
+                    CompareOp::Less => a < b,
+                    CompareOp::LessEqual => a <= b,
+                    CompareOp::Greater => a > b,
+                    CompareOp::GreaterEqual => a >= b,
+                    CompareOp::Equal => (a - b).abs() < f64::EPSILON,
+                    CompareOp::NotEqual => (a - b).abs() >= f64::EPSILON,
+                }
+            },
+            Condition::Cross(direction, lhs, rhs) => {
+                let now = lhs.value_at(candles, offset)? - rhs.value_at(candles, offset)?;
+                let before =
+                    lhs.value_at(candles, offset + 1)? - rhs.value_at(candles, offset + 1)?;
+                match direction {
+                    CrossDirection::Above => before <= 0.0 && now > 0.0,
+                    CrossDirection::Below => before >= 0.0 && now < 0.0,
+                }
+            },
+            Condition::And(lhs, rhs) => {
+                lhs.evaluate_at(candles, offset)? && rhs.evaluate_at(candles, offset)?
+            },
+            Condition::Or(lhs, rhs) => {
+                lhs.evaluate_at(candles, offset)? || rhs.evaluate_at(candles, offset)?
+            },
+            Condition::Not(inner) => !inner.evaluate_at(candles, offset)?,
+        })
+    }
+
+    /// Candles needed to evaluate the condition on the latest bar
+    fn lookback(&self) -> usize {
+        match self {
+            Condition::Compare(_, lhs, rhs) => lhs.lookback().max(rhs.lookback()),
+            // Crosses also look at the previous bar
+            Condition::Cross(_, lhs, rhs) => lhs.lookback().max(rhs.lookback()) + 1,
+            Condition::And(lhs, rhs) | Condition::Or(lhs, rhs) => {
+                lhs.lookback().max(rhs.lookback())
+            },
+            Condition::Not(inner) => inner.lookback(),
+        }
+    }
+}
+
+impl Value {
+    fn value_at(&self, candles: &[CandleData], offset: usize) -> Result<f64, StrategyError> {
+        match self {
+            Value::Number(n) => Ok(*n),
+            Value::Price(field) => {
+                let candle = candles
+                    .len()
+                    .checked_sub(offset + 1)
+                    .map(|i| &candles[i])
+                    .ok_or_else(|| {
+                        StrategyError::InsufficientData("Not enough candles".to_string())
+                    })?;
+                Ok(match field {
+                    PriceField::Open => candle.open,
+                    PriceField::High => candle.high,
+                    PriceField::Low => candle.low,
+                    PriceField::Close => candle.close,
+                    PriceField::Volume => candle.volume,
+                })
+            },
+            Value::Indicator(indicator) => {
+                let series = indicator
+                    .series(candles)
+                    .map_err(StrategyError::CalculationError)?;
+                series
+                    .len()
+                    .checked_sub(offset + 1)
+                    .map(|i| series[i])
+                    .ok_or_else(|| {
+                        StrategyError::InsufficientData(format!("Not enough {indicator:?} values"))
+                    })
+            },
+            Value::Neg(inner) => Ok(-inner.value_at(candles, offset)?),
+            Value::Arith(op, lhs, rhs) => {
+                let (a, b) = (
+                    lhs.value_at(candles, offset)?,
+                    rhs.value_at(candles, offset)?,
+                );
+                match op {
+                    ArithOp::Add => Ok(a + b),
+                    ArithOp::Sub => Ok(a - b),
+                    ArithOp::Mul => Ok(a * b),
+                    ArithOp::Div if b == 0.0 => Err(StrategyError::CalculationError(
+                        "Division by zero in rule".to_string(),
+                    )),
+                    ArithOp::Div => Ok(a / b),
+                }
+            },
+        }
+    }
+
+    fn lookback(&self) -> usize {
+        match self {
+            Value::Number(_) => 0,
+            Value::Price(_) => 1,
+            Value::Indicator(indicator) => indicator.lookback(),
+            Value::Neg(inner) => inner.lookback(),
+            Value::Arith(_, lhs, rhs) => lhs.lookback().max(rhs.lookback()),
+        }
+    }
+}
+
+impl Indicator {
+    fn series(&self, candles: &[CandleData]) -> Result<Vec<f64>, String> {
+        let closes = || candles.iter().map(|c| c.close).collect::<Vec<f64>>();
+        match *self {
+            Indicator::Rsi(period) => calculate_rsi(candles, period),
+            Indicator::Sma(period) => calculate_sma(&closes(), period),
+            Indicator::Ema(period) => calculate_ema(&closes(), period),
+            Indicator::Atr(period) => calculate_atr(candles, period),
+            Indicator::Macd(fast, slow, signal, line) => {
+                let macd = calculate_macd(candles, fast, slow, signal)?;
+                Ok(match line {
+                    MacdLine::Macd => macd.macd_line,
+                    MacdLine::Signal => macd.signal_line,
+                    MacdLine::Histogram => macd.histogram,
+                })
+            },
+            Indicator::Bollinger(period, multiplier, band) => {
+                let bands = calculate_bollinger_bands(candles, period, multiplier)?;
+                Ok(match band {
+                    Band::Upper => bands.upper,
+                    Band::Middle => bands.middle,
+                    Band::Lower => bands.lower,
+                })
+            },
+            Indicator::Stochastic(k_period, d_period, line) => {
+                let stochastic = calculate_stochastic(candles, k_period, d_period)?;
+                Ok(match line {
+                    StochasticLine::K => stochastic.k_percent,
+                    StochasticLine::D => stochastic.d_percent,
+                })
+            },
+        }
+    }
+
+    /// Candles required for at least one value, per the indicator's own input checks
+    fn lookback(&self) -> usize {
+        match *self {
+            Indicator::Rsi(period) | Indicator::Atr(period) => period + 1,
+            Indicator::Sma(period) | Indicator::Ema(period) => period,
+            Indicator::Macd(_, slow, signal, _) => slow + signal,
+            Indicator::Bollinger(period, _, _) => period,
+            Indicator::Stochastic(k_period, d_period, _) => k_period + d_period,
+        }
+    }
+
+    fn from_call(name: &str, args: &[f64]) -> Result<Self, String> {
+        let check_arity = |max: usize| -> Result<(), String> {
+            if args.len() > max {
+                return Err(format!(
+                    "{name} takes at most {max} argument(s), got {}",
+                    args.len()
+                ));
+            }
+            Ok(())
+        };
+        let period = |i: usize, default: usize| -> Result<usize, String> {
+            match args.get(i) {
+                None => Ok(default),
+                Some(&arg) if arg >= 1.0 && arg.fract() == 0.0 => Ok(arg as usize),
+                Some(arg) => Err(format!(
+                    "{name} period must be a positive integer, got {arg}"
+                )),
+            }
+        };
+        let periods = |defaults: &[usize]| -> Result<Vec<usize>, String> {
+            check_arity(defaults.len())?;
+            defaults
+                .iter()
+                .enumerate()
+                .map(|(i, default)| period(i, *default))
+                .collect()
+        };
+        let bollinger = |band: Band| -> Result<Self, String> {
+            check_arity(2)?;
+            let period = period(0, 20)?;
+            let multiplier = args.get(1).copied().unwrap_or(2.0);
+            if multiplier <= 0.0 {
+                return Err(format!("{name} multiplier must be positive"));
+            }
+            Ok(Indicator::Bollinger(period, multiplier, band))
+        };
+        let macd = |line: MacdLine| -> Result<Self, String> {
+            let p = periods(&[12, 26, 9])?;
+            if p[0] >= p[1] {
+                return Err(format!(
+                    "{name} fast period ({}) must be less than slow period ({})",
+                    p[0], p[1]
+                ));
+            }
+            Ok(Indicator::Macd(p[0], p[1], p[2], line))
+        };
+
+        match name {
+            "RSI" => Ok(Indicator::Rsi(periods(&[14])?[0])),
+            "SMA" => Ok(Indicator::Sma(periods(&[20])?[0])),
+            "EMA" => Ok(Indicator::Ema(periods(&[20])?[0])),
+            "ATR" => Ok(Indicator::Atr(periods(&[14])?[0])),
+            "MACD" => macd(MacdLine::Macd),
+            "MACD_SIGNAL" => macd(MacdLine::Signal),
+            "MACD_HIST" => macd(MacdLine::Histogram),
+            "BB_UPPER" => bollinger(Band::Upper),
+            "BB_MIDDLE" => bollinger(Band::Middle),
+            "BB_LOWER" => bollinger(Band::Lower),
+            "STOCH_K" | "STOCH_D" => {
+                let p = periods(&[14, 3])?;
+                let line = if name == "STOCH_K" {
+                    StochasticLine::K
+                } else {
+                    StochasticLine::D
+                };
+                Ok(Indicator::Stochastic(p[0], p[1], line))
+            },
+            _ => Err(format!("Unknown indicator '{name}'")),
+        }
+    }
+}
+
+// ========================================
+// Tokenizer and parser
+// ========================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Compare(CompareOp),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c.is_ascii_digit() || c == '.' {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let number = text
+                .parse::<f64>()
+                .map_err(|_| format!("Invalid number '{text}' at position {start}"))?;
+            tokens.push((start, Token::Number(number)));
+            continue;
+        }
+        if c.is_ascii_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push((start, Token::Ident(text)));
+            continue;
+        }
+
+        let next = chars.get(i + 1).copied();
+        let (token, width) = match (c, next) {
+            ('<', Some('=')) => (Token::Compare(CompareOp::LessEqual), 2),
+            ('>', Some('=')) => (Token::Compare(CompareOp::GreaterEqual), 2),
+            ('=', Some('=')) => (Token::Compare(CompareOp::Equal), 2),
+            ('!', Some('=')) => (Token::Compare(CompareOp::NotEqual), 2),
+            ('<', _) => (Token::Compare(CompareOp::Less), 1),
+            ('>', _) => (Token::Compare(CompareOp::Greater), 1),
+            ('+', _) => (Token::Plus, 1),
+            ('-', _) => (Token::Minus, 1),
+            ('*', _) => (Token::Star, 1),
+            ('/', _) => (Token::Slash, 1),
+            ('(', _) => (Token::LParen, 1),
+            (')', _) => (Token::RParen, 1),
+            (',', _) => (Token::Comma, 1),
+            _ => return Err(format!("Unexpected character '{c}' at position {start}")),
+        };
+        tokens.push((start, token));
+        i += width;
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+struct ParsedRule {
+    condition: Condition,
+    timeframe: Option<String>,
+}
+
+/// Recursive-descent parser:
+///
+/// ```text
+/// rule       := condition [ON timeframe]
+/// condition  := and (OR and)*
+/// and        := unary (AND unary)*
+/// unary      := NOT unary | '(' condition ')' | comparison
+/// comparison := value (< | <= | > | >= | == | != | CROSSES_ABOVE | CROSSES_BELOW) value
+/// value      := term (('+' | '-') term)*
+/// term       := factor (('*' | '/') factor)*
+/// factor     := number | price | INDICATOR['(' args ')'] | '-' factor | '(' value ')'
+/// ```
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    source_len: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Self, String> {
+        Ok(Self {
+            tokens: tokenize(source)?,
+            pos: 0,
+            source_len: source.len(),
+        })
+    }
+
+    fn parse_rule(mut self) -> Result<ParsedRule, String> {
+        if self.tokens.is_empty() {
+            return Err("Rule is empty".to_string());
+        }
+        let condition = self.parse_condition()?;
+
+        let timeframe = if self.eat_keyword("ON") {
+            match self.next() {
+                Some(Token::Number(n)) => match self.next() {
+                    // `1h` tokenizes as number 1 followed by identifier `h`
+                    Some(Token::Ident(unit)) => Some(format!("{n}{}", unit.to_lowercase())),
+                    _ => return Err(self.error("Expected timeframe unit after ON")),
+                },
+                _ => return Err(self.error("Expected timeframe after ON")),
+            }
+        } else {
+            None
+        };
+
+        if self.pos < self.tokens.len() {
+            return Err(self.error("Unexpected trailing input"));
+        }
+        Ok(ParsedRule {
+            condition,
+            timeframe,
+        })
+    }
+
+    fn parse_condition(&mut self) -> Result<Condition, String> {
+        let mut lhs = self.parse_and()?;
+        while self.eat_keyword("OR") {
+            let rhs = self.parse_and()?;
+            lhs = Condition::Or(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Condition, String> {
+        let mut lhs = self.parse_unary_condition()?;
+        while self.eat_keyword("AND") {
+            let rhs = self.parse_unary_condition()?;
+            lhs = Condition::And(Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary_condition(&mut self) -> Result<Condition, String> {
+        if self.eat_keyword("NOT") {
+            return Ok(Condition::Not(Box::new(self.parse_unary_condition()?)));
+        }
+
+        // A parenthesis may open a grouped condition or an arithmetic value; try the
+        // condition first and fall back to a comparison
+        if self.peek() == Some(&Token::LParen) {
+            let start = self.pos;
+            self.pos += 1;
+            if let Ok(condition) = self.parse_condition() {
+                if self.peek() == Some(&Token::RParen) {
+                    self.pos += 1;
+                    return Ok(condition);
+                }
+            }
+            self.pos = start;
+        }
+
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Condition, String> {
+        let lhs = self.parse_value()?;
+        if let Some(Token::Compare(op)) = self.peek().cloned() {
+            self.pos += 1;
+            return Ok(Condition::Compare(op, lhs, self.parse_value()?));
+        }
+        if self.eat_keyword("CROSSES_ABOVE") {
+            return Ok(Condition::Cross(
+                CrossDirection::Above,
+                lhs,
+                self.parse_value()?,
+            ));
+        }
+        if self.eat_keyword("CROSSES_BELOW") {
+            return Ok(Condition::Cross(
+                CrossDirection::Below,
+                lhs,
+                self.parse_value()?,
+            ));
+        }
+        Err(self.error("Expected comparison operator"))
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        let mut lhs = self.parse_term()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Plus) => ArithOp::Add,
+                Some(Token::Minus) => ArithOp::Sub,
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+            lhs = Value::Arith(op, Box::new(lhs), Box::new(self.parse_term()?));
+        }
+    }
+
+    fn parse_term(&mut self) -> Result<Value, String> {
+        let mut lhs = self.parse_factor()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Star) => ArithOp::Mul,
+                Some(Token::Slash) => ArithOp::Div,
+                _ => return Ok(lhs),
+            };
+            self.pos += 1;
+            lhs = Value::Arith(op, Box::new(lhs), Box::new(self.parse_factor()?));
+        }
+    }
+
+    fn parse_factor(&mut self) -> Result<Value, String> {
+        let position = self.position();
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Value::Number(n)),
+            Some(Token::Minus) => Ok(Value::Neg(Box::new(self.parse_factor()?))),
+            Some(Token::LParen) => {
+                let value = self.parse_value()?;
+                self.expect(Token::RParen)?;
+                Ok(value)
+            },
+            Some(Token::Ident(ident)) => {
+                let name = ident.to_uppercase();
+                let price = match name.as_str() {
+                    "OPEN" => Some(PriceField::Open),
+                    "HIGH" => Some(PriceField::High),
+                    "LOW" => Some(PriceField::Low),
+                    "CLOSE" | "PRICE" => Some(PriceField::Close),
+                    "VOLUME" => Some(PriceField::Volume),
+                    _ => None,
+                };
+                if let Some(field) = price {
+                    return Ok(Value::Price(field));
+                }
+
+                let args = if self.peek() == Some(&Token::LParen) {
+                    self.pos += 1;
+                    self.parse_args()?
+                } else {
+                    Vec::new()
+                };
+                Indicator::from_call(&name, &args)
+                    .map(Value::Indicator)
+                    .map_err(|e| format!("{e} at position {position}"))
+            },
+            _ => Err(format!("Expected value at position {position}")),
+        }
+    }
+
+    fn parse_args(&mut self) -> Result<Vec<f64>, String> {
+        let mut args = Vec::new();
+        if self.peek() == Some(&Token::RParen) {
+            self.pos += 1;
+            return Ok(args);
+        }
+        loop {
+            let negative = if self.peek() == Some(&Token::Minus) {
+                self.pos += 1;
+                true
+            } else {
+                false
+            };
+            match self.next() {
+                Some(Token::Number(n)) => args.push(if negative { -n } else { n }),
+                _ => return Err(self.error("Indicator arguments must be numbers")),
+            }
+            match self.next() {
+                Some(Token::Comma) => continue,
+                Some(Token::RParen) => return Ok(args),
+                _ => return Err(self.error("Expected ',' or ')' in argument list")),
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, token)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(_, token)| token.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Ident(ident)) if ident.eq_ignore_ascii_case(keyword) => {
+                self.pos += 1;
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        if self.peek() == Some(&expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("Expected {expected:?}")))
+        }
+    }
+
+    fn position(&self) -> usize {
+        self.tokens
+            .get(self.pos)
+            .map(|(position, _)| *position)
+            .unwrap_or(self.source_len)
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{message} at position {}", self.position())
+    }
+}
+
+use log::warn;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles_from_closes(closes: &[f64]) -> Vec<CandleData> {
+        closes
+            .iter()
+            .enumerate()
+            .map(|(i, &close)| CandleData {
+                open: close,
+                high: close * 1.01,
+                low: close * 0.99,
+                close,
+                volume: 1000.0,
+                open_time: (i as i64) * 3600000,
+                close_time: (i as i64) * 3600000 + 3600000,
+                quote_volume: 1000.0 * close,
+                trades: 100,
+                is_closed: true,
+            })
+            .collect()
+    }
+
+    fn input(timeframe: &str, closes: &[f64]) -> StrategyInput {
+        let mut timeframe_data = HashMap::new();
+        timeframe_data.insert(timeframe.to_string(), candles_from_closes(closes));
+        StrategyInput {
+            symbol: "BTCUSDT".to_string(),
+            timeframe_data,
+            current_price: *closes.last().unwrap(),
+            volume_24h: 1000000.0,
+            timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
+        }
+    }
+
+    fn definition(long_when: Option<&str>, short_when: Option<&str>) -> RuleStrategyDefinition {
+        RuleStrategyDefinition {
+            name: "Test Rule".to_string(),
+            description: String::new(),
+            timeframe: default_rule_timeframe(),
+            long_when: long_when.map(str::to_string),
+            short_when: short_when.map(str::to_string),
+            confidence: 0.8,
+            enabled: true,
+            weight: 1.0,
+        }
+    }
+
+    fn parse(source: &str) -> Result<ParsedRule, String> {
+        Parser::new(source)?.parse_rule()
+    }
+
+    #[test]
+    fn test_parse_example_rule_with_timeframe() {
+        let parsed = parse("RSI(14) < 30 AND close > EMA(50) on 1h").unwrap();
+        assert_eq!(parsed.timeframe.as_deref(), Some("1h"));
+        assert_eq!(
+            parsed.condition,
+            Condition::And(
+                Box::new(Condition::Compare(
+                    CompareOp::Less,
+                    Value::Indicator(Indicator::Rsi(14)),
+                    Value::Number(30.0),
+                )),
+                Box::new(Condition::Compare(
+                    CompareOp::Greater,
+                    Value::Price(PriceField::Close),
+                    Value::Indicator(Indicator::Ema(50)),
+                )),
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_precedence_and_grouping() {
+        // AND binds tighter than OR
+        let parsed = parse("close > 1 OR close < 2 AND close > 3").unwrap();
+        assert!(matches!(parsed.condition, Condition::Or(_, _)));
+
+        let parsed = parse("(close > 1 OR close < 2) AND NOT close > 3").unwrap();
+        match parsed.condition {
+            Condition::And(lhs, rhs) => {
+                assert!(matches!(*lhs, Condition::Or(_, _)));
+                assert!(matches!(*rhs, Condition::Not(_)));
+            },
+            other => panic!("unexpected condition {other:?}"),
+        }
+
+        // Parenthesised arithmetic on the left of a comparison
+        let parsed = parse("(high + low) / 2 > SMA(20) * 1.01").unwrap();
+        assert!(matches!(
+            parsed.condition,
+            Condition::Compare(CompareOp::Greater, Value::Arith(ArithOp::Div, _, _), _)
+        ));
+    }
+
+    #[test]
+    fn test_parse_indicator_defaults_and_arguments() {
+        let parsed = parse("MACD_HIST CROSSES_ABOVE 0").unwrap();
+        assert_eq!(
+            parsed.condition,
+            Condition::Cross(
+                CrossDirection::Above,
+                Value::Indicator(Indicator::Macd(12, 26, 9, MacdLine::Histogram)),
+                Value::Number(0.0),
+            )
+        );
+
+        let parsed = parse("close < bb_lower(20, 2.5)").unwrap();
+        assert_eq!(
+            parsed.condition,
+            Condition::Compare(
+                CompareOp::Less,
+                Value::Price(PriceField::Close),
+                Value::Indicator(Indicator::Bollinger(20, 2.5, Band::Lower)),
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("RSI(14)")
+            .unwrap_err()
+            .contains("Expected comparison"));
+        assert!(parse("FOO(3) > 1")
+            .unwrap_err()
+            .contains("Unknown indicator"));
+        assert!(parse("RSI(0) > 1")
+            .unwrap_err()
+            .contains("positive integer"));
+        assert!(parse("RSI(14, 3) > 1").unwrap_err().contains("at most 1"));
+        assert!(parse("MACD(26, 12, 9) > 0")
+            .unwrap_err()
+            .contains("fast period"));
+        assert!(parse("close > 1 close").unwrap_err().contains("trailing"));
+        assert!(parse("close > 1 ON").unwrap_err().contains("timeframe"));
+        assert!(parse("close # 1")
+            .unwrap_err()
+            .contains("Unexpected character"));
+    }
+
+    #[test]
+    fn test_compile_errors_are_invalid_configuration() {
+        let result = RuleStrategy::compile(&definition(Some("RSI(14) <"), None));
+        match result {
+            Err(StrategyError::InvalidConfiguration(message)) => {
+                assert!(message.contains("Test Rule"));
+                assert!(message.contains("long_when"));
+            },
+            other => panic!("expected InvalidConfiguration, got {other:?}"),
+        }
+
+        assert!(matches!(
+            RuleStrategy::compile(&definition(None, None)),
+            Err(StrategyError::InvalidConfiguration(_))
+        ));
+
+        let mut invalid_confidence = definition(Some("close > 1"), None);
+        invalid_confidence.confidence = 1.5;
+        assert!(RuleStrategy::compile(&invalid_confidence).is_err());
+    }
+
+    #[test]
+    fn test_compile_uses_rule_timeframe_and_lookback() {
+        let strategy =
+            RuleStrategy::compile(&definition(Some("close CROSSES_ABOVE EMA(50) ON 4h"), None))
+                .unwrap();
+        assert_eq!(strategy.timeframe(), "4h");
+        assert_eq!(strategy.required_timeframes(), vec!["4h"]);
+        assert_eq!(strategy.min_candles(), 51);
+    }
+
+    #[test]
+    fn test_compile_rejects_conflicting_rule_timeframes() {
+        let err = RuleStrategy::compile(&definition(
+            Some("close > EMA(20) ON 1h"),
+            Some("close < EMA(20) ON 15m"),
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("long_when runs ON 1h"));
+
+        // One ON clause applies to both rules
+        let strategy = RuleStrategy::compile(&definition(
+            Some("close > EMA(20)"),
+            Some("close < EMA(20) ON 15m"),
+        ))
+        .unwrap();
+        assert_eq!(strategy.timeframe(), "15m");
+    }
+
+    #[tokio::test]
+    async fn test_analyze_long_short_and_neutral() {
+        let strategy =
+            RuleStrategy::compile(&definition(Some("close > SMA(3)"), Some("close < SMA(3)")))
+                .unwrap();
+
+        let rising = input("1h", &[100.0, 101.0, 102.0, 103.0, 110.0]);
+        let output = strategy.analyze(&rising).await.unwrap();
+        assert_eq!(output.signal, TradingSignal::Long);
+        assert_eq!(output.confidence, 0.8);
+        assert_eq!(output.timeframe, "1h");
+
+        let falling = input("1h", &[110.0, 109.0, 108.0, 107.0, 100.0]);
+        let output = strategy.analyze(&falling).await.unwrap();
+        assert_eq!(output.signal, TradingSignal::Short);
+
+        let flat = input("1h", &[100.0, 100.0, 100.0, 100.0]);
+        let output = strategy.analyze(&flat).await.unwrap();
+        assert_eq!(output.signal, TradingSignal::Neutral);
+    }
+
+    #[tokio::test]
+    async fn test_analyze_cross_uses_previous_bar() {
+        let strategy =
+            RuleStrategy::compile(&definition(Some("close CROSSES_ABOVE 100"), None)).unwrap();
+
+        let crossed = input("1h", &[95.0, 98.0, 101.0]);
+        assert_eq!(
+            strategy.analyze(&crossed).await.unwrap().signal,
+            TradingSignal::Long
+        );
+
+        let already_above = input("1h", &[95.0, 101.0, 102.0]);
+        assert_eq!(
+            strategy.analyze(&already_above).await.unwrap().signal,
+            TradingSignal::Neutral
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_data_requires_timeframe_and_candles() {
+        let strategy = RuleStrategy::compile(&definition(Some("RSI(14) < 30"), None)).unwrap();
+
+        let wrong_timeframe = input("5m", &[100.0; 30]);
+        assert!(matches!(
+            strategy.validate_data(&wrong_timeframe),
+            Err(StrategyError::InsufficientData(_))
+        ));
+
+        let too_short = input("1h", &[100.0; 10]);
+        assert!(strategy.analyze(&too_short).await.is_err());
+
+        let enough = input("1h", &[100.0; 15]);
+        assert!(strategy.validate_data(&enough).is_ok());
+    }
+
+    #[test]
+    fn test_update_config_recompiles_and_keeps_rules_on_error() {
+        let mut strategy = RuleStrategy::compile(&definition(Some("close > 1"), None)).unwrap();
+
+        let mut config = strategy.config().clone();
+        config
+            .parameters
+            .insert("long_when".to_string(), json!("close > EMA(20) ON 15m"));
+        strategy.update_config(config);
+        assert_eq!(strategy.timeframe(), "15m");
+        assert_eq!(strategy.min_candles(), 20);
+
+        let mut broken = strategy.config().clone();
+        broken.weight = 2.0;
+        broken
+            .parameters
+            .insert("long_when".to_string(), json!("close >"));
+        strategy.update_config(broken);
+        assert_eq!(strategy.config().weight, 2.0);
+        assert_eq!(
+            strategy.config().parameters.get("long_when"),
+            Some(&json!("close > EMA(20) ON 15m"))
+        );
+        assert_eq!(strategy.timeframe(), "15m");
+    }
+
+    #[test]
+    fn test_definition_from_yaml_uses_defaults() {
+        let yaml = r#"
+name: "RSI Dip Buyer"
+long_when: "RSI(14) < 30 AND close > EMA(50)"
+"#;
+        let definition: RuleStrategyDefinition = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(definition.timeframe, "1h");
+        assert_eq!(definition.confidence, 0.7);
+        assert!(definition.enabled);
+        assert!(definition.short_when.is_none());
+
+        let strategy = RuleStrategy::compile(&definition).unwrap();
+        assert_eq!(strategy.name(), "RSI Dip Buyer");
+        assert_eq!(strategy.description(), "Declarative rule-based strategy");
+    }
+}
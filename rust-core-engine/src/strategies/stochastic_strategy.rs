@@ -1,5 +1,6 @@
 use super::*;
 use crate::strategies::indicators::calculate_stochastic;
+use crate::strategies::streaming_indicators::StochasticValue;
 use async_trait::async_trait;
 use serde_json::json;
 
@@ -92,6 +93,56 @@ impl StochasticStrategy {
             .and_then(|v| v.as_f64())
             .unwrap_or(90.0)
     }
+
+    /// Uses the streaming snapshot when it was built with the same periods
+    fn current_and_previous_stochastic(
+        &self,
+        data: &StrategyInput,
+        timeframe: &str,
+        candles: &[CandleData],
+        k_period: usize,
+        d_period: usize,
+    ) -> Result<(StochasticValue, StochasticValue), StrategyError> {
+        if let Some(values) = data
+            .indicators
+            .get(timeframe)
+            .and_then(|indicators| indicators.stochastic(k_period, d_period))
+        {
+            return Ok(values);
+        }
+
+        let stoch = calculate_stochastic(candles, k_period, d_period)
+            .map_err(StrategyError::CalculationError)?;
+        let last_two = |series: &[f64]| -> Option<(f64, f64)> {
+            let current = *series.last()?;
+            let previous = if series.len() > 1 {
+                series[series.len() - 2]
+            } else {
+                current
+            };
+            Some((current, previous))
+        };
+        let (current_k, prev_k) = last_two(&stoch.k_percent).ok_or_else(|| {
+            StrategyError::InsufficientData(format!(
+                "No Stochastic K values calculated for {timeframe}"
+            ))
+        })?;
+        let (current_d, prev_d) = last_two(&stoch.d_percent).ok_or_else(|| {
+            StrategyError::InsufficientData(format!(
+                "No Stochastic D values calculated for {timeframe}"
+            ))
+        })?;
+        Ok((
+            StochasticValue {
+                k: current_k,
+                d: current_d,
+            },
+            StochasticValue {
+                k: prev_k,
+                d: prev_d,
+            },
+        ))
+    }
 }
 
 #[async_trait]
@@ -133,50 +184,26 @@ impl Strategy for StochasticStrategy {
         let k_period = self.get_k_period();
         let d_period = self.get_d_period();
 
-        // Calculate Stochastic for both timeframes
-        let primary_stoch = calculate_stochastic(primary_candles, k_period, d_period)
-            .map_err(StrategyError::CalculationError)?;
-
-        let confirmation_stoch = calculate_stochastic(confirmation_candles, k_period, d_period)
-            .map_err(StrategyError::CalculationError)?;
-
-        // Get current values
-        let current_k_1h = *primary_stoch.k_percent.last().ok_or_else(|| {
-            StrategyError::InsufficientData("No Stochastic K values calculated for 1h".to_string())
-        })?;
-        let current_d_1h = *primary_stoch.d_percent.last().ok_or_else(|| {
-            StrategyError::InsufficientData("No Stochastic D values calculated for 1h".to_string())
-        })?;
-
-        let current_k_4h = *confirmation_stoch.k_percent.last().ok_or_else(|| {
-            StrategyError::InsufficientData("No Stochastic K values calculated for 4h".to_string())
-        })?;
-        let current_d_4h = *confirmation_stoch.d_percent.last().ok_or_else(|| {
-            StrategyError::InsufficientData("No Stochastic D values calculated for 4h".to_string())
-        })?;
-
-        // Get previous values for crossover detection
-        let prev_k_1h = if primary_stoch.k_percent.len() > 1 {
-            primary_stoch.k_percent[primary_stoch.k_percent.len() - 2]
-        } else {
-            current_k_1h
-        };
-        let prev_d_1h = if primary_stoch.d_percent.len() > 1 {
-            primary_stoch.d_percent[primary_stoch.d_percent.len() - 2]
-        } else {
-            current_d_1h
-        };
-
-        let prev_k_4h = if confirmation_stoch.k_percent.len() > 1 {
-            confirmation_stoch.k_percent[confirmation_stoch.k_percent.len() - 2]
-        } else {
-            current_k_4h
-        };
-        let prev_d_4h = if confirmation_stoch.d_percent.len() > 1 {
-            confirmation_stoch.d_percent[confirmation_stoch.d_percent.len() - 2]
-        } else {
-            current_d_4h
-        };
+        // Current and previous %K/%D for both timeframes (previous for crossover detection)
+        let (current_1h, prev_1h) = self.current_and_previous_stochastic(
+            data,
+            primary_timeframe,
+            primary_candles,
+            k_period,
+            d_period,
+        )?;
+        let (current_4h, prev_4h) = self.current_and_previous_stochastic(
+            data,
+            confirmation_timeframe,
+            confirmation_candles,
+            k_period,
+            d_period,
+        )?;
+
+        let (current_k_1h, current_d_1h) = (current_1h.k, current_1h.d);
+        let (current_k_4h, current_d_4h) = (current_4h.k, current_4h.d);
+        let (prev_k_1h, prev_d_1h) = (prev_1h.k, prev_1h.d);
+        let (prev_k_4h, prev_d_4h) = (prev_4h.k, prev_4h.d);
 
         let oversold = self.get_oversold_threshold();
         let overbought = self.get_overbought_threshold();
@@ -383,6 +410,7 @@ impl Default for StochasticStrategy {
 mod tests {
     use super::*;
     use crate::market_data::cache::CandleData;
+    use crate::market_data::indicator_cache::IndicatorCache;
     use std::collections::HashMap;
 
     fn create_test_candles(prices: Vec<f64>) -> Vec<CandleData> {
@@ -415,9 +443,56 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         }
     }
 
+    /// Attach snapshots the way the paper engine does via `IndicatorCache::sync`
+    fn with_streaming_indicators(mut input: StrategyInput) -> StrategyInput {
+        let cache = IndicatorCache::default();
+        for (timeframe, candles) in &input.timeframe_data {
+            if let Some(snapshot) = cache.sync(&input.symbol, timeframe, candles) {
+                input.indicators.insert(timeframe.clone(), snapshot);
+            }
+        }
+        input
+    }
+
+    #[tokio::test]
+    async fn test_stochastic_strategy_streaming_indicators_match_batch() {
+        let strategy = StochasticStrategy::new();
+        let prices_1h: Vec<f64> = (0..100)
+            .map(|i| 100.0 + (i as f64 * 0.3).sin() * 4.0 + i as f64 * 0.05)
+            .collect();
+        let prices_4h: Vec<f64> = (0..100)
+            .map(|i| 100.0 + (i as f64 * 0.15).cos() * 6.0 - i as f64 * 0.02)
+            .collect();
+
+        let batch_input = create_test_input(prices_1h.clone(), prices_4h.clone());
+        let streaming_input = with_streaming_indicators(batch_input.clone());
+        for snapshot in streaming_input.indicators.values() {
+            assert!(snapshot.stochastic(14, 3).is_some());
+        }
+
+        let batch = strategy.analyze(&batch_input).await.unwrap();
+        let streaming = strategy.analyze(&streaming_input).await.unwrap();
+        assert_eq!(batch.signal, streaming.signal);
+        assert!((batch.confidence - streaming.confidence).abs() < 1e-9);
+
+        // The streaming result comes from the snapshot, not a batch recalculation
+        let reported = streaming.metadata["stoch_k_1h"].as_f64().unwrap();
+        assert_eq!(
+            reported,
+            streaming_input.indicators["5m"]
+                .stochastic(14, 3)
+                .unwrap()
+                .0
+                .k
+        );
+        assert!((reported - batch.metadata["stoch_k_1h"].as_f64().unwrap()).abs() < 1e-6);
+    }
+
     #[tokio::test]
     async fn test_stochastic_strategy_new() {
         let strategy = StochasticStrategy::new();
@@ -3,89 +3,33 @@ use crate::strategies::{
     // @spec:FR-STRATEGIES-005 - Strategy Parameters
     // @ref:specs/02-design/2.5-components/COMP-RUST-TRADING.md#strategies
     // @test:TC-TRADING-029
-    bollinger_strategy::BollingerStrategy,
     hybrid_filter::{HybridFilter, HybridFilterConfig},
-    macd_strategy::MacdStrategy,
     ml_trend_predictor::{MLPredictorConfig, MLTrendPredictor},
-    rsi_strategy::RsiStrategy,
-    stochastic_strategy::StochasticStrategy,
+    registry::StrategyRegistry,
     trend_filter::{TrendFilter, TrendFilterConfig},
-    volume_strategy::VolumeStrategy,
+    types::StrategyPerformance,
 };
 use serde_json::json;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-/// Enum to hold different strategy types (workaround for async trait + dyn compatibility)
-#[derive(Clone)]
-pub enum StrategyType {
-    Rsi(RsiStrategy),
-    Macd(MacdStrategy),
-    Bollinger(BollingerStrategy),
-    Stochastic(StochasticStrategy),
-    Volume(VolumeStrategy),
-}
-
-impl StrategyType {
-    pub async fn analyze(&self, data: &StrategyInput) -> Result<StrategyOutput, StrategyError> {
-        match self {
-            StrategyType::Rsi(s) => s.analyze(data).await,
-            StrategyType::Macd(s) => s.analyze(data).await,
-            StrategyType::Bollinger(s) => s.analyze(data).await,
-            StrategyType::Stochastic(s) => s.analyze(data).await,
-            StrategyType::Volume(s) => s.analyze(data).await,
-        }
-    }
-
-    pub fn name(&self) -> &'static str {
-        match self {
-            StrategyType::Rsi(s) => s.name(),
-            StrategyType::Macd(s) => s.name(),
-            StrategyType::Bollinger(s) => s.name(),
-            StrategyType::Stochastic(s) => s.name(),
-            StrategyType::Volume(s) => s.name(),
-        }
-    }
-
-    pub fn config(&self) -> &StrategyConfig {
-        match self {
-            StrategyType::Rsi(s) => s.config(),
-            StrategyType::Macd(s) => s.config(),
-            StrategyType::Bollinger(s) => s.config(),
-            StrategyType::Stochastic(s) => s.config(),
-            StrategyType::Volume(s) => s.config(),
-        }
-    }
-
-    pub fn update_config(&mut self, config: StrategyConfig) {
-        match self {
-            StrategyType::Rsi(s) => s.update_config(config),
-            StrategyType::Macd(s) => s.update_config(config),
-            StrategyType::Bollinger(s) => s.update_config(config),
-            StrategyType::Stochastic(s) => s.update_config(config),
-            StrategyType::Volume(s) => s.update_config(config),
-        }
-    }
-
-    pub fn validate_data(&self, data: &StrategyInput) -> Result<(), StrategyError> {
-        match self {
-            StrategyType::Rsi(s) => s.validate_data(data),
-            StrategyType::Macd(s) => s.validate_data(data),
-            StrategyType::Bollinger(s) => s.validate_data(data),
-            StrategyType::Stochastic(s) => s.validate_data(data),
-            StrategyType::Volume(s) => s.validate_data(data),
-        }
-    }
-}
-
 /// Main strategy engine that manages and executes multiple strategies
 pub struct StrategyEngine {
-    strategies: Vec<StrategyType>,
+    strategies: Vec<Box<dyn Strategy>>,
     config: StrategyEngineConfig,
     signal_history: Arc<RwLock<Vec<CombinedSignal>>>,
     hybrid_filter: Option<Arc<HybridFilter>>,
+    /// Recent hit rates per strategy, feeding the `LearnedEnsemble` weights
+    performance: Arc<RwLock<HashMap<String, StrategyPerformance>>>,
 }
 
+/// Outcomes over which a strategy's hit rate is averaged (EWMA span)
+const ENSEMBLE_LEARNING_WINDOW: u64 = 50;
+/// Outcomes a strategy needs before its learned weight departs from 1.0
+const ENSEMBLE_MIN_SAMPLES: u64 = 10;
+/// Bounds on the learned weight multiplier
+const ENSEMBLE_WEIGHT_RANGE: (f64, f64) = (0.25, 2.0);
+
 /// Configuration for the strategy engine
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StrategyEngineConfig {
@@ -100,12 +44,18 @@ pub struct StrategyEngineConfig {
 }
 
 /// How to combine signals from multiple strategies
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Configured through `StrategySettings::combination_method`, which converts into this.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum SignalCombinationMode {
     WeightedAverage,
     Consensus,
     BestConfidence,
     Conservative,
+    /// Every strategy must signal the same direction
+    Unanimous,
+    /// Weighted vote with per-strategy weights scaled by recent hit rate
+    LearnedEnsemble,
 }
 
 /// Combined signal from multiple strategies
@@ -133,18 +83,30 @@ pub struct StrategySignalResult {
 
 impl StrategyEngine {
     pub fn new() -> Self {
-        let config = StrategyEngineConfig::default();
+        Self::with_registry(
+            &StrategyRegistry::with_builtin_strategies(),
+            StrategyEngineConfig::default(),
+        )
+    }
+
+    /// Build an engine with one instance of every strategy in the registry.
+    ///
+    /// All registered strategies are instantiated so they can be switched on and off at
+    /// runtime through `enabled_strategies`; names in that list that are not registered
+    /// are ignored with a warning.
+    pub fn with_registry(registry: &StrategyRegistry, config: StrategyEngineConfig) -> Self {
+        for name in &config.enabled_strategies {
+            if !registry.contains(name) {
+                warn!("Enabled strategy '{name}' is not registered");
+            }
+        }
+
         Self {
-            strategies: vec![
-                StrategyType::Rsi(RsiStrategy::new()),
-                StrategyType::Macd(MacdStrategy::new()),
-                StrategyType::Bollinger(BollingerStrategy::new()),
-                StrategyType::Stochastic(StochasticStrategy::new()),
-                StrategyType::Volume(VolumeStrategy::new()),
-            ],
+            strategies: registry.create_all(),
             config,
             signal_history: Arc::new(RwLock::new(Vec::new())),
             hybrid_filter: None,
+            performance: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -176,7 +138,7 @@ impl StrategyEngine {
         engine
     }
 
-    pub fn add_strategy(&mut self, strategy: StrategyType) {
+    pub fn add_strategy(&mut self, strategy: Box<dyn Strategy>) {
         self.strategies.push(strategy);
     }
 
@@ -188,6 +150,92 @@ impl StrategyEngine {
         self.strategies.iter().map(|s| s.name()).collect()
     }
 
+    pub fn config(&self) -> &StrategyEngineConfig {
+        &self.config
+    }
+
+    pub fn set_combination_mode(&mut self, mode: SignalCombinationMode) {
+        self.config.signal_combination_mode = mode;
+    }
+
+    /// Whether the named strategy runs in `analyze_market` (an empty list enables all)
+    pub fn is_strategy_enabled(&self, name: &str) -> bool {
+        self.config.enabled_strategies.is_empty()
+            || self.config.enabled_strategies.iter().any(|s| s == name)
+    }
+
+    /// Other symbols whose candles the enabled strategies expect in `related_data`
+    pub fn related_symbols(&self) -> Vec<String> {
+        let mut symbols: Vec<String> = Vec::new();
+        for strategy in &self.strategies {
+            if !self.is_strategy_enabled(strategy.name()) {
+                continue;
+            }
+            for symbol in strategy.related_symbols() {
+                if !symbols.contains(&symbol) {
+                    symbols.push(symbol);
+                }
+            }
+        }
+        symbols
+    }
+
+    /// Replace the enabled strategy list, rejecting names with no loaded strategy
+    pub fn set_enabled_strategies(&mut self, names: Vec<String>) -> Result<(), StrategyError> {
+        if let Some(unknown) = names
+            .iter()
+            .find(|name| !self.strategies.iter().any(|s| s.name() == name.as_str()))
+        {
+            return Err(StrategyError::InvalidConfiguration(format!(
+                "Strategy '{unknown}' not found"
+            )));
+        }
+        self.config.enabled_strategies = names;
+        Ok(())
+    }
+
+    pub fn enable_strategy(&mut self, name: &str) -> Result<(), StrategyError> {
+        if !self.strategies.iter().any(|s| s.name() == name) {
+            return Err(StrategyError::InvalidConfiguration(format!(
+                "Strategy '{name}' not found"
+            )));
+        }
+        if !self.is_strategy_enabled(name) {
+            self.config.enabled_strategies.push(name.to_string());
+        }
+        Ok(())
+    }
+
+    pub fn disable_strategy(&mut self, name: &str) -> Result<(), StrategyError> {
+        if !self.strategies.iter().any(|s| s.name() == name) {
+            return Err(StrategyError::InvalidConfiguration(format!(
+                "Strategy '{name}' not found"
+            )));
+        }
+        if self.config.enabled_strategies.is_empty() {
+            // Empty means "all enabled", so spell out everything except this one
+            self.config.enabled_strategies = self
+                .strategies
+                .iter()
+                .map(|s| s.name().to_string())
+                .collect();
+        }
+        if self.config.enabled_strategies.iter().all(|s| s == name) {
+            return Err(StrategyError::InvalidConfiguration(
+                "At least one strategy must remain enabled".to_string(),
+            ));
+        }
+        self.config.enabled_strategies.retain(|s| s != name);
+        Ok(())
+    }
+
+    pub fn get_strategy_config(&self, name: &str) -> Option<&StrategyConfig> {
+        self.strategies
+            .iter()
+            .find(|s| s.name() == name)
+            .map(|s| s.config())
+    }
+
     pub fn update_strategy_config(
         &mut self,
         name: &str,
@@ -215,9 +263,7 @@ impl StrategyEngine {
             let strategy_name = strategy.name().to_string();
 
             // Check if strategy is enabled
-            if !self.config.enabled_strategies.is_empty()
-                && !self.config.enabled_strategies.contains(&strategy_name)
-            {
+            if !self.is_strategy_enabled(&strategy_name) {
                 continue;
             }
 
@@ -301,6 +347,13 @@ impl StrategyEngine {
             ));
         }
 
+        if self.config.signal_combination_mode == SignalCombinationMode::LearnedEnsemble {
+            let performance = self.performance.read().await;
+            for result in &mut strategy_results {
+                result.weight *= learned_weight(performance.get(&result.strategy_name));
+            }
+        }
+
         // Combine strategy signals
         let combined_signal = self.combine_signals(&strategy_results, data)?;
 
@@ -321,6 +374,8 @@ impl StrategyEngine {
                 SignalCombinationMode::Consensus => self.combine_consensus(results),
                 SignalCombinationMode::BestConfidence => self.combine_best_confidence(results),
                 SignalCombinationMode::Conservative => self.combine_conservative(results),
+                SignalCombinationMode::Unanimous => self.combine_unanimous(results),
+                SignalCombinationMode::LearnedEnsemble => self.combine_learned_ensemble(results),
             };
 
         // Create metadata with strategy summary
@@ -573,6 +628,158 @@ impl StrategyEngine {
         )
     }
 
+    fn combine_unanimous(&self, results: &[StrategySignalResult]) -> (TradingSignal, f64, String) {
+        let first = results[0].signal;
+        if first == TradingSignal::Neutral || results.iter().any(|r| r.signal != first) {
+            let long_count = results
+                .iter()
+                .filter(|r| r.signal == TradingSignal::Long)
+                .count();
+            let short_count = results
+                .iter()
+                .filter(|r| r.signal == TradingSignal::Short)
+                .count();
+            return (
+                TradingSignal::Neutral,
+                0.5,
+                format!(
+                    "Unanimous: no agreement ({}L/{}S/{}N)",
+                    long_count,
+                    short_count,
+                    results.len() - long_count - short_count
+                ),
+            );
+        }
+
+        let avg_confidence =
+            results.iter().map(|r| r.confidence).sum::<f64>() / results.len() as f64;
+        (
+            first,
+            avg_confidence,
+            format!(
+                "Unanimous: all {} strategies signal {:?}",
+                results.len(),
+                first
+            ),
+        )
+    }
+
+    /// Weighted vote where `weight` already carries the learned hit-rate multiplier;
+    /// the winning direction still needs `min_strategies_agreement` strategies behind it
+    fn combine_learned_ensemble(
+        &self,
+        results: &[StrategySignalResult],
+    ) -> (TradingSignal, f64, String) {
+        let score = |signal: TradingSignal| -> f64 {
+            results
+                .iter()
+                .filter(|r| r.signal == signal)
+                .map(|r| r.weight * r.confidence)
+                .sum()
+        };
+        let (long_score, short_score) = (score(TradingSignal::Long), score(TradingSignal::Short));
+        let neutral_score = score(TradingSignal::Neutral);
+
+        let leader = if long_score > short_score && long_score > neutral_score {
+            TradingSignal::Long
+        } else if short_score > long_score && short_score > neutral_score {
+            TradingSignal::Short
+        } else {
+            TradingSignal::Neutral
+        };
+
+        let backers: Vec<_> = results.iter().filter(|r| r.signal == leader).collect();
+        let min_required = self.config.min_strategies_agreement.min(results.len());
+        let final_signal = if leader != TradingSignal::Neutral && backers.len() < min_required {
+            TradingSignal::Neutral
+        } else {
+            leader
+        };
+
+        let backer_weight: f64 = backers.iter().map(|r| r.weight).sum();
+        let confidence = if final_signal == leader && backer_weight > 0.0 {
+            backers.iter().map(|r| r.weight * r.confidence).sum::<f64>() / backer_weight
+        } else {
+            0.5
+        };
+
+        let weights = results
+            .iter()
+            .map(|r| format!("{}={:.2}", r.strategy_name, r.weight))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let reasoning = format!(
+            "Learned ensemble: Long={long_score:.2}, Short={short_score:.2}, Neutral={neutral_score:.2}, {}/{} backing {:?} (weights: {weights})",
+            backers.len(),
+            results.len(),
+            leader
+        );
+
+        (final_signal, confidence, reasoning)
+    }
+
+    /// Record whether a trade taken after `strategy_signals` was profitable, updating
+    /// each directional strategy's recent hit rate
+    ///
+    /// A strategy scores a hit when it voted with the trade and it won, or against the
+    /// trade and it lost; neutral votes are not scored.
+    pub async fn record_signal_outcome(
+        &self,
+        strategy_signals: &HashMap<String, TradingSignal>,
+        trade_direction: TradingSignal,
+        profitable: bool,
+    ) {
+        let mut performance = self.performance.write().await;
+        for (name, signal) in strategy_signals {
+            if *signal == TradingSignal::Neutral {
+                continue;
+            }
+            let hit = (*signal == trade_direction) == profitable;
+            let entry = performance
+                .entry(name.clone())
+                .or_insert_with(|| StrategyPerformance {
+                    strategy_name: name.clone(),
+                    total_signals: 0,
+                    successful_signals: 0,
+                    accuracy: 0.5,
+                    average_confidence: 0.0,
+                    profit_factor: 0.0,
+                    max_drawdown: 0.0,
+                    sharpe_ratio: 0.0,
+                    last_updated: 0,
+                });
+            entry.total_signals += 1;
+            if hit {
+                entry.successful_signals += 1;
+            }
+            // Plain mean until the window fills, then an EWMA over the window
+            let alpha = 1.0 / entry.total_signals.min(ENSEMBLE_LEARNING_WINDOW) as f64;
+            entry.accuracy += alpha * (if hit { 1.0 } else { 0.0 } - entry.accuracy);
+            entry.last_updated = chrono::Utc::now().timestamp();
+        }
+    }
+
+    /// Recent hit-rate record per strategy
+    pub async fn get_strategy_performance(&self) -> Vec<StrategyPerformance> {
+        let mut performance: Vec<_> = self.performance.read().await.values().cloned().collect();
+        performance.sort_by(|a, b| a.strategy_name.cmp(&b.strategy_name));
+        performance
+    }
+
+    /// Weight multiplier `LearnedEnsemble` applies to each loaded strategy
+    pub async fn get_learned_weights(&self) -> HashMap<String, f64> {
+        let performance = self.performance.read().await;
+        self.strategies
+            .iter()
+            .map(|s| {
+                (
+                    s.name().to_string(),
+                    learned_weight(performance.get(s.name())),
+                )
+            })
+            .collect()
+    }
+
     async fn add_to_history(&self, signal: CombinedSignal) {
         let mut history = self.signal_history.write().await;
         history.push(signal);
@@ -628,6 +835,17 @@ impl Default for StrategyEngine {
     }
 }
 
+/// Hit rate relative to a coin flip, e.g. 60% accuracy → 1.2x, clamped to
+/// `ENSEMBLE_WEIGHT_RANGE`; 1.0 until the strategy has enough recorded outcomes
+fn learned_weight(performance: Option<&StrategyPerformance>) -> f64 {
+    match performance {
+        Some(p) if p.total_signals >= ENSEMBLE_MIN_SAMPLES => {
+            (p.accuracy / 0.5).clamp(ENSEMBLE_WEIGHT_RANGE.0, ENSEMBLE_WEIGHT_RANGE.1)
+        },
+        _ => 1.0,
+    }
+}
+
 // Import log macros
 use log::{info, warn};
 
@@ -635,6 +853,7 @@ use log::{info, warn};
 mod tests {
     use super::*;
     use crate::market_data::cache::CandleData;
+    use crate::strategies::{macd_strategy::MacdStrategy, rsi_strategy::RsiStrategy};
 
     fn create_test_candles(count: usize) -> Vec<CandleData> {
         (0..count)
@@ -664,6 +883,8 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         }
     }
 
@@ -701,12 +922,17 @@ mod tests {
         let engine = StrategyEngine::new();
         let names = engine.get_strategy_names();
 
-        assert_eq!(names.len(), 5);
+        assert_eq!(names.len(), 7);
         assert!(names.contains(&"RSI Strategy"));
         assert!(names.contains(&"MACD Strategy"));
         assert!(names.contains(&"Volume Strategy"));
         assert!(names.contains(&"Bollinger Bands Strategy"));
         assert!(names.contains(&"Stochastic Strategy"));
+        assert!(names.contains(&"Supertrend Strategy"));
+        assert!(names.contains(&"Pairs Spread Strategy"));
+        // Registered but not in the default enabled set
+        assert!(!engine.is_strategy_enabled("Supertrend Strategy"));
+        assert!(!engine.is_strategy_enabled("Pairs Spread Strategy"));
     }
 
     #[test]
@@ -715,7 +941,7 @@ mod tests {
         let initial_count = engine.get_strategy_names().len();
 
         // Add a new RSI strategy — count increases by 1
-        engine.add_strategy(StrategyType::Rsi(RsiStrategy::new()));
+        engine.add_strategy(Box::new(RsiStrategy::new()));
         assert_eq!(engine.get_strategy_names().len(), initial_count + 1);
 
         // remove_strategy removes ALL strategies with that name (retain-based)
@@ -725,6 +951,81 @@ mod tests {
         assert_eq!(engine.get_strategy_names().len(), initial_count - 1);
     }
 
+    #[test]
+    fn test_strategy_engine_with_registry() {
+        let mut registry = StrategyRegistry::new();
+        registry.register_strategy(RsiStrategy::new()).unwrap();
+        registry.register_strategy(MacdStrategy::new()).unwrap();
+
+        let engine = StrategyEngine::with_registry(&registry, StrategyEngineConfig::default());
+        assert_eq!(
+            engine.get_strategy_names(),
+            vec!["RSI Strategy", "MACD Strategy"]
+        );
+        assert!(engine.is_strategy_enabled("MACD Strategy"));
+    }
+
+    #[test]
+    fn test_strategy_engine_enable_disable_at_runtime() {
+        let mut engine = StrategyEngine::with_config(StrategyEngineConfig {
+            enabled_strategies: vec![],
+            ..StrategyEngineConfig::default()
+        });
+        assert!(engine.is_strategy_enabled("RSI Strategy"));
+
+        engine.disable_strategy("RSI Strategy").unwrap();
+        assert!(!engine.is_strategy_enabled("RSI Strategy"));
+        assert!(engine.is_strategy_enabled("MACD Strategy"));
+        // An empty list meant every built-in strategy, so the rest are spelled out
+        assert_eq!(engine.config().enabled_strategies.len(), 6);
+
+        engine.enable_strategy("RSI Strategy").unwrap();
+        assert!(engine.is_strategy_enabled("RSI Strategy"));
+        assert_eq!(engine.config().enabled_strategies.len(), 7);
+
+        assert!(engine.enable_strategy("Unknown Strategy").is_err());
+        assert!(engine.disable_strategy("Unknown Strategy").is_err());
+    }
+
+    #[test]
+    fn test_strategy_engine_related_symbols_follow_enabled_strategies() {
+        let mut engine = StrategyEngine::new();
+        assert!(engine.related_symbols().is_empty());
+
+        engine.enable_strategy("Pairs Spread Strategy").unwrap();
+        assert_eq!(engine.related_symbols(), vec!["BTCUSDT".to_string()]);
+    }
+
+    #[test]
+    fn test_strategy_engine_cannot_disable_last_strategy() {
+        let mut engine = StrategyEngine::new();
+        engine
+            .set_enabled_strategies(vec!["RSI Strategy".to_string()])
+            .unwrap();
+        assert!(engine.disable_strategy("RSI Strategy").is_err());
+        assert!(engine.is_strategy_enabled("RSI Strategy"));
+
+        assert!(engine
+            .set_enabled_strategies(vec!["Unknown Strategy".to_string()])
+            .is_err());
+        assert_eq!(engine.config().enabled_strategies, vec!["RSI Strategy"]);
+    }
+
+    #[tokio::test]
+    async fn test_strategy_engine_disabled_strategy_is_skipped() {
+        let mut engine = StrategyEngine::with_config(StrategyEngineConfig {
+            min_strategies_agreement: 1,
+            ..StrategyEngineConfig::default()
+        });
+        engine.disable_strategy("RSI Strategy").unwrap();
+
+        let result = engine.analyze_market(&create_test_input()).await.unwrap();
+        assert!(result
+            .strategy_signals
+            .iter()
+            .all(|s| s.strategy_name != "RSI Strategy"));
+    }
+
     #[test]
     fn test_strategy_engine_config_default() {
         let config = StrategyEngineConfig::default();
@@ -1543,14 +1844,8 @@ mod tests {
     // ========== COV8 TESTS - Target untested branches ==========
 
     #[test]
-    fn test_cov8_strategy_type_name() {
-        let strategies = vec![
-            StrategyType::Rsi(RsiStrategy::new()),
-            StrategyType::Macd(MacdStrategy::new()),
-            StrategyType::Bollinger(BollingerStrategy::new()),
-            StrategyType::Stochastic(StochasticStrategy::new()),
-            StrategyType::Volume(VolumeStrategy::new()),
-        ];
+    fn test_cov8_boxed_strategy_name() {
+        let strategies = StrategyRegistry::with_builtin_strategies().create_all();
 
         for strategy in strategies {
             let name = strategy.name();
@@ -1559,14 +1854,8 @@ mod tests {
     }
 
     #[test]
-    fn test_cov8_strategy_type_config() {
-        let strategies = vec![
-            StrategyType::Rsi(RsiStrategy::new()),
-            StrategyType::Macd(MacdStrategy::new()),
-            StrategyType::Bollinger(BollingerStrategy::new()),
-            StrategyType::Stochastic(StochasticStrategy::new()),
-            StrategyType::Volume(VolumeStrategy::new()),
-        ];
+    fn test_cov8_boxed_strategy_config() {
+        let strategies = StrategyRegistry::with_builtin_strategies().create_all();
 
         for strategy in strategies {
             let config = strategy.config();
@@ -1575,14 +1864,8 @@ mod tests {
     }
 
     #[test]
-    fn test_cov8_strategy_type_update_config() {
-        let mut strategies = vec![
-            StrategyType::Rsi(RsiStrategy::new()),
-            StrategyType::Macd(MacdStrategy::new()),
-            StrategyType::Bollinger(BollingerStrategy::new()),
-            StrategyType::Stochastic(StochasticStrategy::new()),
-            StrategyType::Volume(VolumeStrategy::new()),
-        ];
+    fn test_cov8_boxed_strategy_update_config() {
+        let mut strategies = StrategyRegistry::with_builtin_strategies().create_all();
 
         for strategy in &mut strategies {
             let mut new_config = StrategyConfig::default();
@@ -1593,8 +1876,8 @@ mod tests {
     }
 
     #[test]
-    fn test_cov8_strategy_type_validate_data() {
-        let strategy = StrategyType::Rsi(RsiStrategy::new());
+    fn test_cov8_boxed_strategy_validate_data() {
+        let strategy: Box<dyn Strategy> = Box::new(RsiStrategy::new());
         let mut timeframe_data = HashMap::new();
         timeframe_data.insert("5m".to_string(), create_test_candles(10));
 
@@ -1604,6 +1887,8 @@ mod tests {
             current_price: 100.0,
             volume_24h: 1000.0,
             timestamp: 123456,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let result = strategy.validate_data(&input);
@@ -1611,8 +1896,8 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_cov8_strategy_type_analyze() {
-        let strategy = StrategyType::Rsi(RsiStrategy::new());
+    async fn test_cov8_boxed_strategy_analyze() {
+        let strategy: Box<dyn Strategy> = Box::new(RsiStrategy::new());
         let input = create_test_input();
 
         let result = strategy.analyze(&input).await;
@@ -1827,11 +2112,12 @@ mod tests {
     }
 
     #[test]
-    fn test_cov8_strategy_type_clone() {
-        let strategy = StrategyType::Rsi(RsiStrategy::new());
-        let cloned = strategy.clone();
+    fn test_cov8_strategy_registry_create() {
+        let registry = StrategyRegistry::with_builtin_strategies();
+        let strategy = registry.create("RSI Strategy").unwrap();
+        let another = registry.create("RSI Strategy").unwrap();
 
-        assert_eq!(strategy.name(), cloned.name());
+        assert_eq!(strategy.name(), another.name());
     }
 
     #[test]
@@ -1866,7 +2152,7 @@ mod tests {
         // Cover add_strategy method (lines 179-181)
         let mut engine = StrategyEngine::new();
         let initial_count = engine.get_strategy_names().len();
-        engine.add_strategy(StrategyType::Macd(MacdStrategy::new()));
+        engine.add_strategy(Box::new(MacdStrategy::new()));
         assert_eq!(engine.get_strategy_names().len(), initial_count + 1);
     }
 
@@ -1922,6 +2208,8 @@ mod tests {
             current_price: 100.0,
             volume_24h: 1000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         // All strategies fail validation → strategy_results empty → InsufficientData error
@@ -1956,6 +2244,8 @@ mod tests {
             current_price: 50000.0,
             volume_24h: 1000000.0,
             timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
         };
 
         let result = engine.analyze_market(&input).await;
@@ -2001,4 +2291,124 @@ mod tests {
         assert_eq!(count_after_remove, initial_count - 1);
         assert!(!engine.get_strategy_names().contains(&"Volume Strategy"));
     }
+
+    fn vote(
+        name: &str,
+        signal: TradingSignal,
+        confidence: f64,
+        weight: f64,
+    ) -> StrategySignalResult {
+        StrategySignalResult {
+            strategy_name: name.to_string(),
+            signal,
+            confidence,
+            reasoning: "".to_string(),
+            weight,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_combine_unanimous_agreement_and_dissent() {
+        let engine = StrategyEngine::new();
+        let agree = vec![
+            vote("S1", TradingSignal::Long, 0.8, 1.0),
+            vote("S2", TradingSignal::Long, 0.6, 1.0),
+        ];
+        let (signal, confidence, _) = engine.combine_unanimous(&agree);
+        assert_eq!(signal, TradingSignal::Long);
+        assert!((confidence - 0.7).abs() < 1e-9);
+
+        let mixed = vec![
+            vote("S1", TradingSignal::Long, 0.8, 1.0),
+            vote("S2", TradingSignal::Neutral, 0.6, 1.0),
+        ];
+        let (signal, _, reasoning) = engine.combine_unanimous(&mixed);
+        assert_eq!(signal, TradingSignal::Neutral);
+        assert!(reasoning.contains("1L/0S/1N"));
+    }
+
+    #[test]
+    fn test_combine_learned_ensemble_weights_outvote_count() {
+        let config = StrategyEngineConfig {
+            signal_combination_mode: SignalCombinationMode::LearnedEnsemble,
+            min_strategies_agreement: 1,
+            ..Default::default()
+        };
+        let engine = StrategyEngine::with_config(config);
+        // One strategy with a strong track record outweighs two weak ones
+        let results = vec![
+            vote("Good", TradingSignal::Short, 0.8, 2.0),
+            vote("Weak1", TradingSignal::Long, 0.7, 0.5),
+            vote("Weak2", TradingSignal::Long, 0.7, 0.5),
+        ];
+        let (signal, confidence, _) = engine.combine_learned_ensemble(&results);
+        assert_eq!(signal, TradingSignal::Short);
+        assert!((confidence - 0.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_combine_learned_ensemble_requires_agreement() {
+        let engine = StrategyEngine::with_config(StrategyEngineConfig {
+            signal_combination_mode: SignalCombinationMode::LearnedEnsemble,
+            min_strategies_agreement: 2,
+            ..Default::default()
+        });
+        let results = vec![
+            vote("S1", TradingSignal::Long, 0.9, 2.0),
+            vote("S2", TradingSignal::Neutral, 0.5, 1.0),
+            vote("S3", TradingSignal::Short, 0.5, 0.5),
+        ];
+        let (signal, _, _) = engine.combine_learned_ensemble(&results);
+        assert_eq!(signal, TradingSignal::Neutral);
+    }
+
+    #[tokio::test]
+    async fn test_record_signal_outcome_adjusts_learned_weights() {
+        let engine = StrategyEngine::new();
+        let votes: HashMap<String, TradingSignal> = [
+            ("RSI Strategy".to_string(), TradingSignal::Long),
+            ("MACD Strategy".to_string(), TradingSignal::Short),
+            ("Volume Strategy".to_string(), TradingSignal::Neutral),
+        ]
+        .into_iter()
+        .collect();
+
+        // Below the sample floor every weight stays neutral
+        for _ in 0..(ENSEMBLE_MIN_SAMPLES - 1) {
+            engine
+                .record_signal_outcome(&votes, TradingSignal::Long, true)
+                .await;
+        }
+        assert_eq!(engine.get_learned_weights().await["RSI Strategy"], 1.0);
+
+        for _ in 0..5 {
+            engine
+                .record_signal_outcome(&votes, TradingSignal::Long, true)
+                .await;
+        }
+        let weights = engine.get_learned_weights().await;
+        assert_eq!(weights["RSI Strategy"], ENSEMBLE_WEIGHT_RANGE.1);
+        assert_eq!(weights["MACD Strategy"], ENSEMBLE_WEIGHT_RANGE.0);
+        assert_eq!(weights["Volume Strategy"], 1.0);
+
+        let performance = engine.get_strategy_performance().await;
+        assert_eq!(performance.len(), 2); // Neutral votes are not scored
+        let rsi = performance
+            .iter()
+            .find(|p| p.strategy_name == "RSI Strategy")
+            .unwrap();
+        assert_eq!(rsi.total_signals, ENSEMBLE_MIN_SAMPLES + 4);
+        assert_eq!(rsi.successful_signals, rsi.total_signals);
+    }
+
+    #[test]
+    fn test_set_combination_mode() {
+        let mut engine = StrategyEngine::new();
+        engine.set_combination_mode(SignalCombinationMode::Unanimous);
+        assert_eq!(
+            engine.config().signal_combination_mode,
+            SignalCombinationMode::Unanimous
+        );
+    }
 }
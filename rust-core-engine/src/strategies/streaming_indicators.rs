@@ -0,0 +1,899 @@
+use crate::market_data::cache::CandleData;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// @spec:FR-STRATEGIES-005 - Strategy Parameters
+// @ref:specs/02-design/2.5-components/COMP-RUST-TRADING.md#strategies
+
+// Incremental counterparts of the batch functions in `indicators`. Each calculator is
+// fed one closed candle at a time in O(1) (amortized for the rolling windows) and
+// produces the same values as the batch version's last element. `preview` evaluates a
+// still-forming candle without committing it.
+
+/// Rolling sums are rebuilt from the window after this many updates to stop drift
+const RESYNC_INTERVAL: usize = 1000;
+
+/// Simple moving average over a fixed window
+#[derive(Debug, Clone)]
+pub struct SmaCalculator {
+    period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+    updates_since_resync: usize,
+}
+
+impl SmaCalculator {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            window: VecDeque::with_capacity(period.max(1) + 1),
+            sum: 0.0,
+            updates_since_resync: 0,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.window.push_back(value);
+        self.sum += value;
+        if self.window.len() > self.period {
+            self.sum -= self.window.pop_front().unwrap_or(0.0);
+        }
+
+        self.updates_since_resync += 1;
+        if self.updates_since_resync >= RESYNC_INTERVAL {
+            self.sum = self.window.iter().sum();
+            self.updates_since_resync = 0;
+        }
+
+        self.value()
+    }
+
+    pub fn preview(&self, value: f64) -> Option<f64> {
+        if self.window.len() + 1 < self.period {
+            return None;
+        }
+        let dropped = if self.window.len() == self.period {
+            self.window.front().copied().unwrap_or(0.0)
+        } else {
+            0.0
+        };
+        Some((self.sum + value - dropped) / self.period as f64)
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        (self.window.len() == self.period).then(|| self.sum / self.period as f64)
+    }
+}
+
+/// Exponential moving average seeded with the SMA of the first `period` values
+#[derive(Debug, Clone)]
+pub struct EmaCalculator {
+    period: usize,
+    multiplier: f64,
+    seed_sum: f64,
+    seed_count: usize,
+    value: Option<f64>,
+}
+
+impl EmaCalculator {
+    pub fn new(period: usize) -> Self {
+        let period = period.max(1);
+        Self {
+            period,
+            multiplier: 2.0 / (period as f64 + 1.0),
+            seed_sum: 0.0,
+            seed_count: 0,
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.value = self.preview(value);
+        if self.value.is_none() {
+            self.seed_sum += value;
+            self.seed_count += 1;
+        }
+        self.value
+    }
+
+    pub fn preview(&self, value: f64) -> Option<f64> {
+        match self.value {
+            Some(ema) => Some(value * self.multiplier + ema * (1.0 - self.multiplier)),
+            None if self.seed_count + 1 == self.period => {
+                Some((self.seed_sum + value) / self.period as f64)
+            },
+            None => None,
+        }
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+}
+
+/// Wilder-smoothed RSI
+#[derive(Debug, Clone)]
+pub struct RsiCalculator {
+    period: usize,
+    prev_close: Option<f64>,
+    seed_gain: f64,
+    seed_loss: f64,
+    seed_count: usize,
+    averages: Option<(f64, f64)>,
+}
+
+impl RsiCalculator {
+    pub fn new(period: usize) -> Self {
+        Self {
+            period: period.max(1),
+            prev_close: None,
+            seed_gain: 0.0,
+            seed_loss: 0.0,
+            seed_count: 0,
+            averages: None,
+        }
+    }
+
+    pub fn update(&mut self, close: f64) -> Option<f64> {
+        let prev_close = self.prev_close.replace(close)?;
+        let (gain, loss) = split_change(close - prev_close);
+
+        match self.averages {
+            Some(averages) => self.averages = Some(self.smooth(averages, gain, loss)),
+            None => {
+                self.seed_gain += gain;
+                self.seed_loss += loss;
+                self.seed_count += 1;
+                if self.seed_count == self.period {
+                    let period = self.period as f64;
+                    self.averages = Some((self.seed_gain / period, self.seed_loss / period));
+                }
+            },
+        }
+        self.value()
+    }
+
+    pub fn preview(&self, close: f64) -> Option<f64> {
+        let (gain, loss) = split_change(close - self.prev_close?);
+        let (avg_gain, avg_loss) = match self.averages {
+            Some(averages) => self.smooth(averages, gain, loss),
+            None if self.seed_count + 1 == self.period => {
+                let period = self.period as f64;
+                (
+                    (self.seed_gain + gain) / period,
+                    (self.seed_loss + loss) / period,
+                )
+            },
+            None => return None,
+        };
+        Some(rsi_from_averages(avg_gain, avg_loss))
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.averages
+            .map(|(avg_gain, avg_loss)| rsi_from_averages(avg_gain, avg_loss))
+    }
+
+    fn smooth(&self, (avg_gain, avg_loss): (f64, f64), gain: f64, loss: f64) -> (f64, f64) {
+        let period = self.period as f64;
+        (
+            (avg_gain * (period - 1.0) + gain) / period,
+            (avg_loss * (period - 1.0) + loss) / period,
+        )
+    }
+}
+
+fn split_change(change: f64) -> (f64, f64) {
+    if change > 0.0 {
+        (change, 0.0)
+    } else {
+        (0.0, -change)
+    }
+}
+
+fn rsi_from_averages(avg_gain: f64, avg_loss: f64) -> f64 {
+    if avg_gain == 0.0 && avg_loss == 0.0 {
+        // No price movement - neutral RSI
+        50.0
+    } else if avg_loss == 0.0 {
+        100.0
+    } else {
+        100.0 - (100.0 / (1.0 + avg_gain / avg_loss))
+    }
+}
+
+/// MACD line, signal line and histogram for one candle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MacdValue {
+    pub macd: f64,
+    pub signal: f64,
+    pub histogram: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct MacdCalculator {
+    fast: EmaCalculator,
+    slow: EmaCalculator,
+    signal: EmaCalculator,
+    value: Option<MacdValue>,
+}
+
+impl MacdCalculator {
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Self {
+        Self {
+            fast: EmaCalculator::new(fast),
+            slow: EmaCalculator::new(slow),
+            signal: EmaCalculator::new(signal),
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, close: f64) -> Option<MacdValue> {
+        let fast = self.fast.update(close);
+        let slow = self.slow.update(close);
+        if let (Some(fast), Some(slow)) = (fast, slow) {
+            let macd = fast - slow;
+            self.value = self.signal.update(macd).map(|signal| MacdValue {
+                macd,
+                signal,
+                histogram: macd - signal,
+            });
+        }
+        self.value
+    }
+
+    pub fn preview(&self, close: f64) -> Option<MacdValue> {
+        let macd = self.fast.preview(close)? - self.slow.preview(close)?;
+        let signal = self.signal.preview(macd)?;
+        Some(MacdValue {
+            macd,
+            signal,
+            histogram: macd - signal,
+        })
+    }
+
+    pub fn value(&self) -> Option<MacdValue> {
+        self.value
+    }
+}
+
+/// Upper, middle and lower Bollinger band for one candle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BollingerValue {
+    pub upper: f64,
+    pub middle: f64,
+    pub lower: f64,
+}
+
+/// Bollinger bands from rolling sums (population std dev).
+///
+/// Sums are kept relative to a shift near the window mean and re-centered every
+/// `period` updates; raw sums of squares lose the variance to cancellation at
+/// price levels like BTC's.
+#[derive(Debug, Clone)]
+pub struct BollingerCalculator {
+    period: usize,
+    multiplier: f64,
+    window: VecDeque<f64>,
+    shift: f64,
+    sum: f64,
+    sum_sq: f64,
+    updates_since_resync: usize,
+}
+
+impl BollingerCalculator {
+    pub fn new(period: usize, multiplier: f64) -> Self {
+        Self {
+            period: period.max(1),
+            multiplier,
+            window: VecDeque::with_capacity(period.max(1) + 1),
+            shift: 0.0,
+            sum: 0.0,
+            sum_sq: 0.0,
+            updates_since_resync: 0,
+        }
+    }
+
+    pub fn update(&mut self, close: f64) -> Option<BollingerValue> {
+        if self.window.is_empty() {
+            self.shift = close;
+        }
+        self.window.push_back(close);
+        let added = close - self.shift;
+        self.sum += added;
+        self.sum_sq += added * added;
+        if self.window.len() > self.period {
+            let dropped = self.window.pop_front().unwrap_or(self.shift) - self.shift;
+            self.sum -= dropped;
+            self.sum_sq -= dropped * dropped;
+        }
+
+        self.updates_since_resync += 1;
+        if self.updates_since_resync >= self.period {
+            self.shift = self.window.iter().sum::<f64>() / self.window.len() as f64;
+            self.sum = self.window.iter().map(|v| v - self.shift).sum();
+            self.sum_sq = self.window.iter().map(|v| (v - self.shift).powi(2)).sum();
+            self.updates_since_resync = 0;
+        }
+
+        self.value()
+    }
+
+    pub fn preview(&self, close: f64) -> Option<BollingerValue> {
+        if self.window.len() + 1 < self.period {
+            return None;
+        }
+        let shift = if self.window.is_empty() {
+            close
+        } else {
+            self.shift
+        };
+        let dropped = if self.window.len() == self.period {
+            self.window.front().map_or(0.0, |v| v - shift)
+        } else {
+            0.0
+        };
+        let added = close - shift;
+        Some(self.bands(
+            shift,
+            self.sum + added - dropped,
+            self.sum_sq + added * added - dropped * dropped,
+        ))
+    }
+
+    pub fn value(&self) -> Option<BollingerValue> {
+        (self.window.len() == self.period).then(|| self.bands(self.shift, self.sum, self.sum_sq))
+    }
+
+    fn bands(&self, shift: f64, sum: f64, sum_sq: f64) -> BollingerValue {
+        let period = self.period as f64;
+        let offset = sum / period;
+        // Rounding can push a flat window's variance slightly below zero
+        let std_dev = (sum_sq / period - offset * offset).max(0.0).sqrt();
+        let mean = shift + offset;
+        BollingerValue {
+            upper: mean + self.multiplier * std_dev,
+            middle: mean,
+            lower: mean - self.multiplier * std_dev,
+        }
+    }
+}
+
+/// Average True Range (SMA of true range, as in `indicators::calculate_atr`)
+#[derive(Debug, Clone)]
+pub struct AtrCalculator {
+    prev_close: Option<f64>,
+    true_range: SmaCalculator,
+}
+
+impl AtrCalculator {
+    pub fn new(period: usize) -> Self {
+        Self {
+            prev_close: None,
+            true_range: SmaCalculator::new(period),
+        }
+    }
+
+    pub fn update(&mut self, candle: &CandleData) -> Option<f64> {
+        match self.prev_close.replace(candle.close) {
+            Some(prev_close) => self.true_range.update(true_range(candle, prev_close)),
+            None => None,
+        }
+    }
+
+    pub fn preview(&self, candle: &CandleData) -> Option<f64> {
+        self.true_range
+            .preview(true_range(candle, self.prev_close?))
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.true_range.value()
+    }
+}
+
+fn true_range(candle: &CandleData, prev_close: f64) -> f64 {
+    (candle.high - candle.low)
+        .max((candle.high - prev_close).abs())
+        .max((candle.low - prev_close).abs())
+}
+
+/// Stochastic %K and %D for one candle
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StochasticValue {
+    pub k: f64,
+    pub d: f64,
+}
+
+/// Stochastic oscillator with monotonic deques for the rolling high/low
+#[derive(Debug, Clone)]
+pub struct StochasticCalculator {
+    k_period: usize,
+    index: usize,
+    highs: VecDeque<(usize, f64)>,
+    lows: VecDeque<(usize, f64)>,
+    d_sma: SmaCalculator,
+    value: Option<StochasticValue>,
+}
+
+impl StochasticCalculator {
+    pub fn new(k_period: usize, d_period: usize) -> Self {
+        Self {
+            k_period: k_period.max(1),
+            index: 0,
+            highs: VecDeque::new(),
+            lows: VecDeque::new(),
+            d_sma: SmaCalculator::new(d_period),
+            value: None,
+        }
+    }
+
+    pub fn update(&mut self, candle: &CandleData) -> Option<StochasticValue> {
+        let index = self.index;
+        self.index += 1;
+
+        while self
+            .highs
+            .back()
+            .is_some_and(|&(_, high)| high <= candle.high)
+        {
+            self.highs.pop_back();
+        }
+        self.highs.push_back((index, candle.high));
+        while self.lows.back().is_some_and(|&(_, low)| low >= candle.low) {
+            self.lows.pop_back();
+        }
+        self.lows.push_back((index, candle.low));
+
+        // Drop entries that left the window [index + 1 - k_period, index]
+        let window_start = (index + 1).saturating_sub(self.k_period);
+        while self.highs.front().is_some_and(|&(i, _)| i < window_start) {
+            self.highs.pop_front();
+        }
+        while self.lows.front().is_some_and(|&(i, _)| i < window_start) {
+            self.lows.pop_front();
+        }
+
+        if index + 1 < self.k_period {
+            return None;
+        }
+        let highest = self
+            .highs
+            .front()
+            .map(|&(_, high)| high)
+            .unwrap_or(candle.high);
+        let lowest = self.lows.front().map(|&(_, low)| low).unwrap_or(candle.low);
+        let k = percent_k(candle.close, highest, lowest);
+        self.value = self.d_sma.update(k).map(|d| StochasticValue { k, d });
+        self.value
+    }
+
+    pub fn preview(&self, candle: &CandleData) -> Option<StochasticValue> {
+        if self.index + 1 < self.k_period {
+            return None;
+        }
+        let window_start = (self.index + 1).saturating_sub(self.k_period);
+        let highest = self
+            .highs
+            .iter()
+            .find(|&&(i, _)| i >= window_start)
+            .map_or(candle.high, |&(_, high)| high.max(candle.high));
+        let lowest = self
+            .lows
+            .iter()
+            .find(|&&(i, _)| i >= window_start)
+            .map_or(candle.low, |&(_, low)| low.min(candle.low));
+        let k = percent_k(candle.close, highest, lowest);
+        self.d_sma.preview(k).map(|d| StochasticValue { k, d })
+    }
+
+    pub fn value(&self) -> Option<StochasticValue> {
+        self.value
+    }
+}
+
+fn percent_k(close: f64, highest: f64, lowest: f64) -> f64 {
+    if highest == lowest {
+        50.0
+    } else {
+        (close - lowest) / (highest - lowest) * 100.0
+    }
+}
+
+/// Indicator parameters tracked per symbol/timeframe
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StreamingIndicatorConfig {
+    pub rsi_period: usize,
+    pub macd_fast: usize,
+    pub macd_slow: usize,
+    pub macd_signal: usize,
+    pub bollinger_period: usize,
+    pub bollinger_multiplier: f64,
+    pub stochastic_k_period: usize,
+    pub stochastic_d_period: usize,
+    pub atr_period: usize,
+}
+
+impl Default for StreamingIndicatorConfig {
+    /// Matches the built-in strategies' default parameters
+    fn default() -> Self {
+        Self {
+            rsi_period: 14,
+            macd_fast: 12,
+            macd_slow: 26,
+            macd_signal: 9,
+            bollinger_period: 20,
+            bollinger_multiplier: 2.0,
+            stochastic_k_period: 14,
+            stochastic_d_period: 3,
+            atr_period: 14,
+        }
+    }
+}
+
+/// Values of every tracked indicator at one candle (None until warmed up)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct IndicatorValues {
+    pub rsi: Option<f64>,
+    pub macd: Option<MacdValue>,
+    pub bollinger: Option<BollingerValue>,
+    pub stochastic: Option<StochasticValue>,
+    pub atr: Option<f64>,
+}
+
+/// Latest and previous indicator values of one timeframe, as handed to strategies
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeframeIndicators {
+    pub config: StreamingIndicatorConfig,
+    pub current: IndicatorValues,
+    pub previous: IndicatorValues,
+}
+
+impl TimeframeIndicators {
+    /// Current and previous RSI if computed with `period`
+    pub fn rsi(&self, period: usize) -> Option<(f64, f64)> {
+        if period != self.config.rsi_period {
+            return None;
+        }
+        let current = self.current.rsi?;
+        Some((current, self.previous.rsi.unwrap_or(current)))
+    }
+
+    pub fn macd(&self, fast: usize, slow: usize, signal: usize) -> Option<(MacdValue, MacdValue)> {
+        let config = &self.config;
+        if (fast, slow, signal) != (config.macd_fast, config.macd_slow, config.macd_signal) {
+            return None;
+        }
+        let current = self.current.macd?;
+        Some((current, self.previous.macd.unwrap_or(current)))
+    }
+
+    pub fn bollinger(
+        &self,
+        period: usize,
+        multiplier: f64,
+    ) -> Option<(BollingerValue, BollingerValue)> {
+        if period != self.config.bollinger_period
+            || (multiplier - self.config.bollinger_multiplier).abs() > f64::EPSILON
+        {
+            return None;
+        }
+        let current = self.current.bollinger?;
+        Some((current, self.previous.bollinger.unwrap_or(current)))
+    }
+
+    pub fn stochastic(
+        &self,
+        k_period: usize,
+        d_period: usize,
+    ) -> Option<(StochasticValue, StochasticValue)> {
+        let config = &self.config;
+        if (k_period, d_period) != (config.stochastic_k_period, config.stochastic_d_period) {
+            return None;
+        }
+        let current = self.current.stochastic?;
+        Some((current, self.previous.stochastic.unwrap_or(current)))
+    }
+}
+
+/// All incremental indicators for one symbol/timeframe
+#[derive(Debug, Clone)]
+pub struct StreamingIndicators {
+    config: StreamingIndicatorConfig,
+    rsi: RsiCalculator,
+    macd: MacdCalculator,
+    bollinger: BollingerCalculator,
+    stochastic: StochasticCalculator,
+    atr: AtrCalculator,
+    last_open_time: Option<i64>,
+    candle_count: usize,
+}
+
+impl StreamingIndicators {
+    pub fn new(config: StreamingIndicatorConfig) -> Self {
+        Self {
+            config,
+            rsi: RsiCalculator::new(config.rsi_period),
+            macd: MacdCalculator::new(config.macd_fast, config.macd_slow, config.macd_signal),
+            bollinger: BollingerCalculator::new(
+                config.bollinger_period,
+                config.bollinger_multiplier,
+            ),
+            stochastic: StochasticCalculator::new(
+                config.stochastic_k_period,
+                config.stochastic_d_period,
+            ),
+            atr: AtrCalculator::new(config.atr_period),
+            last_open_time: None,
+            candle_count: 0,
+        }
+    }
+
+    /// Commit a closed candle. Candles not newer than the last one are ignored.
+    pub fn update(&mut self, candle: &CandleData) -> bool {
+        if self
+            .last_open_time
+            .is_some_and(|open_time| candle.open_time <= open_time)
+        {
+            return false;
+        }
+        self.rsi.update(candle.close);
+        self.macd.update(candle.close);
+        self.bollinger.update(candle.close);
+        self.stochastic.update(candle);
+        self.atr.update(candle);
+        self.last_open_time = Some(candle.open_time);
+        self.candle_count += 1;
+        true
+    }
+
+    /// Values after the committed candles
+    pub fn values(&self) -> IndicatorValues {
+        IndicatorValues {
+            rsi: self.rsi.value(),
+            macd: self.macd.value(),
+            bollinger: self.bollinger.value(),
+            stochastic: self.stochastic.value(),
+            atr: self.atr.value(),
+        }
+    }
+
+    /// Values as if `candle` were committed, without changing state
+    pub fn preview(&self, candle: &CandleData) -> IndicatorValues {
+        IndicatorValues {
+            rsi: self.rsi.preview(candle.close),
+            macd: self.macd.preview(candle.close),
+            bollinger: self.bollinger.preview(candle.close),
+            stochastic: self.stochastic.preview(candle),
+            atr: self.atr.preview(candle),
+        }
+    }
+
+    /// Snapshot whose current values include the still-forming `latest` candle
+    pub fn snapshot(&self, latest: &CandleData) -> TimeframeIndicators {
+        TimeframeIndicators {
+            config: self.config,
+            current: self.preview(latest),
+            previous: self.values(),
+        }
+    }
+
+    pub fn config(&self) -> &StreamingIndicatorConfig {
+        &self.config
+    }
+
+    pub fn last_open_time(&self) -> Option<i64> {
+        self.last_open_time
+    }
+
+    pub fn candle_count(&self) -> usize {
+        self.candle_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategies::indicators::{
+        calculate_atr, calculate_bollinger_bands, calculate_ema, calculate_macd, calculate_rsi,
+        calculate_sma, calculate_stochastic,
+    };
+
+    fn sample_candles(count: usize) -> Vec<CandleData> {
+        (0..count)
+            .map(|i| {
+                let t = i as f64;
+                let close = 100.0 + (t * 0.3).sin() * 5.0 + t * 0.05;
+                CandleData {
+                    open: close - 0.5,
+                    high: close + 1.0 + (t * 0.7).cos().abs(),
+                    low: close - 1.0 - (t * 0.5).sin().abs(),
+                    close,
+                    volume: 1000.0 + t,
+                    open_time: (i as i64) * 60000,
+                    close_time: (i as i64) * 60000 + 59999,
+                    quote_volume: 0.0,
+                    trades: 10,
+                    is_closed: true,
+                }
+            })
+            .collect()
+    }
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "{a} != {b}");
+    }
+
+    #[test]
+    fn test_sma_and_ema_match_batch() {
+        let candles = sample_candles(120);
+        let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+        let mut sma = SmaCalculator::new(20);
+        let mut ema = EmaCalculator::new(20);
+
+        for (i, &close) in closes.iter().enumerate() {
+            assert_eq!(sma.preview(close).is_some(), i + 1 >= 20);
+            let preview = ema.preview(close);
+            let sma_value = sma.update(close);
+            let ema_value = ema.update(close);
+            assert_eq!(preview, ema_value);
+
+            if i + 1 >= 20 {
+                let prefix = &closes[..=i];
+                assert_close(
+                    sma_value.unwrap(),
+                    *calculate_sma(prefix, 20).unwrap().last().unwrap(),
+                );
+                assert_close(
+                    ema_value.unwrap(),
+                    *calculate_ema(prefix, 20).unwrap().last().unwrap(),
+                );
+            } else {
+                assert!(sma_value.is_none());
+                assert!(ema_value.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_rsi_matches_batch() {
+        let candles = sample_candles(100);
+        let mut rsi = RsiCalculator::new(14);
+
+        for i in 0..candles.len() {
+            let preview = rsi.preview(candles[i].close);
+            let value = rsi.update(candles[i].close);
+            assert_eq!(preview, value);
+            if i >= 14 {
+                let batch = calculate_rsi(&candles[..=i], 14).unwrap();
+                assert_close(value.unwrap(), *batch.last().unwrap());
+            } else {
+                assert!(value.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_macd_matches_batch() {
+        let candles = sample_candles(150);
+        let mut macd = MacdCalculator::new(12, 26, 9);
+
+        for i in 0..candles.len() {
+            let value = macd.update(candles[i].close);
+            if i + 1 >= 26 + 9 {
+                let batch = calculate_macd(&candles[..=i], 12, 26, 9).unwrap();
+                let value = value.unwrap();
+                assert_close(value.macd, *batch.macd_line.last().unwrap());
+                assert_close(value.signal, *batch.signal_line.last().unwrap());
+                assert_close(value.histogram, *batch.histogram.last().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_bollinger_matches_batch() {
+        let candles = sample_candles(80);
+        let mut bollinger = BollingerCalculator::new(20, 2.0);
+
+        for i in 0..candles.len() {
+            let preview = bollinger.preview(candles[i].close);
+            let value = bollinger.update(candles[i].close);
+            assert_eq!(preview.is_some(), value.is_some());
+            if i + 1 >= 20 {
+                let batch = calculate_bollinger_bands(&candles[..=i], 20, 2.0).unwrap();
+                let value = value.unwrap();
+                assert_close(value.upper, *batch.upper.last().unwrap());
+                assert_close(value.middle, *batch.middle.last().unwrap());
+                assert_close(value.lower, *batch.lower.last().unwrap());
+                assert_close(preview.unwrap().upper, value.upper);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bollinger_flat_prices_have_zero_width() {
+        let mut bollinger = BollingerCalculator::new(5, 2.0);
+        let mut value = None;
+        for _ in 0..10 {
+            value = bollinger.update(50000.1);
+        }
+        let value = value.unwrap();
+        assert_close(value.upper, value.lower);
+        assert_close(value.middle, 50000.1);
+    }
+
+    #[test]
+    fn test_atr_matches_batch() {
+        let candles = sample_candles(60);
+        let mut atr = AtrCalculator::new(14);
+
+        for i in 0..candles.len() {
+            let preview = atr.preview(&candles[i]);
+            let value = atr.update(&candles[i]);
+            assert_eq!(preview.is_some(), value.is_some());
+            if i >= 14 {
+                let batch = calculate_atr(&candles[..=i], 14).unwrap();
+                assert_close(value.unwrap(), *batch.last().unwrap());
+                assert_close(preview.unwrap(), value.unwrap());
+            } else {
+                assert!(value.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_stochastic_matches_batch() {
+        let candles = sample_candles(80);
+        let mut stochastic = StochasticCalculator::new(14, 3);
+
+        for i in 0..candles.len() {
+            let preview = stochastic.preview(&candles[i]);
+            let value = stochastic.update(&candles[i]);
+            assert_eq!(preview, value);
+            if i + 1 >= 14 + 3 {
+                let batch = calculate_stochastic(&candles[..=i], 14, 3).unwrap();
+                let value = value.unwrap();
+                assert_close(value.k, *batch.k_percent.last().unwrap());
+                assert_close(value.d, *batch.d_percent.last().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_sums_resync_without_drift() {
+        let mut sma = SmaCalculator::new(3);
+        let mut value = None;
+        for i in 0..(RESYNC_INTERVAL * 2 + 7) {
+            value = sma.update(1e9 + (i % 7) as f64 * 0.1);
+        }
+        let expected = sma.window.iter().sum::<f64>() / 3.0;
+        assert_close(value.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_streaming_indicators_snapshot_and_ordering() {
+        let candles = sample_candles(60);
+        let mut indicators = StreamingIndicators::new(StreamingIndicatorConfig::default());
+        let (closed, latest) = candles.split_at(candles.len() - 1);
+        for candle in closed {
+            assert!(indicators.update(candle));
+        }
+        // Replayed or older candles are ignored
+        assert!(!indicators.update(&closed[10]));
+        assert_eq!(indicators.candle_count(), closed.len());
+        assert_eq!(indicators.last_open_time(), Some(closed[58].open_time));
+
+        let snapshot = indicators.snapshot(&latest[0]);
+        assert_eq!(snapshot.previous, indicators.values());
+
+        let rsi = calculate_rsi(&candles, 14).unwrap();
+        let (current, previous) = snapshot.rsi(14).unwrap();
+        assert_close(current, rsi[rsi.len() - 1]);
+        assert_close(previous, rsi[rsi.len() - 2]);
+
+        // Parameters that differ from the tracked ones are not served
+        assert!(snapshot.rsi(21).is_none());
+        assert!(snapshot.macd(8, 26, 9).is_none());
+        assert!(snapshot.bollinger(20, 2.5).is_none());
+        assert!(snapshot.stochastic(14, 3).is_some());
+    }
+}
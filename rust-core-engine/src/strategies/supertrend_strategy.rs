@@ -0,0 +1,468 @@
+use super::*;
+use crate::strategies::indicators::{
+    calculate_atr, calculate_donchian_channels, calculate_supertrend, DonchianChannels,
+};
+use async_trait::async_trait;
+use serde_json::json;
+
+/// Supertrend + Donchian breakout trend-following strategy
+
+// @spec:FR-STRATEGIES-007 - Multi-Timeframe Analysis
+// @ref:specs/02-design/2.5-components/COMP-RUST-TRADING.md#strategies
+
+#[derive(Debug, Clone)]
+pub struct SupertrendStrategy {
+    config: StrategyConfig,
+}
+
+/// Supertrend direction on one timeframe
+#[derive(Debug, Clone, Copy)]
+struct TrendState {
+    is_uptrend: bool,
+    flipped: bool,
+    line: f64,
+}
+
+impl SupertrendStrategy {
+    pub fn new() -> Self {
+        let mut config = StrategyConfig::default();
+        config
+            .parameters
+            .insert("atr_period".to_string(), json!(10));
+        config
+            .parameters
+            .insert("atr_multiplier".to_string(), json!(3.0));
+        config
+            .parameters
+            .insert("donchian_period".to_string(), json!(20));
+        config
+            .parameters
+            .insert("breakout_atr_buffer".to_string(), json!(0.25));
+
+        Self { config }
+    }
+
+    pub fn with_config(config: StrategyConfig) -> Self {
+        Self { config }
+    }
+
+    fn get_atr_period(&self) -> usize {
+        self.config
+            .parameters
+            .get("atr_period")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(10) as usize
+    }
+
+    fn get_atr_multiplier(&self) -> f64 {
+        self.config
+            .parameters
+            .get("atr_multiplier")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(3.0)
+    }
+
+    fn get_donchian_period(&self) -> usize {
+        self.config
+            .parameters
+            .get("donchian_period")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(20) as usize
+    }
+
+    /// Minimum close beyond the channel, in ATRs, for a breakout to count
+    fn get_breakout_atr_buffer(&self) -> f64 {
+        self.config
+            .parameters
+            .get("breakout_atr_buffer")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.25)
+    }
+
+    fn trend_state(&self, candles: &[CandleData]) -> Result<TrendState, StrategyError> {
+        let result =
+            calculate_supertrend(candles, self.get_atr_period(), self.get_atr_multiplier())
+                .map_err(StrategyError::CalculationError)?;
+        let len = result.is_uptrend.len();
+        if len == 0 {
+            return Err(StrategyError::InsufficientData(
+                "No Supertrend values calculated".to_string(),
+            ));
+        }
+
+        let is_uptrend = result.is_uptrend[len - 1];
+        Ok(TrendState {
+            is_uptrend,
+            flipped: len > 1 && result.is_uptrend[len - 2] != is_uptrend,
+            line: result.supertrend[len - 1],
+        })
+    }
+}
+
+#[async_trait]
+impl Strategy for SupertrendStrategy {
+    fn name(&self) -> &'static str {
+        "Supertrend Strategy"
+    }
+
+    fn description(&self) -> &'static str {
+        "Trend-following strategy combining Supertrend direction with ATR-confirmed Donchian channel breakouts"
+    }
+
+    fn required_timeframes(&self) -> Vec<&'static str> {
+        vec!["5m", "15m"]
+    }
+
+    async fn analyze(&self, data: &StrategyInput) -> Result<StrategyOutput, StrategyError> {
+        self.validate_data(data)?;
+
+        let primary_timeframe = "5m";
+        let confirmation_timeframe = "15m";
+
+        let primary_candles = data.timeframe_data.get(primary_timeframe).ok_or_else(|| {
+            StrategyError::InsufficientData(format!("Missing {primary_timeframe} data"))
+        })?;
+        let confirmation_candles =
+            data.timeframe_data
+                .get(confirmation_timeframe)
+                .ok_or_else(|| {
+                    StrategyError::InsufficientData(format!(
+                        "Missing {confirmation_timeframe} data"
+                    ))
+                })?;
+
+        let primary_trend = self.trend_state(primary_candles)?;
+        let confirmation_trend = self.trend_state(confirmation_candles)?;
+
+        // Channel of the candles before the latest one, so the latest close can break it
+        let (latest, previous) = primary_candles
+            .split_last()
+            .ok_or_else(|| StrategyError::InsufficientData("No candles".to_string()))?;
+        let channel = calculate_donchian_channels(previous, self.get_donchian_period())
+            .map_err(StrategyError::CalculationError)?;
+        let DonchianChannels {
+            upper,
+            middle,
+            lower,
+        } = channel;
+        let (channel_upper, channel_middle, channel_lower) = (
+            *upper.last().unwrap_or(&latest.high),
+            *middle.last().unwrap_or(&latest.close),
+            *lower.last().unwrap_or(&latest.low),
+        );
+
+        let atr = calculate_atr(primary_candles, self.get_atr_period())
+            .map_err(StrategyError::CalculationError)?
+            .last()
+            .copied()
+            .unwrap_or(0.0);
+        let buffer = self.get_breakout_atr_buffer() * atr;
+        let close = latest.close;
+        let breakout_up = close > channel_upper + buffer;
+        let breakout_down = close < channel_lower - buffer;
+
+        let (signal, confidence, reasoning) = self.analyze_trend_signals(
+            primary_trend,
+            confirmation_trend,
+            breakout_up,
+            breakout_down,
+            close > channel_middle,
+        );
+
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            format!("supertrend_{primary_timeframe}"),
+            json!(primary_trend.line),
+        );
+        metadata.insert(
+            format!("supertrend_uptrend_{primary_timeframe}"),
+            json!(primary_trend.is_uptrend),
+        );
+        metadata.insert(
+            format!("supertrend_uptrend_{confirmation_timeframe}"),
+            json!(confirmation_trend.is_uptrend),
+        );
+        metadata.insert("donchian_upper".to_string(), json!(channel_upper));
+        metadata.insert("donchian_lower".to_string(), json!(channel_lower));
+        metadata.insert("atr".to_string(), json!(atr));
+        metadata.insert("breakout_up".to_string(), json!(breakout_up));
+        metadata.insert("breakout_down".to_string(), json!(breakout_down));
+
+        Ok(StrategyOutput {
+            signal,
+            confidence,
+            reasoning,
+            timeframe: primary_timeframe.to_string(),
+            timestamp: data.timestamp,
+            metadata,
+        })
+    }
+
+    fn config(&self) -> &StrategyConfig {
+        &self.config
+    }
+
+    fn update_config(&mut self, config: StrategyConfig) {
+        self.config = config;
+    }
+
+    fn validate_data(&self, data: &StrategyInput) -> Result<(), StrategyError> {
+        // Donchian uses the candles before the latest; ATR needs one extra for the first range
+        let min_required = self.get_donchian_period().max(self.get_atr_period()) + 2;
+
+        for timeframe in self.required_timeframes() {
+            let candles = data.timeframe_data.get(timeframe).ok_or_else(|| {
+                StrategyError::DataValidation(format!("Missing {timeframe} timeframe data"))
+            })?;
+
+            if candles.len() < min_required {
+                return Err(StrategyError::InsufficientData(format!(
+                    "Need at least {} candles for {} timeframe, got {}",
+                    min_required,
+                    timeframe,
+                    candles.len()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl SupertrendStrategy {
+    fn analyze_trend_signals(
+        &self,
+        primary: TrendState,
+        confirmation: TrendState,
+        breakout_up: bool,
+        breakout_down: bool,
+        above_channel_middle: bool,
+    ) -> (TradingSignal, f64, String) {
+        let trends_agree = primary.is_uptrend == confirmation.is_uptrend;
+
+        // Channel breakout in the direction of both Supertrends
+        if breakout_up && primary.is_uptrend && confirmation.is_uptrend {
+            return (
+                TradingSignal::Long,
+                0.85,
+                "Donchian breakout above channel confirmed by Supertrend on both timeframes"
+                    .to_string(),
+            );
+        }
+        if breakout_down && !primary.is_uptrend && !confirmation.is_uptrend {
+            return (
+                TradingSignal::Short,
+                0.85,
+                "Donchian breakdown below channel confirmed by Supertrend on both timeframes"
+                    .to_string(),
+            );
+        }
+
+        // Fresh Supertrend flip that joins the higher timeframe trend
+        if primary.flipped && trends_agree {
+            return if primary.is_uptrend {
+                (
+                    TradingSignal::Long,
+                    0.74,
+                    "Supertrend flipped bullish in line with 15m trend".to_string(),
+                )
+            } else {
+                (
+                    TradingSignal::Short,
+                    0.74,
+                    "Supertrend flipped bearish in line with 15m trend".to_string(),
+                )
+            };
+        }
+
+        // Breakout against a still-opposed lower timeframe trend
+        if breakout_up && confirmation.is_uptrend {
+            return (
+                TradingSignal::Long,
+                0.68,
+                "Donchian breakout with bullish 15m Supertrend".to_string(),
+            );
+        }
+        if breakout_down && !confirmation.is_uptrend {
+            return (
+                TradingSignal::Short,
+                0.68,
+                "Donchian breakdown with bearish 15m Supertrend".to_string(),
+            );
+        }
+
+        // Established trend, price on the trend side of the channel
+        if trends_agree && primary.is_uptrend && above_channel_middle {
+            return (
+                TradingSignal::Long,
+                0.62,
+                "Uptrend on both timeframes, price in upper half of channel".to_string(),
+            );
+        }
+        if trends_agree && !primary.is_uptrend && !above_channel_middle {
+            return (
+                TradingSignal::Short,
+                0.62,
+                "Downtrend on both timeframes, price in lower half of channel".to_string(),
+            );
+        }
+
+        if !trends_agree {
+            return (
+                TradingSignal::Neutral,
+                0.45,
+                "Supertrend direction differs between timeframes".to_string(),
+            );
+        }
+
+        (
+            TradingSignal::Neutral,
+            0.5,
+            "Trend intact but price pulled back inside the channel".to_string(),
+        )
+    }
+}
+
+impl Default for SupertrendStrategy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn create_test_candles(prices: Vec<f64>) -> Vec<CandleData> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| CandleData {
+                open: price,
+                high: price * 1.002,
+                low: price * 0.998,
+                close: price,
+                volume: 1000.0,
+                open_time: (i as i64) * 300000,
+                close_time: (i as i64) * 300000 + 300000,
+                quote_volume: 1000.0 * price,
+                trades: 100,
+                is_closed: true,
+            })
+            .collect()
+    }
+
+    fn create_test_input(prices_5m: Vec<f64>, prices_15m: Vec<f64>) -> StrategyInput {
+        let current_price = *prices_5m.last().unwrap();
+        let mut timeframe_data = HashMap::new();
+        timeframe_data.insert("5m".to_string(), create_test_candles(prices_5m));
+        timeframe_data.insert("15m".to_string(), create_test_candles(prices_15m));
+
+        StrategyInput {
+            symbol: "BTCUSDT".to_string(),
+            timeframe_data,
+            current_price,
+            volume_24h: 1000000.0,
+            timestamp: 1234567890,
+            indicators: HashMap::new(),
+            related_data: HashMap::new(),
+        }
+    }
+
+    fn rising(count: usize, step: f64) -> Vec<f64> {
+        (0..count).map(|i| 100.0 + i as f64 * step).collect()
+    }
+
+    fn falling(count: usize, step: f64) -> Vec<f64> {
+        (0..count).map(|i| 200.0 - i as f64 * step).collect()
+    }
+
+    #[test]
+    fn test_supertrend_strategy_new() {
+        let strategy = SupertrendStrategy::new();
+        assert_eq!(strategy.name(), "Supertrend Strategy");
+        assert_eq!(strategy.required_timeframes(), vec!["5m", "15m"]);
+        assert_eq!(strategy.get_atr_period(), 10);
+        assert_eq!(strategy.get_atr_multiplier(), 3.0);
+        assert_eq!(strategy.get_donchian_period(), 20);
+        assert_eq!(strategy.get_breakout_atr_buffer(), 0.25);
+    }
+
+    #[tokio::test]
+    async fn test_supertrend_strategy_breakout_long() {
+        let strategy = SupertrendStrategy::new();
+        let mut prices_5m = rising(40, 0.5);
+        // Latest candle jumps well clear of the prior 20-candle high
+        prices_5m.push(125.0);
+
+        let output = strategy
+            .analyze(&create_test_input(prices_5m, rising(40, 1.0)))
+            .await
+            .unwrap();
+        assert_eq!(output.signal, TradingSignal::Long);
+        assert_eq!(output.confidence, 0.85);
+        assert_eq!(output.metadata["breakout_up"], json!(true));
+    }
+
+    #[tokio::test]
+    async fn test_supertrend_strategy_breakdown_short() {
+        let strategy = SupertrendStrategy::new();
+        let mut prices_5m = falling(40, 0.5);
+        prices_5m.push(175.0);
+
+        let output = strategy
+            .analyze(&create_test_input(prices_5m, falling(40, 1.0)))
+            .await
+            .unwrap();
+        assert_eq!(output.signal, TradingSignal::Short);
+        assert_eq!(output.confidence, 0.85);
+        assert_eq!(output.metadata["breakout_down"], json!(true));
+    }
+
+    #[tokio::test]
+    async fn test_supertrend_strategy_small_move_needs_atr_buffer() {
+        // A new high by less than the ATR buffer is not a breakout
+        let config = StrategyConfig {
+            parameters: HashMap::from([("breakout_atr_buffer".to_string(), json!(50.0))]),
+            ..SupertrendStrategy::new().config().clone()
+        };
+        let strategy = SupertrendStrategy::with_config(config);
+
+        let output = strategy
+            .analyze(&create_test_input(rising(41, 0.5), rising(40, 1.0)))
+            .await
+            .unwrap();
+        assert_eq!(output.metadata["breakout_up"], json!(false));
+        assert_eq!(output.signal, TradingSignal::Long);
+        assert!(output.confidence < 0.85);
+    }
+
+    #[tokio::test]
+    async fn test_supertrend_strategy_conflicting_timeframes_neutral() {
+        let strategy = SupertrendStrategy::new();
+        let output = strategy
+            .analyze(&create_test_input(rising(40, 0.5), falling(40, 1.0)))
+            .await
+            .unwrap();
+        assert_eq!(output.signal, TradingSignal::Neutral);
+        assert_eq!(output.metadata["supertrend_uptrend_5m"], json!(true));
+        assert_eq!(output.metadata["supertrend_uptrend_15m"], json!(false));
+    }
+
+    #[tokio::test]
+    async fn test_supertrend_strategy_insufficient_data() {
+        let strategy = SupertrendStrategy::new();
+        let result = strategy
+            .analyze(&create_test_input(rising(15, 1.0), rising(40, 1.0)))
+            .await;
+        assert!(matches!(result, Err(StrategyError::InsufficientData(_))));
+
+        let mut input = create_test_input(rising(40, 1.0), rising(40, 1.0));
+        input.timeframe_data.remove("15m");
+        assert!(matches!(
+            strategy.validate_data(&input),
+            Err(StrategyError::DataValidation(_))
+        ));
+    }
+}
@@ -36,6 +36,8 @@ fn create_strategy_input(candles: Vec<CandleData>, symbol: &str) -> StrategyInpu
         current_price,
         volume_24h,
         timestamp,
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     }
 }
 
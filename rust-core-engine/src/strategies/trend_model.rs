@@ -0,0 +1,427 @@
+use crate::market_data::cache::CandleData;
+use crate::strategies::indicators::{
+    align_to_candles, calculate_atr, calculate_bollinger_bands, calculate_ema, calculate_macd,
+    calculate_rsi, calculate_sma,
+};
+use crate::strategies::trend_filter::TrendDirection;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Serialized model format version, bumped whenever features or layout change
+pub const TREND_MODEL_VERSION: u32 = 1;
+
+/// Engineered features, in the order the model weights expect them
+pub const TREND_FEATURES: [&str; 9] = [
+    "return_1",
+    "return_5",
+    "return_20",
+    "rsi_14",
+    "macd_histogram",
+    "bollinger_percent_b",
+    "ema_20_50_spread",
+    "atr_14",
+    "volume_ratio_20",
+];
+
+/// Candles needed before the first complete feature row (EMA 50 warm-up)
+pub const TREND_FEATURE_WARMUP: usize = 50;
+
+/// Training hyper-parameters for the logistic trend model
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendModelConfig {
+    /// Candles ahead the label looks at
+    pub horizon: usize,
+    /// Minimum absolute forward log return for a candle to be labeled; smaller moves are skipped
+    pub label_threshold: f64,
+    pub learning_rate: f64,
+    pub epochs: usize,
+    /// L2 regularization strength
+    pub l2: f64,
+    /// Fraction of samples (the most recent ones) held out for validation
+    pub validation_split: f64,
+    /// Probabilities within this distance of 0.5 are reported as Neutral
+    pub neutral_band: f64,
+}
+
+impl Default for TrendModelConfig {
+    fn default() -> Self {
+        Self {
+            horizon: 6,
+            label_threshold: 0.002,
+            learning_rate: 0.1,
+            epochs: 500,
+            l2: 0.001,
+            validation_split: 0.2,
+            neutral_band: 0.05,
+        }
+    }
+}
+
+/// Logistic regression over standardized indicator features, predicting whether
+/// price is higher `horizon` candles ahead
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrendModel {
+    pub version: u32,
+    pub features: Vec<String>,
+    pub means: Vec<f64>,
+    pub stds: Vec<f64>,
+    pub weights: Vec<f64>,
+    pub bias: f64,
+    pub config: TrendModelConfig,
+    pub training_samples: usize,
+    pub validation_accuracy: Option<f64>,
+    pub trained_at: i64,
+}
+
+impl TrendModel {
+    /// Fit the model on a chronologically ordered candle series
+    pub fn train(candles: &[CandleData], config: &TrendModelConfig) -> Result<Self, String> {
+        if config.horizon == 0 || config.epochs == 0 || config.learning_rate <= 0.0 {
+            return Err("horizon, epochs and learning_rate must be positive".to_string());
+        }
+        if !(0.0..1.0).contains(&config.validation_split) {
+            return Err("validation_split must be in [0, 1)".to_string());
+        }
+
+        let rows = extract_features(candles)?;
+        let mut samples = Vec::new();
+        let mut labels = Vec::new();
+        for (i, row) in rows.iter().enumerate() {
+            let (Some(row), Some(future)) = (row, candles.get(i + config.horizon)) else {
+                continue;
+            };
+            let forward_return = (future.close / candles[i].close).ln();
+            if forward_return.abs() < config.label_threshold || !forward_return.is_finite() {
+                continue;
+            }
+            samples.push(*row);
+            labels.push(if forward_return > 0.0 { 1.0 } else { 0.0 });
+        }
+
+        let split = ((samples.len() as f64) * (1.0 - config.validation_split)).round() as usize;
+        let (train_x, valid_x) = samples.split_at(split);
+        let (train_y, valid_y) = labels.split_at(split);
+        if train_x.len() < 50 {
+            return Err(format!(
+                "Need at least 50 labeled training samples, got {}",
+                train_x.len()
+            ));
+        }
+        if train_y.iter().all(|&y| y == train_y[0]) {
+            return Err("Training labels contain a single class".to_string());
+        }
+
+        let n_features = TREND_FEATURES.len();
+        let n = train_x.len() as f64;
+        let means: Vec<f64> = (0..n_features)
+            .map(|j| train_x.iter().map(|x| x[j]).sum::<f64>() / n)
+            .collect();
+        let stds: Vec<f64> = means
+            .iter()
+            .enumerate()
+            .map(|(j, mean)| {
+                let variance = train_x.iter().map(|x| (x[j] - mean).powi(2)).sum::<f64>() / n;
+                if variance > 1e-18 {
+                    variance.sqrt()
+                } else {
+                    1.0
+                }
+            })
+            .collect();
+
+        let mut model = Self {
+            version: TREND_MODEL_VERSION,
+            features: TREND_FEATURES.iter().map(|f| f.to_string()).collect(),
+            means,
+            stds,
+            weights: vec![0.0; n_features],
+            bias: 0.0,
+            config: config.clone(),
+            training_samples: train_x.len(),
+            validation_accuracy: None,
+            trained_at: chrono::Utc::now().timestamp(),
+        };
+
+        let standardized: Vec<Vec<f64>> = train_x.iter().map(|x| model.standardize(x)).collect();
+        // Full-batch gradient descent from zero weights keeps training deterministic
+        for _ in 0..config.epochs {
+            let mut grad_w = vec![0.0; n_features];
+            let mut grad_b = 0.0;
+            for (x, &y) in standardized.iter().zip(train_y) {
+                let error = sigmoid(model.linear(x)) - y;
+                for (g, xj) in grad_w.iter_mut().zip(x) {
+                    *g += error * xj;
+                }
+                grad_b += error;
+            }
+            for (w, g) in model.weights.iter_mut().zip(&grad_w) {
+                *w -= config.learning_rate * (g / n + config.l2 * *w);
+            }
+            model.bias -= config.learning_rate * grad_b / n;
+        }
+
+        if !valid_x.is_empty() {
+            let correct = valid_x
+                .iter()
+                .zip(valid_y)
+                .filter(|(x, &y)| (model.probability(&x[..]) >= 0.5) == (y == 1.0))
+                .count();
+            model.validation_accuracy = Some(correct as f64 / valid_x.len() as f64);
+        }
+
+        Ok(model)
+    }
+
+    /// Probability that price is higher `horizon` candles after the last candle
+    pub fn predict_probability(&self, candles: &[CandleData]) -> Result<f64, String> {
+        let rows = extract_features(candles)?;
+        match rows.last() {
+            Some(Some(row)) => Ok(self.probability(row)),
+            _ => Err(format!(
+                "Need at least {} candles for trend features",
+                TREND_FEATURE_WARMUP + 1
+            )),
+        }
+    }
+
+    /// Trend direction with confidence `max(p, 1 - p)`
+    pub fn predict(&self, candles: &[CandleData]) -> Result<(TrendDirection, f64), String> {
+        let p = self.predict_probability(candles)?;
+        let trend = if p >= 0.5 + self.config.neutral_band {
+            TrendDirection::Uptrend
+        } else if p <= 0.5 - self.config.neutral_band {
+            TrendDirection::Downtrend
+        } else {
+            TrendDirection::Neutral
+        };
+        Ok((trend, p.max(1.0 - p)))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize trend model: {}", e))?;
+        std::fs::write(path.as_ref(), json).map_err(|e| {
+            format!(
+                "Failed to write trend model to {}: {}",
+                path.as_ref().display(),
+                e
+            )
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            format!(
+                "Failed to read trend model from {}: {}",
+                path.as_ref().display(),
+                e
+            )
+        })?;
+        let model: Self = serde_json::from_str(&json)
+            .map_err(|e| format!("Failed to parse trend model: {}", e))?;
+        model.validate()?;
+        Ok(model)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.version != TREND_MODEL_VERSION {
+            return Err(format!(
+                "Unsupported trend model version {} (expected {})",
+                self.version, TREND_MODEL_VERSION
+            ));
+        }
+        let expected: Vec<String> = TREND_FEATURES.iter().map(|f| f.to_string()).collect();
+        if self.features != expected
+            || self.means.len() != expected.len()
+            || self.stds.len() != expected.len()
+            || self.weights.len() != expected.len()
+        {
+            return Err("Trend model features do not match this build".to_string());
+        }
+        Ok(())
+    }
+
+    fn standardize(&self, row: &[f64]) -> Vec<f64> {
+        row.iter()
+            .zip(self.means.iter().zip(&self.stds))
+            .map(|(x, (mean, std))| (x - mean) / std)
+            .collect()
+    }
+
+    fn linear(&self, standardized: &[f64]) -> f64 {
+        self.bias
+            + self
+                .weights
+                .iter()
+                .zip(standardized)
+                .map(|(w, x)| w * x)
+                .sum::<f64>()
+    }
+
+    fn probability(&self, row: &[f64]) -> f64 {
+        sigmoid(self.linear(&self.standardize(row)))
+    }
+}
+
+fn sigmoid(z: f64) -> f64 {
+    1.0 / (1.0 + (-z).exp())
+}
+
+/// Feature row for every candle, `None` until all indicators have warmed up
+pub fn extract_features(
+    candles: &[CandleData],
+) -> Result<Vec<Option<[f64; TREND_FEATURES.len()]>>, String> {
+    let n = candles.len();
+    if n <= TREND_FEATURE_WARMUP {
+        return Err(format!(
+            "Need at least {} candles for trend features",
+            TREND_FEATURE_WARMUP + 1
+        ));
+    }
+
+    let closes: Vec<f64> = candles.iter().map(|c| c.close).collect();
+    let volumes: Vec<f64> = candles.iter().map(|c| c.volume).collect();
+    let rsi = align_to_candles(calculate_rsi(candles, 14)?, n);
+    let macd = align_to_candles(calculate_macd(candles, 12, 26, 9)?.histogram, n);
+    let bands = calculate_bollinger_bands(candles, 20, 2.0)?;
+    let (upper, lower) = (
+        align_to_candles(bands.upper, n),
+        align_to_candles(bands.lower, n),
+    );
+    let ema_fast = align_to_candles(calculate_ema(&closes, 20)?, n);
+    let ema_slow = align_to_candles(calculate_ema(&closes, 50)?, n);
+    let atr = align_to_candles(calculate_atr(candles, 14)?, n);
+    let volume_sma = align_to_candles(calculate_sma(&volumes, 20)?, n);
+
+    let log_return = |i: usize, lag: usize| -> Option<f64> {
+        let past = closes.get(i.checked_sub(lag)?)?;
+        Some((closes[i] / past).ln())
+    };
+
+    Ok((0..n)
+        .map(|i| {
+            let close = closes[i];
+            let (upper, lower) = (upper[i]?, lower[i]?);
+            let width = upper - lower;
+            let row = [
+                log_return(i, 1)?,
+                log_return(i, 5)?,
+                log_return(i, 20)?,
+                rsi[i]? / 100.0 - 0.5,
+                macd[i]? / close,
+                if width > 0.0 {
+                    (close - lower) / width - 0.5
+                } else {
+                    0.0
+                },
+                ema_fast[i]? / ema_slow[i]? - 1.0,
+                atr[i]? / close,
+                ((volumes[i] + 1e-9) / (volume_sma[i]? + 1e-9)).ln(),
+            ];
+            row.iter().all(|v| v.is_finite()).then_some(row)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Alternating regimes: each 40-candle trend continues for a while, so recent
+    /// momentum carries information about the next few candles
+    fn regime_candles(count: usize) -> Vec<CandleData> {
+        let mut price = 100.0;
+        (0..count)
+            .map(|i| {
+                let drift = if (i / 40) % 2 == 0 { 0.004 } else { -0.004 };
+                let wiggle = ((i as f64) * 1.7).sin() * 0.001;
+                let open = price;
+                price *= 1.0 + drift + wiggle;
+                CandleData {
+                    open_time: i as i64 * 900_000,
+                    close_time: i as i64 * 900_000 + 899_999,
+                    open,
+                    high: open.max(price) * 1.001,
+                    low: open.min(price) * 0.999,
+                    close: price,
+                    volume: 1000.0 + (i % 7) as f64 * 50.0,
+                    quote_volume: 1000.0 * price,
+                    trades: 100,
+                    is_closed: true,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_features_warmup() {
+        let candles = regime_candles(120);
+        let rows = extract_features(&candles).unwrap();
+        assert_eq!(rows.len(), 120);
+        assert!(rows[..TREND_FEATURE_WARMUP - 1].iter().all(|r| r.is_none()));
+        assert!(rows[TREND_FEATURE_WARMUP..].iter().all(|r| r.is_some()));
+
+        assert!(extract_features(&candles[..TREND_FEATURE_WARMUP]).is_err());
+    }
+
+    #[test]
+    fn test_train_learns_trend_persistence() {
+        let candles = regime_candles(1200);
+        let model = TrendModel::train(&candles, &TrendModelConfig::default()).unwrap();
+
+        assert!(model.training_samples >= 50);
+        assert!(model.validation_accuracy.unwrap() > 0.6);
+
+        let rising = &candles[..110]; // Deep inside the second up regime
+        let (trend, confidence) = model.predict(rising).unwrap();
+        assert_eq!(trend, TrendDirection::Uptrend);
+        assert!(confidence > 0.5);
+
+        let falling = &candles[..150]; // Deep inside the second down regime
+        assert_eq!(model.predict(falling).unwrap().0, TrendDirection::Downtrend);
+    }
+
+    #[test]
+    fn test_train_is_deterministic() {
+        let candles = regime_candles(600);
+        let config = TrendModelConfig::default();
+        let a = TrendModel::train(&candles, &config).unwrap();
+        let b = TrendModel::train(&candles, &config).unwrap();
+        assert_eq!(a.weights, b.weights);
+        assert_eq!(a.bias, b.bias);
+    }
+
+    #[test]
+    fn test_train_rejects_insufficient_data() {
+        let candles = regime_candles(80);
+        assert!(TrendModel::train(&candles, &TrendModelConfig::default()).is_err());
+
+        let config = TrendModelConfig {
+            horizon: 0,
+            ..Default::default()
+        };
+        assert!(TrendModel::train(&regime_candles(600), &config).is_err());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let candles = regime_candles(600);
+        let model = TrendModel::train(&candles, &TrendModelConfig::default()).unwrap();
+        let path = std::env::temp_dir().join(format!("trend_model_{}.json", uuid::Uuid::new_v4()));
+
+        model.save(&path).unwrap();
+        let loaded = TrendModel::load(&path).unwrap();
+        let delta = (loaded.predict_probability(&candles).unwrap()
+            - model.predict_probability(&candles).unwrap())
+        .abs();
+        assert!(delta < 1e-12);
+
+        let mut stale = model.clone();
+        stale.features.pop();
+        stale.save(&path).unwrap();
+        assert!(TrendModel::load(&path).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(TrendModel::load(&path).is_err());
+    }
+}
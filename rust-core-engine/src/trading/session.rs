@@ -0,0 +1,246 @@
+//! Trading session calendar shared by the paper and real engines
+//!
+//! A symbol's `TradingHours` lists the windows in which new entries are allowed, in a
+//! fixed UTC offset, plus weekdays on which it does not trade at all. Windows may wrap
+//! past midnight. Independently, new entries can be blocked for a few minutes before
+//! each Binance Futures funding timestamp (00:00, 08:00 and 16:00 UTC).
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveTime, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// Hours between Binance Futures funding timestamps
+const FUNDING_INTERVAL_HOURS: u32 = 8;
+
+/// One daily window `start..end` in the session's timezone; `end` is exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradingWindow {
+    pub start_hour: u8,
+    pub start_minute: u8,
+    pub end_hour: u8,
+    pub end_minute: u8,
+}
+
+/// Trading session hours
+///
+/// The `start_*`/`end_*` fields are the first window; `additional_windows` add more
+/// per day. A window whose start equals its end is empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradingHours {
+    pub start_hour: u8,
+    pub start_minute: u8,
+    pub end_hour: u8,
+    pub end_minute: u8,
+    /// "UTC" or a fixed offset such as "+08:00" or "UTC-5"
+    pub timezone: String,
+    /// More windows per day, in the same timezone
+    #[serde(default)]
+    pub additional_windows: Vec<TradingWindow>,
+    /// Weekdays (in the session's timezone) with no trading, e.g. ["Sat", "Sun"]
+    #[serde(default)]
+    pub blackout_days: Vec<Weekday>,
+}
+
+impl TradingWindow {
+    fn bounds(&self) -> Result<(NaiveTime, NaiveTime), String> {
+        let time = |hour: u8, minute: u8| {
+            NaiveTime::from_hms_opt(hour.into(), minute.into(), 0)
+                .ok_or_else(|| format!("Invalid session time {:02}:{:02}", hour, minute))
+        };
+        Ok((
+            time(self.start_hour, self.start_minute)?,
+            time(self.end_hour, self.end_minute)?,
+        ))
+    }
+
+    /// Whether `time` falls in the window, wrapping past midnight when end < start
+    fn contains(&self, time: NaiveTime) -> Result<bool, String> {
+        let (start, end) = self.bounds()?;
+        Ok(if start <= end {
+            start <= time && time < end
+        } else {
+            time >= start || time < end
+        })
+    }
+}
+
+impl TradingHours {
+    /// Every window of the day, the primary one first
+    pub fn windows(&self) -> Vec<TradingWindow> {
+        let primary = TradingWindow {
+            start_hour: self.start_hour,
+            start_minute: self.start_minute,
+            end_hour: self.end_hour,
+            end_minute: self.end_minute,
+        };
+        std::iter::once(primary)
+            .chain(self.additional_windows.iter().copied())
+            .collect()
+    }
+
+    /// Check the timezone and every window can be evaluated
+    pub fn validate(&self) -> Result<(), String> {
+        parse_utc_offset(&self.timezone)?;
+        for window in self.windows() {
+            window.bounds()?;
+        }
+        Ok(())
+    }
+
+    /// Ok when a new entry is allowed at `at`, otherwise the reason it is not
+    pub fn check_open(&self, at: DateTime<Utc>) -> Result<(), String> {
+        let offset = parse_utc_offset(&self.timezone)?;
+        let local = at.with_timezone(&offset);
+
+        if self.blackout_days.contains(&local.weekday()) {
+            return Err(format!(
+                "{:?} is a blackout day ({} {})",
+                local.weekday(),
+                local.format("%Y-%m-%d %H:%M"),
+                self.timezone
+            ));
+        }
+
+        let time = local.time();
+        for window in self.windows() {
+            if window.contains(time)? {
+                return Ok(());
+            }
+        }
+        Err(format!(
+            "{} {} is outside the trading windows",
+            local.format("%H:%M"),
+            self.timezone
+        ))
+    }
+}
+
+/// Parse "UTC", "GMT", "Z" or a fixed offset ("+08:00", "-0530", "UTC+8")
+///
+/// Named zones such as "Europe/London" are rejected since their offset changes with
+/// daylight saving.
+pub fn parse_utc_offset(timezone: &str) -> Result<FixedOffset, String> {
+    let trimmed = timezone.trim();
+    let offset = trimmed
+        .strip_prefix("UTC")
+        .or_else(|| trimmed.strip_prefix("GMT"))
+        .unwrap_or(trimmed);
+    if offset.is_empty() || offset == "Z" {
+        return Ok(FixedOffset::east_opt(0).expect("zero offset is valid"));
+    }
+
+    let invalid = || {
+        format!(
+            "Unsupported timezone {:?}: use UTC or a fixed offset like +08:00",
+            timezone
+        )
+    };
+    let (sign, digits) = match offset.as_bytes()[0] {
+        b'+' => (1, &offset[1..]),
+        b'-' => (-1, &offset[1..]),
+        _ => return Err(invalid()),
+    };
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((hours, minutes)) => (hours, minutes),
+        None if digits.len() == 4 => digits.split_at(2),
+        None => (digits, "0"),
+    };
+    let hours: i32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+    if hours > 14 || minutes >= 60 {
+        return Err(invalid());
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+/// Next Binance Futures funding timestamp strictly after `at`
+pub fn next_funding_time(at: DateTime<Utc>) -> DateTime<Utc> {
+    let next_hour = (at.hour() / FUNDING_INTERVAL_HOURS + 1) * FUNDING_INTERVAL_HOURS;
+    let midnight = at
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is valid")
+        .and_utc();
+    midnight + chrono::Duration::hours(next_hour.into())
+}
+
+/// Ok unless `at` is within `blackout_minutes` before the next funding timestamp
+pub fn check_funding_blackout(at: DateTime<Utc>, blackout_minutes: u32) -> Result<(), String> {
+    if blackout_minutes == 0 {
+        return Ok(());
+    }
+    let funding = next_funding_time(at);
+    if funding - at <= chrono::Duration::minutes(blackout_minutes.into()) {
+        return Err(format!(
+            "No new entries within {} minutes of funding at {}",
+            blackout_minutes,
+            funding.format("%H:%M UTC")
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn hours(timezone: &str) -> TradingHours {
+        TradingHours {
+            start_hour: 9,
+            start_minute: 30,
+            end_hour: 16,
+            end_minute: 0,
+            timezone: timezone.to_string(),
+            additional_windows: Vec::new(),
+            blackout_days: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_windows_offsets_and_blackout_days() {
+        // Wednesday
+        let at = |h, m| Utc.with_ymd_and_hms(2024, 1, 3, h, m, 0).unwrap();
+        let mut session = hours("UTC");
+        assert!(session.check_open(at(9, 30)).is_ok());
+        assert!(session.check_open(at(16, 0)).is_err());
+
+        // 01:30 UTC is 09:30 at +08:00
+        assert!(hours("+08:00").check_open(at(1, 30)).is_ok());
+        assert!(hours("UTC+8").check_open(at(9, 30)).is_err());
+
+        session.additional_windows.push(TradingWindow {
+            start_hour: 22,
+            start_minute: 0,
+            end_hour: 2,
+            end_minute: 0,
+        });
+        assert!(session.check_open(at(23, 0)).is_ok());
+        assert!(session.check_open(at(1, 59)).is_ok());
+        assert!(session.check_open(at(2, 0)).is_err());
+
+        session.blackout_days = vec![Weekday::Wed];
+        let reason = session.check_open(at(10, 0)).unwrap_err();
+        assert!(reason.contains("blackout"), "{}", reason);
+
+        assert!(hours("Europe/London").validate().is_err());
+        assert_eq!(
+            parse_utc_offset("-0530").unwrap().local_minus_utc(),
+            -(5 * 3600 + 30 * 60)
+        );
+    }
+
+    #[test]
+    fn test_funding_blackout() {
+        let at = |h, m| Utc.with_ymd_and_hms(2024, 1, 3, h, m, 0).unwrap();
+        assert_eq!(next_funding_time(at(7, 59)), at(8, 0));
+        assert_eq!(next_funding_time(at(8, 0)), at(16, 0));
+        assert_eq!(
+            next_funding_time(at(23, 0)),
+            Utc.with_ymd_and_hms(2024, 1, 4, 0, 0, 0).unwrap()
+        );
+
+        assert!(check_funding_blackout(at(7, 50), 15).is_err());
+        assert!(check_funding_blackout(at(7, 40), 15).is_ok());
+        assert!(check_funding_blackout(at(7, 59), 0).is_ok());
+    }
+}
@@ -44,6 +44,8 @@ fn make_strategy_input() -> StrategyInput {
         current_price: 45100.0,
         volume_24h: 1_000_000.0,
         timestamp: 1700000060000,
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     }
 }
 
@@ -94,6 +94,8 @@ async fn test_all_5_strategies_with_real_binance_data() {
         current_price,
         volume_24h,
         timestamp: chrono::Utc::now().timestamp_millis(),
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     };
 
     // Step 3: Initialize all 5 strategies
@@ -340,7 +342,7 @@ async fn test_strategy_count() {
     assert_eq!(strategies.len(), 5, "Should have exactly 5 strategies");
 
     // Verify each strategy has a unique name
-    let names: Vec<&'static str> = strategies.iter().map(|s| s.name()).collect();
+    let names: Vec<&str> = strategies.iter().map(|s| s.name()).collect();
     println!("\n✅ Verified 5 strategies:");
     for (i, name) in names.iter().enumerate() {
         println!("   {}. {}", i + 1, name);
@@ -26,6 +26,10 @@ fn test_create_order_request_serialization() {
         leverage: Some(10),
         stop_loss_pct: Some(2.0),
         take_profit_pct: Some(5.0),
+        time_in_force: None,
+        stop_limit_price: None,
+        stop_loss_price: None,
+        take_profit_price: None,
     };
 
     let json = serde_json::to_string(&request).unwrap();
@@ -53,6 +57,10 @@ fn test_create_order_request_validation() {
         leverage: Some(5),
         stop_loss_pct: None,
         take_profit_pct: None,
+        time_in_force: None,
+        stop_limit_price: None,
+        stop_loss_price: None,
+        take_profit_price: None,
     };
     assert_eq!(market_order.order_type, "market");
 
@@ -67,6 +75,10 @@ fn test_create_order_request_validation() {
         leverage: Some(1),
         stop_loss_pct: Some(3.0),
         take_profit_pct: Some(6.0),
+        time_in_force: None,
+        stop_limit_price: None,
+        stop_loss_price: None,
+        take_profit_price: None,
     };
     assert!(limit_order.price.is_some());
 
@@ -81,6 +93,10 @@ fn test_create_order_request_validation() {
         leverage: Some(2),
         stop_loss_pct: None,
         take_profit_pct: None,
+        time_in_force: None,
+        stop_limit_price: None,
+        stop_loss_price: None,
+        take_profit_price: None,
     };
     assert!(stop_limit_order.stop_price.is_some());
 }
@@ -112,6 +128,7 @@ fn test_close_trade_request() {
     let request = CloseTradeRequest {
         trade_id: Some("trade_456".to_string()),
         reason: Some("Manual close by user".to_string()),
+        fraction: None,
     };
 
     let json = serde_json::to_string(&request).unwrap();
@@ -58,6 +58,8 @@ fn create_test_input(
         current_price,
         volume_24h: 1000000.0,
         timestamp: 1234567890,
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     }
 }
 
@@ -77,6 +79,8 @@ async fn test_cov3_bollinger_missing_5m_data() {
         current_price: 100.0,
         volume_24h: 1000000.0,
         timestamp: 1234567890,
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     };
 
     let result = strategy.analyze(&input).await;
@@ -100,6 +104,8 @@ async fn test_cov3_bollinger_missing_15m_data() {
         current_price: 100.0,
         volume_24h: 1000000.0,
         timestamp: 1234567890,
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     };
 
     let result = strategy.analyze(&input).await;
@@ -174,6 +180,8 @@ async fn test_cov3_stochastic_missing_5m_data() {
         current_price: 100.0,
         volume_24h: 1000000.0,
         timestamp: 1234567890,
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     };
 
     let result = strategy.analyze(&input).await;
@@ -193,6 +201,8 @@ async fn test_cov3_stochastic_missing_15m_data() {
         current_price: 100.0,
         volume_24h: 1000000.0,
         timestamp: 1234567890,
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     };
 
     let result = strategy.analyze(&input).await;
@@ -215,6 +225,8 @@ async fn test_cov3_stochastic_insufficient_candles() {
         current_price: 100.0,
         volume_24h: 1000000.0,
         timestamp: 1234567890,
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     };
 
     let result = strategy.validate_data(&input);
@@ -285,6 +297,7 @@ async fn test_cov3_ml_predictor_custom_config() {
         timeout_ms: 3500,
         min_confidence: 0.7,
         fallback_on_error: false,
+        model_path: None,
     };
 
     let predictor = MLTrendPredictor::new(config.clone());
@@ -376,6 +389,7 @@ fn test_cov3_ml_config_serialization() {
         timeout_ms: 4000,
         min_confidence: 0.8,
         fallback_on_error: true,
+        model_path: None,
     };
 
     let json = serde_json::to_string(&config).unwrap();
@@ -450,6 +464,7 @@ fn test_cov3_ml_config_clone() {
         timeout_ms: 1500,
         min_confidence: 0.6,
         fallback_on_error: false,
+        model_path: None,
     };
 
     let config2 = config1.clone();
@@ -674,6 +689,8 @@ async fn test_cov3_stochastic_with_oscillating_prices() {
         current_price: 100.0,
         volume_24h: 1000000.0,
         timestamp: 1234567890,
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     };
 
     let result = strategy.analyze(&input).await;
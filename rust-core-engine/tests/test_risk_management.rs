@@ -49,6 +49,8 @@ fn create_multi_timeframe_input(symbol: &str) -> StrategyInput {
         current_price: 50000.0,
         volume_24h: 1000000.0,
         timestamp: chrono::Utc::now().timestamp_millis(),
+        indicators: HashMap::new(),
+        related_data: HashMap::new(),
     }
 }
 